@@ -0,0 +1,39 @@
+//! Compares encoding place order messages into a single buffer with building a String per field, as messages were previously encoded.
+//!
+//! Run with: cargo bench --features bench
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ibapi::bench;
+use ibapi::contracts::Contract;
+use ibapi::orders::{order_builder, Action};
+
+const ITERATIONS: u32 = 100_000;
+
+// Average time per iteration of `f`.
+fn time(mut f: impl FnMut(i32) -> usize) -> Duration {
+    let started = Instant::now();
+    for i in 0..ITERATIONS {
+        black_box(f(i as i32));
+    }
+    started.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let contract = Contract::stock("AAPL");
+    let order = order_builder::limit_order(Action::Buy, 100.0, 150.25);
+    let fields = bench::place_order_fields(9000, &contract, &order);
+
+    let place_order = time(|order_id| bench::encode_place_order(order_id, &contract, &order));
+    let single_buffer = time(|_| bench::encode_single_buffer(black_box(&fields)));
+    let string_per_field = time(|_| bench::encode_string_per_field(black_box(&fields)));
+
+    println!("place order message, {} fields: {place_order:?} per message", fields.len());
+    println!("single buffer: {single_buffer:?} per message");
+    println!("String per field: {string_per_field:?} per message");
+    println!(
+        "saved by the single buffer: {:?} per message",
+        string_per_field.saturating_sub(single_buffer)
+    );
+}
@@ -17,7 +17,7 @@ fn main() {
 
     println!("connection_string: {connection_string}, stock_symbol: {stock_symbol}");
 
-    let client = Client::connect(&connection_string, 100).expect("connection failed");
+    let client = Client::connect(connection_string, 100).expect("connection failed");
 
     let contract = Contract::stock(stock_symbol);
     let what_to_show = WhatToShow::Trades;
@@ -30,7 +30,7 @@ fn main() {
     let stock_symbol = matches.get_one::<String>("STOCK_SYMBOL").expect("stock symbol is required");
     let number_of_ticks = matches.get_one::<i32>("number_of_ticks").expect("number of ticks required");
 
-    let client = Client::connect(&connection_string, 100).expect("connection failed");
+    let client = Client::connect(connection_string, 100).expect("connection failed");
 
     let interval_start = parse_interval(interval_raw);
     let contract = Contract::stock(stock_symbol);
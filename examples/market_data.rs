@@ -28,6 +28,7 @@ fn main() {
             TickTypes::EFP(tick_efp) => println!("{:?}", tick_efp),
             TickTypes::OptionComputation(option_computation) => println!("{:?}", option_computation),
             TickTypes::RequestParameters(tick_request_parameters) => println!("{:?}", tick_request_parameters),
+            TickTypes::DeltaNeutralValidation(delta_neutral_contract) => println!("{:?}", delta_neutral_contract),
             TickTypes::SnapshotEnd => subscription.cancel(),
             TickTypes::Notice(notice) => println!("{:?}", notice),
         }
@@ -1,3 +1,4 @@
+use ibapi::news::ArticleContent;
 use ibapi::Client;
 
 fn main() {
@@ -10,5 +11,8 @@ fn main() {
     let article_id = "DJ-N$1915168d";
 
     let article = client.news_article(provider_code, article_id).expect("request news article failed");
-    println!("{:?}", article);
+    match article.content().expect("invalid article") {
+        ArticleContent::Text(text) => println!("{text}"),
+        ArticleContent::Binary(data) => println!("binary article of {} bytes", data.len()),
+    }
 }
@@ -13,9 +13,9 @@ fn main() {
     let gateway_url = matches.get_one::<String>("connection_url").expect("connection_string is required");
     let account = matches.get_one::<String>("account").expect("account is required");
 
-    let client = Client::connect(&gateway_url, 919).expect("connection failed");
+    let client = Client::connect(gateway_url, 919).expect("connection failed");
 
-    let subscription = client.pnl(&account, None).expect("pnl request failed");
+    let subscription = client.pnl(account, None).expect("pnl request failed");
 
     // Get next item non-blocking
     if let Some(pnl) = subscription.try_next() {
@@ -16,9 +16,9 @@ fn main() {
     let contract_id = matches.get_one::<String>("contract_id").expect("contract_id is required");
     let contract_id = contract_id.parse::<i32>().expect("invalid number");
 
-    let client = Client::connect(&gateway_url, 919).expect("connection failed");
+    let client = Client::connect(gateway_url, 919).expect("connection failed");
 
-    let subscription = client.pnl_single(&account, contract_id, None).expect("pnl single request failed");
+    let subscription = client.pnl_single(account, contract_id, None).expect("pnl single request failed");
 
     // Get next item non-blocking
     if let Some(pnl) = subscription.try_next() {
@@ -26,11 +26,10 @@ fn main() {
     }
 
     // Consume items blocking for next
-    while let Some(pnl) = subscription.next() {
+    if let Some(pnl) = subscription.next() {
         println!("PnL single: {:?}", pnl);
 
         // After processing items subscription could be cancelled.
         subscription.cancel();
-        break;
     }
 }
@@ -28,7 +28,7 @@ fn main() {
     }
 
     if let Some(symbol) = matches.get_one::<String>("all_last") {
-        stream_all_last(&mut client, &symbol.to_uppercase()).unwrap();
+        stream_all_last(&client, &symbol.to_uppercase()).unwrap();
     }
 
     if let Some(symbol) = matches.get_one::<String>("bid_ask") {
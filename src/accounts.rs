@@ -7,21 +7,51 @@
 //! - Daily, unrealized, and realized PnL calculations
 //! - Family code management
 //! - Real-time PnL updates for individual positions
+//! - PnL history with daily curves and drawdown
+//! - Daily PnL reset detection
+//! - Per currency cash ledger
+//! - Margin cushion alerting
+//! - Model portfolios of advisor accounts
 //!
 
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::client::{DataStream, ResponseContext, SharesChannel, Subscription};
+use crate::client::{DataStream, ResponseContext, SharesChannel, Subscription, SubscriptionKind};
 use crate::contracts::Contract;
 use crate::messages::{IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::money::Money;
 use crate::{server_versions, Client, Error};
 
+#[cfg(feature = "fa")]
+use fa::{Alias, FaConfiguration, FaDataType};
+
+pub use cash::{CashLedger, CurrencyBalances};
+pub use history::{PnLHistory, PnLSample};
+pub use margin::{MarginAlert, MarginAlerts, MarginMetric, MarginMonitor};
+pub use model::Model;
+pub use positions::{PositionChange, PositionTracker};
+pub use reset::{PnLResetDetector, PnLUpdate, PnLUpdates};
+
+mod cash;
 mod decoders;
 mod encoders;
+#[cfg(feature = "fa")]
+pub mod fa;
+mod history;
+mod margin;
+mod model;
+mod positions;
+mod reset;
 #[cfg(test)]
 mod tests;
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Account information as it appears in the TWS’ Account Summary Window
 pub struct AccountSummary {
     /// The account identifier.
@@ -34,6 +64,13 @@ pub struct AccountSummary {
     pub currency: String,
 }
 
+impl AccountSummary {
+    /// The value as an amount of [Money]. `None` if the value is not a monetary amount.
+    pub fn money(&self) -> Option<Money> {
+        parse_money(&self.value, &self.currency)
+    }
+}
+
 pub struct AccountSummaryTags {}
 
 impl AccountSummaryTags {
@@ -101,6 +138,7 @@ impl AccountSummaryTags {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AccountSummaries {
     Summary(AccountSummary),
     End,
@@ -108,6 +146,7 @@ pub enum AccountSummaries {
 
 impl DataStream<AccountSummaries> for AccountSummaries {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::AccountSummary, IncomingMessages::AccountSummaryEnd];
+    const KIND: SubscriptionKind = SubscriptionKind::AccountSummary;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -126,10 +165,11 @@ impl DataStream<AccountSummaries> for AccountSummaries {
 }
 
 // Realtime PnL update for account.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PnL {
-    /// DailyPnL for the position
-    pub daily_pnl: f64,
+    /// DailyPnL for the position. None if not yet available.
+    pub daily_pnl: Option<f64>,
     /// UnrealizedPnL total unrealized PnL for the position (since inception) updating in real time.
     pub unrealized_pnl: Option<f64>,
     /// Realized PnL for the position
@@ -138,6 +178,7 @@ pub struct PnL {
 
 impl DataStream<PnL> for PnL {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::PnL];
+    const KIND: SubscriptionKind = SubscriptionKind::PnL;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         decoders::decode_pnl(client.server_version, message)
@@ -151,21 +192,23 @@ impl DataStream<PnL> for PnL {
 
 // Realtime PnL update for a position in account.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PnLSingle {
     // Current size of the position
     pub position: f64,
-    /// DailyPnL for the position
-    pub daily_pnl: f64,
-    /// UnrealizedPnL total unrealized PnL for the position (since inception) updating in real time.
-    pub unrealized_pnl: f64,
-    /// Realized PnL for the position
-    pub realized_pnl: f64,
-    /// Current market value of the position
-    pub value: f64,
+    /// DailyPnL for the position. None if not yet available.
+    pub daily_pnl: Option<f64>,
+    /// UnrealizedPnL total unrealized PnL for the position (since inception) updating in real time. None if not yet available.
+    pub unrealized_pnl: Option<f64>,
+    /// Realized PnL for the position. None if not yet available.
+    pub realized_pnl: Option<f64>,
+    /// Current market value of the position. None if not yet available.
+    pub value: Option<f64>,
 }
 
 impl DataStream<PnLSingle> for PnLSingle {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::PnLSingle];
+    const KIND: SubscriptionKind = SubscriptionKind::PnLSingle;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         decoders::decode_pnl_single(client.server_version, message)
@@ -178,6 +221,7 @@ impl DataStream<PnLSingle> for PnLSingle {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Position {
     /// Account holding position
     pub account: String,
@@ -191,6 +235,7 @@ pub struct Position {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PositionUpdate {
     Position(Position),
     PositionEnd,
@@ -198,6 +243,7 @@ pub enum PositionUpdate {
 
 impl DataStream<PositionUpdate> for PositionUpdate {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::Position, IncomingMessages::PositionEnd];
+    const KIND: SubscriptionKind = SubscriptionKind::Positions;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -214,6 +260,7 @@ impl DataStream<PositionUpdate> for PositionUpdate {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PositionUpdateMulti {
     Position(PositionMulti),
     PositionEnd,
@@ -221,6 +268,7 @@ pub enum PositionUpdateMulti {
 
 /// Portfolio's open positions.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PositionMulti {
     /// The account holding the position.
     pub account: String,
@@ -236,6 +284,7 @@ pub struct PositionMulti {
 
 impl DataStream<PositionUpdateMulti> for PositionUpdateMulti {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::PositionMulti, IncomingMessages::PositionMultiEnd];
+    const KIND: SubscriptionKind = SubscriptionKind::PositionsMulti;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -251,7 +300,60 @@ impl DataStream<PositionUpdateMulti> for PositionUpdateMulti {
     }
 }
 
+/// Maps account aliases to account codes.
+///
+/// Account APIs on [Client] accept either an alias or an account code once aliases are registered with
+/// [Client::account_aliases] or [Client::set_account_aliases].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountAliases {
+    accounts: HashMap<String, String>,
+    aliases: HashMap<String, String>,
+}
+
+impl AccountAliases {
+    /// Creates an empty alias map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `alias` for `account`.
+    pub fn insert(&mut self, account: &str, alias: &str) {
+        if let Some(previous) = self.aliases.insert(account.to_owned(), alias.to_owned()) {
+            self.accounts.remove(&previous);
+        }
+        self.accounts.insert(alias.to_owned(), account.to_owned());
+    }
+
+    /// Returns the account code for an alias or account code. Unknown names are returned unchanged.
+    pub fn resolve<'a>(&'a self, account_or_alias: &'a str) -> &'a str {
+        self.accounts.get(account_or_alias).map(String::as_str).unwrap_or(account_or_alias)
+    }
+
+    /// Returns the alias registered for `account`.
+    pub fn alias(&self, account: &str) -> Option<&str> {
+        self.aliases.get(account).map(String::as_str)
+    }
+
+    /// Returns true if no aliases are registered.
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+}
+
+#[cfg(feature = "fa")]
+impl From<&[Alias]> for AccountAliases {
+    fn from(aliases: &[Alias]) -> Self {
+        let mut account_aliases = AccountAliases::new();
+        for alias in aliases {
+            account_aliases.insert(&alias.account, &alias.alias);
+        }
+        account_aliases
+    }
+}
+
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FamilyCode {
     /// Account ID
     pub account_id: String,
@@ -262,6 +364,7 @@ pub struct FamilyCode {
 /// Account's information, portfolio and last update time
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AccountUpdate {
     /// Receives the subscribed account's information.
     AccountValue(AccountValue),
@@ -280,6 +383,7 @@ impl DataStream<AccountUpdate> for AccountUpdate {
         IncomingMessages::AccountUpdateTime,
         IncomingMessages::AccountDownloadEnd,
     ];
+    const KIND: SubscriptionKind = SubscriptionKind::AccountUpdates;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -301,6 +405,7 @@ impl DataStream<AccountUpdate> for AccountUpdate {
 
 /// A value of subscribed account's information.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccountValue {
     /// The value being updated.
     pub key: String,
@@ -312,8 +417,16 @@ pub struct AccountValue {
     pub account: Option<String>,
 }
 
+impl AccountValue {
+    /// The value as an amount of [Money]. `None` if the value is not a monetary amount.
+    pub fn money(&self) -> Option<Money> {
+        parse_money(&self.value, &self.currency)
+    }
+}
+
 /// Subscribed account's portfolio.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccountPortfolioValue {
     /// The Contract for which a position is held.
     pub contract: Contract,
@@ -335,14 +448,119 @@ pub struct AccountPortfolioValue {
 
 /// Last time at which the account was updated.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccountUpdateTime {
     /// The last update system time.
     pub timestamp: String,
 }
 
+/// Change of an account value observed on the account updates stream.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountValueChange {
+    /// The account identifier.
+    pub account: Option<String>,
+    /// The value that changed.
+    pub key: String,
+    /// The currency in which the value is expressed.
+    pub currency: String,
+    /// Previous value. `None` the first time a key is observed.
+    pub old_value: Option<String>,
+    /// Current value.
+    pub new_value: String,
+}
+
+/// Tracks the last known account values and reports only the values that changed.
+#[derive(Debug, Default)]
+pub struct AccountValueTracker {
+    values: HashMap<(Option<String>, String, String), String>,
+}
+
+impl AccountValueTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `value` and returns the change, or `None` if the value is unchanged.
+    pub fn update(&mut self, value: &AccountValue) -> Option<AccountValueChange> {
+        let key = (value.account.clone(), value.key.clone(), value.currency.clone());
+        let old_value = self.values.insert(key, value.value.clone());
+
+        if old_value.as_deref() == Some(value.value.as_str()) {
+            return None;
+        }
+
+        Some(AccountValueChange {
+            account: value.account.clone(),
+            key: value.key.clone(),
+            currency: value.currency.clone(),
+            old_value,
+            new_value: value.value.clone(),
+        })
+    }
+
+    /// Returns the last known value for `key` in `currency`, for any account.
+    pub fn value(&self, key: &str, currency: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|((_, k, c), _)| k == key && c == currency)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl<'a> Subscription<'a, AccountUpdate> {
+    /// Returns a blocking iterator over the account values that changed, skipping unchanged values,
+    /// portfolio updates and update times.
+    ///
+    /// ```no_run
+    /// use ibapi::accounts::AccountSummaryTags;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let subscription = client.account_updates("DU1234567").expect("error requesting account updates");
+    /// for change in subscription.changes() {
+    ///     if change.key == AccountSummaryTags::EXCESS_LIQUIDITY {
+    ///         println!("excess liquidity: {:?} -> {}", change.old_value, change.new_value);
+    ///     }
+    /// }
+    /// ```
+    pub fn changes(&self) -> AccountValueChanges<'_, 'a> {
+        AccountValueChanges {
+            subscription: self,
+            tracker: AccountValueTracker::new(),
+        }
+    }
+}
+
+/// Blocking iterator over changed account values. See [Subscription::changes].
+pub struct AccountValueChanges<'s, 'a> {
+    subscription: &'s Subscription<'a, AccountUpdate>,
+    tracker: AccountValueTracker,
+}
+
+impl Iterator for AccountValueChanges<'_, '_> {
+    type Item = AccountValueChange;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.subscription.next()? {
+                AccountUpdate::AccountValue(value) => {
+                    if let Some(change) = self.tracker.update(&value) {
+                        return Some(change);
+                    }
+                }
+                _ => continue,
+            }
+        }
+    }
+}
+
 /// Account's information, portfolio and last update time
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AccountUpdateMulti {
     /// Receives the subscribed account's information.
     AccountMultiValue(AccountMultiValue),
@@ -352,6 +570,7 @@ pub enum AccountUpdateMulti {
 
 // Provides the account updates.
 #[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccountMultiValue {
     /// he account with updates.
     pub account: String,
@@ -365,8 +584,16 @@ pub struct AccountMultiValue {
     pub currency: String,
 }
 
+impl AccountMultiValue {
+    /// The value as an amount of [Money]. `None` if the value is not a monetary amount.
+    pub fn money(&self) -> Option<Money> {
+        parse_money(&self.value, &self.currency)
+    }
+}
+
 impl DataStream<AccountUpdateMulti> for AccountUpdateMulti {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::AccountUpdateMulti, IncomingMessages::AccountUpdateMultiEnd];
+    const KIND: SubscriptionKind = SubscriptionKind::AccountUpdatesMulti;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -382,9 +609,16 @@ impl DataStream<AccountUpdateMulti> for AccountUpdateMulti {
     }
 }
 
+fn parse_money(value: &str, currency: &str) -> Option<Money> {
+    if currency.is_empty() {
+        return None;
+    }
+    value.parse().ok().map(|amount| Money::new(amount, currency))
+}
+
 // Subscribes to position updates for all accessible accounts.
 // All positions sent initially, and then only updates as positions change.
-pub(crate) fn positions(client: &Client) -> Result<Subscription<PositionUpdate>, Error> {
+pub(crate) fn positions(client: &Client) -> Result<Subscription<'_, PositionUpdate>, Error> {
     client.check_server_version(server_versions::ACCOUNT_SUMMARY, "It does not support position requests.")?;
 
     let request = encoders::encode_request_positions()?;
@@ -403,7 +637,8 @@ pub(super) fn positions_multi<'a>(
     client.check_server_version(server_versions::MODELS_SUPPORT, "It does not support positions multi requests.")?;
 
     let request_id = client.next_request_id();
-    let request = encoders::encode_request_positions_multi(request_id, account, model_code)?;
+    let account = account.map(|account| client.resolve_account(account));
+    let request = encoders::encode_request_positions_multi(request_id, account.as_deref(), model_code)?;
     let subscription = client.send_request(request_id, request)?;
 
     Ok(Subscription::new(client, subscription, ResponseContext::default()))
@@ -434,7 +669,8 @@ pub(super) fn pnl<'a>(client: &'a Client, account: &str, model_code: Option<&str
     client.check_server_version(server_versions::PNL, "It does not support PnL requests.")?;
 
     let request_id = client.next_request_id();
-    let request = encoders::encode_request_pnl(request_id, account, model_code)?;
+    let account = client.resolve_account(account);
+    let request = encoders::encode_request_pnl(request_id, &account, model_code)?;
     let subscription = client.send_request(request_id, request)?;
 
     Ok(Subscription::new(client, subscription, ResponseContext::default()))
@@ -456,7 +692,8 @@ pub(super) fn pnl_single<'a>(
     client.check_server_version(server_versions::REALIZED_PNL, "It does not support PnL requests.")?;
 
     let request_id = client.next_request_id();
-    let request = encoders::encode_request_pnl_single(request_id, account, contract_id, model_code)?;
+    let account = client.resolve_account(account);
+    let request = encoders::encode_request_pnl_single(request_id, &account, contract_id, model_code)?;
     let subscription = client.send_request(request_id, request)?;
 
     Ok(Subscription::new(client, subscription, ResponseContext::default()))
@@ -473,7 +710,8 @@ pub(super) fn account_summary<'a>(client: &'a Client, group: &str, tags: &[&str]
 }
 
 pub(super) fn account_updates<'a>(client: &'a Client, account: &str) -> Result<Subscription<'a, AccountUpdate>, Error> {
-    let request = encoders::encode_request_account_updates(client.server_version(), account)?;
+    let account = client.resolve_account(account);
+    let request = encoders::encode_request_account_updates(client.server_version(), &account)?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestAccountData, request)?;
 
     Ok(Subscription::new(client, subscription, ResponseContext::default()))
@@ -487,12 +725,70 @@ pub(super) fn account_updates_multi<'a>(
     client.check_server_version(server_versions::MODELS_SUPPORT, "It does not support account updates multi requests.")?;
 
     let request_id = client.next_request_id();
-    let request = encoders::encode_request_account_updates_multi(request_id, account, model_code)?;
+    let account = account.map(|account| client.resolve_account(account));
+    let request = encoders::encode_request_account_updates_multi(request_id, account.as_deref(), model_code)?;
     let subscription = client.send_request(request_id, request)?;
 
     Ok(Subscription::new(client, subscription, ResponseContext::default()))
 }
 
+// Requests the financial advisor configuration of the given type.
+#[cfg(feature = "fa")]
+pub(super) fn request_fa(client: &Client, data_type: FaDataType) -> Result<FaConfiguration, Error> {
+    if data_type == FaDataType::Profiles && client.server_version() >= server_versions::FA_PROFILE_DESUPPORT {
+        return Err(Error::InvalidArgument("FA profiles are not supported by this version of TWS.".into()));
+    }
+
+    let request = encoders::encode_request_fa(data_type)?;
+    let subscription = client.send_shared_request(OutgoingMessages::RequestFA, request)?;
+
+    match subscription.next() {
+        Some(Ok(mut message)) => decoders::decode_receive_fa(&mut message),
+        Some(Err(Error::ConnectionReset)) => request_fa(client, data_type),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfStream),
+    }
+}
+
+// Replaces the financial advisor configuration of the configuration's type.
+#[cfg(feature = "fa")]
+pub(super) fn replace_fa(client: &Client, configuration: &FaConfiguration) -> Result<(), Error> {
+    client.check_server_version(server_versions::REPLACE_FA_END, "It does not support replace FA requests.")?;
+
+    if configuration.data_type() == FaDataType::Profiles && client.server_version() >= server_versions::FA_PROFILE_DESUPPORT {
+        return Err(Error::InvalidArgument("FA profiles are not supported by this version of TWS.".into()));
+    }
+
+    let request_id = client.next_request_id();
+    let request = encoders::encode_replace_fa(request_id, configuration.data_type(), &configuration.to_xml())?;
+    let subscription = client.send_request(request_id, request)?;
+
+    match subscription.next() {
+        Some(Ok(message)) if message.message_type() == IncomingMessages::ReplaceFAEnd => Ok(()),
+        Some(Ok(message)) => Err(Error::from_response(message)),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfStream),
+    }
+}
+
+// Requests the account aliases configured for a financial advisor account and registers them with the client.
+#[cfg(feature = "fa")]
+pub(super) fn account_aliases(client: &Client) -> Result<AccountAliases, Error> {
+    let aliases = match request_fa(client, FaDataType::Aliases)? {
+        FaConfiguration::Aliases(aliases) => AccountAliases::from(aliases.as_slice()),
+        configuration => {
+            return Err(Error::InvalidFormat {
+                kind: "FA configuration",
+                reason: format!("expected aliases, received {:?}", configuration.data_type()),
+            })
+        }
+    };
+
+    client.set_account_aliases(aliases.clone());
+
+    Ok(aliases)
+}
+
 pub(super) fn managed_accounts(client: &Client) -> Result<Vec<String>, Error> {
     let request = encoders::encode_request_managed_accounts()?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestManagedAccounts, request)?;
@@ -511,11 +807,28 @@ pub(super) fn managed_accounts(client: &Client) -> Result<Vec<String>, Error> {
     }
 }
 
-pub(super) fn server_time(client: &Client) -> Result<OffsetDateTime, Error> {
+// Requests the white branding id of the logged user, empty unless the account is white branded.
+pub(super) fn user_info(client: &Client, timeout: Option<Duration>) -> Result<String, Error> {
+    client.check_server_version(server_versions::USER_INFO, "It does not support user info requests.")?;
+
+    let request_id = client.next_request_id();
+    let request = encoders::encode_request_user_info(request_id)?;
+    let subscription = client.send_request(request_id, request)?;
+
+    match subscription.next_within(timeout) {
+        Some(Ok(mut message)) if message.message_type() == IncomingMessages::UserInfo => decoders::decode_user_info(&mut message),
+        Some(Ok(message)) => Err(Error::from_response(message)),
+        Some(Err(Error::ConnectionReset)) => user_info(client, timeout),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfStream),
+    }
+}
+
+pub(super) fn server_time(client: &Client, timeout: Option<Duration>) -> Result<OffsetDateTime, Error> {
     let request = encoders::encode_request_server_time()?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestCurrentTime, request)?;
 
-    match subscription.next() {
+    match subscription.next_within(timeout) {
         Some(Ok(mut message)) => {
             message.skip(); // message type
             message.skip(); // message version
@@ -523,11 +836,11 @@ pub(super) fn server_time(client: &Client) -> Result<OffsetDateTime, Error> {
             let timestamp = message.next_long()?;
             match OffsetDateTime::from_unix_timestamp(timestamp) {
                 Ok(date) => Ok(date),
-                Err(e) => Err(Error::Simple(format!("Error parsing date: {e}"))),
+                Err(e) => Err(message.decode_error(e.to_string())),
             }
         }
-        Some(Err(Error::ConnectionReset)) => server_time(client),
+        Some(Err(Error::ConnectionReset)) => server_time(client, timeout),
         Some(Err(e)) => Err(e),
-        None => Err(Error::Simple("No response from server".to_string())),
+        None => Err(Error::UnexpectedEndOfStream),
     }
 }
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{AccountUpdate, AccountValue};
+use crate::money::{Currency, Money};
+
+#[cfg(test)]
+mod tests;
+
+/// Cash balances of an account in a single currency, as reported by account updates.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CurrencyBalances {
+    /// Cash balance (`CashBalance`).
+    pub cash_balance: Option<f64>,
+    /// Total cash balance including futures PnL (`TotalCashBalance`).
+    pub total_cash_balance: Option<f64>,
+    /// Settled cash (`SettledCash`).
+    pub settled_cash: Option<f64>,
+    /// Accrued interest (`AccruedCash`).
+    pub accrued_cash: Option<f64>,
+    /// Dividends accrued but not yet paid (`AccruedDividend`).
+    pub accrued_dividend: Option<f64>,
+    /// Net liquidation value (`NetLiquidationByCurrency`).
+    pub net_liquidation: Option<f64>,
+    /// Realized PnL (`RealizedPnL`).
+    pub realized_pnl: Option<f64>,
+    /// Unrealized PnL (`UnrealizedPnL`).
+    pub unrealized_pnl: Option<f64>,
+    /// Exchange rate to the account base currency (`ExchangeRate`).
+    pub exchange_rate: Option<f64>,
+}
+
+impl CurrencyBalances {
+    fn field_mut(&mut self, key: &str) -> Option<&mut Option<f64>> {
+        match key {
+            "CashBalance" => Some(&mut self.cash_balance),
+            "TotalCashBalance" => Some(&mut self.total_cash_balance),
+            "SettledCash" => Some(&mut self.settled_cash),
+            "AccruedCash" => Some(&mut self.accrued_cash),
+            "AccruedDividend" => Some(&mut self.accrued_dividend),
+            "NetLiquidationByCurrency" => Some(&mut self.net_liquidation),
+            "RealizedPnL" => Some(&mut self.realized_pnl),
+            "UnrealizedPnL" => Some(&mut self.unrealized_pnl),
+            "ExchangeRate" => Some(&mut self.exchange_rate),
+            _ => None,
+        }
+    }
+}
+
+/// Per currency cash ledger maintained from account updates.
+///
+/// Balances in the account base currency are reported under [Currency::BASE].
+///
+/// ```no_run
+/// use ibapi::accounts::{AccountUpdate, CashLedger};
+/// use ibapi::money::Currency;
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut ledger = CashLedger::new();
+///
+/// let subscription = client.account_updates("DU1234567").expect("error requesting account updates");
+/// for update in &subscription {
+///     ledger.apply(&update);
+///
+///     if let AccountUpdate::End = update {
+///         subscription.cancel();
+///     }
+/// }
+///
+/// println!("settled USD: {:?}", ledger.settled_cash(&Currency::USD));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CashLedger {
+    balances: HashMap<Currency, CurrencyBalances>,
+}
+
+impl CashLedger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an account value. Returns `true` if the value is a cash balance tracked by the ledger.
+    pub fn update(&mut self, value: &AccountValue) -> bool {
+        if value.currency.is_empty() {
+            return false;
+        }
+
+        let currency = Currency::new(&value.currency);
+        let mut balances = self.balances.get(&currency).cloned().unwrap_or_default();
+        let Some(field) = balances.field_mut(&value.key) else {
+            return false;
+        };
+        let Ok(amount) = value.value.parse::<f64>() else {
+            return false;
+        };
+        *field = Some(amount);
+
+        self.balances.insert(currency, balances);
+        true
+    }
+
+    /// Records the account value carried by an account update. Other updates are ignored.
+    pub fn apply(&mut self, update: &AccountUpdate) -> bool {
+        match update {
+            AccountUpdate::AccountValue(value) => self.update(value),
+            _ => false,
+        }
+    }
+
+    /// Currencies with balances in the ledger.
+    pub fn currencies(&self) -> impl Iterator<Item = &Currency> {
+        self.balances.keys()
+    }
+
+    /// All balances held in `currency`.
+    pub fn balances(&self, currency: &Currency) -> Option<&CurrencyBalances> {
+        self.balances.get(currency)
+    }
+
+    /// Cash balance in `currency`.
+    pub fn cash_balance(&self, currency: &Currency) -> Option<Money> {
+        self.money(currency, |balances| balances.cash_balance)
+    }
+
+    /// Total cash balance in `currency`.
+    pub fn total_cash_balance(&self, currency: &Currency) -> Option<Money> {
+        self.money(currency, |balances| balances.total_cash_balance)
+    }
+
+    /// Settled cash in `currency`.
+    pub fn settled_cash(&self, currency: &Currency) -> Option<Money> {
+        self.money(currency, |balances| balances.settled_cash)
+    }
+
+    /// Accrued interest in `currency`.
+    pub fn accrued_cash(&self, currency: &Currency) -> Option<Money> {
+        self.money(currency, |balances| balances.accrued_cash)
+    }
+
+    /// Accrued dividends in `currency`.
+    pub fn accrued_dividend(&self, currency: &Currency) -> Option<Money> {
+        self.money(currency, |balances| balances.accrued_dividend)
+    }
+
+    /// Net liquidation value in `currency`.
+    pub fn net_liquidation(&self, currency: &Currency) -> Option<Money> {
+        self.money(currency, |balances| balances.net_liquidation)
+    }
+
+    /// Exchange rate from `currency` to the account base currency.
+    pub fn exchange_rate(&self, currency: &Currency) -> Option<f64> {
+        self.balances.get(currency)?.exchange_rate
+    }
+
+    fn money(&self, currency: &Currency, field: impl Fn(&CurrencyBalances) -> Option<f64>) -> Option<Money> {
+        let amount = field(self.balances.get(currency)?)?;
+        Some(Money::new(amount, currency.clone()))
+    }
+}
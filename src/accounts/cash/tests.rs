@@ -0,0 +1,77 @@
+use super::*;
+
+fn account_value(key: &str, value: &str, currency: &str) -> AccountValue {
+    AccountValue {
+        key: key.to_owned(),
+        value: value.to_owned(),
+        currency: currency.to_owned(),
+        account: Some("DU1234567".to_owned()),
+    }
+}
+
+#[test]
+fn test_cash_ledger() {
+    let mut ledger = CashLedger::new();
+
+    assert!(ledger.update(&account_value("CashBalance", "1000.50", "USD")), "CashBalance");
+    assert!(ledger.update(&account_value("SettledCash", "900.00", "USD")), "SettledCash");
+    assert!(ledger.update(&account_value("AccruedCash", "1.25", "USD")), "AccruedCash");
+    assert!(ledger.update(&account_value("CashBalance", "-250.00", "EUR")), "EUR CashBalance");
+    assert!(ledger.update(&account_value("ExchangeRate", "1.08", "EUR")), "ExchangeRate");
+
+    assert_eq!(
+        ledger.cash_balance(&Currency::USD),
+        Some(Money::new(1000.50, Currency::USD)),
+        "USD cash balance"
+    );
+    assert_eq!(
+        ledger.settled_cash(&Currency::USD),
+        Some(Money::new(900.00, Currency::USD)),
+        "USD settled cash"
+    );
+    assert_eq!(
+        ledger.accrued_cash(&Currency::USD),
+        Some(Money::new(1.25, Currency::USD)),
+        "USD accrued cash"
+    );
+    assert_eq!(
+        ledger.cash_balance(&Currency::EUR),
+        Some(Money::new(-250.00, Currency::EUR)),
+        "EUR cash balance"
+    );
+    assert_eq!(ledger.exchange_rate(&Currency::EUR), Some(1.08), "EUR exchange rate");
+
+    assert_eq!(ledger.settled_cash(&Currency::EUR), None, "EUR settled cash not reported");
+    assert_eq!(ledger.cash_balance(&Currency::JPY), None, "JPY not held");
+
+    let mut currencies: Vec<&str> = ledger.currencies().map(|currency| currency.code()).collect();
+    currencies.sort();
+    assert_eq!(currencies, vec!["EUR", "USD"], "ledger.currencies()");
+}
+
+#[test]
+fn test_cash_ledger_ignores_other_values() {
+    let mut ledger = CashLedger::new();
+
+    assert!(!ledger.update(&account_value("AccountType", "INDIVIDUAL", "")), "no currency");
+    assert!(!ledger.update(&account_value("BuyingPower", "4000.00", "USD")), "untracked key");
+    assert!(!ledger.update(&account_value("CashBalance", "n/a", "USD")), "not a number");
+    assert!(!ledger.apply(&AccountUpdate::End), "end of updates");
+
+    assert_eq!(ledger.currencies().count(), 0, "ledger.currencies()");
+}
+
+#[test]
+fn test_cash_ledger_apply() {
+    let mut ledger = CashLedger::new();
+
+    assert!(
+        ledger.apply(&AccountUpdate::AccountValue(account_value("TotalCashBalance", "12.5", "BASE"))),
+        "apply"
+    );
+    assert_eq!(
+        ledger.total_cash_balance(&Currency::BASE),
+        Some(Money::new(12.5, Currency::BASE)),
+        "base total cash balance"
+    );
+}
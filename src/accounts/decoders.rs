@@ -2,6 +2,8 @@ use crate::contracts::{Contract, SecurityType};
 use crate::messages::ResponseMessage;
 use crate::{server_versions, Error};
 
+#[cfg(feature = "fa")]
+use super::fa::{FaConfiguration, FaDataType};
 use super::{
     AccountMultiValue, AccountPortfolioValue, AccountSummary, AccountUpdateTime, AccountValue, FamilyCode, PnL, PnLSingle, Position, PositionMulti,
 };
@@ -69,6 +71,13 @@ pub(crate) fn decode_position_multi(message: &mut ResponseMessage) -> Result<Pos
     Ok(position)
 }
 
+pub(super) fn decode_user_info(message: &mut ResponseMessage) -> Result<String, Error> {
+    message.skip(); // message type
+    message.skip(); // request id
+
+    message.next_string()
+}
+
 pub(crate) fn decode_family_codes(message: &mut ResponseMessage) -> Result<Vec<FamilyCode>, Error> {
     message.skip(); // message type
 
@@ -78,7 +87,7 @@ pub(crate) fn decode_family_codes(message: &mut ResponseMessage) -> Result<Vec<F
         return Ok(Vec::default());
     }
 
-    let mut family_codes: Vec<FamilyCode> = Vec::with_capacity(family_codes_count as usize);
+    let mut family_codes: Vec<FamilyCode> = Vec::new();
 
     for _ in 0..family_codes_count {
         let family_code = FamilyCode {
@@ -95,14 +104,14 @@ pub(crate) fn decode_pnl(server_version: i32, message: &mut ResponseMessage) ->
     message.skip(); // message type
     message.skip(); // request id
 
-    let daily_pnl = message.next_double()?;
+    let daily_pnl = message.next_optional_double()?;
     let unrealized_pnl = if server_version >= server_versions::UNREALIZED_PNL {
-        Some(message.next_double()?)
+        message.next_optional_double()?
     } else {
         None
     };
     let realized_pnl = if server_version >= server_versions::REALIZED_PNL {
-        Some(message.next_double()?)
+        message.next_optional_double()?
     } else {
         None
     };
@@ -118,11 +127,11 @@ pub(crate) fn decode_pnl_single(_server_version: i32, message: &mut ResponseMess
     message.skip(); // message type
     message.skip(); // request id
 
-    let position = message.next_double()?;
-    let daily_pnl = message.next_double()?;
-    let unrealized_pnl = message.next_double()?;
-    let realized_pnl = message.next_double()?;
-    let value = message.next_double()?;
+    let position = message.next_decimal()?;
+    let daily_pnl = message.next_optional_double()?;
+    let unrealized_pnl = message.next_optional_double()?;
+    let realized_pnl = message.next_optional_double()?;
+    let value = message.next_optional_double()?;
 
     Ok(PnLSingle {
         position,
@@ -241,3 +250,14 @@ pub(crate) fn decode_account_multi_value(message: &mut ResponseMessage) -> Resul
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(feature = "fa")]
+pub(crate) fn decode_receive_fa(message: &mut ResponseMessage) -> Result<FaConfiguration, Error> {
+    message.skip(); // message type
+    message.skip(); // message version
+
+    let data_type = FaDataType::try_from(message.next_int()?)?;
+    let xml = message.next_string()?;
+
+    FaConfiguration::parse(data_type, &xml)
+}
@@ -1,3 +1,4 @@
+use crate::money::{Currency, Money};
 use crate::{accounts::AccountSummaryTags, server_versions, testdata::responses};
 
 #[test]
@@ -58,6 +59,15 @@ fn test_decode_position_multi() {
     assert_eq!(position.average_cost, 196.77, "position.average_cost");
 }
 
+#[test]
+fn test_decode_user_info() {
+    let mut message = super::ResponseMessage::from("107\09000\0BRAND1\0");
+
+    let white_branding_id = super::decode_user_info(&mut message).expect("error decoding user info");
+
+    assert_eq!(white_branding_id, "BRAND1", "white_branding_id");
+}
+
 #[test]
 fn test_decode_family_codes() {
     let mut message = super::ResponseMessage::from("78\01\0*\0\0");
@@ -74,7 +84,7 @@ fn test_decode_pnl() {
 
     let pnl = super::decode_pnl(server_versions::REALIZED_PNL, &mut message).expect("error decoding pnl");
 
-    assert_eq!(pnl.daily_pnl, 0.10, "pnl.daily_pnl");
+    assert_eq!(pnl.daily_pnl, Some(0.10), "pnl.daily_pnl");
     assert_eq!(pnl.unrealized_pnl, Some(0.20), "pnl.unrealized_pnl");
     assert_eq!(pnl.realized_pnl, Some(0.30), "pnl.realized_pnl");
 
@@ -82,7 +92,7 @@ fn test_decode_pnl() {
 
     let pnl = super::decode_pnl(server_versions::UNREALIZED_PNL, &mut message).expect("error decoding pnl");
 
-    assert_eq!(pnl.daily_pnl, 0.10, "pnl.daily_pnl");
+    assert_eq!(pnl.daily_pnl, Some(0.10), "pnl.daily_pnl");
     assert_eq!(pnl.unrealized_pnl, Some(0.20), "pnl.unrealized_pnl");
     assert_eq!(pnl.realized_pnl, None, "pnl.realized_pnl");
 
@@ -90,11 +100,22 @@ fn test_decode_pnl() {
 
     let pnl = super::decode_pnl(server_versions::PNL, &mut message).expect("error decoding pnl");
 
-    assert_eq!(pnl.daily_pnl, 0.10, "pnl.daily_pnl");
+    assert_eq!(pnl.daily_pnl, Some(0.10), "pnl.daily_pnl");
     assert_eq!(pnl.unrealized_pnl, None, "pnl.unrealized_pnl");
     assert_eq!(pnl.realized_pnl, None, "pnl.realized_pnl");
 }
 
+#[test]
+fn test_decode_pnl_unset_values() {
+    let mut message = super::ResponseMessage::from("94\09000\01.7976931348623157E308\01.7976931348623157E308\00.3\0");
+
+    let pnl = super::decode_pnl(server_versions::REALIZED_PNL, &mut message).expect("error decoding pnl");
+
+    assert_eq!(pnl.daily_pnl, None, "pnl.daily_pnl");
+    assert_eq!(pnl.unrealized_pnl, None, "pnl.unrealized_pnl");
+    assert_eq!(pnl.realized_pnl, Some(0.30), "pnl.realized_pnl");
+}
+
 #[test]
 fn test_decode_pnl_single() {
     let mut message = super::ResponseMessage::from("95\09000\0100.0\00.1\00.2\00.3\00.4\0");
@@ -102,10 +123,22 @@ fn test_decode_pnl_single() {
     let pnl_single = super::decode_pnl_single(server_versions::REALIZED_PNL, &mut message).expect("error decoding pnl single");
 
     assert_eq!(pnl_single.position, 100., "pnl_single.position");
-    assert_eq!(pnl_single.daily_pnl, 0.10, "pnl_single.daily_pnl");
-    assert_eq!(pnl_single.unrealized_pnl, 0.20, "pnl_single.unrealized_pnl");
-    assert_eq!(pnl_single.realized_pnl, 0.30, "pnl_single.realized_pnl");
-    assert_eq!(pnl_single.value, 0.40, "pnl_single.value");
+    assert_eq!(pnl_single.daily_pnl, Some(0.10), "pnl_single.daily_pnl");
+    assert_eq!(pnl_single.unrealized_pnl, Some(0.20), "pnl_single.unrealized_pnl");
+    assert_eq!(pnl_single.realized_pnl, Some(0.30), "pnl_single.realized_pnl");
+    assert_eq!(pnl_single.value, Some(0.40), "pnl_single.value");
+}
+
+#[test]
+fn test_decode_pnl_single_unset_values() {
+    let mut message = super::ResponseMessage::from("95\09000\0100\0-12.5\01.7976931348623157E308\01.7976931348623157E308\01.7976931348623157E308\0");
+
+    let pnl_single = super::decode_pnl_single(server_versions::REALIZED_PNL, &mut message).expect("error decoding pnl single");
+
+    assert_eq!(pnl_single.daily_pnl, Some(-12.5), "pnl_single.daily_pnl");
+    assert_eq!(pnl_single.unrealized_pnl, None, "pnl_single.unrealized_pnl");
+    assert_eq!(pnl_single.realized_pnl, None, "pnl_single.realized_pnl");
+    assert_eq!(pnl_single.value, None, "pnl_single.value");
 }
 
 #[test]
@@ -118,6 +151,7 @@ fn test_decode_account_summary() {
     assert_eq!(account_summary.tag, AccountSummaryTags::ACCOUNT_TYPE, "account_summary.tag");
     assert_eq!(account_summary.value, "FA", "account_summary.value");
     assert_eq!(account_summary.currency, "", "account_summary.currency");
+    assert_eq!(account_summary.money(), None, "account_summary.money()");
 }
 
 #[test]
@@ -131,4 +165,16 @@ fn test_decode_account_multi_value() {
     assert_eq!(value.key, "Currency", "value.key");
     assert_eq!(value.value, "USD", "value.value");
     assert_eq!(value.currency, "USD", "value.currency");
+    assert_eq!(value.money(), None, "value.money()");
+}
+
+#[test]
+fn test_account_multi_value_money() {
+    let mut message = super::ResponseMessage::from_simple(responses::ACCOUNT_UPDATE_MULTI_CASH_BALANCE);
+    let value = super::decode_account_multi_value(&mut message).expect("error decoding account multi value");
+    assert_eq!(value.money(), Some(Money::new(94629.71, Currency::USD)), "cash balance");
+
+    let mut message = super::ResponseMessage::from_simple(responses::ACCOUNT_UPDATE_MULTI_STOCK_MARKET_VALUE);
+    let value = super::decode_account_multi_value(&mut message).expect("error decoding account multi value");
+    assert_eq!(value.money(), Some(Money::zero(Currency::BASE)), "stock market value");
 }
@@ -2,6 +2,9 @@ use crate::messages::OutgoingMessages;
 use crate::messages::RequestMessage;
 use crate::Error;
 
+#[cfg(feature = "fa")]
+use super::fa::FaDataType;
+
 #[cfg(test)]
 mod tests;
 
@@ -93,6 +96,15 @@ pub(super) fn encode_request_managed_accounts() -> Result<RequestMessage, Error>
     encode_simple(OutgoingMessages::RequestManagedAccounts, VERSION)
 }
 
+pub(super) fn encode_request_user_info(request_id: i32) -> Result<RequestMessage, Error> {
+    let mut message = RequestMessage::new();
+
+    message.push_field(&OutgoingMessages::RequestUserInfo);
+    message.push_field(&request_id);
+
+    Ok(message)
+}
+
 pub(super) fn encode_request_account_updates(server_version: i32, account: &str) -> Result<RequestMessage, Error> {
     const VERSION: i32 = 2;
 
@@ -159,6 +171,34 @@ pub(super) fn encode_request_server_time() -> Result<RequestMessage, Error> {
     encode_simple(OutgoingMessages::RequestCurrentTime, VERSION)
 }
 
+#[cfg(feature = "fa")]
+pub(super) fn encode_request_fa(data_type: FaDataType) -> Result<RequestMessage, Error> {
+    const VERSION: i32 = 1;
+
+    let mut message = RequestMessage::new();
+
+    message.push_field(&OutgoingMessages::RequestFA);
+    message.push_field(&VERSION);
+    message.push_field(&data_type);
+
+    Ok(message)
+}
+
+#[cfg(feature = "fa")]
+pub(super) fn encode_replace_fa(request_id: i32, data_type: FaDataType, xml: &str) -> Result<RequestMessage, Error> {
+    const VERSION: i32 = 1;
+
+    let mut message = RequestMessage::new();
+
+    message.push_field(&OutgoingMessages::ReplaceFA);
+    message.push_field(&VERSION);
+    message.push_field(&data_type);
+    message.push_field(&xml);
+    message.push_field(&request_id);
+
+    Ok(message)
+}
+
 fn encode_simple(message_type: OutgoingMessages, version: i32) -> Result<RequestMessage, Error> {
     let mut message = RequestMessage::new();
 
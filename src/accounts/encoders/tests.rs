@@ -54,13 +54,20 @@ fn test_encode_request_family_codes() {
     assert_eq!(message[1], "1", "message.version");
 }
 
+#[test]
+fn test_encode_request_user_info() {
+    let message = super::encode_request_user_info(9000).expect("error encoding request");
+
+    assert_eq!(message.encode_simple(), "104|9000|");
+}
+
 #[test]
 fn test_encode_request_pnl() {
     let request_id = 3000;
     let account = "DU1234567";
     let model_code: Option<&str> = None;
 
-    let request = super::encode_request_pnl(request_id, &account, model_code).expect("encode request pnl failed");
+    let request = super::encode_request_pnl(request_id, account, model_code).expect("encode request pnl failed");
 
     assert_eq!(request[0], OutgoingMessages::RequestPnL.to_field(), "message.type");
     assert_eq!(request[1], request_id.to_field(), "message.request_id");
@@ -75,7 +82,7 @@ fn test_encode_request_pnl_single() {
     let model_code: Option<&str> = None;
     let contract_id = 1001;
 
-    let request = super::encode_request_pnl_single(request_id, &account, contract_id, model_code).expect("encode request pnl failed");
+    let request = super::encode_request_pnl_single(request_id, account, contract_id, model_code).expect("encode request pnl failed");
 
     assert_eq!(request[0], OutgoingMessages::RequestPnLSingle.to_field(), "message.type");
     assert_eq!(request[1], request_id.to_field(), "message.request_id");
@@ -106,7 +113,7 @@ fn test_encode_request_account_updates() {
     let version = 2;
     let account = "DU1234567";
 
-    let request = super::encode_request_account_updates(server_version, &account).expect("encode request account updates");
+    let request = super::encode_request_account_updates(server_version, account).expect("encode request account updates");
 
     assert_eq!(request[0], OutgoingMessages::RequestAccountData.to_field(), "message.type");
     assert_eq!(request[1], version.to_field(), "message.version");
@@ -114,7 +121,7 @@ fn test_encode_request_account_updates() {
 
     let server_version = 10;
 
-    let request = super::encode_request_account_updates(server_version, &account).expect("encode request account updates");
+    let request = super::encode_request_account_updates(server_version, account).expect("encode request account updates");
 
     assert_eq!(request[0], OutgoingMessages::RequestAccountData.to_field(), "message.type");
     assert_eq!(request[1], version.to_field(), "message.version");
@@ -151,7 +158,7 @@ fn test_encode_request_account_updates_multi() {
     let account = "DU1234567";
     let model_code = None;
 
-    let request = super::encode_request_account_updates_multi(request_id, Some(&account), model_code).expect("encode request account updates");
+    let request = super::encode_request_account_updates_multi(request_id, Some(account), model_code).expect("encode request account updates");
 
     assert_eq!(request[0], OutgoingMessages::RequestAccountUpdatesMulti.to_field(), "message.type");
     assert_eq!(request[1], version.to_field(), "message.version");
@@ -160,3 +167,28 @@ fn test_encode_request_account_updates_multi() {
     assert_eq!(request[4], model_code.to_field(), "message.model_code");
     assert_eq!(request[5], true.to_field(), "message.subscribe");
 }
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_encode_request_fa() {
+    let message = super::encode_request_fa(FaDataType::Groups).expect("error encoding request");
+
+    assert_eq!(message[0], OutgoingMessages::RequestFA.to_field(), "message.type");
+    assert_eq!(message[1], "1", "message.version");
+    assert_eq!(message[2], FaDataType::Groups.to_field(), "message.data_type");
+}
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_encode_replace_fa() {
+    let request_id = 9000;
+    let xml = "<ListOfGroups></ListOfGroups>";
+
+    let message = super::encode_replace_fa(request_id, FaDataType::Groups, xml).expect("error encoding request");
+
+    assert_eq!(message[0], OutgoingMessages::ReplaceFA.to_field(), "message.type");
+    assert_eq!(message[1], "1", "message.version");
+    assert_eq!(message[2], FaDataType::Groups.to_field(), "message.data_type");
+    assert_eq!(message[3], xml, "message.xml");
+    assert_eq!(message[4], request_id.to_field(), "message.request_id");
+}
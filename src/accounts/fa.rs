@@ -0,0 +1,272 @@
+//! Financial advisor (FA) configuration.
+//!
+//! Advisor accounts allocate orders across managed accounts using groups, profiles and account aliases.
+//! TWS exchanges these configurations as XML documents which are decoded into the types in this module
+//! and can be serialized back to XML when replacing a configuration.
+
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::xml::{escape, Element};
+use crate::{Error, ToField};
+
+#[cfg(test)]
+mod tests;
+
+/// Type of financial advisor configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FaDataType {
+    /// Account groups.
+    Groups = 1,
+    /// Allocation profiles. Not supported by TWS 10.16 and later, where profiles are merged into groups.
+    Profiles = 2,
+    /// Account aliases.
+    Aliases = 3,
+}
+
+impl ToField for FaDataType {
+    fn to_field(&self) -> String {
+        (*self as i32).to_string()
+    }
+}
+
+impl TryFrom<i32> for FaDataType {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(FaDataType::Groups),
+            2 => Ok(FaDataType::Profiles),
+            3 => Ok(FaDataType::Aliases),
+            _ => Err(Error::InvalidFormat {
+                kind: "FA data type",
+                reason: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// A group of accounts orders can be allocated across.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Group {
+    /// Name of the group.
+    pub name: String,
+    /// Default allocation method, e.g. NetLiq, AvailableEquity, Equal or PctChange.
+    pub default_method: String,
+    /// Accounts in the group.
+    pub accounts: Vec<GroupAccount>,
+}
+
+/// An account in an allocation [Group].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GroupAccount {
+    /// Account identifier.
+    pub account: String,
+    /// Allocation amount for methods that use explicit amounts (ratios, percentages or shares).
+    pub amount: Option<f64>,
+}
+
+/// An allocation profile.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Profile {
+    /// Name of the profile.
+    pub name: String,
+    /// Allocation type: 1 = percentages, 2 = financial ratios, 3 = shares.
+    pub profile_type: i32,
+    /// Allocations in the profile.
+    pub allocations: Vec<Allocation>,
+}
+
+/// Allocation of an account in a [Profile].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Allocation {
+    /// Account identifier.
+    pub account: String,
+    /// Allocation amount.
+    pub amount: f64,
+}
+
+/// Alias assigned to an account.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Alias {
+    /// Account identifier.
+    pub account: String,
+    /// Alias of the account.
+    pub alias: String,
+}
+
+/// Financial advisor configuration as exchanged with TWS.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FaConfiguration {
+    Groups(Vec<Group>),
+    Profiles(Vec<Profile>),
+    Aliases(Vec<Alias>),
+}
+
+impl FaConfiguration {
+    /// Returns the [FaDataType] of the configuration.
+    pub fn data_type(&self) -> FaDataType {
+        match self {
+            FaConfiguration::Groups(_) => FaDataType::Groups,
+            FaConfiguration::Profiles(_) => FaDataType::Profiles,
+            FaConfiguration::Aliases(_) => FaDataType::Aliases,
+        }
+    }
+
+    /// Parses the XML document TWS returns for the given [FaDataType].
+    pub fn parse(data_type: FaDataType, xml: &str) -> Result<FaConfiguration, Error> {
+        let root = Element::parse(xml)?;
+
+        match data_type {
+            FaDataType::Groups => Ok(FaConfiguration::Groups(
+                root.children_named("Group").map(parse_group).collect::<Result<_, _>>()?,
+            )),
+            FaDataType::Profiles => Ok(FaConfiguration::Profiles(
+                root.children_named("AllocationProfile").map(parse_profile).collect::<Result<_, _>>()?,
+            )),
+            FaDataType::Aliases => Ok(FaConfiguration::Aliases(root.children_named("AccountAlias").map(parse_alias).collect())),
+        }
+    }
+
+    /// Serializes the configuration to the XML document format expected by TWS.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        match self {
+            FaConfiguration::Groups(groups) => {
+                xml.push_str("<ListOfGroups>\n");
+                for group in groups {
+                    xml.push_str("  <Group>\n");
+                    let _ = writeln!(xml, "    <name>{}</name>", escape(&group.name));
+                    xml.push_str("    <ListOfAccts varName=\"list\">\n");
+                    for account in &group.accounts {
+                        let _ = write!(xml, "      <Account><acct>{}</acct>", escape(&account.account));
+                        if let Some(amount) = account.amount {
+                            let _ = write!(xml, "<amount>{amount}</amount>");
+                        }
+                        xml.push_str("</Account>\n");
+                    }
+                    xml.push_str("    </ListOfAccts>\n");
+                    let _ = writeln!(xml, "    <defaultMethod>{}</defaultMethod>", escape(&group.default_method));
+                    xml.push_str("  </Group>\n");
+                }
+                xml.push_str("</ListOfGroups>\n");
+            }
+            FaConfiguration::Profiles(profiles) => {
+                xml.push_str("<ListOfAllocationProfiles>\n");
+                for profile in profiles {
+                    xml.push_str("  <AllocationProfile>\n");
+                    let _ = writeln!(xml, "    <name>{}</name>", escape(&profile.name));
+                    let _ = writeln!(xml, "    <type>{}</type>", profile.profile_type);
+                    xml.push_str("    <ListOfAllocations varName=\"listOfAllocations\">\n");
+                    for allocation in &profile.allocations {
+                        let _ = writeln!(
+                            xml,
+                            "      <Allocation><acct>{}</acct><amount>{}</amount></Allocation>",
+                            escape(&allocation.account),
+                            allocation.amount
+                        );
+                    }
+                    xml.push_str("    </ListOfAllocations>\n");
+                    xml.push_str("  </AllocationProfile>\n");
+                }
+                xml.push_str("</ListOfAllocationProfiles>\n");
+            }
+            FaConfiguration::Aliases(aliases) => {
+                xml.push_str("<ListOfAccountAliases>\n");
+                for alias in aliases {
+                    let _ = writeln!(
+                        xml,
+                        "  <AccountAlias><account>{}</account><alias>{}</alias></AccountAlias>",
+                        escape(&alias.account),
+                        escape(&alias.alias)
+                    );
+                }
+                xml.push_str("</ListOfAccountAliases>\n");
+            }
+        }
+
+        xml
+    }
+}
+
+fn parse_group(element: &Element) -> Result<Group, Error> {
+    let mut accounts = Vec::new();
+
+    if let Some(list) = element.child("ListOfAccts") {
+        for account in &list.children {
+            match account.name.as_str() {
+                // Older TWS versions list bare account identifiers.
+                "String" => accounts.push(GroupAccount {
+                    account: account.text.trim().to_owned(),
+                    amount: None,
+                }),
+                "Account" => accounts.push(GroupAccount {
+                    account: account.child_text("acct").to_owned(),
+                    amount: parse_optional_amount(account.child_text("amount"))?,
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Group {
+        name: element.child_text("name").to_owned(),
+        default_method: element.child_text("defaultMethod").to_owned(),
+        accounts,
+    })
+}
+
+fn parse_profile(element: &Element) -> Result<Profile, Error> {
+    let mut allocations = Vec::new();
+
+    if let Some(list) = element.child("ListOfAllocations") {
+        for allocation in list.children_named("Allocation") {
+            allocations.push(Allocation {
+                account: allocation.child_text("acct").to_owned(),
+                amount: parse_optional_amount(allocation.child_text("amount"))?.unwrap_or_default(),
+            });
+        }
+    }
+
+    let profile_type = match element.child_text("type") {
+        "" => 0,
+        text => text.parse().map_err(|e| Error::InvalidFormat {
+            kind: "FA profile type",
+            reason: format!("{text}: {e}"),
+        })?,
+    };
+
+    Ok(Profile {
+        name: element.child_text("name").to_owned(),
+        profile_type,
+        allocations,
+    })
+}
+
+fn parse_alias(element: &Element) -> Alias {
+    Alias {
+        account: element.child_text("account").to_owned(),
+        alias: element.child_text("alias").to_owned(),
+    }
+}
+
+fn parse_optional_amount(amount: &str) -> Result<Option<f64>, Error> {
+    if amount.is_empty() {
+        return Ok(None);
+    }
+
+    amount.parse().map(Some).map_err(|e| Error::InvalidFormat {
+        kind: "FA amount",
+        reason: format!("{amount}: {e}"),
+    })
+}
@@ -0,0 +1,118 @@
+use super::*;
+
+const GROUPS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListOfGroups>
+  <Group>
+    <name>Equal</name>
+    <ListOfAccts varName="list">
+      <Account><acct>DU1234567</acct><amount>0.0</amount></Account>
+      <Account><acct>DU7654321</acct></Account>
+    </ListOfAccts>
+    <defaultMethod>Equal</defaultMethod>
+  </Group>
+  <Group>
+    <name>Legacy</name>
+    <ListOfAccts varName="list">
+      <String>DU1234567</String>
+    </ListOfAccts>
+    <defaultMethod>NetLiq</defaultMethod>
+  </Group>
+</ListOfGroups>"#;
+
+const PROFILES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListOfAllocationProfiles>
+  <AllocationProfile>
+    <name>Percent</name>
+    <type>1</type>
+    <ListOfAllocations varName="listOfAllocations">
+      <Allocation><acct>DU1234567</acct><amount>60.0</amount></Allocation>
+      <Allocation><acct>DU7654321</acct><amount>40.0</amount></Allocation>
+    </ListOfAllocations>
+  </AllocationProfile>
+</ListOfAllocationProfiles>"#;
+
+const ALIASES_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListOfAccountAliases>
+  <AccountAlias><account>DU1234567</account><alias>Growth &amp; Income</alias></AccountAlias>
+</ListOfAccountAliases>"#;
+
+#[test]
+fn test_parse_groups() {
+    let configuration = FaConfiguration::parse(FaDataType::Groups, GROUPS_XML).expect("error parsing groups");
+
+    assert_eq!(configuration.data_type(), FaDataType::Groups, "configuration.data_type()");
+
+    let FaConfiguration::Groups(groups) = configuration else {
+        panic!("groups expected");
+    };
+
+    assert_eq!(groups.len(), 2, "groups.len()");
+    assert_eq!(groups[0].name, "Equal", "groups[0].name");
+    assert_eq!(groups[0].default_method, "Equal", "groups[0].default_method");
+    assert_eq!(
+        groups[0].accounts,
+        vec![
+            GroupAccount {
+                account: "DU1234567".into(),
+                amount: Some(0.0)
+            },
+            GroupAccount {
+                account: "DU7654321".into(),
+                amount: None
+            },
+        ],
+        "groups[0].accounts"
+    );
+    assert_eq!(groups[1].accounts[0].account, "DU1234567", "groups[1].accounts[0].account");
+    assert_eq!(groups[1].default_method, "NetLiq", "groups[1].default_method");
+}
+
+#[test]
+fn test_parse_profiles() {
+    let configuration = FaConfiguration::parse(FaDataType::Profiles, PROFILES_XML).expect("error parsing profiles");
+
+    let FaConfiguration::Profiles(profiles) = configuration else {
+        panic!("profiles expected");
+    };
+
+    assert_eq!(profiles.len(), 1, "profiles.len()");
+    assert_eq!(profiles[0].name, "Percent", "profiles[0].name");
+    assert_eq!(profiles[0].profile_type, 1, "profiles[0].profile_type");
+    assert_eq!(profiles[0].allocations[0].amount, 60.0, "profiles[0].allocations[0].amount");
+    assert_eq!(profiles[0].allocations[1].account, "DU7654321", "profiles[0].allocations[1].account");
+}
+
+#[test]
+fn test_parse_aliases() {
+    let configuration = FaConfiguration::parse(FaDataType::Aliases, ALIASES_XML).expect("error parsing aliases");
+
+    assert_eq!(
+        configuration,
+        FaConfiguration::Aliases(vec![Alias {
+            account: "DU1234567".into(),
+            alias: "Growth & Income".into()
+        }])
+    );
+}
+
+#[test]
+fn test_to_xml_round_trip() {
+    for (data_type, xml) in [
+        (FaDataType::Groups, GROUPS_XML),
+        (FaDataType::Profiles, PROFILES_XML),
+        (FaDataType::Aliases, ALIASES_XML),
+    ] {
+        let configuration = FaConfiguration::parse(data_type, xml).expect("error parsing configuration");
+        let reparsed = FaConfiguration::parse(data_type, &configuration.to_xml()).expect("error parsing serialized configuration");
+
+        assert_eq!(reparsed, configuration, "round trip of {data_type:?}");
+    }
+}
+
+#[test]
+fn test_fa_data_type() {
+    assert_eq!(FaDataType::Groups.to_field(), "1");
+    assert_eq!(FaDataType::Aliases.to_field(), "3");
+    assert_eq!(FaDataType::try_from(2).unwrap(), FaDataType::Profiles);
+    assert!(FaDataType::try_from(4).is_err());
+}
@@ -0,0 +1,268 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
+
+use super::{PnL, PnLResetDetector};
+use crate::client::Subscription;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// A timestamped [PnL] observation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PnLSample {
+    /// Time the sample was recorded.
+    pub timestamp: OffsetDateTime,
+    /// Daily PnL at the time of the sample. None if not yet available.
+    pub daily_pnl: Option<f64>,
+    /// Unrealized PnL at the time of the sample.
+    pub unrealized_pnl: Option<f64>,
+    /// Realized PnL at the time of the sample.
+    pub realized_pnl: Option<f64>,
+}
+
+/// Records a timestamped history of [PnL] updates, in memory and optionally appended to a file.
+///
+/// Samples are grouped into trading days at the daily PnL resets found by a [PnLResetDetector], rather than at midnight UTC.
+/// By default resets are inferred from the PnL values; configure the account's reset time with [PnLHistory::with_reset_detector].
+///
+/// ```no_run
+/// use ibapi::accounts::{PnLHistory, PnLResetDetector};
+/// use ibapi::Client;
+/// use time::macros::time;
+/// use time_tz::timezones;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut history = PnLHistory::with_file("pnl.csv")
+///     .expect("error opening history file")
+///     .with_reset_detector(PnLResetDetector::new().with_reset_time(time!(17:00), timezones::db::america::NEW_YORK));
+/// let subscription = client.pnl("DU1234567", None).expect("error requesting pnl");
+///
+/// for pnl in subscription.iter().take(100) {
+///     history.record(&pnl).expect("error recording pnl");
+/// }
+///
+/// println!("daily curve: {:?}", history.daily_curve());
+/// println!("max drawdown: {}", history.max_drawdown());
+/// ```
+#[derive(Debug, Default)]
+pub struct PnLHistory {
+    samples: Vec<PnLSample>,
+    writer: Option<BufWriter<File>>,
+    reset_detector: PnLResetDetector,
+}
+
+impl PnLHistory {
+    /// Creates an in memory history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a history that appends each sample to the file at `path`, loading any samples already in the file.
+    ///
+    /// Samples are stored one per line as `timestamp,daily_pnl,unrealized_pnl,realized_pnl` with RFC 3339 timestamps.
+    pub fn with_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let samples = if path.exists() { Self::load(path)? } else { Vec::new() };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            samples,
+            writer: Some(BufWriter::new(file)),
+            ..Self::default()
+        })
+    }
+
+    /// Detector used to find the daily PnL resets that separate trading days.
+    pub fn with_reset_detector(mut self, detector: PnLResetDetector) -> Self {
+        self.reset_detector = detector;
+        self
+    }
+
+    /// Reads the samples stored in the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<PnLSample>, Error> {
+        let reader = BufReader::new(File::open(path)?);
+
+        let mut samples = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                samples.push(parse_sample(&line)?);
+            }
+        }
+
+        Ok(samples)
+    }
+
+    /// Records `pnl` with the current time.
+    pub fn record(&mut self, pnl: &PnL) -> Result<(), Error> {
+        self.record_at(OffsetDateTime::now_utc(), pnl)
+    }
+
+    /// Records `pnl` with the given timestamp.
+    pub fn record_at(&mut self, timestamp: OffsetDateTime, pnl: &PnL) -> Result<(), Error> {
+        let sample = PnLSample {
+            timestamp,
+            daily_pnl: pnl.daily_pnl,
+            unrealized_pnl: pnl.unrealized_pnl,
+            realized_pnl: pnl.realized_pnl,
+        };
+
+        if let Some(writer) = self.writer.as_mut() {
+            writeln!(writer, "{}", format_sample(&sample)?)?;
+            writer.flush()?;
+        }
+
+        self.samples.push(sample);
+
+        Ok(())
+    }
+
+    /// Records every update received on `subscription` until the subscription ends.
+    pub fn record_subscription(&mut self, subscription: &Subscription<'_, PnL>) -> Result<(), Error> {
+        for pnl in subscription.iter() {
+            self.record(&pnl)?;
+        }
+
+        match subscription.error() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Recorded samples, in the order they were recorded.
+    pub fn samples(&self) -> &[PnLSample] {
+        &self.samples
+    }
+
+    /// Daily PnL curve: the last daily PnL recorded for each trading day, keyed by the date of the day's last sample.
+    /// Samples without a daily PnL are skipped.
+    pub fn daily_curve(&self) -> Vec<(Date, f64)> {
+        self.trading_days()
+            .iter()
+            .filter_map(|(date, samples)| samples.last().map(|(_, daily_pnl)| (*date, *daily_pnl)))
+            .collect()
+    }
+
+    /// Intraday daily PnL samples recorded on the trading day keyed by `date`. Samples without a daily PnL are skipped.
+    pub fn intraday_curve(&self, date: Date) -> Vec<(OffsetDateTime, f64)> {
+        self.trading_days()
+            .into_iter()
+            .find(|(day, _)| *day == date)
+            .map(|(_, samples)| samples)
+            .unwrap_or_default()
+    }
+
+    /// Largest peak-to-trough decline of cumulative PnL, reported as a non-negative amount.
+    ///
+    /// Cumulative PnL at each sample is the sample's daily PnL plus the closing daily PnL of all prior trading days.
+    pub fn max_drawdown(&self) -> f64 {
+        let mut peak = 0.0;
+        let mut max_drawdown = 0.0;
+        let mut prior_days = 0.0;
+
+        for (_, samples) in self.trading_days() {
+            for (_, daily_pnl) in &samples {
+                let equity = prior_days + daily_pnl;
+                if equity > peak {
+                    peak = equity;
+                }
+                if peak - equity > max_drawdown {
+                    max_drawdown = peak - equity;
+                }
+            }
+
+            if let Some((_, close)) = samples.last() {
+                prior_days += close;
+            }
+        }
+
+        max_drawdown
+    }
+
+    // Samples with a daily PnL, split into trading days at the resets found by the reset detector.
+    fn trading_days(&self) -> Vec<(Date, Vec<(OffsetDateTime, f64)>)> {
+        let mut detector = self.reset_detector.clone();
+        let mut days: Vec<(Date, Vec<(OffsetDateTime, f64)>)> = Vec::new();
+
+        for sample in &self.samples {
+            let Some(daily_pnl) = sample.daily_pnl else {
+                continue;
+            };
+
+            let pnl = PnL {
+                daily_pnl: sample.daily_pnl,
+                unrealized_pnl: sample.unrealized_pnl,
+                realized_pnl: sample.realized_pnl,
+            };
+            let reset = detector.update_at(sample.timestamp, &pnl);
+
+            match days.last_mut() {
+                Some((date, samples)) if !reset => {
+                    *date = sample.timestamp.date();
+                    samples.push((sample.timestamp, daily_pnl));
+                }
+                _ => days.push((sample.timestamp.date(), vec![(sample.timestamp, daily_pnl)])),
+            }
+        }
+
+        days
+    }
+}
+
+fn format_sample(sample: &PnLSample) -> Result<String, Error> {
+    let timestamp = sample
+        .timestamp
+        .format(&Rfc3339)
+        .map_err(|e| invalid(format!("error formatting timestamp: {e}")))?;
+
+    Ok(format!(
+        "{timestamp},{},{},{}",
+        format_optional(sample.daily_pnl),
+        format_optional(sample.unrealized_pnl),
+        format_optional(sample.realized_pnl)
+    ))
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn parse_sample(line: &str) -> Result<PnLSample, Error> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() != 4 {
+        return Err(invalid(line.to_owned()));
+    }
+
+    Ok(PnLSample {
+        timestamp: OffsetDateTime::parse(fields[0], &Rfc3339)?,
+        daily_pnl: parse_optional(fields[1])?,
+        unrealized_pnl: parse_optional(fields[2])?,
+        realized_pnl: parse_optional(fields[3])?,
+    })
+}
+
+fn parse_f64(field: &str) -> Result<f64, Error> {
+    field.parse().map_err(|e| invalid(format!("{field}: {e}")))
+}
+
+fn parse_optional(field: &str) -> Result<Option<f64>, Error> {
+    if field.is_empty() {
+        Ok(None)
+    } else {
+        parse_f64(field).map(Some)
+    }
+}
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidFormat { kind: "pnl history", reason }
+}
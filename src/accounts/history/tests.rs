@@ -0,0 +1,144 @@
+use time::macros::{date, datetime, time};
+use time_tz::timezones;
+
+use super::*;
+
+fn pnl(daily_pnl: f64) -> PnL {
+    PnL {
+        daily_pnl: Some(daily_pnl),
+        unrealized_pnl: Some(daily_pnl * 2.0),
+        realized_pnl: None,
+    }
+}
+
+// Resets at 17:00 in New York, i.e. 22:00 UTC in March 2024.
+fn new_york_reset() -> PnLResetDetector {
+    PnLResetDetector::new().with_reset_time(time!(17:00), timezones::db::america::NEW_YORK)
+}
+
+fn flat() -> PnL {
+    PnL {
+        daily_pnl: Some(0.0),
+        unrealized_pnl: Some(0.0),
+        realized_pnl: Some(0.0),
+    }
+}
+
+fn sample_history() -> PnLHistory {
+    let mut history = PnLHistory::new().with_reset_detector(new_york_reset());
+
+    history.record_at(datetime!(2024-03-04 14:00 UTC), &pnl(100.0)).unwrap();
+    history.record_at(datetime!(2024-03-04 15:00 UTC), &pnl(40.0)).unwrap();
+    history.record_at(datetime!(2024-03-04 20:00 UTC), &pnl(60.0)).unwrap();
+    history.record_at(datetime!(2024-03-05 14:00 UTC), &pnl(-30.0)).unwrap();
+    history.record_at(datetime!(2024-03-05 20:00 UTC), &pnl(10.0)).unwrap();
+
+    history
+}
+
+#[test]
+fn test_daily_curve() {
+    let history = sample_history();
+
+    assert_eq!(history.samples().len(), 5, "history.samples().len()");
+    assert_eq!(
+        history.daily_curve(),
+        vec![(date!(2024 - 03 - 04), 60.0), (date!(2024 - 03 - 05), 10.0)],
+        "history.daily_curve()"
+    );
+    assert_eq!(
+        history.intraday_curve(date!(2024 - 03 - 05)),
+        vec![(datetime!(2024-03-05 14:00 UTC), -30.0), (datetime!(2024-03-05 20:00 UTC), 10.0)],
+        "history.intraday_curve()"
+    );
+}
+
+#[test]
+fn test_max_drawdown() {
+    let history = sample_history();
+
+    // cumulative pnl: 100, 40, 60, 30, 70 -> drawdown from 100 to 30
+    assert_eq!(history.max_drawdown(), 70.0, "history.max_drawdown()");
+    assert_eq!(PnLHistory::new().max_drawdown(), 0.0, "empty history");
+}
+
+#[test]
+fn test_trading_day_spans_utc_midnight() {
+    let mut history = PnLHistory::new().with_reset_detector(new_york_reset());
+
+    history.record_at(datetime!(2024-03-04 20:00 UTC), &pnl(60.0)).unwrap();
+    history.record_at(datetime!(2024-03-04 23:00 UTC), &pnl(5.0)).unwrap();
+    history.record_at(datetime!(2024-03-05 01:00 UTC), &pnl(-20.0)).unwrap();
+    history.record_at(datetime!(2024-03-05 20:00 UTC), &pnl(10.0)).unwrap();
+
+    assert_eq!(
+        history.daily_curve(),
+        vec![(date!(2024 - 03 - 04), 60.0), (date!(2024 - 03 - 05), 10.0)],
+        "history.daily_curve()"
+    );
+    assert_eq!(history.intraday_curve(date!(2024 - 03 - 05)).len(), 3, "samples after the reset");
+    // cumulative pnl: 60, 65, 40, 70
+    assert_eq!(history.max_drawdown(), 25.0, "history.max_drawdown()");
+}
+
+#[test]
+fn test_inferred_resets() {
+    let mut history = PnLHistory::new();
+
+    history.record_at(datetime!(2024-03-04 20:00 UTC), &pnl(60.0)).unwrap();
+    history.record_at(datetime!(2024-03-05 01:00 UTC), &pnl(80.0)).unwrap();
+    history.record_at(datetime!(2024-03-06 14:00 UTC), &flat()).unwrap();
+    history.record_at(datetime!(2024-03-06 15:00 UTC), &pnl(-10.0)).unwrap();
+
+    // the daily pnl is not reset at midnight UTC, only when it returns to zero
+    assert_eq!(
+        history.daily_curve(),
+        vec![(date!(2024 - 03 - 05), 80.0), (date!(2024 - 03 - 06), -10.0)],
+        "history.daily_curve()"
+    );
+}
+
+#[test]
+fn test_history_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pnl.csv");
+
+    {
+        let mut history = PnLHistory::with_file(&path).expect("error creating history");
+        history.record_at(datetime!(2024-03-04 14:00 UTC), &pnl(100.0)).unwrap();
+        history.record_at(datetime!(2024-03-04 15:00 UTC), &pnl(40.0)).unwrap();
+    }
+
+    let mut history = PnLHistory::with_file(&path).expect("error reopening history");
+    assert_eq!(history.samples().len(), 2, "samples loaded from file");
+    assert_eq!(history.samples()[1].unrealized_pnl, Some(80.0), "samples[1].unrealized_pnl");
+    assert_eq!(history.samples()[1].realized_pnl, None, "samples[1].realized_pnl");
+
+    history.record_at(datetime!(2024-03-05 14:00 UTC), &pnl(5.0)).unwrap();
+
+    let samples = PnLHistory::load(&path).expect("error loading history");
+    assert_eq!(samples.len(), 3, "samples.len()");
+    assert_eq!(samples[2].timestamp, datetime!(2024-03-05 14:00 UTC), "samples[2].timestamp");
+}
+
+#[test]
+fn test_samples_without_daily_pnl() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pnl.csv");
+
+    let mut history = PnLHistory::with_file(&path).expect("error creating history");
+    let unset = PnL {
+        daily_pnl: None,
+        unrealized_pnl: Some(25.0),
+        realized_pnl: None,
+    };
+    history.record_at(datetime!(2024-03-04 13:30 UTC), &unset).unwrap();
+    history.record_at(datetime!(2024-03-04 14:00 UTC), &pnl(100.0)).unwrap();
+
+    assert_eq!(history.daily_curve(), vec![(date!(2024 - 03 - 04), 100.0)], "history.daily_curve()");
+    assert_eq!(history.max_drawdown(), 0.0, "history.max_drawdown()");
+
+    let samples = PnLHistory::load(&path).expect("error loading history");
+    assert_eq!(samples[0].daily_pnl, None, "samples[0].daily_pnl");
+    assert_eq!(samples[0].unrealized_pnl, Some(25.0), "samples[0].unrealized_pnl");
+}
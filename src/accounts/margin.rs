@@ -0,0 +1,162 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{AccountSummary, AccountSummaryTags, AccountUpdate, AccountValue};
+use crate::client::Subscription;
+
+#[cfg(test)]
+mod tests;
+
+/// Account value watched by a [MarginMonitor].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MarginMetric {
+    /// Excess liquidity as a fraction of net liquidation value (`Cushion`).
+    Cushion,
+    /// Equity with loan value less maintenance margin (`ExcessLiquidity`).
+    ExcessLiquidity,
+}
+
+impl MarginMetric {
+    fn key(&self) -> &'static str {
+        match self {
+            MarginMetric::Cushion => AccountSummaryTags::CUSHION,
+            MarginMetric::ExcessLiquidity => AccountSummaryTags::EXCESS_LIQUIDITY,
+        }
+    }
+}
+
+/// Warning emitted by a [MarginMonitor] when a metric crosses a threshold.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MarginAlert {
+    /// The metric fell below the threshold.
+    Breached { metric: MarginMetric, threshold: f64, value: f64 },
+    /// The metric rose back to or above a previously breached threshold.
+    Recovered { metric: MarginMetric, threshold: f64, value: f64 },
+}
+
+#[derive(Clone, Debug)]
+struct Threshold {
+    metric: MarginMetric,
+    level: f64,
+    breached: bool,
+}
+
+/// Watches cushion and excess liquidity on account streams and emits a [MarginAlert] when a configured threshold is crossed.
+///
+/// Several thresholds can be configured for the same metric, e.g. a warning and a shutdown level. Each threshold alerts
+/// once when breached and once when recovered.
+///
+/// ```no_run
+/// use ibapi::accounts::{MarginAlert, MarginMetric, MarginMonitor};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let monitor = MarginMonitor::new()
+///     .with_threshold(MarginMetric::Cushion, 0.10)
+///     .with_threshold(MarginMetric::ExcessLiquidity, 5000.0);
+///
+/// let subscription = client.account_updates("DU1234567").expect("error requesting account updates");
+/// for alert in subscription.margin_alerts(monitor) {
+///     if let MarginAlert::Breached { metric, value, .. } = alert {
+///         println!("{metric:?} fell to {value}, flattening positions");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MarginMonitor {
+    thresholds: Vec<Threshold>,
+}
+
+impl MarginMonitor {
+    /// Creates a monitor without thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Alerts when `metric` falls below `level`.
+    pub fn with_threshold(mut self, metric: MarginMetric, level: f64) -> Self {
+        self.thresholds.push(Threshold {
+            metric,
+            level,
+            breached: false,
+        });
+        self
+    }
+
+    /// Checks an account value against the thresholds.
+    pub fn update(&mut self, value: &AccountValue) -> Vec<MarginAlert> {
+        self.observe(&value.key, &value.value)
+    }
+
+    /// Checks an account summary value against the thresholds.
+    pub fn update_summary(&mut self, summary: &AccountSummary) -> Vec<MarginAlert> {
+        self.observe(&summary.tag, &summary.value)
+    }
+
+    /// Checks the account value carried by an account update. Other updates are ignored.
+    pub fn apply(&mut self, update: &AccountUpdate) -> Vec<MarginAlert> {
+        match update {
+            AccountUpdate::AccountValue(value) => self.update(value),
+            _ => Vec::new(),
+        }
+    }
+
+    fn observe(&mut self, key: &str, value: &str) -> Vec<MarginAlert> {
+        let mut alerts = Vec::new();
+
+        for threshold in self.thresholds.iter_mut().filter(|threshold| threshold.metric.key() == key) {
+            let Ok(value) = value.parse::<f64>() else {
+                continue;
+            };
+
+            let breached = value < threshold.level;
+            if breached == threshold.breached {
+                continue;
+            }
+            threshold.breached = breached;
+
+            let (metric, threshold) = (threshold.metric, threshold.level);
+            alerts.push(if breached {
+                MarginAlert::Breached { metric, threshold, value }
+            } else {
+                MarginAlert::Recovered { metric, threshold, value }
+            });
+        }
+
+        alerts
+    }
+}
+
+impl<'a> Subscription<'a, AccountUpdate> {
+    /// Returns an iterator over the margin alerts raised by `monitor` on this subscription.
+    pub fn margin_alerts(&self, monitor: MarginMonitor) -> MarginAlerts<'_, 'a> {
+        MarginAlerts {
+            subscription: self,
+            monitor,
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// Blocking iterator over [MarginAlert]s. See [Subscription::margin_alerts].
+pub struct MarginAlerts<'s, 'a> {
+    subscription: &'s Subscription<'a, AccountUpdate>,
+    monitor: MarginMonitor,
+    pending: Vec<MarginAlert>,
+}
+
+impl Iterator for MarginAlerts<'_, '_> {
+    type Item = MarginAlert;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let update = self.subscription.next()?;
+            self.pending = self.monitor.apply(&update);
+            self.pending.reverse();
+        }
+        self.pending.pop()
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::stubs::MessageBusStub;
+use crate::{server_versions, Client};
+
+fn account_value(key: &str, value: &str) -> AccountValue {
+    AccountValue {
+        key: key.to_owned(),
+        value: value.to_owned(),
+        currency: "USD".to_owned(),
+        account: Some("DU1234567".to_owned()),
+    }
+}
+
+#[test]
+fn test_margin_monitor() {
+    let mut monitor = MarginMonitor::new()
+        .with_threshold(MarginMetric::Cushion, 0.20)
+        .with_threshold(MarginMetric::Cushion, 0.10);
+
+    assert_eq!(monitor.update(&account_value("Cushion", "0.35")), vec![], "above thresholds");
+    assert_eq!(
+        monitor.update(&account_value("Cushion", "0.15")),
+        vec![MarginAlert::Breached {
+            metric: MarginMetric::Cushion,
+            threshold: 0.20,
+            value: 0.15
+        }],
+        "warning level breached"
+    );
+    assert_eq!(monitor.update(&account_value("Cushion", "0.12")), vec![], "still breached");
+    assert_eq!(
+        monitor.update(&account_value("Cushion", "0.05")),
+        vec![MarginAlert::Breached {
+            metric: MarginMetric::Cushion,
+            threshold: 0.10,
+            value: 0.05
+        }],
+        "shutdown level breached"
+    );
+    assert_eq!(
+        monitor.update(&account_value("Cushion", "0.25")),
+        vec![
+            MarginAlert::Recovered {
+                metric: MarginMetric::Cushion,
+                threshold: 0.20,
+                value: 0.25
+            },
+            MarginAlert::Recovered {
+                metric: MarginMetric::Cushion,
+                threshold: 0.10,
+                value: 0.25
+            },
+        ],
+        "both levels recovered"
+    );
+}
+
+#[test]
+fn test_margin_monitor_ignores_other_values() {
+    let mut monitor = MarginMonitor::new().with_threshold(MarginMetric::ExcessLiquidity, 5000.0);
+
+    assert_eq!(monitor.update(&account_value("Cushion", "0.01")), vec![], "unwatched metric");
+    assert_eq!(monitor.update(&account_value("ExcessLiquidity-S", "10.0")), vec![], "segment value");
+    assert_eq!(monitor.update(&account_value("ExcessLiquidity", "")), vec![], "not a number");
+    assert_eq!(monitor.apply(&AccountUpdate::End), vec![], "end of updates");
+
+    let summary = AccountSummary {
+        account: "DU1234567".to_owned(),
+        tag: AccountSummaryTags::EXCESS_LIQUIDITY.to_owned(),
+        value: "4000.0".to_owned(),
+        currency: "USD".to_owned(),
+    };
+    assert_eq!(
+        monitor.update_summary(&summary),
+        vec![MarginAlert::Breached {
+            metric: MarginMetric::ExcessLiquidity,
+            threshold: 5000.0,
+            value: 4000.0
+        }],
+        "account summary"
+    );
+}
+
+#[test]
+fn test_margin_alerts() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "6|2|ExcessLiquidity|8000.00|USD|DU1234567|".into(),
+            "6|2|ExcessLiquidity|4500.00|USD|DU1234567|".into(),
+            "6|2|Cushion|0.05|USD|DU1234567|".into(),
+            "6|2|ExcessLiquidity|6000.00|USD|DU1234567|".into(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let monitor = MarginMonitor::new().with_threshold(MarginMetric::ExcessLiquidity, 5000.0);
+
+    let subscription = client.account_updates("DU1234567").expect("request account updates failed");
+    let alerts: Vec<MarginAlert> = subscription.margin_alerts(monitor).collect();
+
+    assert_eq!(
+        alerts,
+        vec![
+            MarginAlert::Breached {
+                metric: MarginMetric::ExcessLiquidity,
+                threshold: 5000.0,
+                value: 4500.0
+            },
+            MarginAlert::Recovered {
+                metric: MarginMetric::ExcessLiquidity,
+                threshold: 5000.0,
+                value: 6000.0
+            },
+        ],
+        "alerts"
+    );
+}
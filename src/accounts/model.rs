@@ -0,0 +1,87 @@
+use super::{AccountUpdateMulti, PnL, PnLSingle, PositionUpdateMulti};
+use crate::client::Subscription;
+use crate::contracts::Contract;
+use crate::orders::{self, Order, PlaceOrder};
+use crate::{Client, Error};
+
+/// A model portfolio within an advisor account.
+///
+/// Requests made through a model are scoped to its account and model code, and orders placed through a model are allocated to it.
+///
+/// ```no_run
+/// use ibapi::contracts::Contract;
+/// use ibapi::orders::{order_builder, Action};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let model = client.model("DU1234567", "TARGET2024");
+///
+/// let positions = model.positions().expect("error requesting model positions");
+/// for position in positions.iter() {
+///     println!("{position:?}");
+/// }
+///
+/// let contract = Contract::stock("MSFT");
+/// let order = order_builder::market_order(Action::Buy, 100.0);
+/// let events = model.place_order(client.next_order_id(), &contract, &order).expect("error placing order");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Model<'a> {
+    client: &'a Client,
+    account: String,
+    code: String,
+}
+
+impl<'a> Model<'a> {
+    pub(crate) fn new(client: &'a Client, account: &str, code: &str) -> Self {
+        Self {
+            client,
+            account: client.resolve_account(account),
+            code: code.to_owned(),
+        }
+    }
+
+    /// Account the model belongs to.
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Code identifying the model.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Subscribes to position updates for the model.
+    pub fn positions(&self) -> Result<Subscription<'a, PositionUpdateMulti>, Error> {
+        super::positions_multi(self.client, Some(&self.account), Some(&self.code))
+    }
+
+    /// Subscribes to account value and portfolio updates for the model.
+    pub fn account_updates(&self) -> Result<Subscription<'a, AccountUpdateMulti>, Error> {
+        super::account_updates_multi(self.client, Some(&self.account), Some(&self.code))
+    }
+
+    /// Subscribes to daily, unrealized and realized PnL updates for the model.
+    pub fn pnl(&self) -> Result<Subscription<'a, PnL>, Error> {
+        super::pnl(self.client, &self.account, Some(&self.code))
+    }
+
+    /// Subscribes to daily PnL updates for the model's position in the contract.
+    pub fn pnl_single(&self, contract_id: i32) -> Result<Subscription<'a, PnLSingle>, Error> {
+        super::pnl_single(self.client, &self.account, contract_id, Some(&self.code))
+    }
+
+    /// Places an order allocated to the model.
+    ///
+    /// The order's account and model code are set to those of the model.
+    pub fn place_order(&self, order_id: i32, contract: &Contract, order: &Order) -> Result<Subscription<'a, PlaceOrder>, Error> {
+        let order = Order {
+            account: self.account.clone(),
+            model_code: self.code.clone(),
+            ..order.clone()
+        };
+
+        orders::place_order(self.client, order_id, contract, &order)
+    }
+}
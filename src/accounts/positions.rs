@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Position, PositionUpdate};
+use crate::orders::{CommissionReport, ExecutionData, PlaceOrder};
+use crate::{Client, Error};
+
+#[cfg(test)]
+mod tests;
+
+/// Change of a position tracked by [PositionTracker].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PositionChange {
+    /// The position before the change. `None` if the position was not previously tracked.
+    pub previous: Option<Position>,
+    /// The position after the change.
+    pub current: Position,
+}
+
+// Execution applied to a position, kept until its commission report arrives.
+#[derive(Debug)]
+struct AppliedExecution {
+    key: (String, i32),
+    increased: bool,
+    commission_applied: bool,
+}
+
+/// Maintains position quantity and average cost intraday.
+///
+/// The tracker starts from the positions reported by TWS and applies live executions and commission reports
+/// as they arrive, so the average cost reflects fills before TWS publishes updated positions.
+///
+/// ```no_run
+/// use ibapi::accounts::PositionTracker;
+/// use ibapi::contracts::Contract;
+/// use ibapi::orders::{order_builder, Action};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut tracker = PositionTracker::load(&client).expect("error loading positions");
+///
+/// let contract = Contract::stock("MSFT");
+/// let order = order_builder::market_order(Action::Buy, 100.0);
+/// let events = client.place_order(client.next_order_id(), &contract, &order).expect("error placing order");
+///
+/// for event in &events {
+///     if let Some(change) = tracker.apply(&event) {
+///         println!("{} {} @ {}", change.current.contract.symbol, change.current.position, change.current.average_cost);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PositionTracker {
+    positions: HashMap<(String, i32), Position>,
+    executions: HashMap<String, AppliedExecution>,
+}
+
+impl PositionTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tracker initialized with the current positions of all accessible accounts.
+    pub fn load(client: &Client) -> Result<Self, Error> {
+        let mut tracker = Self::new();
+
+        let subscription = client.positions()?;
+        loop {
+            match subscription.next() {
+                Some(PositionUpdate::Position(position)) => {
+                    tracker.apply_position(&position);
+                }
+                Some(PositionUpdate::PositionEnd) => break,
+                None => return Err(subscription.error().unwrap_or(Error::UnexpectedEndOfStream)),
+            }
+        }
+
+        Ok(tracker)
+    }
+
+    /// Returns the tracked position for the account and contract id.
+    pub fn position(&self, account: &str, contract_id: i32) -> Option<&Position> {
+        self.positions.get(&(account.to_owned(), contract_id))
+    }
+
+    /// Returns all tracked positions.
+    pub fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.positions.values()
+    }
+
+    /// Replaces the tracked position with the position reported by TWS.
+    pub fn apply_position(&mut self, position: &Position) -> Option<PositionChange> {
+        let key = (position.account.clone(), position.contract.contract_id);
+        let previous = self.positions.insert(key, position.clone());
+
+        if let Some(previous) = &previous {
+            if previous.position == position.position && previous.average_cost == position.average_cost {
+                return None;
+            }
+        }
+
+        Some(PositionChange {
+            previous,
+            current: position.clone(),
+        })
+    }
+
+    /// Applies a fill to the position. Executions that were already applied are ignored.
+    pub fn apply_execution(&mut self, execution_data: &ExecutionData) -> Option<PositionChange> {
+        let execution = &execution_data.execution;
+
+        if self.executions.contains_key(&execution.execution_id) {
+            return None;
+        }
+
+        let quantity = match execution.side.as_str() {
+            "BOT" => execution.shares,
+            "SLD" => -execution.shares,
+            _ => return None,
+        };
+
+        let key = (execution.account_number.clone(), execution_data.contract.contract_id);
+        let previous = self.positions.get(&key).cloned();
+
+        let mut current = previous.clone().unwrap_or_else(|| Position {
+            account: execution.account_number.clone(),
+            contract: execution_data.contract.clone(),
+            ..Position::default()
+        });
+
+        let multiplier = execution_data.contract.multiplier.parse::<f64>().unwrap_or(1.0);
+        let cost = execution.price * multiplier;
+
+        let old_quantity = current.position;
+        let new_quantity = old_quantity + quantity;
+        let increased = old_quantity == 0.0 || old_quantity.signum() == quantity.signum();
+
+        current.average_cost = if new_quantity == 0.0 {
+            0.0
+        } else if increased {
+            (old_quantity.abs() * current.average_cost + quantity.abs() * cost) / new_quantity.abs()
+        } else if old_quantity.signum() != new_quantity.signum() {
+            // position flipped, the remainder was opened at the execution price
+            cost
+        } else {
+            current.average_cost
+        };
+        current.position = new_quantity;
+
+        self.executions.insert(
+            execution.execution_id.clone(),
+            AppliedExecution {
+                key: key.clone(),
+                increased,
+                commission_applied: false,
+            },
+        );
+        self.positions.insert(key, current.clone());
+
+        Some(PositionChange { previous, current })
+    }
+
+    /// Adds the commission of an opening execution to the average cost of its position.
+    ///
+    /// Commissions of executions that reduce a position are realized and leave the average cost unchanged.
+    pub fn apply_commission(&mut self, report: &CommissionReport) -> Option<PositionChange> {
+        let applied = self.executions.get_mut(&report.execution_id)?;
+        if !applied.increased || applied.commission_applied || report.commission.amount == 0.0 {
+            return None;
+        }
+        applied.commission_applied = true;
+
+        let position = self.positions.get_mut(&applied.key)?;
+        if position.position == 0.0 {
+            return None;
+        }
+
+        let previous = position.clone();
+        position.average_cost += report.commission.amount / position.position.abs();
+
+        Some(PositionChange {
+            previous: Some(previous),
+            current: position.clone(),
+        })
+    }
+
+    /// Applies executions and commission reports received on an order subscription.
+    pub fn apply(&mut self, event: &PlaceOrder) -> Option<PositionChange> {
+        match event {
+            PlaceOrder::ExecutionData(execution_data) => self.apply_execution(execution_data),
+            PlaceOrder::CommissionReport(report) => self.apply_commission(report),
+            _ => None,
+        }
+    }
+}
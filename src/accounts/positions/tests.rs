@@ -0,0 +1,129 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::contracts::Contract;
+use crate::money::{Currency, Money};
+use crate::orders::Execution;
+use crate::stubs::MessageBusStub;
+use crate::{server_versions, Client};
+
+fn position(account: &str, contract_id: i32, quantity: f64, average_cost: f64) -> Position {
+    Position {
+        account: account.to_owned(),
+        contract: Contract {
+            contract_id,
+            symbol: "TSLA".to_owned(),
+            ..Contract::default()
+        },
+        position: quantity,
+        average_cost,
+    }
+}
+
+fn execution(execution_id: &str, side: &str, shares: f64, price: f64) -> ExecutionData {
+    ExecutionData {
+        request_id: -1,
+        contract: Contract {
+            contract_id: 76792991,
+            symbol: "TSLA".to_owned(),
+            ..Contract::default()
+        },
+        execution: Execution {
+            execution_id: execution_id.to_owned(),
+            account_number: "DU1234567".to_owned(),
+            side: side.to_owned(),
+            shares,
+            price,
+            ..Execution::default()
+        },
+    }
+}
+
+fn commission(execution_id: &str, commission: f64) -> CommissionReport {
+    CommissionReport {
+        execution_id: execution_id.to_owned(),
+        commission: Money::new(commission, Currency::USD),
+        ..CommissionReport::default()
+    }
+}
+
+#[test]
+fn test_load() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "61|3|DU1234567|76792991|TSLA|STK||0.0|||NASDAQ|USD|TSLA|NMS|500|196.77|".to_owned(),
+            "62|1|".to_owned(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let tracker = PositionTracker::load(&client).expect("error loading positions");
+
+    let position = tracker.position("DU1234567", 76792991).expect("position expected");
+    assert_eq!(position.position, 500.0, "position.position");
+    assert_eq!(position.average_cost, 196.77, "position.average_cost");
+    assert_eq!(tracker.positions().count(), 1, "tracker.positions().count()");
+}
+
+#[test]
+fn test_apply_executions() {
+    let mut tracker = PositionTracker::new();
+    tracker.apply_position(&position("DU1234567", 76792991, 100.0, 200.0));
+
+    let change = tracker
+        .apply_execution(&execution("0001.01", "BOT", 100.0, 210.0))
+        .expect("change expected");
+    assert_eq!(change.previous.unwrap().position, 100.0, "previous.position");
+    assert_eq!(change.current.position, 200.0, "current.position");
+    assert_eq!(change.current.average_cost, 205.0, "current.average_cost");
+
+    assert!(
+        tracker.apply_execution(&execution("0001.01", "BOT", 100.0, 210.0)).is_none(),
+        "duplicate execution ignored"
+    );
+
+    let change = tracker.apply_commission(&commission("0001.01", 2.0)).expect("change expected");
+    assert_eq!(change.current.average_cost, 205.01, "average_cost with commission");
+    assert!(
+        tracker.apply_commission(&commission("0001.01", 2.0)).is_none(),
+        "duplicate commission ignored"
+    );
+
+    let change = tracker
+        .apply_execution(&execution("0002.01", "SLD", 50.0, 220.0))
+        .expect("change expected");
+    assert_eq!(change.current.position, 150.0, "reduced position");
+    assert_eq!(change.current.average_cost, 205.01, "reducing keeps average cost");
+    assert!(
+        tracker.apply_commission(&commission("0002.01", 1.0)).is_none(),
+        "closing commission is realized"
+    );
+
+    let change = tracker
+        .apply_execution(&execution("0003.01", "SLD", 200.0, 230.0))
+        .expect("change expected");
+    assert_eq!(change.current.position, -50.0, "flipped position");
+    assert_eq!(change.current.average_cost, 230.0, "flipped average cost");
+
+    let change = tracker
+        .apply(&PlaceOrder::ExecutionData(execution("0004.01", "BOT", 50.0, 225.0)))
+        .expect("change expected");
+    assert_eq!(change.current.position, 0.0, "flat position");
+    assert_eq!(change.current.average_cost, 0.0, "flat average cost");
+}
+
+#[test]
+fn test_apply_execution_new_position() {
+    let mut tracker = PositionTracker::new();
+
+    let change = tracker
+        .apply_execution(&execution("0001.01", "SLD", 10.0, 50.0))
+        .expect("change expected");
+
+    assert!(change.previous.is_none(), "previous");
+    assert_eq!(change.current.account, "DU1234567", "current.account");
+    assert_eq!(change.current.position, -10.0, "current.position");
+    assert_eq!(change.current.average_cost, 50.0, "current.average_cost");
+}
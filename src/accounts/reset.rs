@@ -0,0 +1,147 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime, Time};
+use time_tz::{OffsetDateTimeExt, Tz};
+
+use super::PnL;
+use crate::client::Subscription;
+
+#[cfg(test)]
+mod tests;
+
+const DEFAULT_TOLERANCE: f64 = 0.005;
+
+/// A [PnL] update annotated with whether the daily PnL was reset before it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PnLUpdate {
+    /// The PnL reported by TWS.
+    pub pnl: PnL,
+    /// `true` if a daily reset happened between the previous update and this one.
+    pub reset: bool,
+}
+
+/// Detects the daily PnL reset on a stream of [PnL] updates.
+///
+/// IB resets the daily PnL once a day at a time that depends on the exchange and account. When the reset time is known it
+/// can be configured with [PnLResetDetector::with_reset_time]; an update is then a reset if the reset time passed since the
+/// previous update. Otherwise a reset is inferred when the daily and realized PnL return to zero from a non-zero daily PnL,
+/// which distinguishes the reset from the PnL moving against the account.
+#[derive(Clone, Debug)]
+pub struct PnLResetDetector {
+    reset_time: Option<(Time, &'static Tz)>,
+    tolerance: f64,
+    previous: Option<(OffsetDateTime, PnL)>,
+}
+
+impl Default for PnLResetDetector {
+    fn default() -> Self {
+        Self {
+            reset_time: None,
+            tolerance: DEFAULT_TOLERANCE,
+            previous: None,
+        }
+    }
+}
+
+impl PnLResetDetector {
+    /// Creates a detector that infers resets from the PnL values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detects resets at `time` in the time zone `tz`, e.g. the close of the account's primary exchange.
+    pub fn with_reset_time(mut self, time: Time, tz: &'static Tz) -> Self {
+        self.reset_time = Some((time, tz));
+        self
+    }
+
+    /// Sets the amount under which a PnL is considered zero when inferring resets.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Returns `true` if a reset happened before `pnl`, received now.
+    pub fn update(&mut self, pnl: &PnL) -> bool {
+        self.update_at(OffsetDateTime::now_utc(), pnl)
+    }
+
+    /// Returns `true` if a reset happened before `pnl`, received at `timestamp`.
+    pub fn update_at(&mut self, timestamp: OffsetDateTime, pnl: &PnL) -> bool {
+        let reset = match &self.previous {
+            Some((previous_timestamp, previous)) => match self.reset_time {
+                Some((time, tz)) => crossed_reset_time(*previous_timestamp, timestamp, time, tz),
+                None => {
+                    pnl.daily_pnl.is_some_and(|v| self.is_zero(v))
+                        && self.is_zero(pnl.realized_pnl.unwrap_or_default())
+                        && previous.daily_pnl.is_some_and(|v| !self.is_zero(v))
+                }
+            },
+            None => false,
+        };
+
+        self.previous = Some((timestamp, pnl.clone()));
+
+        reset
+    }
+
+    fn is_zero(&self, value: f64) -> bool {
+        value.abs() <= self.tolerance
+    }
+}
+
+// True if a reset at `time` in `tz` happened after `previous` and at or before `current`.
+fn crossed_reset_time(previous: OffsetDateTime, current: OffsetDateTime, time: Time, tz: &Tz) -> bool {
+    let local = current.to_timezone(tz);
+
+    let mut boundary = local.replace_time(time);
+    if boundary > local {
+        boundary -= Duration::days(1);
+    }
+
+    previous < boundary
+}
+
+impl<'a> Subscription<'a, PnL> {
+    /// Returns an iterator over PnL updates that flags daily resets detected by `detector`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::accounts::PnLResetDetector;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let subscription = client.pnl("DU1234567", None).expect("error requesting pnl");
+    /// for update in subscription.with_reset_detection(PnLResetDetector::new()) {
+    ///     if update.reset {
+    ///         println!("daily pnl reset");
+    ///     }
+    ///     println!("daily pnl: {:?}", update.pnl.daily_pnl);
+    /// }
+    /// ```
+    pub fn with_reset_detection(&self, detector: PnLResetDetector) -> PnLUpdates<'_, 'a> {
+        PnLUpdates {
+            subscription: self,
+            detector,
+        }
+    }
+}
+
+/// Blocking iterator over [PnLUpdate]s. See [Subscription::with_reset_detection].
+pub struct PnLUpdates<'s, 'a> {
+    subscription: &'s Subscription<'a, PnL>,
+    detector: PnLResetDetector,
+}
+
+impl Iterator for PnLUpdates<'_, '_> {
+    type Item = PnLUpdate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pnl = self.subscription.next()?;
+        let reset = self.detector.update(&pnl);
+        Some(PnLUpdate { pnl, reset })
+    }
+}
@@ -0,0 +1,75 @@
+use std::sync::{Arc, RwLock};
+
+use time::macros::{datetime, time};
+use time_tz::timezones;
+
+use super::*;
+use crate::stubs::MessageBusStub;
+use crate::{server_versions, Client};
+
+fn pnl(daily_pnl: f64, realized_pnl: f64) -> PnL {
+    PnL {
+        daily_pnl: Some(daily_pnl),
+        unrealized_pnl: Some(500.0),
+        realized_pnl: Some(realized_pnl),
+    }
+}
+
+#[test]
+fn test_inferred_reset() {
+    let mut detector = PnLResetDetector::new();
+
+    assert!(!detector.update(&pnl(120.0, 20.0)), "first update");
+    assert!(!detector.update(&pnl(-80.0, 20.0)), "pnl dropped");
+    assert!(detector.update(&pnl(0.0, 0.0)), "daily reset");
+    assert!(!detector.update(&pnl(0.0, 0.0)), "unchanged after reset");
+    assert!(!detector.update(&pnl(15.0, 0.0)), "new day");
+}
+
+#[test]
+fn test_inferred_reset_requires_realized_reset() {
+    let mut detector = PnLResetDetector::new().with_tolerance(0.5);
+
+    assert!(!detector.update(&pnl(120.0, 20.0)), "first update");
+    assert!(!detector.update(&pnl(0.2, 20.0)), "realized pnl kept");
+    assert!(!detector.update(&pnl(10.0, 20.0)), "pnl recovered");
+    assert!(detector.update(&pnl(-0.3, 0.0)), "within tolerance");
+}
+
+#[test]
+fn test_reset_time() {
+    let mut detector = PnLResetDetector::new().with_reset_time(time!(17:00), timezones::db::america::NEW_YORK);
+
+    assert!(!detector.update_at(datetime!(2024-03-04 20:00 UTC), &pnl(100.0, 0.0)), "first update");
+    assert!(
+        !detector.update_at(datetime!(2024-03-04 21:59 UTC), &pnl(0.0, 0.0)),
+        "zero pnl before reset time"
+    );
+    assert!(
+        detector.update_at(datetime!(2024-03-04 22:01 UTC), &pnl(50.0, 0.0)),
+        "update after reset time"
+    );
+    assert!(!detector.update_at(datetime!(2024-03-05 14:00 UTC), &pnl(60.0, 0.0)), "same trading day");
+    assert!(
+        detector.update_at(datetime!(2024-03-07 14:00 UTC), &pnl(60.0, 0.0)),
+        "gap over several resets"
+    );
+}
+
+#[test]
+fn test_subscription_with_reset_detection() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["94|9000|75.5|500.0|25.0|".into(), "94|9000|0|500.0|0|".into()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let subscription = client.pnl("DU1234567", None).expect("request pnl failed");
+    let updates: Vec<PnLUpdate> = subscription.with_reset_detection(PnLResetDetector::new()).collect();
+
+    assert_eq!(updates.len(), 2, "updates.len()");
+    assert_eq!(updates[0].pnl.daily_pnl, Some(75.5), "updates[0].pnl.daily_pnl");
+    assert!(!updates[0].reset, "updates[0].reset");
+    assert!(updates[1].reset, "updates[1].reset");
+}
@@ -1,8 +1,10 @@
 use std::sync::{Arc, RwLock};
 
-use crate::accounts::AccountUpdateMulti;
+#[cfg(feature = "fa")]
+use crate::accounts::fa::{Alias, FaConfiguration, FaDataType};
+use crate::accounts::{AccountAliases, AccountUpdateMulti, AccountValue, AccountValueChange, AccountValueTracker};
 use crate::testdata::responses;
-use crate::{accounts::AccountSummaryTags, server_versions, stubs::MessageBusStub, Client};
+use crate::{accounts::AccountSummaryTags, server_versions, stubs::MessageBusStub, Client, Error};
 
 #[test]
 fn test_pnl() {
@@ -128,6 +130,47 @@ fn test_managed_accounts() {
     assert_eq!(accounts, &["DU1234567", "DU7654321"]);
 }
 
+#[test]
+fn test_user_info() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["107|9000|BRAND1|".into()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::USER_INFO);
+
+    let white_branding_id = client.user_info().expect("request user info failed");
+
+    assert_eq!(white_branding_id, "BRAND1");
+    assert_eq!(client.message_bus.request_messages()[0].encode_simple(), "104|9000|");
+
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::USER_INFO - 1);
+
+    assert!(matches!(client.user_info(), Err(Error::ServerVersion(_, _, _))));
+}
+
+#[test]
+fn test_server_time() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["49|1|1678323335|".into()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let server_time = client.server_time().expect("request server time failed");
+
+    assert_eq!(server_time, time::macros::datetime!(2023-03-09 00:55:35 UTC));
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "49|1|");
+}
+
 #[test]
 fn test_account_updates_multi() {
     let message_bus = Arc::new(MessageBusStub {
@@ -169,3 +212,198 @@ fn test_account_updates_multi() {
     assert_eq!(request_messages[0].encode_simple(), "76|1|9000|DU1234567||1|");
     assert_eq!(request_messages[1].encode_simple(), "77|1|9000|");
 }
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_request_fa() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![responses::RECEIVE_FA_ALIASES.into()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let configuration = client.request_fa(FaDataType::Aliases).expect("request FA failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "18|1|3|");
+
+    assert_eq!(
+        configuration,
+        FaConfiguration::Aliases(vec![Alias {
+            account: "DU1234567".into(),
+            alias: "Growth".into(),
+        }])
+    );
+}
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_request_fa_profiles_desupported() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::FA_PROFILE_DESUPPORT);
+
+    let result = client.request_fa(FaDataType::Profiles);
+    assert!(matches!(result, Err(Error::InvalidArgument(_))), "expected invalid argument: {result:?}");
+    assert!(client.message_bus.request_messages().is_empty(), "no request expected");
+}
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_replace_fa() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["103|9000|FA data replaced|".into()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let configuration = FaConfiguration::Aliases(vec![Alias {
+        account: "DU1234567".into(),
+        alias: "Growth".into(),
+    }]);
+
+    client.replace_fa(&configuration).expect("replace FA failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0][0], "19", "message.type");
+    assert_eq!(request_messages[0][2], "3", "message.data_type");
+    assert_eq!(request_messages[0][3], configuration.to_xml(), "message.xml");
+    assert_eq!(request_messages[0][4], "9000", "message.request_id");
+}
+
+#[test]
+fn test_account_value_changes() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "6|2|ExcessLiquidity|1000.00|USD|DU1234567|".into(),
+            "6|2|Currency|USD|USD|DU1234567|".into(),
+            "8|1|12:30|".into(),
+            "6|2|ExcessLiquidity|1000.00|USD|DU1234567|".into(),
+            "6|2|ExcessLiquidity|950.00|USD|DU1234567|".into(),
+            "54|1|DU1234567|".into(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let subscription = client.account_updates("DU1234567").expect("request account updates failed");
+    let changes: Vec<AccountValueChange> = subscription.changes().collect();
+
+    assert_eq!(changes.len(), 3, "changes.len()");
+    assert_eq!(changes[0].key, "ExcessLiquidity", "changes[0].key");
+    assert_eq!(changes[0].old_value, None, "changes[0].old_value");
+    assert_eq!(changes[1].key, "Currency", "changes[1].key");
+    assert_eq!(
+        changes[2],
+        AccountValueChange {
+            account: Some("DU1234567".into()),
+            key: "ExcessLiquidity".into(),
+            currency: "USD".into(),
+            old_value: Some("1000.00".into()),
+            new_value: "950.00".into(),
+        },
+        "changes[2]"
+    );
+}
+
+#[test]
+fn test_account_value_tracker() {
+    let mut tracker = AccountValueTracker::new();
+
+    let value = AccountValue {
+        key: "NetLiquidation".into(),
+        value: "100.0".into(),
+        currency: "USD".into(),
+        account: Some("DU1234567".into()),
+    };
+
+    assert!(tracker.update(&value).is_some(), "first value is a change");
+    assert!(tracker.update(&value).is_none(), "repeated value is not a change");
+    assert_eq!(tracker.value("NetLiquidation", "USD"), Some("100.0"));
+    assert_eq!(tracker.value("NetLiquidation", "EUR"), None);
+}
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_account_aliases() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![responses::RECEIVE_FA_ALIASES.into()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let aliases = client.account_aliases().expect("request account aliases failed");
+    assert_eq!(aliases.resolve("Growth"), "DU1234567", "aliases.resolve(Growth)");
+    assert_eq!(aliases.alias("DU1234567"), Some("Growth"), "aliases.alias(DU1234567)");
+
+    let _ = client.pnl("Growth", None).expect("request pnl failed");
+    let _ = client
+        .account_updates_multi(Some("Growth"), None)
+        .expect("request account updates multi failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[1].encode_simple(), "92|9000|DU1234567||");
+    assert_eq!(request_messages[3].encode_simple(), "76|1|9001|DU1234567||1|");
+}
+
+#[test]
+fn test_account_aliases_resolve() {
+    let mut aliases = AccountAliases::new();
+    assert!(aliases.is_empty(), "aliases.is_empty()");
+
+    aliases.insert("DU1234567", "Growth");
+    aliases.insert("DU1234567", "Income");
+
+    assert_eq!(aliases.resolve("Income"), "DU1234567", "resolve new alias");
+    assert_eq!(aliases.resolve("Growth"), "Growth", "replaced alias is unknown");
+    assert_eq!(aliases.resolve("DU7654321"), "DU7654321", "account codes resolve to themselves");
+    assert_eq!(aliases.alias("DU1234567"), Some("Income"), "aliases.alias()");
+}
+
+#[test]
+fn test_model() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let model = client.model("DU1234567", "TARGET2024");
+    assert_eq!(model.account(), "DU1234567", "model.account()");
+    assert_eq!(model.code(), "TARGET2024", "model.code()");
+
+    let _ = model.positions().expect("request positions failed");
+    let _ = model.account_updates().expect("request account updates failed");
+    let _ = model.pnl().expect("request pnl failed");
+    let _ = model.pnl_single(1001).expect("request pnl single failed");
+
+    let request_messages = client.message_bus.request_messages();
+
+    assert_eq!(request_messages[0].encode_simple(), "74|1|9000|DU1234567|TARGET2024|");
+    assert_eq!(request_messages[2].encode_simple(), "76|1|9001|DU1234567|TARGET2024|1|");
+    assert_eq!(request_messages[4].encode_simple(), "92|9002|DU1234567|TARGET2024|");
+    assert_eq!(request_messages[6].encode_simple(), "94|9003|DU1234567|TARGET2024|1001|");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn account_types_implement_serde() {
+    use serde::{Deserialize, Serialize};
+
+    fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>() {}
+
+    assert_serde::<crate::accounts::AccountSummaries>();
+    assert_serde::<crate::accounts::PnL>();
+    assert_serde::<crate::accounts::PnLSingle>();
+    assert_serde::<crate::accounts::PositionUpdate>();
+    assert_serde::<crate::accounts::PositionUpdateMulti>();
+    assert_serde::<crate::accounts::AccountUpdate>();
+    assert_serde::<AccountUpdateMulti>();
+    #[cfg(feature = "fa")]
+    assert_serde::<FaConfiguration>();
+}
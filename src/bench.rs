@@ -0,0 +1,34 @@
+use crate::contracts::Contract;
+use crate::messages::RequestMessage;
+use crate::orders::{encoders, Order};
+use crate::{server_versions, ToField};
+
+/// Fields of the place order message for `order`, used as input to compare how messages are encoded.
+pub fn place_order_fields(order_id: i32, contract: &Contract, order: &Order) -> Vec<String> {
+    let message = encoders::encode_place_order(server_versions::NOT_HELD, order_id, contract, order).expect("error encoding place order");
+    message.as_str().split_terminator('\0').map(|field| field.to_owned()).collect()
+}
+
+/// Encodes the place order message for `order` into a single buffer, as messages are sent to TWS. Returns the encoded length.
+pub fn encode_place_order(order_id: i32, contract: &Contract, order: &Order) -> usize {
+    let message = encoders::encode_place_order(server_versions::NOT_HELD, order_id, contract, order).expect("error encoding place order");
+    message.as_str().len()
+}
+
+/// Encodes `fields` into a single buffer. Returns the encoded length.
+pub fn encode_single_buffer(fields: &[String]) -> usize {
+    let mut message = RequestMessage::new();
+    for field in fields {
+        message.push_field(field);
+    }
+    message.as_str().len()
+}
+
+/// Encodes `fields` with a String per field joined when sending, as messages were encoded before fields were written into a single buffer.
+/// Returns the encoded length.
+pub fn encode_string_per_field(fields: &[String]) -> usize {
+    let fields: Vec<String> = fields.iter().map(|field| field.to_field()).collect();
+    let mut data = fields.join("\0");
+    data.push('\0');
+    data.len()
+}
@@ -1,30 +1,73 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
 use log::{debug, error, warn};
 use time::{Date, OffsetDateTime};
 use time_tz::Tz;
 
-use crate::accounts::{AccountSummaries, AccountUpdate, AccountUpdateMulti, FamilyCode, PnL, PnLSingle, PositionUpdate, PositionUpdateMulti};
+#[cfg(feature = "fa")]
+use crate::accounts::fa::{FaConfiguration, FaDataType};
+use crate::accounts::{
+    AccountAliases, AccountSummaries, AccountUpdate, AccountUpdateMulti, FamilyCode, Model, PnL, PnLSingle, PositionUpdate, PositionUpdateMulti,
+};
 use crate::contracts::{Contract, OptionComputation, SecurityType};
 use crate::errors::Error;
+#[cfg(feature = "fundamentals")]
+use crate::fundamentals;
+#[cfg(feature = "historical")]
 use crate::market_data::historical::{self, HistogramEntry};
+#[cfg(feature = "market-data")]
 use crate::market_data::realtime::{self, Bar, BarSize, DepthMarketDataDescription, MarketDepths, MidPoint, TickTypes, WhatToShow};
 use crate::market_data::MarketDataType;
-use crate::messages::{IncomingMessages, OutgoingMessages};
+use crate::messages::{IncomingMessages, Notice, OutgoingMessages};
 use crate::messages::{RequestMessage, ResponseMessage};
+#[cfg(feature = "news")]
+use crate::news;
+#[cfg(feature = "news")]
 use crate::news::NewsArticle;
-use crate::orders::{CancelOrder, Executions, ExerciseOptions, Order, Orders, PlaceOrder};
+use crate::orders::{CancelOrder, Executions, ExerciseOptions, MarginImpact, Order, Orders, PlaceOrder, SoftDollarTier};
+#[cfg(feature = "scanner")]
+use crate::scanner;
+#[cfg(feature = "scanner")]
 use crate::scanner::ScannerData;
-use crate::transport::{Connection, ConnectionMetadata, InternalSubscription, MessageBus, TcpMessageBus};
+use crate::transport::{Connection, ConnectionMetadata, ConnectionOptions, EventChannel, InternalSubscription, MessageBus, TcpMessageBus};
 use crate::wsh::AutoFill;
-use crate::{accounts, contracts, market_data, news, orders, scanner, wsh};
-
+use crate::{accounts, contracts, display_groups, market_data, orders, wsh};
+
+pub use builder::{ConnectionBuilder, RetryPolicy};
+pub use cancel::CancelHandle;
+pub use capabilities::Capabilities;
+pub use connection_info::ConnectionInfo;
+pub use events::{ConnectionEvent, ConnectionEvents, DataFarm, DataFarmStatus, NoticeFilter};
+pub use health::{DataFarmState, Health};
+pub use journal::{JournalEntry, RequestOutcome};
+pub use metrics::{BufferPoolStats, Metrics};
+pub use replay::{Replay, SessionRecorder};
+#[cfg(unix)]
+pub use stream::UnixTransport;
+pub use stream::{MemoryStream, MemoryTransport, Proxy, TcpTransport, Transport, TransportStream};
+pub use subscriptions::{SubscriptionInfo, SubscriptionKind};
+pub use timeout::WithTimeout;
+pub use wire::{Direction, MessageObserver, TwsLogFile, WireMessage};
+
+mod builder;
+mod cancel;
+mod capabilities;
+mod connection_info;
+mod events;
+mod health;
+pub(crate) mod journal;
+pub(crate) mod metrics;
+pub(crate) mod replay;
+pub(crate) mod stream;
+mod subscriptions;
 #[cfg(test)]
 mod tests;
+mod timeout;
+pub(crate) mod wire;
 
 // Client
 
@@ -35,12 +78,20 @@ pub struct Client {
     /// IB server version
     pub(crate) server_version: i32,
     pub(crate) connection_time: Option<OffsetDateTime>,
+    #[cfg_attr(not(any(feature = "historical", feature = "news")), allow(dead_code))]
     pub(crate) time_zone: Option<&'static Tz>,
     pub(crate) message_bus: Arc<dyn MessageBus>,
+    /// Maximum time blocking requests wait for TWS to respond. None waits indefinitely.
+    pub(crate) request_timeout: Option<Duration>,
+    pub(crate) metrics: Option<metrics::SharedMetrics>,
 
     client_id: i32,             // ID of client.
     next_request_id: AtomicI32, // Next available request_id.
     order_id: AtomicI32,        // Next available order_id. Starts with value returned on connection.
+    account: Option<String>,    // Default account. Configured with ConnectionBuilder::account or first managed account.
+    account_aliases: RwLock<AccountAliases>,
+    subscriptions: subscriptions::SubscriptionRegistry,
+    connection_info: ConnectionInfo,
 }
 
 impl Client {
@@ -64,31 +115,179 @@ impl Client {
     /// println!("next_order_id: {}", client.next_order_id());
     /// ```
     pub fn connect(address: &str, client_id: i32) -> Result<Client, Error> {
-        let connection = Connection::connect(client_id, address)?;
+        Client::connect_with_options(ConnectionOptions::new(address, client_id), None)
+    }
+
+    /// Creates a [ConnectionBuilder] to configure the host, port, client id, timeouts, reconnection policy and account of the connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::builder().port(7497).client_id(42).connect().expect("connection failed");
+    ///
+    /// println!("server_version: {}", client.server_version());
+    /// ```
+    pub fn builder() -> ConnectionBuilder {
+        ConnectionBuilder::new()
+    }
+
+    fn connect_with_options(options: ConnectionOptions, account: Option<String>) -> Result<Client, Error> {
+        let connection = Connection::connect_with_options(options)?;
         let connection_metadata = connection.connection_metadata();
 
+        let account = match account {
+            Some(account) => Some(verify_account(&connection_metadata.managed_accounts, account)?),
+            None => None,
+        };
+
         let message_bus = Arc::new(TcpMessageBus::new(connection)?);
 
         // Starts thread to read messages from TWS
-        message_bus.process_messages(connection_metadata.server_version)?;
+        message_bus.process_messages()?;
 
-        Client::new(connection_metadata, message_bus)
+        let mut client = Client::new(connection_metadata, message_bus)?;
+        if account.is_some() {
+            client.account = account;
+        }
+
+        Ok(client)
     }
 
     fn new(connection_metadata: ConnectionMetadata, message_bus: Arc<dyn MessageBus>) -> Result<Client, Error> {
+        let connection_info = ConnectionInfo {
+            server_version: connection_metadata.server_version,
+            connection_time: connection_metadata.raw_connection_time.clone(),
+            optional_capabilities: connection_metadata.optional_capabilities.clone(),
+            managed_accounts: parse_managed_accounts(&connection_metadata.managed_accounts),
+        };
+
         let client = Client {
             server_version: connection_metadata.server_version,
             connection_time: connection_metadata.connection_time,
             time_zone: connection_metadata.time_zone,
             message_bus,
             client_id: connection_metadata.client_id,
+            request_timeout: None,
+            metrics: None,
             next_request_id: AtomicI32::new(9000),
             order_id: AtomicI32::new(1000),
+            account: parse_managed_accounts(&connection_metadata.managed_accounts).into_iter().next(),
+            account_aliases: RwLock::new(AccountAliases::default()),
+            subscriptions: subscriptions::SubscriptionRegistry::default(),
+            connection_info,
         };
 
         Ok(client)
     }
 
+    /// Disconnects from TWS or Gateway.
+    ///
+    /// Active subscriptions end with [Error::Shutdown], pending writes are flushed, the socket is closed and background threads are joined.
+    /// TWS cancels the subscriptions of a client when its connection closes. Requests made after disconnecting fail with [Error::Shutdown].
+    /// Dropping the client disconnects it as well.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// // ...
+    ///
+    /// client.disconnect();
+    /// ```
+    pub fn disconnect(&self) {
+        debug!("disconnecting client");
+        self.message_bus.ensure_shutdown();
+    }
+
+    /// Sends the requests made by `requests` to TWS with a single write, so grouped orders such as bracket orders reach TWS back to back.
+    ///
+    /// Requests are buffered until `requests` returns or panics, or [Client::flush] is called. Only requests made by the calling thread are buffered,
+    /// requests made by other threads in the meantime are sent as usual.
+    /// Responses to buffered requests do not arrive until the requests are flushed, so wait for responses after the batch.
+    ///
+    /// # Arguments
+    /// * `requests` - Makes the requests to send together.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::orders::{order_builder, Action};
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract = Contract::stock("AAPL");
+    /// let orders = order_builder::bracket_order(client.next_order_id(), Action::Buy, 100.0, 150.0, 155.0, 145.0);
+    ///
+    /// let subscriptions = client
+    ///     .batch(|client| orders.iter().map(|order| client.place_order(order.order_id, &contract, order)).collect::<Result<Vec<_>, _>>())
+    ///     .expect("error placing bracket order");
+    ///
+    /// for event in subscriptions.iter().flat_map(|subscription| subscription.iter()) {
+    ///     println!("{event:?}");
+    /// }
+    /// ```
+    pub fn batch<'a, T>(&'a self, requests: impl FnOnce(&'a Client) -> Result<T, Error>) -> Result<T, Error> {
+        let batch = Batch::begin(self.message_bus.as_ref())?;
+        let result = requests(self);
+        let flushed = batch.end();
+
+        let value = result?;
+        flushed?;
+        Ok(value)
+    }
+
+    /// Writes the requests buffered by an open [batch](Client::batch) of the calling thread to TWS. Does nothing outside of a batch.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.message_bus.flush_batch()
+    }
+
+    /// Returns a handle for making blocking requests that fail with [Error::Timeout] if TWS does not respond within `timeout`.
+    ///
+    /// Historical data, historical schedule, head timestamp and fundamental data requests that time out are cancelled in TWS.
+    /// Late replies to other requests are discarded when they arrive.
+    /// Use [ConnectionBuilder::request_timeout] to set a timeout for all blocking requests of the client.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::{Client, Error};
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// match client.with_timeout(Duration::from_secs(5)).contract_details(&Contract::stock("AAPL")) {
+    ///     Ok(details) => println!("{details:?}"),
+    ///     Err(Error::Timeout) => println!("TWS did not respond in time"),
+    ///     Err(e) => println!("error: {e}"),
+    /// }
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> WithTimeout<'_> {
+        WithTimeout::new(self, timeout)
+    }
+
+    /// Returns the default account of the client.
+    ///
+    /// This is the account configured with [ConnectionBuilder::account], or the first account managed by the connection.
+    pub fn account(&self) -> Option<&str> {
+        self.account.as_deref()
+    }
+
+    /// Returns the id the client connected with.
+    ///
+    /// This differs from the requested id if it was already in use and the builder was configured with a [ConnectionBuilder::client_id_range].
+    pub fn client_id(&self) -> i32 {
+        self.client_id
+    }
+
     /// Returns the next request ID.
     pub fn next_request_id(&self) -> i32 {
         self.next_request_id.fetch_add(1, Ordering::Relaxed)
@@ -97,7 +296,11 @@ impl Client {
     /// Returns and increments the order ID.
     ///
     /// The client maintains a sequence of order IDs. This function returns the next order ID in the sequence.
+    /// The sequence is advanced to the next valid order ID reported by TWS after reconnecting.
     pub fn next_order_id(&self) -> i32 {
+        if let Some(order_id) = self.message_bus.connection_order_id() {
+            self.order_id.fetch_max(order_id, Ordering::Relaxed);
+        }
         self.order_id.fetch_add(1, Ordering::Relaxed)
     }
 
@@ -133,11 +336,204 @@ impl Client {
         self.server_version
     }
 
+    /// Returns the features supported by the connected TWS or Gateway, derived from [Self::server_version].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let capabilities = client.capabilities();
+    /// println!("decimal sizes: {}", capabilities.supports_decimal_sizes());
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::new(self.server_version)
+    }
+
     /// The time of the server when the client connected
     pub fn connection_time(&self) -> Option<OffsetDateTime> {
         self.connection_time
     }
 
+    /// Returns the details negotiated with TWS or Gateway when the client connected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let info = client.connection_info();
+    /// println!("server version {} connected at {}", info.server_version, info.connection_time);
+    /// println!("managed accounts: {:?}", info.managed_accounts);
+    /// ```
+    pub fn connection_info(&self) -> &ConnectionInfo {
+        &self.connection_info
+    }
+
+    /// Subscribes to [Notice]s that TWS sends without a request id, such as connectivity notices (1100-1102) and market data farm status (2104, 2106, 2158).
+    ///
+    /// Each subscription receives every notice sent after it was created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let notices = client.notices().expect("error subscribing to notices");
+    /// for notice in &notices {
+    ///     println!("notice: {notice}");
+    /// }
+    /// ```
+    pub fn notices(&self) -> Result<Subscription<'_, Notice>, Error> {
+        let subscription = self.message_bus.subscribe_events(EventChannel::Errors)?;
+        Ok(Subscription::new(self, subscription, ResponseContext::default()))
+    }
+
+    /// Subscribes to notices classified as informational by the [NoticeFilter] configured with [ConnectionBuilder::notice_filter],
+    /// e.g. market data farm connection status. These notices are not delivered to [Client::notices].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::NoticeFilter;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::builder().notice_filter(NoticeFilter::data_farm_status()).connect().expect("connection failed");
+    ///
+    /// let notices = client.info_notices().expect("error subscribing to notices");
+    /// for notice in &notices {
+    ///     println!("info: {notice}");
+    /// }
+    /// ```
+    pub fn info_notices(&self) -> Result<Subscription<'_, Notice>, Error> {
+        let subscription = self.message_bus.subscribe_events(EventChannel::Info)?;
+        Ok(Subscription::new(self, subscription, ResponseContext::default()))
+    }
+
+    /// Subscribes to [ConnectionEvent]s, such as connectivity between TWS and IB servers, data farm status and reconnects of the client.
+    ///
+    /// Applications can use these events to display connection health or pause trading while data farms are down.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::{ConnectionEvent, DataFarmStatus};
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// for event in client.connection_events().expect("error subscribing to connection events") {
+    ///     match event {
+    ///         ConnectionEvent::DataFarm { name, status: DataFarmStatus::Disconnected, .. } => println!("data farm {name} is down"),
+    ///         ConnectionEvent::Closed => break,
+    ///         event => println!("{event:?}"),
+    ///     }
+    /// }
+    /// ```
+    pub fn connection_events(&self) -> Result<ConnectionEvents<'_>, Error> {
+        let subscription = self.message_bus.subscribe_events(EventChannel::All)?;
+        Ok(ConnectionEvents::new(Subscription::new(self, subscription, ResponseContext::default())))
+    }
+
+    /// Returns a snapshot of the connection health, for supervisors and dashboards that poll the client.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::DataFarmStatus;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let health = client.health();
+    /// println!("connected: {}, last message {:?} ago", health.connected, health.last_message_age);
+    /// for farm in health.data_farms.iter().filter(|farm| farm.status == DataFarmStatus::Disconnected) {
+    ///     println!("data farm {} is down", farm.name);
+    /// }
+    /// ```
+    pub fn health(&self) -> Health {
+        self.message_bus.health()
+    }
+
+    /// Returns the most recent requests sent to TWS, oldest first, with the error TWS reported for each rejected request.
+    /// Useful to inspect exactly what was sent when TWS rejects a request. The number of requests kept is configured with [ConnectionBuilder::request_journal].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::RequestOutcome;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// for entry in client.request_journal() {
+    ///     if let RequestOutcome::Rejected { code, message } = &entry.outcome {
+    ///         println!("{} rejected with [{code}] {message}: {:?}", entry.sent_at, entry.fields);
+    ///     }
+    /// }
+    /// ```
+    pub fn request_journal(&self) -> Vec<JournalEntry> {
+        self.message_bus.request_journal()
+    }
+
+    /// Lists the subscriptions that have not been cancelled or dropped, in the order they were created.
+    /// Useful to find subscriptions that are kept alive unintentionally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let _subscription = client.market_data(&Contract::stock("AAPL"), &[], false, false).expect("market data request failed");
+    ///
+    /// for subscription in client.active_subscriptions() {
+    ///     println!("{:?} request {:?} open for {:?}", subscription.kind, subscription.request_id, subscription.age);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
+    /// ```
+    pub fn active_subscriptions(&self) -> Vec<SubscriptionInfo> {
+        self.subscriptions.active()
+    }
+
+    /// Cancels every active subscription, e.g. when a strategy stops. Returns the number of subscriptions cancelled.
+    ///
+    /// Each subscription is cancelled as with [Subscription::cancel], ending iteration of the subscription with [Error::Cancelled].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let _subscription = client.market_data(&Contract::stock("AAPL"), &[], false, false).expect("market data request failed");
+    ///
+    /// let cancelled = client.cancel_all();
+    /// println!("cancelled {cancelled} subscriptions");
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
+    /// ```
+    pub fn cancel_all(&self) -> usize {
+        self.subscriptions.cancel_all()
+    }
+
     // === Accounts ===
 
     /// TWS's current time. TWS is synchronized with the server (not local computer) using NTP and this function will receive the current time in TWS.
@@ -152,7 +548,22 @@ impl Client {
     /// println!("server time: {server_time:?}");
     /// ```
     pub fn server_time(&self) -> Result<OffsetDateTime, Error> {
-        accounts::server_time(self)
+        accounts::server_time(self, self.request_timeout)
+    }
+
+    /// Requests the white branding id of the logged user, which is empty unless the account is white branded. Requires server version 167 or later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    /// let white_branding_id = client.user_info().expect("error requesting user info");
+    /// println!("white branding id: {white_branding_id:?}");
+    /// ```
+    pub fn user_info(&self) -> Result<String, Error> {
+        accounts::user_info(self, self.request_timeout)
     }
 
     /// Subscribes to [PositionUpdate]s for all accessible accounts.
@@ -173,7 +584,7 @@ impl Client {
     ///     }
     /// }
     /// ```
-    pub fn positions(&self) -> Result<Subscription<PositionUpdate>, Error> {
+    pub fn positions(&self) -> Result<Subscription<'_, PositionUpdate>, Error> {
         accounts::positions(self)
     }
 
@@ -197,7 +608,7 @@ impl Client {
     ///     println!("{position:?}")
     /// }
     /// ```
-    pub fn positions_multi(&self, account: Option<&str>, model_code: Option<&str>) -> Result<Subscription<PositionUpdateMulti>, Error> {
+    pub fn positions_multi(&self, account: Option<&str>, model_code: Option<&str>) -> Result<Subscription<'_, PositionUpdateMulti>, Error> {
         accounts::positions_multi(self, account, model_code)
     }
 
@@ -219,7 +630,7 @@ impl Client {
     ///     println!("{pnl:?}")
     /// }
     /// ```
-    pub fn pnl(&self, account: &str, model_code: Option<&str>) -> Result<Subscription<PnL>, Error> {
+    pub fn pnl(&self, account: &str, model_code: Option<&str>) -> Result<Subscription<'_, PnL>, Error> {
         accounts::pnl(self, account, model_code)
     }
 
@@ -342,61 +753,231 @@ impl Client {
         accounts::account_updates_multi(self, account, model_code)
     }
 
-    /// Requests the accounts to which the logged user has access to.
+    /// Requests the accounts to which the logged user has access to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let accounts = client.managed_accounts().expect("error requesting managed accounts");
+    /// println!("managed accounts: {accounts:?}")
+    /// ```
+    pub fn managed_accounts(&self) -> Result<Vec<String>, Error> {
+        accounts::managed_accounts(self)
+    }
+
+    // === Contracts ===
+
+    /// Requests contract information.
+    ///
+    /// Provides all the contracts matching the contract provided. It can also be used to retrieve complete options and futures chains. Though it is now (in API version > 9.72.12) advised to use reqSecDefOptParams for that purpose.
+    ///
+    /// # Arguments
+    /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use ibapi::contracts::Contract;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract = Contract::stock("TSLA");
+    /// let results = client.contract_details(&contract).expect("request failed");
+    /// for contract_detail in results {
+    ///     println!("contract: {:?}", contract_detail);
+    /// }
+    /// ```
+    pub fn contract_details(&self, contract: &Contract) -> Result<Vec<contracts::ContractDetails>, Error> {
+        contracts::contract_details(self, contract, self.request_timeout)
+    }
+
+    /// Get current [FamilyCode]s for all accessible accounts.
+    pub fn family_codes(&self) -> Result<Vec<FamilyCode>, Error> {
+        accounts::family_codes(self)
+    }
+
+    /// Requests the account aliases of a financial advisor account and registers them with the client.
+    ///
+    /// Once registered, account APIs such as [Client::pnl], [Client::account_updates] and [Client::place_order] accept either an alias or an account code.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let aliases = client.account_aliases().expect("request failed");
+    /// println!("Growth resolves to {}", aliases.resolve("Growth"));
+    ///
+    /// let subscription = client.pnl("Growth", None).expect("request failed");
+    /// ```
+    #[cfg(feature = "fa")]
+    pub fn account_aliases(&self) -> Result<AccountAliases, Error> {
+        accounts::account_aliases(self)
+    }
+
+    /// Registers account aliases with the client, replacing any previously registered aliases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use ibapi::accounts::AccountAliases;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let mut aliases = AccountAliases::new();
+    /// aliases.insert("DU1234567", "Growth");
+    /// client.set_account_aliases(aliases);
+    /// ```
+    pub fn set_account_aliases(&self, aliases: AccountAliases) {
+        *self.account_aliases.write().unwrap() = aliases;
+    }
+
+    /// Returns a handle for operating on a model portfolio of an advisor account.
+    ///
+    /// Positions, account updates, PnL and orders requested through the handle are scoped to the model.
+    ///
+    /// # Arguments
+    /// * `account`    - The advisor account, or an alias of it, the model belongs to.
+    /// * `model_code` - The code of the model.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let model = client.model("DU1234567", "TARGET2024");
+    /// let subscription = model.pnl().expect("error requesting model pnl");
+    /// for pnl in subscription.iter() {
+    ///     println!("{pnl:?}")
+    /// }
+    /// ```
+    pub fn model(&self, account: &str, model_code: &str) -> Model<'_> {
+        Model::new(self, account, model_code)
+    }
+
+    /// Requests the financial advisor configuration of the given type. Only available for financial advisor accounts.
+    ///
+    /// # Arguments
+    /// * `data_type` - type of configuration to request: groups, profiles or aliases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use ibapi::accounts::fa::{FaConfiguration, FaDataType};
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// if let FaConfiguration::Groups(groups) = client.request_fa(FaDataType::Groups).expect("request failed") {
+    ///     for group in groups {
+    ///         println!("{}: {:?}", group.name, group.accounts);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "fa")]
+    pub fn request_fa(&self, data_type: FaDataType) -> Result<FaConfiguration, Error> {
+        accounts::request_fa(self, data_type)
+    }
+
+    /// Replaces the financial advisor configuration of the same type as `configuration`.
+    ///
+    /// # Arguments
+    /// * `configuration` - the new groups, profiles or aliases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use ibapi::accounts::fa::{FaConfiguration, FaDataType, GroupAccount};
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let mut configuration = client.request_fa(FaDataType::Groups).expect("request failed");
+    /// if let FaConfiguration::Groups(groups) = &mut configuration {
+    ///     groups[0].accounts.push(GroupAccount { account: "DU1234567".into(), amount: None });
+    /// }
+    ///
+    /// client.replace_fa(&configuration).expect("replace failed");
+    /// ```
+    #[cfg(feature = "fa")]
+    pub fn replace_fa(&self, configuration: &FaConfiguration) -> Result<(), Error> {
+        accounts::replace_fa(self, configuration)
+    }
+
+    /// Requests details about a given market rule
+    ///
+    /// The market rule for an instrument on a particular exchange provides details about how the minimum price increment changes with price.
+    /// A list of market rule ids can be obtained by invoking [Self::contract_details()] for a particular contract.
+    /// The returned market rule ID list will provide the market rule ID for the instrument in the correspond valid exchange list in [contracts::ContractDetails].
+    pub fn market_rule(&self, market_rule_id: i32) -> Result<contracts::MarketRule, Error> {
+        contracts::market_rule(self, market_rule_id, self.request_timeout)
+    }
+
+    /// Resolves a continuous futures contract to the futures contract currently backing the series, for trading.
+    ///
+    /// # Arguments
+    /// * `contract` - The continuous futures contract, see [Contract::continuous_futures].
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use ibapi::Client;
+    /// use ibapi::contracts::Contract;
     ///
     /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
     ///
-    /// let accounts = client.managed_accounts().expect("error requesting managed accounts");
-    /// println!("managed accounts: {accounts:?}")
+    /// let continuous = Contract::continuous_futures("ES", "CME");
+    /// let front = client.front_month_futures(&continuous).expect("request failed");
+    /// println!("front month: {} {}", front.local_symbol, front.last_trade_date_or_contract_month);
     /// ```
-    pub fn managed_accounts(&self) -> Result<Vec<String>, Error> {
-        accounts::managed_accounts(self)
+    pub fn front_month_futures(&self, contract: &Contract) -> Result<Contract, Error> {
+        contracts::front_month_futures(self, contract)
     }
 
-    // === Contracts ===
-
-    /// Requests contract information.
+    /// Lists the contracts matching `contract`, including expired contracts, ordered by last trade date.
     ///
-    /// Provides all the contracts matching the contract provided. It can also be used to retrieve complete options and futures chains. Though it is now (in API version > 9.72.12) advised to use reqSecDefOptParams for that purpose.
+    /// The returned contracts include their last trade dates and have [Contract::include_expired] set, so they can be used to request historical data of expired futures.
     ///
     /// # Arguments
-    /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
+    /// * `contract` - The [Contract] used as sample to query the available contracts, e.g. [contracts::futures].
     ///
     /// # Examples
     ///
     /// ```no_run
+    /// # #[cfg(feature = "historical")]
+    /// # fn main() {
+    /// use ibapi::contracts;
+    /// use ibapi::market_data::historical::{BarSize, ToDuration, WhatToShow};
     /// use ibapi::Client;
-    /// use ibapi::contracts::Contract;
+    /// use time::macros::datetime;
     ///
     /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
     ///
-    /// let contract = Contract::stock("TSLA");
-    /// let results = client.contract_details(&contract).expect("request failed");
-    /// for contract_detail in results {
-    ///     println!("contract: {:?}", contract_detail);
+    /// let contract = contracts::futures("ES", "CME", "202212");
+    ///
+    /// let expired = client.expired_contracts(&contract).expect("request failed");
+    /// for contract in &expired {
+    ///     let bars = client
+    ///         .historical_data(contract, datetime!(2022-12-16 16:00 UTC), 1.months(), BarSize::Day, WhatToShow::Trades, true)
+    ///         .expect("historical data request failed");
+    ///     println!("{}: {} bars", contract.local_symbol, bars.bars.len());
     /// }
+    /// # }
+    /// # #[cfg(not(feature = "historical"))]
+    /// # fn main() {}
     /// ```
-    pub fn contract_details(&self, contract: &Contract) -> Result<Vec<contracts::ContractDetails>, Error> {
-        contracts::contract_details(self, contract)
-    }
-
-    /// Get current [FamilyCode]s for all accessible accounts.
-    pub fn family_codes(&self) -> Result<Vec<FamilyCode>, Error> {
-        accounts::family_codes(self)
-    }
-
-    /// Requests details about a given market rule
-    ///
-    /// The market rule for an instrument on a particular exchange provides details about how the minimum price increment changes with price.
-    /// A list of market rule ids can be obtained by invoking [Self::contract_details()] for a particular contract.
-    /// The returned market rule ID list will provide the market rule ID for the instrument in the correspond valid exchange list in [contracts::ContractDetails].
-    pub fn market_rule(&self, market_rule_id: i32) -> Result<contracts::MarketRule, Error> {
-        contracts::market_rule(self, market_rule_id)
+    pub fn expired_contracts(&self, contract: &Contract) -> Result<Vec<Contract>, Error> {
+        contracts::expired_contracts(self, contract)
     }
 
     /// Requests matching stock symbols.
@@ -417,7 +998,7 @@ impl Client {
     /// }
     /// ```
     pub fn matching_symbols(&self, pattern: &str) -> Result<impl Iterator<Item = contracts::ContractDescription>, Error> {
-        Ok(contracts::matching_symbols(self, pattern)?.into_iter())
+        Ok(contracts::matching_symbols(self, pattern, self.request_timeout)?.into_iter())
     }
 
     /// Calculates an option’s price based on the provided volatility and its underlying’s price.
@@ -469,10 +1050,10 @@ impl Client {
     /// Requests security definition option parameters for viewing a contract’s option chain.
     ///
     /// # Arguments
-    /// `symbol`   - Contract symbol of the underlying.
-    /// `exchange` - The exchange on which the returned options are trading. Can be set to the empty string for all exchanges.
-    /// `security_type` - The type of the underlying security, i.e. STK
-    /// `contract_id`   - The contract ID of the underlying security.
+    /// * `symbol`   - Contract symbol of the underlying.
+    /// * `exchange` - The exchange on which the returned options are trading. Can be set to the empty string for all exchanges.
+    /// * `security_type` - The type of the underlying security, i.e. STK
+    /// * `contract_id`   - The contract ID of the underlying security.
     ///
     /// # Examples
     ///
@@ -500,7 +1081,7 @@ impl Client {
         exchange: &str,
         security_type: SecurityType,
         contract_id: i32,
-    ) -> Result<Subscription<contracts::OptionChain>, Error> {
+    ) -> Result<Subscription<'_, contracts::OptionChain>, Error> {
         contracts::option_chain(self, symbol, exchange, security_type, contract_id)
     }
 
@@ -521,7 +1102,7 @@ impl Client {
     ///    println!("{order_data:?}")
     /// }
     /// ```
-    pub fn all_open_orders(&self) -> Result<Subscription<Orders>, Error> {
+    pub fn all_open_orders(&self) -> Result<Subscription<'_, Orders>, Error> {
         orders::all_open_orders(self)
     }
 
@@ -542,7 +1123,7 @@ impl Client {
     ///    println!("{order_data:?}")
     /// }
     /// ```
-    pub fn auto_open_orders(&self, auto_bind: bool) -> Result<Subscription<Orders>, Error> {
+    pub fn auto_open_orders(&self, auto_bind: bool) -> Result<Subscription<'_, Orders>, Error> {
         orders::auto_open_orders(self, auto_bind)
     }
 
@@ -565,7 +1146,7 @@ impl Client {
     ///    println!("{result:?}");
     /// }
     /// ```
-    pub fn cancel_order(&self, order_id: i32, manual_order_cancel_time: &str) -> Result<Subscription<CancelOrder>, Error> {
+    pub fn cancel_order(&self, order_id: i32, manual_order_cancel_time: &str) -> Result<Subscription<'_, CancelOrder>, Error> {
         orders::cancel_order(self, order_id, manual_order_cancel_time)
     }
 
@@ -586,7 +1167,7 @@ impl Client {
     ///    println!("{order_data:?}")
     /// }
     /// ```
-    pub fn completed_orders(&self, api_only: bool) -> Result<Subscription<Orders>, Error> {
+    pub fn completed_orders(&self, api_only: bool) -> Result<Subscription<'_, Orders>, Error> {
         orders::completed_orders(self, api_only)
     }
 
@@ -617,10 +1198,30 @@ impl Client {
     ///    println!("{execution_data:?}")
     /// }
     /// ```
-    pub fn executions(&self, filter: orders::ExecutionFilter) -> Result<Subscription<Executions>, Error> {
+    pub fn executions(&self, filter: orders::ExecutionFilter) -> Result<Subscription<'_, Executions>, Error> {
         orders::executions(self, filter)
     }
 
+    /// Requests the soft dollar tiers available to the account.
+    ///
+    /// A tier can be assigned to [Order::soft_dollar_tier] when placing orders.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let tiers = client.soft_dollar_tiers().expect("request failed");
+    /// for tier in &tiers {
+    ///     println!("{}: {}", tier.display_name, tier.value);
+    /// }
+    /// ```
+    pub fn soft_dollar_tiers(&self) -> Result<Vec<SoftDollarTier>, Error> {
+        orders::soft_dollar_tiers(self)
+    }
+
     /// Cancels all open [Order]s.
     ///
     /// # Examples
@@ -651,7 +1252,7 @@ impl Client {
     ///    println!("{order_data:?}")
     /// }
     /// ```
-    pub fn open_orders(&self) -> Result<Subscription<Orders>, Error> {
+    pub fn open_orders(&self) -> Result<Subscription<'_, Orders>, Error> {
         orders::open_orders(self)
     }
 
@@ -692,10 +1293,39 @@ impl Client {
     ///    }
     /// }
     /// ```
-    pub fn place_order(&self, order_id: i32, contract: &Contract, order: &Order) -> Result<Subscription<PlaceOrder>, Error> {
+    pub fn place_order(&self, order_id: i32, contract: &Contract, order: &Order) -> Result<Subscription<'_, PlaceOrder>, Error> {
         orders::place_order(self, order_id, contract, order)
     }
 
+    /// Estimates the margin impact of a basket of [Order]s without executing them.
+    ///
+    /// Each order is submitted as a what-if order and the projected initial and maintenance margin changes are summed across the basket.
+    ///
+    /// # Arguments
+    /// * `orders` - [Contract] and [Order] pairs to evaluate.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::orders::{order_builder, Action};
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let basket = vec![
+    ///     (Contract::stock("MSFT"), order_builder::market_order(Action::Buy, 100.0)),
+    ///     (Contract::stock("AAPL"), order_builder::market_order(Action::Sell, 50.0)),
+    /// ];
+    ///
+    /// let impact = client.what_if_margin(&basket).expect("what-if request failed");
+    /// println!("initial margin change: {}", impact.initial_margin_change);
+    /// println!("maintenance margin after: {:?}", impact.maintenance_margin_after());
+    /// ```
+    pub fn what_if_margin(&self, orders: &[(Contract, Order)]) -> Result<MarginImpact, Error> {
+        orders::what_if_margin(self, orders)
+    }
+
     /// Exercises an options contract.
     ///
     /// Note: this function is affected by a TWS setting which specifies if an exercise request must be finalized.
@@ -706,7 +1336,7 @@ impl Client {
     /// * `exercise_quantity` - Number of contracts to be exercised.
     /// * `account`           - Destination account.
     /// * `ovrd`              - Specifies whether your setting will override the system’s natural action.
-    ///                         For example, if your action is "exercise" and the option is not in-the-money, by natural action the option would not exercise. If you have override set to true the natural action would be overridden and the out-of-the money option would be exercised.
+    ///   For example, if your action is "exercise" and the option is not in-the-money, by natural action the option would not exercise. If you have override set to true the natural action would be overridden and the out-of-the money option would be exercised.
     /// * `manual_order_time` - Specify the time at which the options should be exercised. If `None`, the current time will be used. Requires TWS API 10.26 or higher.
     pub fn exercise_options<'a>(
         &'a self,
@@ -739,8 +1369,9 @@ impl Client {
     ///
     /// print!("head_timestamp: {result:?}");
     /// ```
+    #[cfg(feature = "historical")]
     pub fn head_timestamp(&self, contract: &Contract, what_to_show: historical::WhatToShow, use_rth: bool) -> Result<OffsetDateTime, Error> {
-        historical::head_timestamp(self, contract, what_to_show, use_rth)
+        historical::head_timestamp(self, contract, what_to_show, use_rth, self.request_timeout)
     }
 
     /// Requests interval of historical data ending at specified time for [Contract].
@@ -776,6 +1407,7 @@ impl Client {
     ///     println!("{bar:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_data(
         &self,
         contract: &Contract,
@@ -785,7 +1417,16 @@ impl Client {
         what_to_show: historical::WhatToShow,
         use_rth: bool,
     ) -> Result<historical::HistoricalData, Error> {
-        historical::historical_data(self, contract, Some(interval_end), duration, bar_size, Some(what_to_show), use_rth)
+        historical::historical_data(
+            self,
+            contract,
+            Some(interval_end),
+            duration,
+            bar_size,
+            Some(what_to_show),
+            use_rth,
+            self.request_timeout,
+        )
     }
 
     /// Requests interval of historical data ending now for [Contract].
@@ -818,6 +1459,7 @@ impl Client {
     ///     println!("{bar:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_data_ending_now(
         &self,
         contract: &Contract,
@@ -826,7 +1468,16 @@ impl Client {
         what_to_show: historical::WhatToShow,
         use_rth: bool,
     ) -> Result<historical::HistoricalData, Error> {
-        historical::historical_data(self, contract, None, duration, bar_size, Some(what_to_show), use_rth)
+        historical::historical_data(
+            self,
+            contract,
+            None,
+            duration,
+            bar_size,
+            Some(what_to_show),
+            use_rth,
+            self.request_timeout,
+        )
     }
 
     /// Requests [Schedule](historical::Schedule) for an interval of given duration
@@ -841,7 +1492,6 @@ impl Client {
     ///
     /// ```no_run
     /// use time::macros::datetime;
-    //
     /// use ibapi::contracts::Contract;
     /// use ibapi::Client;
     /// use ibapi::market_data::historical::ToDuration;
@@ -860,13 +1510,14 @@ impl Client {
     ///     println!("{session:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_schedules(
         &self,
         contract: &Contract,
         interval_end: OffsetDateTime,
         duration: historical::Duration,
     ) -> Result<historical::Schedule, Error> {
-        historical::historical_schedule(self, contract, Some(interval_end), duration)
+        historical::historical_schedule(self, contract, Some(interval_end), duration, self.request_timeout)
     }
 
     /// Requests [historical::Schedule] for interval ending at current time.
@@ -896,8 +1547,9 @@ impl Client {
     ///     println!("{session:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_schedules_ending_now(&self, contract: &Contract, duration: historical::Duration) -> Result<historical::Schedule, Error> {
-        historical::historical_schedule(self, contract, None, duration)
+        historical::historical_schedule(self, contract, None, duration, self.request_timeout)
     }
 
     /// Requests historical time & sales data (Bid/Ask) for an instrument.
@@ -930,6 +1582,7 @@ impl Client {
     ///     println!("{tick:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_ticks_bid_ask(
         &self,
         contract: &Contract,
@@ -971,6 +1624,7 @@ impl Client {
     ///     println!("{tick:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_ticks_mid_point(
         &self,
         contract: &Contract,
@@ -1011,6 +1665,7 @@ impl Client {
     ///     println!("{tick:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn historical_ticks_trade(
         &self,
         contract: &Contract,
@@ -1033,7 +1688,6 @@ impl Client {
     ///
     /// ```no_run
     /// use time::macros::datetime;
-    //
     /// use ibapi::contracts::Contract;
     /// use ibapi::Client;
     /// use ibapi::market_data::historical::BarSize;
@@ -1050,6 +1704,7 @@ impl Client {
     ///     println!("{item:?}");
     /// }
     /// ```
+    #[cfg(feature = "historical")]
     pub fn histogram_data(&self, contract: &Contract, use_rth: bool, period: historical::BarSize) -> Result<Vec<HistogramEntry>, Error> {
         historical::histogram_data(self, contract, use_rth, period)
     }
@@ -1079,6 +1734,7 @@ impl Client {
     ///     println!("bar[{i}]: {bar:?}");
     /// }
     /// ```
+    #[cfg(feature = "market-data")]
     pub fn realtime_bars<'a>(
         &'a self,
         contract: &Contract,
@@ -1095,6 +1751,7 @@ impl Client {
     /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
     /// * `number_of_ticks` - number of ticks.
     /// * `ignore_size` - ignore size flag.
+    #[cfg(feature = "market-data")]
     pub fn tick_by_tick_all_last<'a>(
         &'a self,
         contract: &Contract,
@@ -1110,6 +1767,7 @@ impl Client {
     /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
     /// * `number_of_ticks` - number of ticks.
     /// * `ignore_size` - ignore size flag.
+    #[cfg(feature = "market-data")]
     pub fn tick_by_tick_bid_ask<'a>(
         &'a self,
         contract: &Contract,
@@ -1125,6 +1783,7 @@ impl Client {
     /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
     /// * `number_of_ticks` - number of ticks.
     /// * `ignore_size` - ignore size flag.
+    #[cfg(feature = "market-data")]
     pub fn tick_by_tick_last<'a>(
         &'a self,
         contract: &Contract,
@@ -1140,6 +1799,7 @@ impl Client {
     /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
     /// * `number_of_ticks` - number of ticks.
     /// * `ignore_size` - ignore size flag.
+    #[cfg(feature = "market-data")]
     pub fn tick_by_tick_midpoint<'a>(
         &'a self,
         contract: &Contract,
@@ -1190,6 +1850,7 @@ impl Client {
     /// client.switch_market_data_type(market_data_type).expect("request failed");
     /// println!("market data switched: {:?}", market_data_type);
     /// ```
+    #[cfg(feature = "market-data")]
     pub fn market_depth<'a>(
         &'a self,
         contract: &Contract,
@@ -1212,6 +1873,7 @@ impl Client {
     ///     println!("{exchange:?}");
     /// }
     /// ```
+    #[cfg(feature = "market-data")]
     pub fn market_depth_exchanges(&self) -> Result<Vec<DepthMarketDataDescription>, Error> {
         realtime::market_depth_exchanges(self)
     }
@@ -1224,21 +1886,21 @@ impl Client {
     ///
     /// * `contract` - Contract for which the data is being requested.
     /// * `generic_ticks` - IDs of the available generic ticks:
-    ///         - 100 Option Volume (currently for stocks)
-    ///         - 101 Option Open Interest (currently for stocks)
-    ///         - 104 Historical Volatility (currently for stocks)
-    ///         - 105 Average Option Volume (currently for stocks)
-    ///         - 106 Option Implied Volatility (currently for stocks)
-    ///         - 162 Index Future Premium
-    ///         - 165 Miscellaneous Stats
-    ///         - 221 Mark Price (used in TWS P&L computations)
-    ///         - 225 Auction values (volume, price and imbalance)
-    ///         - 233 RTVolume - contains the last trade price, last trade size, last trade time, total volume, VWAP, and single trade flag.
-    ///         - 236 Shortable
-    ///         - 256 Inventory
-    ///         - 258 Fundamental Ratios
-    ///         - 411 Realtime Historical Volatility
-    ///         - 456 IBDividends
+    ///   - 100 Option Volume (currently for stocks)
+    ///   - 101 Option Open Interest (currently for stocks)
+    ///   - 104 Historical Volatility (currently for stocks)
+    ///   - 105 Average Option Volume (currently for stocks)
+    ///   - 106 Option Implied Volatility (currently for stocks)
+    ///   - 162 Index Future Premium
+    ///   - 165 Miscellaneous Stats
+    ///   - 221 Mark Price (used in TWS P&L computations)
+    ///   - 225 Auction values (volume, price and imbalance)
+    ///   - 233 RTVolume - contains the last trade price, last trade size, last trade time, total volume, VWAP, and single trade flag.
+    ///   - 236 Shortable
+    ///   - 256 Inventory
+    ///   - 258 Fundamental Ratios
+    ///   - 411 Realtime Historical Volatility
+    ///   - 456 IBDividends
     /// * `snapshot` - for users with corresponding real time market data subscriptions. A true value will return a one-time snapshot, while a false value will provide streaming data.
     /// * `regulatory_snapshot` - snapshot for US stocks requests NBBO snapshots for users which have "US Securities Snapshot Bundle" subscription but not corresponding Network A, B, or C subscription necessary for streaming market data. One-time snapshot of current market price that will incur a fee of 1 cent to the account per snapshot.
     ///
@@ -1270,18 +1932,20 @@ impl Client {
     ///         TickTypes::EFP(tick_efp) => println!("{:?}", tick_efp),
     ///         TickTypes::OptionComputation(option_computation) => println!("{:?}", option_computation),
     ///         TickTypes::RequestParameters(tick_request_parameters) => println!("{:?}", tick_request_parameters),
+    ///         TickTypes::DeltaNeutralValidation(delta_neutral_contract) => println!("{:?}", delta_neutral_contract),
     ///         TickTypes::Notice(notice) => println!("{:?}", notice),
     ///         TickTypes::SnapshotEnd => subscription.cancel(),
     ///     }
     /// }
     /// ```
+    #[cfg(feature = "market-data")]
     pub fn market_data(
         &self,
         contract: &Contract,
         generic_ticks: &[&str],
         snapshot: bool,
         regulatory_snapshot: bool,
-    ) -> Result<Subscription<TickTypes>, Error> {
+    ) -> Result<Subscription<'_, TickTypes>, Error> {
         realtime::market_data(self, contract, generic_ticks, snapshot, regulatory_snapshot)
     }
 
@@ -1301,12 +1965,16 @@ impl Client {
     ///   println!("news provider {:?}", news_provider);
     /// }
     /// ```
+    #[cfg(feature = "news")]
     pub fn news_providers(&self) -> Result<Vec<news::NewsProvider>, Error> {
         news::news_providers(self)
     }
 
     /// Subscribes to IB's News Bulletins.
     ///
+    /// Bulletins include exchange-wide alerts, such as trading halts. See [NewsBulletin::bulletin_type](news::NewsBulletin::bulletin_type).
+    /// Dropping the subscription cancels it.
+    ///
     /// # Arguments
     ///
     /// * `all_messages` - If set to true, will return all the existing bulletins for the current day, set to false to receive only the new bulletins.
@@ -1320,10 +1988,11 @@ impl Client {
     ///
     /// let news_bulletins = client.news_bulletins(true).expect("request news providers failed");
     /// for news_bulletin in &news_bulletins {
-    ///   println!("news bulletin {:?}", news_bulletin);
+    ///   println!("{:?} from {}: {}", news_bulletin.bulletin_type(), news_bulletin.exchange, news_bulletin.message);
     /// }
     /// ```
-    pub fn news_bulletins(&self, all_messages: bool) -> Result<Subscription<news::NewsBulletin>, Error> {
+    #[cfg(feature = "news")]
+    pub fn news_bulletins(&self, all_messages: bool) -> Result<Subscription<'_, news::NewsBulletin>, Error> {
         news::news_bulletins(self, all_messages)
     }
 
@@ -1351,11 +2020,14 @@ impl Client {
     /// let end_time = datetime!(2023-04-15 0:00 UTC);
     /// let total_results = 10;
     ///
-    /// let news_bulletins = client.news_bulletins(true).expect("request news providers failed");
-    /// for news_bulletin in &news_bulletins {
-    ///   println!("news bulletin {:?}", news_bulletin);
+    /// let articles = client
+    ///     .historical_news(contract_id, &provider_codes, start_time, end_time, total_results)
+    ///     .expect("request historical news failed");
+    /// for article in &articles {
+    ///   println!("article {:?}", article);
     /// }
     /// ```
+    #[cfg(feature = "news")]
     pub fn historical_news(
         &self,
         contract_id: i32,
@@ -1363,12 +2035,98 @@ impl Client {
         start_time: OffsetDateTime,
         end_time: OffsetDateTime,
         total_results: u8,
-    ) -> Result<Subscription<news::NewsArticle>, Error> {
+    ) -> Result<Subscription<'_, news::NewsArticle>, Error> {
         news::historical_news(self, contract_id, provider_codes, start_time, end_time, total_results)
     }
 
+    /// Requests historical news headlines, reporting whether more headlines are available in the date range.
+    ///
+    /// To fetch the next page, request again with `end_time` set to the time of the oldest headline returned.
+    /// See [historical_news_all](Client::historical_news_all) to fetch every headline in the range.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id`    - Contract ID of ticker. See [contract_details](Client::contract_details) for how to retrieve contract ID.
+    /// * `provider_codes` - A list of provider codes.
+    /// * `start_time`     - Marks the (exclusive) start of the date range.
+    /// * `end_time`       - Marks the (inclusive) end of the date range.
+    /// * `total_results`  - The maximum number of headlines to fetch (1 – 300)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use time::macros::datetime;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract_id = 76792991; // TSLA
+    /// let start_time = datetime!(2023-04-01 0:00 UTC);
+    /// let end_time = datetime!(2023-04-15 0:00 UTC);
+    ///
+    /// let page = client
+    ///     .historical_news_page(contract_id, &["BRFG", "DJ-N"], start_time, end_time, 50)
+    ///     .expect("request historical news failed");
+    /// for article in &page.headlines {
+    ///   println!("article {:?}", article);
+    /// }
+    /// if page.has_more {
+    ///   println!("more headlines available");
+    /// }
+    /// ```
+    #[cfg(feature = "news")]
+    pub fn historical_news_page(
+        &self,
+        contract_id: i32,
+        provider_codes: &[&str],
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        total_results: u16,
+    ) -> Result<news::HistoricalNews, Error> {
+        news::historical_news_page(self, contract_id, provider_codes, start_time, end_time, total_results)
+    }
+
+    /// Requests every historical news headline in the date range, requesting further pages while TWS reports more are available.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_id`    - Contract ID of ticker. See [contract_details](Client::contract_details) for how to retrieve contract ID.
+    /// * `provider_codes` - A list of provider codes.
+    /// * `start_time`     - Marks the (exclusive) start of the date range.
+    /// * `end_time`       - Marks the (inclusive) end of the date range.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    /// use time::macros::datetime;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract_id = 76792991; // TSLA
+    /// let start_time = datetime!(2023-04-01 0:00 UTC);
+    /// let end_time = datetime!(2023-04-15 0:00 UTC);
+    ///
+    /// let articles = client
+    ///     .historical_news_all(contract_id, &["BRFG", "DJ-N"], start_time, end_time)
+    ///     .expect("request historical news failed");
+    /// println!("{} headlines", articles.len());
+    /// ```
+    #[cfg(feature = "news")]
+    pub fn historical_news_all(
+        &self,
+        contract_id: i32,
+        provider_codes: &[&str],
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Result<Vec<news::NewsArticle>, Error> {
+        news::historical_news_all(self, contract_id, provider_codes, start_time, end_time)
+    }
+
     /// Requests news article body given articleId.
     ///
+    /// Binary articles, such as PDF documents, are decoded with [NewsArticleBody::content](news::NewsArticleBody::content).
+    ///
     /// # Arguments
     ///
     /// * `provider_code` - Short code indicating news provider, e.g. FLY.
@@ -1377,6 +2135,7 @@ impl Client {
     /// # Examples
     ///
     /// ```no_run
+    /// use ibapi::news::ArticleContent;
     /// use ibapi::Client;
     ///
     /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
@@ -1386,8 +2145,12 @@ impl Client {
     /// let article_id = "DJ-N$1915168d";
     ///
     /// let article = client.news_article(provider_code, article_id).expect("request news article failed");
-    /// println!("{:?}", article);
+    /// match article.content().expect("invalid article") {
+    ///     ArticleContent::Text(text) => println!("{text}"),
+    ///     ArticleContent::Binary(data) => std::fs::write("article.pdf", data).expect("error saving article"),
+    /// }
     /// ```
+    #[cfg(feature = "news")]
     pub fn news_article(&self, provider_code: &str, article_id: &str) -> Result<news::NewsArticleBody, Error> {
         news::news_article(self, provider_code, article_id)
     }
@@ -1415,7 +2178,8 @@ impl Client {
     ///     println!("{:?}", article);
     /// }
     /// ```
-    pub fn contract_news(&self, contract: &Contract, provider_codes: &[&str]) -> Result<Subscription<NewsArticle>, Error> {
+    #[cfg(feature = "news")]
+    pub fn contract_news(&self, contract: &Contract, provider_codes: &[&str]) -> Result<Subscription<'_, NewsArticle>, Error> {
         news::contract_news(self, contract, provider_codes)
     }
 
@@ -1439,45 +2203,108 @@ impl Client {
     ///     println!("{:?}", article);
     /// }
     /// ```
-    pub fn broad_tape_news(&self, provider_code: &str) -> Result<Subscription<NewsArticle>, Error> {
+    #[cfg(feature = "news")]
+    pub fn broad_tape_news(&self, provider_code: &str) -> Result<Subscription<'_, NewsArticle>, Error> {
         news::broad_tape_news(self, provider_code)
     }
 
+    // === Fundamentals ===
+
+    /// Requests a fundamental data report for the contract as an XML document. Requires a subscription to Refinitiv fundamental data.
+    ///
+    /// The request is cancelled if no report arrives within the [request timeout](ConnectionBuilder::request_timeout).
+    /// Snapshot and ratios reports can be decoded with [CompanySnapshot::parse](fundamentals::CompanySnapshot::parse)
+    /// and [FundamentalRatios::parse](fundamentals::FundamentalRatios::parse).
+    ///
+    /// # Arguments
+    /// * `contract`    - Contract to request the report for.
+    /// * `report_type` - Type of report, e.g. [FundamentalReportType::ReportSnapshot](fundamentals::FundamentalReportType::ReportSnapshot).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::fundamentals::{CompanySnapshot, FundamentalReportType};
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract = Contract::stock("AAPL");
+    /// let xml = client
+    ///     .fundamental_data(&contract, FundamentalReportType::ReportSnapshot)
+    ///     .expect("request fundamental data failed");
+    ///
+    /// let snapshot = CompanySnapshot::parse(&xml).expect("error parsing snapshot");
+    /// println!("{}: market cap {:?}", snapshot.company_name, snapshot.ratios.market_cap());
+    /// ```
+    #[cfg(feature = "fundamentals")]
+    pub fn fundamental_data(&self, contract: &Contract, report_type: fundamentals::FundamentalReportType) -> Result<String, Error> {
+        fundamentals::fundamental_data(self, contract, report_type, self.request_timeout)
+    }
+
     // === Scanner ===
 
     /// Requests an XML list of scanner parameters valid in TWS.
     ///
+    /// The list can be decoded with [ScannerParameters::parse](scanner::parameters::ScannerParameters::parse).
+    ///
     /// # Examples
     ///
     /// ```no_run
+    /// use ibapi::scanner::parameters::ScannerParameters;
     /// use ibapi::Client;
     ///
     /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
     ///
-    /// let parameters = client.scanner_parameters().expect("request scanner parameters failed");
-    /// println!("{:?}", parameters);
+    /// let xml = client.scanner_parameters().expect("request scanner parameters failed");
+    /// let parameters = ScannerParameters::parse(&xml).expect("error parsing scanner parameters");
+    /// for scan_code in parameters.scan_codes_for("STK") {
+    ///     println!("{}: {}", scan_code.scan_code, scan_code.display_name);
+    /// }
     /// ```
+    #[cfg(feature = "scanner")]
     pub fn scanner_parameters(&self) -> Result<String, Error> {
         scanner::scanner_parameters(self)
     }
 
     /// Starts a subscription to market scan results based on the provided parameters.
     ///
+    /// TWS updates the results periodically. Each item is a snapshot of the matching contracts, ordered by rank,
+    /// and the subscription keeps receiving snapshots until it is dropped or cancelled.
+    ///
+    /// # Arguments
+    /// * `subscription` - Instrument, location, scan code and filters of the scan.
+    /// * `filter`       - Additional filter options, e.g. `priceAbove`. See [ScannerParameters](scanner::parameters::ScannerParameters) for valid codes.
+    ///
     /// # Examples
     ///
     /// ```no_run
+    /// use ibapi::scanner::ScannerSubscription;
     /// use ibapi::Client;
     ///
     /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
     ///
-    /// let parameters = client.scanner_parameters().expect("request scanner parameters failed");
-    /// println!("{:?}", parameters);
+    /// let scan = ScannerSubscription {
+    ///     number_of_rows: 10,
+    ///     instrument: Some("STK".into()),
+    ///     location_code: Some("STK.US.MAJOR".into()),
+    ///     scan_code: Some("TOP_PERC_GAIN".into()),
+    ///     ..ScannerSubscription::default()
+    /// };
+    ///
+    /// let subscription = client.scanner_subscription(&scan, &vec![]).expect("request scanner subscription failed");
+    /// for snapshot in &subscription {
+    ///     for row in snapshot {
+    ///         println!("{}: {}", row.rank, row.contract_details.contract.symbol);
+    ///     }
+    /// }
     /// ```
+    #[cfg(feature = "scanner")]
     pub fn scanner_subscription(
         &self,
         subscription: &scanner::ScannerSubscription,
         filter: &Vec<orders::TagValue>,
-    ) -> Result<Subscription<Vec<ScannerData>>, Error> {
+    ) -> Result<Subscription<'_, Vec<ScannerData>>, Error> {
         scanner::scanner_subscription(self, subscription, filter)
     }
 
@@ -1555,25 +2382,80 @@ impl Client {
         filter: &str,
         limit: Option<i32>,
         auto_fill: Option<AutoFill>,
-    ) -> Result<Subscription<wsh::WshEventData>, Error> {
+    ) -> Result<Subscription<'_, wsh::WshEventData>, Error> {
         wsh::wsh_event_data_by_filter(self, filter, limit, auto_fill)
     }
 
+    // === Display Groups ===
+
+    /// Requests the ids of the window groups available in TWS, used to link the contract displayed in TWS windows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let groups = client.display_groups().expect("request display groups failed");
+    /// println!("display groups: {groups:?}");
+    /// ```
+    pub fn display_groups(&self) -> Result<Vec<i32>, Error> {
+        display_groups::display_groups(self)
+    }
+
+    /// Subscribes to changes of the contract displayed in a TWS window group.
+    ///
+    /// The contract displayed in the group can be changed with [update](Subscription::update). Dropping the subscription unsubscribes from the group.
+    ///
+    /// # Arguments
+    /// * `group_id` - Id of the window group, as returned by [display_groups](Client::display_groups).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let subscription = client.subscribe_to_group_events(1).expect("error subscribing to group events");
+    /// for update in &subscription {
+    ///     println!("group displays {:?} on {:?}", update.contract_id(), update.exchange());
+    /// }
+    /// ```
+    pub fn subscribe_to_group_events(&self, group_id: i32) -> Result<Subscription<'_, display_groups::DisplayGroupUpdate>, Error> {
+        display_groups::subscribe_to_group_events(self, group_id)
+    }
+
     // == Internal Use ==
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "fuzzing"))]
     pub(crate) fn stubbed(message_bus: Arc<dyn MessageBus>, server_version: i32) -> Client {
         Client {
-            server_version: server_version,
+            server_version,
             connection_time: None,
             time_zone: None,
             message_bus,
             client_id: 100,
+            request_timeout: None,
+            metrics: None,
             next_request_id: AtomicI32::new(9000),
             order_id: AtomicI32::new(-1),
+            account: None,
+            account_aliases: RwLock::new(AccountAliases::default()),
+            subscriptions: subscriptions::SubscriptionRegistry::default(),
+            connection_info: ConnectionInfo {
+                server_version,
+                ..ConnectionInfo::default()
+            },
         }
     }
 
+    // Resolves an account alias registered with the client to its account code.
+    pub(crate) fn resolve_account(&self, account_or_alias: &str) -> String {
+        self.account_aliases.read().unwrap().resolve(account_or_alias).to_owned()
+    }
+
     pub(crate) fn send_request(&self, request_id: i32, message: RequestMessage) -> Result<InternalSubscription, Error> {
         debug!("send_message({:?}, {:?})", request_id, message);
         self.message_bus.send_request(request_id, &message)
@@ -1605,6 +2487,34 @@ impl Drop for Client {
     }
 }
 
+// Open batch of requests, closed when dropped so buffered requests are sent even if the batch panics.
+struct Batch<'a> {
+    message_bus: &'a dyn MessageBus,
+    open: bool,
+}
+
+impl<'a> Batch<'a> {
+    fn begin(message_bus: &'a dyn MessageBus) -> Result<Self, Error> {
+        message_bus.begin_batch()?;
+        Ok(Self { message_bus, open: true })
+    }
+
+    fn end(mut self) -> Result<(), Error> {
+        self.open = false;
+        self.message_bus.end_batch()
+    }
+}
+
+impl Drop for Batch<'_> {
+    fn drop(&mut self) {
+        if self.open {
+            if let Err(e) = self.message_bus.end_batch() {
+                error!("error sending batched requests: {e}");
+            }
+        }
+    }
+}
+
 impl Debug for Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Client")
@@ -1615,6 +2525,28 @@ impl Debug for Client {
     }
 }
 
+// Parses the comma separated list of accounts reported when connecting.
+fn parse_managed_accounts(managed_accounts: &str) -> Vec<String> {
+    managed_accounts
+        .split(',')
+        .map(str::trim)
+        .filter(|account| !account.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn verify_account(managed_accounts: &str, account: String) -> Result<String, Error> {
+    let managed_accounts = parse_managed_accounts(managed_accounts);
+    if managed_accounts.is_empty() || managed_accounts.contains(&account) {
+        Ok(account)
+    } else {
+        Err(Error::InvalidArgument(format!(
+            "account {account} is not managed by this connection: {}",
+            managed_accounts.join(",")
+        )))
+    }
+}
+
 /// Subscriptions facilitate handling responses from TWS that may be delayed or delivered periodically.
 ///
 /// They offer both blocking and non-blocking methods for retrieving data.
@@ -1623,6 +2555,8 @@ impl Debug for Client {
 /// that cancels the subscription when it goes out of scope.
 ///
 /// ```no_run
+/// # #[cfg(feature = "market-data")]
+/// # fn main() {
 /// use ibapi::contracts::Contract;
 /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
 /// use ibapi::Client;
@@ -1642,6 +2576,9 @@ impl Debug for Client {
 ///     println!("Received bar: {bar:?}");
 /// }
 /// // The subscription goes out of scope and is automatically cancelled.
+/// # }
+/// # #[cfg(not(feature = "market-data"))]
+/// # fn main() {}
 /// ```
 ///
 /// Subscriptions can be explicitly canceled using the [cancel](Subscription::cancel) method.
@@ -1649,67 +2586,95 @@ impl Debug for Client {
 /// You can convert subscriptions into blocking or non-blocking iterators using the [iter](Subscription::iter), [try_iter](Subscription::try_iter) or [timeout_iter](Subscription::timeout_iter) methods.
 ///
 /// Alternatively, you may poll subscriptions in a blocking or non-blocking manner using the [next](Subscription::next), [try_next](Subscription::try_next) or [next_timeout](Subscription::next_timeout) methods.
+///
+/// When the connection to TWS is lost, the client reconnects with backoff. Streaming subscriptions, such as market data, market depth,
+/// real-time bars, PnL and account updates, are re-sent to TWS after reconnecting. Iteration stops with [Error::ConnectionReset] at the gap,
+/// and polling the subscription again resumes with data received after reconnecting.
+///
+/// Each subscription queues up to 100,000 messages, configured with [ConnectionBuilder::subscription_capacity].
+/// When a subscriber falls behind and its queue is full, newer messages are dropped. Iteration continues with the messages received since,
+/// and [Subscription::dropped_messages] gives the number of messages dropped so far.
+///
+/// ```no_run
+/// # #[cfg(feature = "market-data")]
+/// # fn main() {
+/// use ibapi::contracts::Contract;
+/// use ibapi::{Client, Error};
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection to TWS failed!");
+///
+/// let subscription = client.market_data(&Contract::stock("AAPL"), &[], false, false).expect("market data request failed!");
+/// loop {
+///     for tick in &subscription {
+///         println!("tick: {tick:?}");
+///     }
+///
+///     match subscription.error() {
+///         Some(Error::ConnectionReset) => println!("connection lost, data may have been missed"),
+///         _ => break,
+///     }
+/// }
+/// println!("{} ticks were dropped", subscription.dropped_messages());
+/// # }
+/// # #[cfg(not(feature = "market-data"))]
+/// # fn main() {}
+/// ```
 #[allow(private_bounds)]
 #[derive(Debug)]
 pub struct Subscription<'a, T: DataStream<T>> {
     client: &'a Client,
-    request_id: Option<i32>,
-    order_id: Option<i32>,
-    message_type: Option<OutgoingMessages>,
     phantom: PhantomData<T>,
-    cancelled: AtomicBool,
+    cancel_handle: CancelHandle,
     subscription: InternalSubscription,
-    response_context: ResponseContext,
     error: Mutex<Option<Error>>,
+    dropped: AtomicU64,
+    registry_key: u64,
 }
 
 // Extra metadata that might be need
 #[derive(Debug, Default)]
 pub(crate) struct ResponseContext {
     pub(crate) request_type: Option<OutgoingMessages>,
+    // Contract the data is requested for, listed by Client::active_subscriptions.
+    pub(crate) contract: Option<Contract>,
+}
+
+impl ResponseContext {
+    pub(crate) fn for_contract(contract: &Contract) -> Self {
+        ResponseContext {
+            contract: Some(contract.clone()),
+            ..ResponseContext::default()
+        }
+    }
 }
 
 #[allow(private_bounds)]
 impl<'a, T: DataStream<T>> Subscription<'a, T> {
     pub(crate) fn new(client: &'a Client, subscription: InternalSubscription, context: ResponseContext) -> Self {
-        if let Some(request_id) = subscription.request_id {
-            Subscription {
-                client,
-                request_id: Some(request_id),
-                order_id: None,
-                message_type: None,
-                subscription,
-                response_context: context,
-                phantom: PhantomData,
-                cancelled: AtomicBool::new(false),
-                error: Mutex::new(None),
-            }
-        } else if let Some(order_id) = subscription.order_id {
-            Subscription {
-                client,
-                request_id: None,
-                order_id: Some(order_id),
-                message_type: None,
-                subscription,
-                response_context: context,
-                phantom: PhantomData,
-                cancelled: AtomicBool::new(false),
-                error: Mutex::new(None),
-            }
-        } else if let Some(message_type) = subscription.message_type {
-            Subscription {
-                client,
-                request_id: None,
-                order_id: None,
-                message_type: Some(message_type),
-                subscription,
-                response_context: context,
-                phantom: PhantomData,
-                cancelled: AtomicBool::new(false),
-                error: Mutex::new(None),
-            }
-        } else {
-            panic!("unsupported internal subscription: {:?}", subscription)
+        let cancel_handle = CancelHandle::new(
+            client.message_bus.clone(),
+            subscription.request_id,
+            subscription.order_id,
+            subscription.message_type,
+            T::cancel_message(client.server_version(), subscription.request_id, &context).ok(),
+            subscription.waker(),
+        );
+        let registry_key = client.subscriptions.register(
+            T::KIND,
+            context.contract,
+            subscription.request_id,
+            subscription.order_id,
+            cancel_handle.clone(),
+        );
+
+        Subscription {
+            client,
+            subscription,
+            phantom: PhantomData,
+            cancel_handle,
+            error: Mutex::new(None),
+            dropped: AtomicU64::new(0),
+            registry_key,
         }
     }
 
@@ -1723,6 +2688,8 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// # Example
     ///
     /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
     /// use ibapi::contracts::Contract;
     /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
     /// use ibapi::Client;
@@ -1745,12 +2712,15 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// if let Some(err) = subscription.error() {
     ///     eprintln!("subscription error: {err}");
     /// }
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
     /// ```
     /// # Returns
     /// * `Some(T)` - The next available item from the subscription
     /// * `None` - If the subscription has ended or encountered an error
     pub fn next(&self) -> Option<T> {
-        match self.process_response(self.subscription.next()) {
+        match self.process_response(self.receive(|| self.subscription.next())) {
             Some(val) => Some(val),
             None => match self.error() {
                 Some(Error::UnexpectedResponse(m)) => {
@@ -1762,6 +2732,19 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
         }
     }
 
+    // Receives the next response, counting and skipping notifications of messages dropped while the subscription fell behind.
+    fn receive(&self, next: impl Fn() -> Option<Result<ResponseMessage, Error>>) -> Option<Result<ResponseMessage, Error>> {
+        loop {
+            match next() {
+                Some(Err(Error::Lagged(dropped))) => {
+                    warn!("subscription fell behind, {dropped} messages were dropped");
+                    self.dropped.fetch_add(dropped, Ordering::Relaxed);
+                }
+                response => return response,
+            }
+        }
+    }
+
     fn process_response(&self, response: Option<Result<ResponseMessage, Error>>) -> Option<T> {
         self.clear_error();
 
@@ -1782,6 +2765,9 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
             Err(Error::EndOfStream) => None,
             Err(err) => {
                 error!("error decoding message: {err}");
+                if let Some(metrics) = &self.client.metrics {
+                    metrics.decode_error(message.message_type() as i32);
+                }
                 let mut error = self.error.lock().unwrap();
                 *error = Some(err);
                 None
@@ -1800,6 +2786,8 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// # Example
     ///
     /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
     /// use ibapi::contracts::Contract;
     /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
     /// use ibapi::Client;
@@ -1829,12 +2817,15 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     ///     // The subscription remains active and can be checked again
     /// }
     ///
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
     /// ```
     /// # Returns
     /// * `Some(T)` - The next available item from the subscription
     /// * `None` - If no data is immediately available or if an error occurred
     pub fn try_next(&self) -> Option<T> {
-        self.process_response(self.subscription.try_next())
+        self.process_response(self.receive(|| self.subscription.try_next()))
     }
 
     /// Polls the subscription for the next item, waiting up to the specified timeout duration.
@@ -1848,6 +2839,8 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// # Example
     ///
     /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
     /// use ibapi::contracts::Contract;
     /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
     /// use ibapi::Client;
@@ -1878,6 +2871,9 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     ///     // Perform other work before checking again
     /// }
     ///
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
     /// ```
     ///
     /// # Arguments
@@ -1892,41 +2888,33 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// - [Subscription::try_next] - For immediate non-blocking access
     /// - [Subscription::error] - For checking error status
     pub fn next_timeout(&self, timeout: Duration) -> Option<T> {
-        self.process_response(self.subscription.next_timeout(timeout))
+        self.process_response(self.receive(|| self.subscription.next_timeout(timeout)))
     }
 
     /// Cancel the subscription
     pub fn cancel(&self) {
-        if self.cancelled.load(Ordering::Relaxed) {
-            return;
-        }
+        self.cancel_handle.cancel();
+    }
 
-        self.cancelled.store(true, Ordering::Relaxed);
+    /// Returns a handle that cancels the subscription from another thread, e.g. to stop a thread blocked in [Subscription::next].
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel_handle.clone()
+    }
 
-        if let Some(request_id) = self.request_id {
-            if let Ok(message) = T::cancel_message(self.client.server_version(), self.request_id, &self.response_context) {
-                if let Err(e) = self.client.message_bus.cancel_subscription(request_id, &message) {
-                    warn!("error cancelling subscription: {e}")
-                }
-                self.subscription.cancel();
-            }
-        } else if let Some(order_id) = self.order_id {
-            if let Ok(message) = T::cancel_message(self.client.server_version(), self.request_id, &self.response_context) {
-                if let Err(e) = self.client.message_bus.cancel_order_subscription(order_id, &message) {
-                    warn!("error cancelling order subscription: {e}")
-                }
-                self.subscription.cancel();
-            }
-        } else if let Some(message_type) = self.message_type {
-            if let Ok(message) = T::cancel_message(self.client.server_version(), self.request_id, &self.response_context) {
-                if let Err(e) = self.client.message_bus.cancel_shared_subscription(message_type, &message) {
-                    warn!("error cancelling shared subscription: {e}")
-                }
-                self.subscription.cancel();
-            }
-        } else {
-            debug!("Could not determine cancel method")
-        }
+    pub(crate) fn client(&self) -> &'a Client {
+        self.client
+    }
+
+    /// Request id TWS uses for the subscription, e.g. to match it with [Client::active_subscriptions] or TWS logs.
+    /// None for streams TWS does not identify by request id, such as positions or order updates.
+    pub fn request_id(&self) -> Option<i32> {
+        self.subscription.request_id
+    }
+
+    // Channel the responses are received on, e.g. to wait on several subscriptions at once.
+    #[cfg(feature = "news")]
+    pub(crate) fn receiver(&self) -> Option<&crossbeam::channel::Receiver<crate::transport::Response>> {
+        self.subscription.receiver()
     }
 
     /// Creates an iterator from the [Subscription] that blocks until the next item is available.
@@ -1936,6 +2924,8 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// # Example
     ///
     /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
     /// use ibapi::contracts::Contract;
     /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
     /// use ibapi::Client;
@@ -1955,10 +2945,13 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     ///     println!("Received bar: {bar:?}");
     /// }
     /// // The subscription is still in scope and can be explicitly canceled.
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
     /// ```
     /// # Returns
     /// A [SubscriptionIter] that yields items as they become available, blocking if necessary.
-    pub fn iter(&self) -> SubscriptionIter<T> {
+    pub fn iter(&self) -> SubscriptionIter<'_, T> {
         SubscriptionIter { subscription: self }
     }
 
@@ -1969,6 +2962,8 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// # Example
     ///
     /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
     /// use ibapi::contracts::Contract;
     /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
     /// use ibapi::Client;
@@ -1997,10 +2992,13 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     ///     // Optional: Add a small delay to prevent excessive CPU usage
     ///     thread::sleep(Duration::from_secs(1));
     /// }
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
     /// ```
     /// # Returns
     /// A [SubscriptionTryIter] that yields items if they are available, without waiting.
-    pub fn try_iter(&self) -> SubscriptionTryIter<T> {
+    pub fn try_iter(&self) -> SubscriptionTryIter<'_, T> {
         SubscriptionTryIter { subscription: self }
     }
 
@@ -2014,6 +3012,8 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     /// # Example
     ///
     /// ```no_run
+    /// # #[cfg(feature = "market-data")]
+    /// # fn main() {
     /// use ibapi::contracts::Contract;
     /// use ibapi::market_data::realtime::{BarSize, WhatToShow};
     /// use ibapi::Client;
@@ -2040,6 +3040,9 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     ///     // Perform other work between checking for data.
     ///     // The subscription remains active and can be cancelled when needed.
     /// }
+    /// # }
+    /// # #[cfg(not(feature = "market-data"))]
+    /// # fn main() {}
     /// ```
     ///
     /// # Arguments
@@ -2047,7 +3050,7 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
     ///
     /// # Returns
     /// A [SubscriptionTimeoutIter] that waits for the specified timeout duration for available data.
-    pub fn timeout_iter(&self, timeout: Duration) -> SubscriptionTimeoutIter<T> {
+    pub fn timeout_iter(&self, timeout: Duration) -> SubscriptionTimeoutIter<'_, T> {
         SubscriptionTimeoutIter { subscription: self, timeout }
     }
 
@@ -2069,11 +3072,19 @@ impl<'a, T: DataStream<T>> Subscription<'a, T> {
         let mut error = self.error.lock().unwrap();
         *error = None;
     }
+
+    /// Number of messages dropped because the subscriber fell behind and its queue was full.
+    ///
+    /// Iteration skips over the gap, so compare the count before and after processing a batch of items to detect lost data.
+    pub fn dropped_messages(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
 }
 
 impl<'a, T: DataStream<T>> Drop for Subscription<'a, T> {
     fn drop(&mut self) {
         self.cancel();
+        self.client.subscriptions.unregister(self.registry_key);
     }
 }
 
@@ -2086,7 +3097,10 @@ impl<'a, T: DataStream<T>> Drop for Subscription<'a, T> {
 /// This trait is used internally by the Subscription system to handle
 /// different types of streaming data from IB.
 pub(crate) trait DataStream<T> {
+    #[allow(dead_code)]
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[];
+    // Kind of data listed by Client::active_subscriptions.
+    const KIND: SubscriptionKind;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<T, Error>;
     fn cancel_message(_server_version: i32, _request_id: Option<i32>, _context: &ResponseContext) -> Result<RequestMessage, Error> {
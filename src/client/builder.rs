@@ -0,0 +1,344 @@
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use super::events::NoticeFilter;
+use super::metrics::{Metrics, SharedMetrics};
+use super::stream::{Proxy, SharedTransport, Transport};
+use super::wire::{MessageObserver, SharedObserver};
+use super::Client;
+use crate::transport::{ConnectionOptions, MAX_MESSAGES_PER_SECOND, REQUEST_JOURNAL_CAPACITY, SUBSCRIPTION_CAPACITY};
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Controls how the client reconnects after the connection to TWS is lost.
+///
+/// Delays between attempts follow a Fibonacci sequence in seconds, capped at `max_delay`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of reconnection attempts before the client shuts down.
+    pub max_retries: i32,
+    /// Maximum delay between reconnection attempts.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy making up to `max_retries` attempts, waiting up to `max_delay` between attempts.
+    pub fn new(max_retries: i32, max_delay: Duration) -> Self {
+        RetryPolicy { max_retries, max_delay }
+    }
+
+    /// Creates a policy that never reconnects.
+    pub fn never() -> Self {
+        RetryPolicy::new(0, Duration::ZERO)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// 20 attempts, waiting up to 30 seconds between attempts.
+    fn default() -> Self {
+        RetryPolicy::new(20, Duration::from_secs(30))
+    }
+}
+
+/// Configures and establishes a connection to TWS or Gateway. Created with [Client::builder].
+///
+/// Defaults to client id 100 connecting to 127.0.0.1:4002, the paper trading port of IB Gateway.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use ibapi::client::RetryPolicy;
+/// use ibapi::Client;
+///
+/// let client = Client::builder()
+///     .host("10.0.0.5")
+///     .port(7497)
+///     .client_id(7)
+///     .connect_timeout(Duration::from_secs(5))
+///     .keepalive_interval(Duration::from_secs(30))
+///     .stale_timeout(Duration::from_secs(90))
+///     .retry_policy(RetryPolicy::new(5, Duration::from_secs(10)))
+///     .account("DU1234567")
+///     .connect()
+///     .expect("connection failed");
+///
+/// println!("account: {:?}", client.account());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectionBuilder {
+    host: String,
+    port: u16,
+    failover_endpoints: Vec<String>,
+    client_id: i32,
+    max_client_id: Option<i32>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    connect_options: String,
+    optional_capabilities: String,
+    notice_filter: NoticeFilter,
+    request_timeout: Option<Duration>,
+    keepalive_interval: Option<Duration>,
+    stale_timeout: Option<Duration>,
+    max_messages_per_second: Option<u32>,
+    subscription_capacity: Option<usize>,
+    request_journal_capacity: usize,
+    retry_policy: RetryPolicy,
+    account: Option<String>,
+    message_observer: Option<SharedObserver>,
+    metrics: Option<SharedMetrics>,
+    transport: SharedTransport,
+}
+
+impl Default for ConnectionBuilder {
+    fn default() -> Self {
+        ConnectionBuilder {
+            host: "127.0.0.1".into(),
+            port: 4002,
+            failover_endpoints: Vec::new(),
+            client_id: 100,
+            max_client_id: None,
+            connect_timeout: None,
+            read_timeout: None,
+            connect_options: String::new(),
+            optional_capabilities: String::new(),
+            notice_filter: NoticeFilter::new(),
+            request_timeout: None,
+            keepalive_interval: None,
+            stale_timeout: None,
+            max_messages_per_second: Some(MAX_MESSAGES_PER_SECOND),
+            subscription_capacity: Some(SUBSCRIPTION_CAPACITY),
+            request_journal_capacity: REQUEST_JOURNAL_CAPACITY,
+            retry_policy: RetryPolicy::default(),
+            account: None,
+            message_observer: None,
+            metrics: None,
+            transport: SharedTransport::default(),
+        }
+    }
+}
+
+impl ConnectionBuilder {
+    /// Creates a builder with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Host name or IP address of TWS or Gateway.
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// Port TWS or Gateway listens on. e.g. 7496 for TWS live trading, 7497 for TWS paper trading, 4001 and 4002 for Gateway.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Adds an endpoint, e.g. `10.0.0.6:4002` for a backup Gateway, to connect to when the endpoints added before do not accept connections.
+    ///
+    /// Endpoints are tried in order, starting with [host](ConnectionBuilder::host) and [port](ConnectionBuilder::port),
+    /// both when connecting and on each reconnection attempt. Streaming subscriptions are re-established on the endpoint connected to,
+    /// and [ConnectionEvent::Failover](super::ConnectionEvent::Failover) reports the change of endpoint.
+    pub fn failover_endpoint(mut self, address: &str) -> Self {
+        self.failover_endpoints.push(address.into());
+        self
+    }
+
+    /// Id identifying the client to TWS. Each client connected to the same TWS must use a distinct id.
+    pub fn client_id(mut self, client_id: i32) -> Self {
+        self.client_id = client_id;
+        self
+    }
+
+    /// Connects with the first client id in `client_ids` not already in use, e.g. `100..=109`.
+    /// Connecting fails with [Error::ClientIdInUse] if all ids are in use. Use [Client::client_id] to get the id connected with.
+    pub fn client_id_range(mut self, client_ids: RangeInclusive<i32>) -> Self {
+        self.client_id = *client_ids.start();
+        self.max_client_id = Some(*client_ids.end());
+        self
+    }
+
+    /// Maximum time to wait for the TCP connection to be established. Waits for the operating system timeout by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum time a single read from the socket blocks. Also determines how quickly the message reader notices a shutdown.
+    /// Defaults to 1 second.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Connection options sent to TWS during the handshake, e.g. `+PACEAPI` to have TWS pace requests exceeding the message rate limit
+    /// instead of rejecting them.
+    pub fn connect_options(mut self, options: &str) -> Self {
+        self.connect_options = options.into();
+        self
+    }
+
+    /// Classifies notices TWS sends without a request id as informational, delivering them to [Client::info_notices] instead of [Client::notices].
+    /// By default no notices are classified as informational.
+    pub fn notice_filter(mut self, filter: NoticeFilter) -> Self {
+        self.notice_filter = filter;
+        self
+    }
+
+    /// Optional capabilities sent to TWS when starting the API, reported by [Client::connection_info].
+    pub fn optional_capabilities(mut self, capabilities: &str) -> Self {
+        self.optional_capabilities = capabilities.into();
+        self
+    }
+
+    /// Maximum time blocking requests, such as [Client::contract_details] or [Client::historical_data], wait for TWS to respond.
+    /// Requests that time out fail with [Error::Timeout]. Waits indefinitely by default.
+    ///
+    /// Historical data, historical schedule, head timestamp and fundamental data requests that time out are cancelled in TWS.
+    /// Other requests have no cancel message, so late replies are discarded when they arrive.
+    ///
+    /// Use [Client::with_timeout] to set the timeout of individual requests.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Requests the current time from TWS when no messages were received for `interval`, so that idle connections are checked.
+    /// Disabled by default.
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Treats the connection as lost and reconnects when no messages were received for `timeout`.
+    /// Detects half-open sockets that would otherwise hang the client. Use with [ConnectionBuilder::keepalive_interval] shorter than `timeout`.
+    /// Disabled by default.
+    pub fn stale_timeout(mut self, timeout: Duration) -> Self {
+        self.stale_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum number of messages sent to TWS per second. TWS disconnects clients exceeding 50 messages per second, the default.
+    /// Bursts are delayed so the rate is not exceeded, with requests from all threads sent in the order they were made.
+    pub fn rate_limit(mut self, messages_per_second: u32) -> Self {
+        self.max_messages_per_second = Some(messages_per_second);
+        self
+    }
+
+    /// Sends messages without limiting their rate, e.g. when TWS paces requests because of the `+PACEAPI` [connect option](ConnectionBuilder::connect_options).
+    pub fn without_rate_limit(mut self) -> Self {
+        self.max_messages_per_second = None;
+        self
+    }
+
+    /// Maximum number of messages queued for each subscription, 100,000 by default. Queues grow as messages arrive, so the capacity is not allocated up front.
+    /// When a subscriber falls behind and its queue is full, further messages are dropped. Subscriptions skip over the gap and count
+    /// the dropped messages in [Subscription::dropped_messages](crate::client::Subscription::dropped_messages), while blocking requests
+    /// such as [Client::contract_details] fail with [Error::Lagged].
+    pub fn subscription_capacity(mut self, capacity: usize) -> Self {
+        self.subscription_capacity = Some(capacity);
+        self
+    }
+
+    /// Queues messages for subscriptions without limit, so no messages are dropped for subscribers that fall behind.
+    pub fn unbounded_subscriptions(mut self) -> Self {
+        self.subscription_capacity = None;
+        self
+    }
+
+    /// Number of recent requests kept for inspection with [Client::request_journal], 100 by default. Zero disables the journal.
+    pub fn request_journal(mut self, capacity: usize) -> Self {
+        self.request_journal_capacity = capacity;
+        self
+    }
+
+    /// Policy for reconnecting after the connection is lost.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Account returned by [Client::account]. Connecting fails if TWS does not manage the account.
+    pub fn account(mut self, account: &str) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+
+    /// Observer called with every raw message sent to or received from TWS, e.g. a [TwsLogFile](super::TwsLogFile).
+    pub fn message_observer(mut self, observer: impl MessageObserver + 'static) -> Self {
+        self.message_observer = Some(SharedObserver::new(observer));
+        self
+    }
+
+    /// Collects measurements of requests sent, messages received, decode errors, request latencies and pending requests.
+    pub fn metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Some(SharedMetrics::new(metrics));
+        self
+    }
+
+    /// Opens connections with `transport` instead of TCP, e.g. to connect over a Unix socket or an in-memory connection in tests.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = SharedTransport::new(transport);
+        self
+    }
+
+    /// Connects through `proxy`, e.g. when the Gateway runs on a host only reachable through a corporate proxy.
+    /// The host and port are those of TWS or the Gateway, as seen from the proxy. Replaces a transport set with [transport](Self::transport).
+    pub fn proxy(self, proxy: Proxy) -> Self {
+        self.transport(proxy)
+    }
+
+    /// Establishes the connection.
+    pub fn connect(self) -> Result<Client, Error> {
+        if matches!(self.read_timeout, Some(timeout) if timeout.is_zero()) {
+            return Err(Error::InvalidArgument("read timeout must be greater than zero".into()));
+        }
+        if self.subscription_capacity == Some(0) {
+            return Err(Error::InvalidArgument("subscription capacity must be greater than zero".into()));
+        }
+
+        let mut client = Client::connect_with_options(self.options(), self.account.clone())?;
+        client.request_timeout = self.request_timeout;
+        client.metrics = self.metrics.clone();
+
+        Ok(client)
+    }
+
+    pub(crate) fn address(&self) -> String {
+        if self.host.contains(':') {
+            // IPv6 literal
+            format!("[{}]:{}", self.host.trim_start_matches('[').trim_end_matches(']'), self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    pub(crate) fn options(&self) -> ConnectionOptions {
+        let mut options = ConnectionOptions::new(&self.address(), self.client_id);
+
+        options.failover_addresses = self.failover_endpoints.clone();
+        options.max_client_id = self.max_client_id;
+        options.connect_timeout = self.connect_timeout;
+        if let Some(read_timeout) = self.read_timeout {
+            options.read_timeout = read_timeout;
+        }
+        options.connect_options = self.connect_options.clone();
+        options.optional_capabilities = self.optional_capabilities.clone();
+        options.notice_filter = self.notice_filter.clone();
+        options.keepalive_interval = self.keepalive_interval;
+        options.stale_timeout = self.stale_timeout;
+        options.max_messages_per_second = self.max_messages_per_second;
+        options.subscription_capacity = self.subscription_capacity;
+        options.request_journal_capacity = self.request_journal_capacity;
+        options.message_observer = self.message_observer.clone();
+        options.metrics = self.metrics.clone();
+        options.transport = self.transport.clone();
+        options.max_retries = self.retry_policy.max_retries;
+        options.max_retry_delay = self.retry_policy.max_delay;
+
+        options
+    }
+}
@@ -0,0 +1,79 @@
+use super::*;
+use crate::client::verify_account;
+
+#[test]
+fn builds_connection_options() {
+    let builder = ConnectionBuilder::new()
+        .host("10.0.0.5")
+        .port(7497)
+        .failover_endpoint("10.0.0.6:4002")
+        .client_id(7)
+        .connect_timeout(Duration::from_secs(5))
+        .read_timeout(Duration::from_millis(250))
+        .connect_options("+PACEAPI")
+        .optional_capabilities("E")
+        .keepalive_interval(Duration::from_secs(30))
+        .stale_timeout(Duration::from_secs(90))
+        .rate_limit(40)
+        .subscription_capacity(1000)
+        .request_journal(20)
+        .retry_policy(RetryPolicy::new(3, Duration::from_secs(8)));
+
+    let options = builder.options();
+
+    assert_eq!(options.address, "10.0.0.5:7497");
+    assert_eq!(options.failover_addresses, vec!["10.0.0.6:4002".to_string()]);
+    assert_eq!(options.client_id, 7);
+    assert_eq!(options.connect_timeout, Some(Duration::from_secs(5)));
+    assert_eq!(options.read_timeout, Duration::from_millis(250));
+    assert_eq!(options.connect_options, "+PACEAPI");
+    assert_eq!(options.optional_capabilities, "E");
+    assert_eq!(options.keepalive_interval, Some(Duration::from_secs(30)));
+    assert_eq!(options.stale_timeout, Some(Duration::from_secs(90)));
+    assert_eq!(options.max_messages_per_second, Some(40));
+    assert_eq!(options.subscription_capacity, Some(1000));
+    assert_eq!(options.request_journal_capacity, 20);
+    assert_eq!(options.max_retries, 3);
+    assert_eq!(options.max_retry_delay, Duration::from_secs(8));
+}
+
+#[test]
+fn defaults_to_local_gateway() {
+    let options = ConnectionBuilder::new().options();
+
+    assert_eq!(options.address, "127.0.0.1:4002");
+    assert_eq!(options.client_id, 100);
+    assert_eq!(options.connect_timeout, None);
+    assert_eq!(options.max_client_id, None);
+
+    let options = ConnectionBuilder::new().client_id_range(7..=9).options();
+    assert_eq!((options.client_id, options.max_client_id), (7, Some(9)), "client id range");
+    assert_eq!(options.max_retries, RetryPolicy::default().max_retries);
+    assert_eq!(options.max_messages_per_second, Some(50));
+    assert_eq!(ConnectionBuilder::new().without_rate_limit().options().max_messages_per_second, None);
+    assert_eq!(options.subscription_capacity, Some(100_000));
+    assert_eq!(options.request_journal_capacity, 100);
+    assert_eq!(ConnectionBuilder::new().unbounded_subscriptions().options().subscription_capacity, None);
+
+    assert_eq!(ConnectionBuilder::new().host("::1").address(), "[::1]:4002");
+    assert_eq!(ConnectionBuilder::new().host("[::1]").address(), "[::1]:4002");
+}
+
+#[test]
+fn verifies_account_is_managed() {
+    assert_eq!(verify_account("DU1234,DU5678,", "DU5678".into()).unwrap(), "DU5678");
+    assert_eq!(verify_account("", "DU5678".into()).unwrap(), "DU5678");
+    assert!(matches!(verify_account("DU1234", "DU5678".into()), Err(Error::InvalidArgument(_))));
+}
+
+#[test]
+fn rejects_zero_read_timeout() {
+    let result = ConnectionBuilder::new().read_timeout(Duration::ZERO).connect();
+    assert!(matches!(result, Err(Error::InvalidArgument(_))), "{result:?}");
+}
+
+#[test]
+fn rejects_zero_subscription_capacity() {
+    let result = ConnectionBuilder::new().subscription_capacity(0).connect();
+    assert!(matches!(result, Err(Error::InvalidArgument(_))), "{result:?}");
+}
@@ -0,0 +1,124 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use log::warn;
+
+use crate::messages::{OutgoingMessages, RequestMessage};
+use crate::transport::{MessageBus, SubscriptionWaker};
+
+#[cfg(all(test, feature = "market-data"))]
+mod tests;
+
+/// Cancels a pending request from any thread. Created with [Subscription::cancel_handle](super::Subscription::cancel_handle).
+///
+/// Cancelling ends waits for the next response with [Error::Cancelled](crate::Error::Cancelled), sends the cancel message
+/// of the request to TWS when one exists, and stops routing responses to the request. Cancelling more than once,
+/// or dropping the subscription after cancelling, has no further effect.
+///
+/// ```no_run
+/// # #[cfg(feature = "market-data")]
+/// # fn main() {
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use ibapi::contracts::Contract;
+/// use ibapi::market_data::realtime::{BarSize, WhatToShow};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let subscription = client
+///     .realtime_bars(&Contract::stock("AAPL"), BarSize::Sec5, WhatToShow::Trades, false)
+///     .expect("realtime bars request failed!");
+///
+/// let cancel_handle = subscription.cancel_handle();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(60));
+///     cancel_handle.cancel();
+/// });
+///
+/// for bar in &subscription {
+///     println!("bar: {bar:?}");
+/// }
+/// # }
+/// # #[cfg(not(feature = "market-data"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct CancelHandle {
+    message_bus: Arc<dyn MessageBus>,
+    request_id: Option<i32>,
+    order_id: Option<i32>,
+    message_type: Option<OutgoingMessages>,
+    cancel_message: Option<RequestMessage>,
+    waker: SubscriptionWaker,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    pub(crate) fn new(
+        message_bus: Arc<dyn MessageBus>,
+        request_id: Option<i32>,
+        order_id: Option<i32>,
+        message_type: Option<OutgoingMessages>,
+        cancel_message: Option<RequestMessage>,
+        waker: SubscriptionWaker,
+    ) -> Self {
+        CancelHandle {
+            message_bus,
+            request_id,
+            order_id,
+            message_type,
+            cancel_message,
+            waker,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Cancels the request.
+    pub fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let Some(message) = &self.cancel_message else {
+            // requests without cancel messages complete in TWS, only stop waiting for them
+            self.waker.wake();
+            self.waker.release();
+            return;
+        };
+
+        let result = if let Some(request_id) = self.request_id {
+            self.message_bus.cancel_subscription(request_id, message)
+        } else if let Some(order_id) = self.order_id {
+            self.message_bus.cancel_order_subscription(order_id, message)
+        } else if let Some(message_type) = self.message_type {
+            self.message_bus.cancel_shared_subscription(message_type, message)
+        } else {
+            Ok(())
+        };
+
+        if let Err(e) = result {
+            warn!("error cancelling request: {e}");
+        }
+
+        self.waker.wake();
+    }
+
+    /// Returns true if the request was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Debug for CancelHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancelHandle")
+            .field("request_id", &self.request_id)
+            .field("order_id", &self.order_id)
+            .field("message_type", &self.message_type)
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::RwLock;
+use std::thread;
+
+use super::*;
+use crate::contracts::Contract;
+use crate::market_data::realtime::{BarSize, WhatToShow};
+use crate::messages::OutgoingMessages;
+use crate::stubs::MessageBusStub;
+use crate::{server_versions, Client, ToField};
+
+fn stubbed_client() -> Client {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![],
+    });
+
+    Client::stubbed(message_bus, server_versions::SIZE_RULES)
+}
+
+#[test]
+fn sends_cancel_message_once() {
+    let client = stubbed_client();
+
+    let subscription = client
+        .realtime_bars(&Contract::stock("AAPL"), BarSize::Sec5, WhatToShow::Trades, false)
+        .expect("error requesting realtime bars");
+
+    let cancel_handle = subscription.cancel_handle();
+    assert!(!cancel_handle.is_cancelled());
+
+    thread::spawn(move || cancel_handle.cancel()).join().unwrap();
+    assert!(subscription.cancel_handle().is_cancelled(), "handles share cancellation");
+
+    subscription.cancel();
+    drop(subscription);
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages.len(), 2, "request and cancel messages");
+    assert_eq!(request_messages[1][0], OutgoingMessages::CancelRealTimeBars.to_field(), "cancel message");
+}
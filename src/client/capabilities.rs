@@ -0,0 +1,111 @@
+use crate::server_versions;
+
+#[cfg(test)]
+mod tests;
+
+/// Features supported by the connected TWS or Gateway, derived from the server version negotiated when connecting.
+///
+/// ```no_run
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// if client.capabilities().supports_tick_by_tick() {
+///     println!("tick by tick data is available");
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    server_version: i32,
+}
+
+impl Capabilities {
+    pub(crate) fn new(server_version: i32) -> Self {
+        Capabilities { server_version }
+    }
+
+    /// Server version negotiated when connecting.
+    pub fn server_version(&self) -> i32 {
+        self.server_version
+    }
+
+    /// Returns true if the server version is at least `version`.
+    pub fn supports(&self, version: i32) -> bool {
+        self.server_version >= version
+    }
+
+    /// Tick by tick data: [Client::tick_by_tick_last](crate::Client::tick_by_tick_last) and related requests.
+    pub fn supports_tick_by_tick(&self) -> bool {
+        self.supports(server_versions::TICK_BY_TICK)
+    }
+
+    /// Sizes and positions with fractional quantities.
+    pub fn supports_decimal_sizes(&self) -> bool {
+        self.supports(server_versions::FRACTIONAL_SIZE_SUPPORT)
+    }
+
+    /// Minimum size and size increment in contract details.
+    pub fn supports_size_rules(&self) -> bool {
+        self.supports(server_versions::SIZE_RULES)
+    }
+
+    /// Market rules: [Client::market_rule](crate::Client::market_rule).
+    pub fn supports_market_rules(&self) -> bool {
+        self.supports(server_versions::MARKET_RULES)
+    }
+
+    /// Matching symbols: [Client::matching_symbols](crate::Client::matching_symbols).
+    pub fn supports_matching_symbols(&self) -> bool {
+        self.supports(server_versions::REQ_MATCHING_SYMBOLS)
+    }
+
+    /// Earliest available historical data: [Client::head_timestamp](crate::Client::head_timestamp).
+    pub fn supports_head_timestamp(&self) -> bool {
+        self.supports(server_versions::REQ_HEAD_TIMESTAMP)
+    }
+
+    /// Histogram data: [Client::histogram_data](crate::Client::histogram_data).
+    pub fn supports_histogram_data(&self) -> bool {
+        self.supports(server_versions::REQ_HISTOGRAM)
+    }
+
+    /// Historical time and sales data, e.g. [Client::historical_ticks_trade](crate::Client::historical_ticks_trade).
+    pub fn supports_historical_ticks(&self) -> bool {
+        self.supports(server_versions::HISTORICAL_TICKS)
+    }
+
+    /// Trading schedules: [Client::historical_schedules](crate::Client::historical_schedules).
+    pub fn supports_historical_schedule(&self) -> bool {
+        self.supports(server_versions::HISTORICAL_SCHEDULE)
+    }
+
+    /// Profit and loss: [Client::pnl](crate::Client::pnl) and [Client::pnl_single](crate::Client::pnl_single).
+    pub fn supports_pnl(&self) -> bool {
+        self.supports(server_versions::PNL)
+    }
+
+    /// News providers, articles and historical news.
+    pub fn supports_news(&self) -> bool {
+        self.supports(server_versions::REQ_HISTORICAL_NEWS)
+    }
+
+    /// Completed orders: [Client::completed_orders](crate::Client::completed_orders).
+    pub fn supports_completed_orders(&self) -> bool {
+        self.supports(server_versions::COMPLETED_ORDERS)
+    }
+
+    /// Wall Street Horizon metadata and event data.
+    pub fn supports_wsh(&self) -> bool {
+        self.supports(server_versions::WSHE_CALENDAR)
+    }
+
+    /// Filters, date ranges and limits for Wall Street Horizon event data.
+    pub fn supports_wsh_event_data_filters(&self) -> bool {
+        self.supports(server_versions::WSH_EVENT_DATA_FILTERS_DATE)
+    }
+
+    /// Time zone of instruments in contract details.
+    pub fn supports_instrument_time_zone(&self) -> bool {
+        self.supports(server_versions::INSTRUMENT_TIMEZONE)
+    }
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+#[test]
+fn capabilities_follow_server_version() {
+    let capabilities = Capabilities::new(server_versions::SIZE_RULES);
+
+    assert_eq!(capabilities.server_version(), server_versions::SIZE_RULES);
+    assert!(capabilities.supports_tick_by_tick());
+    assert!(capabilities.supports_decimal_sizes());
+    assert!(capabilities.supports_size_rules());
+    assert!(!capabilities.supports_historical_schedule());
+    assert!(!capabilities.supports_wsh_event_data_filters());
+
+    let capabilities = Capabilities::new(server_versions::TICK_BY_TICK - 1);
+    assert!(!capabilities.supports_tick_by_tick());
+    assert!(capabilities.supports_market_rules());
+    assert!(!capabilities.supports_decimal_sizes());
+}
@@ -0,0 +1,12 @@
+/// Details negotiated with TWS or Gateway during the handshake, returned by [Client::connection_info](crate::Client::connection_info).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// Server version negotiated in the handshake.
+    pub server_version: i32,
+    /// Connection time as reported by TWS, e.g. `20230405 22:20:39 PST`.
+    pub connection_time: String,
+    /// Optional capabilities sent when starting the API. None if the server version does not accept optional capabilities.
+    pub optional_capabilities: Option<String>,
+    /// Accounts managed by the connection.
+    pub managed_accounts: Vec<String>,
+}
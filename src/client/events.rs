@@ -0,0 +1,185 @@
+use std::collections::BTreeSet;
+
+use super::Subscription;
+use crate::errors::ErrorCategory;
+use crate::messages::Notice;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Connection health notification. See [Client::connection_events](crate::Client::connection_events).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectionEvent {
+    /// Connection between the client and TWS was lost. The client is attempting to reconnect.
+    Disconnected,
+    /// Connection between the client and TWS was re-established. Data sent while disconnected was missed.
+    Reconnected,
+    /// The client reconnected to a different endpoint than before the connection was lost,
+    /// e.g. a backup Gateway configured with [ConnectionBuilder::failover_endpoint](crate::client::ConnectionBuilder::failover_endpoint).
+    /// Follows [ConnectionEvent::Reconnected].
+    Failover {
+        /// Address of the endpoint now connected to.
+        address: String,
+    },
+    /// Connection between the client and TWS was closed. No further events are received.
+    Closed,
+    /// Connectivity between TWS and IB servers was lost (1100, 2110).
+    ConnectivityLost,
+    /// Connectivity between TWS and IB servers was restored (1101, 1102).
+    /// If `data_lost` is true market data subscriptions were cancelled by TWS and must be requested again.
+    ConnectivityRestored { data_lost: bool },
+    /// Status of a market data, historical data or security definition farm changed (2103-2108, 2157, 2158).
+    DataFarm {
+        farm: DataFarm,
+        /// Name of the farm, e.g. usfarm.
+        name: String,
+        status: DataFarmStatus,
+    },
+}
+
+/// Kind of data farm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataFarm {
+    /// Real-time market data.
+    MarketData,
+    /// Historical market data (HMDS).
+    Historical,
+    /// Security definitions.
+    SecurityDefinition,
+}
+
+/// Connection status of a data farm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataFarmStatus {
+    /// Connection to the farm is OK.
+    Connected,
+    /// Connection to the farm is broken. Requests served by the farm fail until it is restored.
+    Disconnected,
+    /// Farm is connected but inactive. It becomes active when data is requested.
+    Inactive,
+}
+
+impl ConnectionEvent {
+    /// Converts a [Notice] into a connection event. Returns None for notices unrelated to connection health.
+    pub fn from_notice(notice: &Notice) -> Option<ConnectionEvent> {
+        let data_farm = |farm: DataFarm, status: DataFarmStatus| {
+            let name = notice.message.rsplit_once(':').map(|(_, name)| name.trim()).unwrap_or_default();
+            Some(ConnectionEvent::DataFarm {
+                farm,
+                name: name.to_string(),
+                status,
+            })
+        };
+
+        match notice.code {
+            1100 | 2110 => Some(ConnectionEvent::ConnectivityLost),
+            1101 => Some(ConnectionEvent::ConnectivityRestored { data_lost: true }),
+            1102 => Some(ConnectionEvent::ConnectivityRestored { data_lost: false }),
+            2103 => data_farm(DataFarm::MarketData, DataFarmStatus::Disconnected),
+            2104 => data_farm(DataFarm::MarketData, DataFarmStatus::Connected),
+            2108 => data_farm(DataFarm::MarketData, DataFarmStatus::Inactive),
+            2105 => data_farm(DataFarm::Historical, DataFarmStatus::Disconnected),
+            2106 => data_farm(DataFarm::Historical, DataFarmStatus::Connected),
+            2107 => data_farm(DataFarm::Historical, DataFarmStatus::Inactive),
+            2157 => data_farm(DataFarm::SecurityDefinition, DataFarmStatus::Disconnected),
+            2158 => data_farm(DataFarm::SecurityDefinition, DataFarmStatus::Connected),
+            _ => None,
+        }
+    }
+}
+
+/// Blocking iterator over [ConnectionEvent]s, created by [Client::connection_events](crate::Client::connection_events).
+pub struct ConnectionEvents<'a> {
+    notices: Subscription<'a, Notice>,
+    closed: bool,
+}
+
+impl<'a> ConnectionEvents<'a> {
+    pub(crate) fn new(notices: Subscription<'a, Notice>) -> Self {
+        ConnectionEvents { notices, closed: false }
+    }
+}
+
+impl Iterator for ConnectionEvents<'_> {
+    type Item = ConnectionEvent;
+
+    fn next(&mut self) -> Option<ConnectionEvent> {
+        while !self.closed {
+            match self.notices.next() {
+                Some(notice) => {
+                    if let Some(event) = ConnectionEvent::from_notice(&notice) {
+                        return Some(event);
+                    }
+                }
+                None => match self.notices.error() {
+                    Some(Error::Disconnected) => return Some(ConnectionEvent::Disconnected),
+                    Some(Error::ConnectionReset) => return Some(ConnectionEvent::Reconnected),
+                    Some(Error::Failover(address)) => return Some(ConnectionEvent::Failover { address }),
+                    _ => {
+                        self.closed = true;
+                        return Some(ConnectionEvent::Closed);
+                    }
+                },
+            }
+        }
+        None
+    }
+}
+
+/// Classifies notices TWS sends without a request id as informational. Informational notices are delivered to
+/// [Client::info_notices](crate::Client::info_notices) instead of [Client::notices](crate::Client::notices), keeping the error path clean.
+/// Configured with [ConnectionBuilder::notice_filter](crate::client::ConnectionBuilder::notice_filter).
+///
+/// [Client::connection_events](crate::Client::connection_events) receives all notices regardless of the filter.
+///
+/// ```no_run
+/// use ibapi::client::NoticeFilter;
+/// use ibapi::Client;
+///
+/// let client = Client::builder()
+///     .notice_filter(NoticeFilter::data_farm_status().code(2119))
+///     .connect()
+///     .expect("connection failed");
+///
+/// for notice in &client.notices().expect("error subscribing to notices") {
+///     eprintln!("error: {notice}");
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NoticeFilter {
+    codes: BTreeSet<i32>,
+    categories: Vec<ErrorCategory>,
+}
+
+impl NoticeFilter {
+    /// Creates a filter classifying no notices as informational.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a filter classifying data farm connections that are OK (2104, 2106, 2158) or inactive (2107, 2108) as informational.
+    /// Broken farm connections remain errors.
+    pub fn data_farm_status() -> Self {
+        [2104, 2106, 2107, 2108, 2158].into_iter().fold(Self::new(), Self::code)
+    }
+
+    /// Classifies notices with `code` as informational.
+    pub fn code(mut self, code: i32) -> Self {
+        self.codes.insert(code);
+        self
+    }
+
+    /// Classifies notices with codes of `category` as informational, e.g. [ErrorCategory::Warning].
+    pub fn category(mut self, category: ErrorCategory) -> Self {
+        if !self.categories.contains(&category) {
+            self.categories.push(category);
+        }
+        self
+    }
+
+    /// Returns true if `notice` is classified as informational.
+    pub fn is_info(&self, notice: &Notice) -> bool {
+        self.codes.contains(&notice.code) || self.categories.contains(&notice.category())
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::server_versions;
+use crate::stubs::MessageBusStub;
+use crate::Client;
+
+fn notice(code: i32, message: &str) -> Notice {
+    Notice {
+        code,
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn events_from_notices() {
+    assert_eq!(
+        ConnectionEvent::from_notice(&notice(2104, "Market data farm connection is OK:usfarm")),
+        Some(ConnectionEvent::DataFarm {
+            farm: DataFarm::MarketData,
+            name: "usfarm".to_string(),
+            status: DataFarmStatus::Connected
+        })
+    );
+    assert_eq!(
+        ConnectionEvent::from_notice(&notice(2105, "HMDS data farm connection is broken:ushmds")),
+        Some(ConnectionEvent::DataFarm {
+            farm: DataFarm::Historical,
+            name: "ushmds".to_string(),
+            status: DataFarmStatus::Disconnected
+        })
+    );
+    assert_eq!(
+        ConnectionEvent::from_notice(&notice(1101, "Connectivity between IB and TWS has been restored- data lost.")),
+        Some(ConnectionEvent::ConnectivityRestored { data_lost: true })
+    );
+    assert_eq!(ConnectionEvent::from_notice(&notice(202, "Order Canceled - reason:")), None);
+}
+
+#[test]
+fn connection_events_end_with_closed() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "4|2|-1|1100|Connectivity between IB and Trader Workstation has been lost.|".to_string(),
+            "4|2|-1|399|Order Message: Warning: your order will not be placed at the exchange until 2024-01-02 09:30:00 US/Eastern|".to_string(),
+            "4|2|-1|2158|Sec-def data farm connection is OK:secdefnj|".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let events: Vec<ConnectionEvent> = client.connection_events().expect("error subscribing to connection events").collect();
+
+    assert_eq!(events.len(), 3, "{events:?}");
+    assert_eq!(events[0], ConnectionEvent::ConnectivityLost);
+    assert!(matches!(
+        events[1],
+        ConnectionEvent::DataFarm {
+            farm: DataFarm::SecurityDefinition,
+            ..
+        }
+    ));
+    assert_eq!(events[2], ConnectionEvent::Closed);
+}
+
+#[test]
+fn notice_filter_classifies_info_notices() {
+    let filter = NoticeFilter::data_farm_status().code(2119).category(ErrorCategory::Pacing);
+
+    assert!(filter.is_info(&notice(2104, "Market data farm connection is OK:usfarm")));
+    assert!(filter.is_info(&notice(2158, "Sec-def data farm connection is OK:secdefnj")));
+    assert!(filter.is_info(&notice(2119, "Market data farm is connecting:usfarm")));
+    assert!(filter.is_info(&notice(162, "Historical Market Data Service error message")));
+    assert!(!filter.is_info(&notice(2103, "Market data farm connection is broken:usfarm")));
+    assert!(!filter.is_info(&notice(1100, "Connectivity between IB and Trader Workstation has been lost.")));
+
+    assert!(!NoticeFilter::new().is_info(&notice(2104, "Market data farm connection is OK:usfarm")));
+}
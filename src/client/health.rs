@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use super::{DataFarm, DataFarmStatus};
+
+/// Snapshot of the health of a connection to TWS, returned by [Client::health](crate::Client::health).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Health {
+    /// True while connected to TWS. False while reconnecting and after the client disconnected.
+    pub connected: bool,
+    /// Time since the last message was received from TWS.
+    pub last_message_age: Duration,
+    /// Last status TWS reported for each data farm, ordered by farm name.
+    pub data_farms: Vec<DataFarmState>,
+    /// Number of requests and orders with an open subscription, including streaming subscriptions.
+    pub outstanding_requests: usize,
+    /// Number of times the client reconnected after losing the connection to TWS.
+    pub reconnects: u32,
+}
+
+/// Last reported status of a data farm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataFarmState {
+    pub farm: DataFarm,
+    /// Name of the farm, e.g. usfarm.
+    pub name: String,
+    pub status: DataFarmStatus,
+}
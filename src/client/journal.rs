@@ -0,0 +1,123 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+
+use time::OffsetDateTime;
+
+use crate::messages::{Notice, RequestMessage};
+
+#[cfg(test)]
+mod tests;
+
+/// A request sent to TWS, as recorded in the journal returned by [Client::request_journal](crate::Client::request_journal).
+#[derive(Clone, Debug, PartialEq)]
+pub struct JournalEntry {
+    /// Numeric id of the request message type, as defined by the TWS API, e.g. 1 for market data requests.
+    pub message_type: i32,
+    /// Request id, or order id for order requests. None for requests without an id, e.g. positions.
+    pub request_id: Option<i32>,
+    /// Encoded fields of the request, starting with the message type.
+    pub fields: Vec<String>,
+    /// Time the request was sent.
+    pub sent_at: OffsetDateTime,
+    pub outcome: RequestOutcome,
+}
+
+/// Outcome of a request recorded in the journal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// No response was received for the request, or the request has no id responses can be matched with.
+    Pending,
+    /// TWS responded to the request.
+    Responded,
+    /// TWS reported an error for the request.
+    Rejected { code: i32, message: String },
+}
+
+// Keeps the most recent requests sent to TWS, matching responses and errors to requests by request id.
+#[derive(Debug)]
+pub(crate) struct RequestJournal {
+    capacity: usize,
+    state: Mutex<JournalState>,
+}
+
+#[derive(Debug, Default)]
+struct JournalState {
+    entries: VecDeque<JournalEntry>,
+    // Ids of entries awaiting their first response. Spares searching the journal for each streamed message.
+    pending: HashSet<i32>,
+}
+
+impl RequestJournal {
+    // Journal keeping the last `capacity` requests. A capacity of zero records no requests.
+    pub(crate) fn new(capacity: usize) -> Self {
+        RequestJournal {
+            capacity,
+            state: Mutex::new(JournalState::default()),
+        }
+    }
+
+    // Called before the request is written, as TWS may respond before the write returns.
+    pub(crate) fn record_request(&self, request_id: Option<i32>, message: &RequestMessage) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let fields: Vec<String> = message.as_str().split_terminator('\0').map(String::from).collect();
+        let entry = JournalEntry {
+            message_type: message.message_id(),
+            request_id,
+            fields,
+            sent_at: OffsetDateTime::now_utc(),
+            outcome: RequestOutcome::Pending,
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if state.entries.len() == self.capacity {
+            state.entries.pop_front();
+        }
+        if let Some(request_id) = request_id {
+            state.pending.insert(request_id);
+        }
+        state.entries.push_back(entry);
+    }
+
+    // Marks the latest request with `request_id` as responded, unless a response or error was already recorded.
+    pub(crate) fn record_response(&self, request_id: i32) {
+        let mut state = self.state.lock().unwrap();
+        if !state.pending.remove(&request_id) {
+            return;
+        }
+        if let Some(entry) = state.latest(request_id) {
+            if entry.outcome == RequestOutcome::Pending {
+                entry.outcome = RequestOutcome::Responded;
+            }
+        }
+    }
+
+    // Records the error TWS reported for the latest request with `request_id`. Warnings do not fail requests and are not recorded.
+    pub(crate) fn record_error(&self, request_id: i32, notice: &Notice) {
+        if notice.is_warning() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.pending.remove(&request_id);
+        if let Some(entry) = state.latest(request_id) {
+            entry.outcome = RequestOutcome::Rejected {
+                code: notice.code,
+                message: notice.message.clone(),
+            };
+        }
+    }
+
+    // Recorded requests, oldest first.
+    pub(crate) fn entries(&self) -> Vec<JournalEntry> {
+        self.state.lock().unwrap().entries.iter().cloned().collect()
+    }
+}
+
+impl JournalState {
+    fn latest(&mut self, request_id: i32) -> Option<&mut JournalEntry> {
+        self.entries.iter_mut().rev().find(|entry| entry.request_id == Some(request_id))
+    }
+}
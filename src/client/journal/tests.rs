@@ -0,0 +1,66 @@
+use super::*;
+use crate::messages::OutgoingMessages;
+
+fn request(message_type: OutgoingMessages, request_id: i32) -> RequestMessage {
+    let mut message = RequestMessage::new();
+    message.push_field(&message_type);
+    message.push_field(&1);
+    message.push_field(&request_id);
+    message
+}
+
+fn notice(code: i32, message: &str) -> Notice {
+    Notice {
+        code,
+        message: message.into(),
+    }
+}
+
+#[test]
+fn keeps_latest_requests() {
+    let journal = RequestJournal::new(2);
+    journal.record_request(Some(9000), &request(OutgoingMessages::RequestMarketData, 9000));
+    journal.record_request(None, &request(OutgoingMessages::RequestPositions, 0));
+    journal.record_request(Some(9001), &request(OutgoingMessages::RequestRealTimeBars, 9001));
+
+    let entries = journal.entries();
+    assert_eq!(entries.len(), 2, "entries");
+    assert_eq!(entries[0].message_type, OutgoingMessages::RequestPositions as i32);
+    assert_eq!(entries[0].request_id, None);
+    assert_eq!(entries[1].request_id, Some(9001));
+    assert_eq!(entries[1].fields, vec!["50", "1", "9001"]);
+    assert_eq!(entries[1].outcome, RequestOutcome::Pending);
+
+    let journal = RequestJournal::new(0);
+    journal.record_request(Some(9000), &request(OutgoingMessages::RequestMarketData, 9000));
+    assert!(journal.entries().is_empty(), "disabled journal");
+}
+
+#[test]
+fn records_outcomes() {
+    let journal = RequestJournal::new(10);
+    journal.record_request(Some(9000), &request(OutgoingMessages::RequestMarketData, 9000));
+    journal.record_request(Some(9001), &request(OutgoingMessages::RequestContractData, 9001));
+    journal.record_request(Some(9002), &request(OutgoingMessages::RequestHistoricalData, 9002));
+
+    journal.record_response(9000);
+    journal.record_error(
+        9000,
+        &notice(10167, "Requested market data is not subscribed. Displaying delayed market data."),
+    );
+    journal.record_error(9001, &notice(200, "No security definition has been found for the request"));
+    journal.record_response(9001);
+
+    let outcomes: Vec<RequestOutcome> = journal.entries().into_iter().map(|entry| entry.outcome).collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            RequestOutcome::Responded,
+            RequestOutcome::Rejected {
+                code: 200,
+                message: "No security definition has been found for the request".into()
+            },
+            RequestOutcome::Pending,
+        ]
+    );
+}
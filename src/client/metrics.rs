@@ -0,0 +1,88 @@
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Receives measurements of the client's traffic with TWS. Register with [ConnectionBuilder::metrics](crate::client::ConnectionBuilder::metrics).
+///
+/// Message types are the numeric message ids of the TWS API, e.g. 9 for contract details requests or 10 for contract data responses.
+/// All methods default to doing nothing, so implementations only override the measurements they export.
+/// Methods are called on the threads sending and receiving messages and should return quickly.
+///
+/// ```no_run
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// use ibapi::client::Metrics;
+/// use ibapi::Client;
+///
+/// #[derive(Default)]
+/// struct Counters {
+///     sent: AtomicU64,
+///     received: AtomicU64,
+/// }
+///
+/// impl Metrics for Counters {
+///     fn request_sent(&self, _message_type: i32) {
+///         self.sent.fetch_add(1, Ordering::Relaxed);
+///     }
+///
+///     fn message_received(&self, _message_type: i32) {
+///         self.received.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let client = Client::builder().metrics(Counters::default()).connect().expect("connection failed");
+/// ```
+pub trait Metrics: Send + Sync {
+    /// A message of `message_type` was sent to TWS.
+    fn request_sent(&self, _message_type: i32) {}
+
+    /// A message of `message_type` was received from TWS.
+    fn message_received(&self, _message_type: i32) {}
+
+    /// A message of `message_type` received from TWS could not be decoded.
+    fn decode_error(&self, _message_type: i32) {}
+
+    /// Time between sending a request of `message_type` and receiving its first response.
+    fn request_latency(&self, _message_type: i32, _latency: Duration) {}
+
+    /// Number of requests and orders awaiting responses from TWS.
+    fn pending_requests(&self, _requests: usize, _orders: usize) {}
+
+    /// Statistics of the pool of buffers messages from TWS are read into, reported after each message is read.
+    fn buffer_pool(&self, _stats: BufferPoolStats) {}
+}
+
+/// Statistics of the pool of buffers messages from TWS are read into. See [Metrics::buffer_pool].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferPoolStats {
+    /// Messages read into a buffer reused from an earlier message.
+    pub reused: u64,
+    /// Messages read into a newly allocated buffer.
+    pub allocated: u64,
+    /// Buffers currently idle in the pool.
+    pub idle: usize,
+}
+
+// Shared handle to the metrics of a client, so connection settings stay cloneable and printable.
+#[derive(Clone)]
+pub(crate) struct SharedMetrics(Arc<dyn Metrics>);
+
+impl SharedMetrics {
+    pub(crate) fn new(metrics: impl Metrics + 'static) -> Self {
+        SharedMetrics(Arc::new(metrics))
+    }
+}
+
+impl std::ops::Deref for SharedMetrics {
+    type Target = dyn Metrics;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for SharedMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedMetrics")
+    }
+}
@@ -0,0 +1,179 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use log::{debug, warn};
+
+use super::stream::{MemoryStream, MemoryTransport};
+use super::wire::{Direction, MessageObserver, WireMessage};
+use super::Client;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Records every message exchanged with TWS to a file, so the session can be replayed with [Replay].
+///
+/// Each message is written as a header line with its direction and length in bytes, followed by the raw message and a newline.
+///
+/// ```no_run
+/// use ibapi::client::SessionRecorder;
+/// use ibapi::Client;
+///
+/// let recorder = SessionRecorder::create("session.rec").expect("error creating recording");
+///
+/// let client = Client::builder().message_observer(recorder).connect().expect("connection failed");
+/// println!("server time: {:?}", client.server_time());
+/// ```
+pub struct SessionRecorder {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl SessionRecorder {
+    /// Creates the recording at `path`, replacing an existing file.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+        Ok(SessionRecorder {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+}
+
+impl MessageObserver for SessionRecorder {
+    fn observe(&self, message: &WireMessage) {
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+
+        let result = write!(writer, "{} {}\n{}\n", arrow(message.direction), message.raw.len(), message.raw).and_then(|_| writer.flush());
+        if let Err(e) = result {
+            warn!("error writing session recording: {e}");
+        }
+    }
+}
+
+/// Replays a session recorded with [SessionRecorder], feeding the recorded responses through the decoders without a connection to TWS.
+///
+/// The replayed client connects to an in-memory server that answers each recorded request with the responses recorded after it.
+/// Requests must be made in the recorded order, as request ids are assigned in sequence.
+///
+/// ```no_run
+/// use ibapi::client::Replay;
+///
+/// let replay = Replay::open("session.rec").expect("error reading recording");
+///
+/// let client = replay.connect().expect("connection failed");
+/// println!("server time: {:?}", client.server_time());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replay {
+    messages: Vec<(Direction, String)>,
+}
+
+impl Replay {
+    /// Reads the recording at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let data = fs::read_to_string(path)?;
+        Self::parse(&data)
+    }
+
+    pub(crate) fn parse(mut data: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::InvalidFormat {
+            kind: "session recording",
+            reason: reason.to_owned(),
+        };
+
+        let mut messages = Vec::new();
+        while !data.is_empty() {
+            let (header, rest) = data.split_once('\n').ok_or_else(|| invalid("missing message header"))?;
+
+            let (arrow, length) = header.split_once(' ').ok_or_else(|| invalid(header))?;
+            let direction = match arrow {
+                "->" => Direction::Outgoing,
+                "<-" => Direction::Incoming,
+                _ => return Err(invalid(header)),
+            };
+            let length: usize = length.parse().map_err(|_| invalid(header))?;
+
+            let raw = rest.get(..length).ok_or_else(|| invalid("truncated message"))?;
+            data = rest[length..].strip_prefix('\n').ok_or_else(|| invalid("missing message terminator"))?;
+
+            messages.push((direction, raw.to_owned()));
+        }
+
+        Ok(Replay { messages })
+    }
+
+    /// Number of recorded messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns true if no messages were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Connects a client to the recorded session.
+    pub fn connect(&self) -> Result<Client, Error> {
+        let transport = MemoryTransport::new();
+
+        let server = transport.clone();
+        let messages = self.messages.clone();
+        thread::spawn(move || serve(server.accept(), &messages));
+
+        Client::builder().transport(transport).without_rate_limit().connect()
+    }
+}
+
+fn arrow(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Outgoing => "->",
+        Direction::Incoming => "<-",
+    }
+}
+
+// Plays the recorded responses back to the client, waiting for each recorded request before sending the responses that followed it.
+fn serve(mut stream: MemoryStream, messages: &[(Direction, String)]) {
+    let mut prefix = [0_u8; 4];
+    if stream.read_exact(&mut prefix).is_err() || read_frame(&mut stream).is_err() {
+        return;
+    }
+
+    for (direction, raw) in messages {
+        match direction {
+            Direction::Outgoing => match read_frame(&mut stream) {
+                Ok(request) if request != *raw => warn!("replayed request {request:?} differs from recorded request {raw:?}"),
+                Ok(_) => {}
+                Err(_) => return,
+            },
+            Direction::Incoming => {
+                if write_frame(&mut stream, raw).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    // keeps the connection open until the client disconnects
+    while let Ok(request) = read_frame(&mut stream) {
+        debug!("request after the end of the recording: {request:?}");
+    }
+}
+
+// Reads a length prefixed message.
+pub(crate) fn read_frame(stream: &mut impl Read) -> Result<String, Error> {
+    let length = stream.read_u32::<BigEndian>()? as usize;
+    let mut data = vec![0_u8; length];
+    stream.read_exact(&mut data)?;
+    Ok(String::from_utf8(data)?)
+}
+
+pub(crate) fn write_frame(stream: &mut impl Write, raw: &str) -> Result<(), Error> {
+    stream.write_u32::<BigEndian>(raw.len() as u32)?;
+    stream.write_all(raw.as_bytes())?;
+    Ok(())
+}
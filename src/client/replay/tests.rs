@@ -0,0 +1,62 @@
+use time::macros::datetime;
+
+use super::*;
+
+// Answers the handshake and a single current time request, as TWS would.
+fn fake_tws(mut stream: MemoryStream) {
+    let mut prefix = [0_u8; 4];
+    stream.read_exact(&mut prefix).unwrap();
+    read_frame(&mut stream).unwrap(); // supported versions
+    write_frame(&mut stream, "173\x0020230405 22:20:39 PST\x00").unwrap();
+
+    read_frame(&mut stream).unwrap(); // start api
+    write_frame(&mut stream, "9\x001\x0090\x00").unwrap();
+    write_frame(&mut stream, "15\x001\x00DU1234\x00").unwrap();
+
+    read_frame(&mut stream).unwrap(); // current time
+    write_frame(&mut stream, "49\x001\x001678323335\x00").unwrap();
+
+    while read_frame(&mut stream).is_ok() {}
+}
+
+#[test]
+fn replays_recorded_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.rec");
+
+    let transport = MemoryTransport::new();
+    let server = transport.clone();
+    let tws = thread::spawn(move || fake_tws(server.accept()));
+
+    let client = Client::builder()
+        .transport(transport)
+        .message_observer(SessionRecorder::create(&path).unwrap())
+        .connect()
+        .expect("connection failed");
+    let recorded_time = client.server_time().expect("error requesting server time");
+    drop(client);
+    tws.join().unwrap();
+
+    let replay = Replay::open(&path).expect("error reading recording");
+    assert_eq!(replay.len(), 6, "recorded messages");
+
+    let client = replay.connect().expect("error connecting to replay");
+    assert_eq!(client.server_time().unwrap(), recorded_time, "replayed server time");
+    assert_eq!(recorded_time, datetime!(2023-03-09 00:55:35 UTC));
+}
+
+#[test]
+fn rejects_malformed_recordings() {
+    for (recording, reason) in [
+        ("-> 4", "missing message header"),
+        ("=> 4\n49\x001\x00\n", "=> 4"),
+        ("<- 10\n49\x001\x00\n", "truncated message"),
+        ("<- 2\n49\x001\x00\n", "missing message terminator"),
+    ] {
+        let error = Replay::parse(recording).unwrap_err();
+        assert_eq!(error.to_string(), format!("invalid session recording: {reason}"), "{recording:?}");
+    }
+
+    let replay = Replay::parse("-> 5\n49\x001\x00\n<- 7\n49\x001\x001\x00\n").unwrap();
+    assert_eq!(replay.len(), 2);
+}
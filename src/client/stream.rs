@@ -0,0 +1,359 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::{self, Receiver, Sender};
+
+use crate::Error;
+
+pub use proxy::Proxy;
+
+mod proxy;
+#[cfg(test)]
+mod tests;
+
+/// Byte stream to TWS or IB Gateway opened by a [Transport].
+///
+/// Messages are read on the dispatcher thread while requests are written from the calling threads,
+/// so streams must be cloneable into handles sharing the same connection.
+pub trait TransportStream: Read + Write + Send + Debug {
+    /// Returns a handle to the same connection.
+    fn try_clone(&self) -> io::Result<Box<dyn TransportStream>>;
+
+    /// Sets how long reads wait for data before failing with [io::ErrorKind::WouldBlock] or [io::ErrorKind::TimedOut].
+    /// None waits indefinitely.
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Returns the read timeout.
+    fn read_timeout(&self) -> io::Result<Option<Duration>>;
+
+    /// Closes the connection for reading and writing, which unblocks pending reads on all handles.
+    fn shutdown(&self) -> io::Result<()>;
+}
+
+/// Opens connections to TWS or IB Gateway. Register with [ConnectionBuilder::transport](crate::client::ConnectionBuilder::transport).
+///
+/// The client connects with [TcpTransport] unless another transport is registered.
+/// Transports are also used to reconnect and fail over, so `connect` may be called several times.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use ibapi::client::{Transport, TransportStream};
+/// use ibapi::{Client, Error};
+///
+/// // connects through a tunnel listening on a fixed local port, whatever the configured endpoint
+/// struct Tunnel;
+///
+/// impl Transport for Tunnel {
+///     fn connect(&self, _address: &str, _timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error> {
+///         Ok(Box::new(std::net::TcpStream::connect("127.0.0.1:14002")?))
+///     }
+/// }
+///
+/// let client = Client::builder().transport(Tunnel).connect().expect("connection failed");
+/// ```
+pub trait Transport: Send + Sync {
+    /// Opens a connection to `address`, e.g. 127.0.0.1:4002, giving up after `timeout` if set.
+    fn connect(&self, address: &str, timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error>;
+}
+
+// Shared handle to the transport of a client, so connection settings stay cloneable and printable.
+#[derive(Clone)]
+pub(crate) struct SharedTransport(Arc<dyn Transport>);
+
+impl SharedTransport {
+    pub(crate) fn new(transport: impl Transport + 'static) -> Self {
+        SharedTransport(Arc::new(transport))
+    }
+
+    pub(crate) fn connect(&self, address: &str, timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error> {
+        self.0.connect(address, timeout)
+    }
+}
+
+impl Default for SharedTransport {
+    fn default() -> Self {
+        SharedTransport::new(TcpTransport)
+    }
+}
+
+impl Debug for SharedTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedTransport")
+    }
+}
+
+/// Connects over TCP. The default transport.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TcpTransport;
+
+impl Transport for TcpTransport {
+    fn connect(&self, address: &str, timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error> {
+        Ok(Box::new(connect_tcp(address, timeout)?))
+    }
+}
+
+fn connect_tcp(address: &str, timeout: Option<Duration>) -> Result<TcpStream, Error> {
+    match timeout {
+        Some(timeout) => connect_timeout(address, timeout),
+        None => Ok(TcpStream::connect(address)?),
+    }
+}
+
+// Tries each address `address` resolves to until one accepts the connection within `timeout`.
+fn connect_timeout(address: &str, timeout: Duration) -> Result<TcpStream, Error> {
+    let mut last_error = None;
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    match last_error {
+        Some(e) => Err(e.into()),
+        None => Err(Error::InvalidArgument(format!("could not resolve address: {address}"))),
+    }
+}
+
+impl TransportStream for TcpStream {
+    fn try_clone(&self) -> io::Result<Box<dyn TransportStream>> {
+        Ok(Box::new(TcpStream::try_clone(self)?))
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        TcpStream::read_timeout(self)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        TcpStream::shutdown(self, Shutdown::Both)
+    }
+}
+
+/// Connects to a Unix domain socket, e.g. one forwarded to a remote Gateway. The configured endpoint address is ignored.
+#[cfg(unix)]
+#[derive(Clone, Debug)]
+pub struct UnixTransport {
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl UnixTransport {
+    /// Creates a transport connecting to the socket at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        UnixTransport { path: path.into() }
+    }
+}
+
+#[cfg(unix)]
+impl Transport for UnixTransport {
+    fn connect(&self, _address: &str, _timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error> {
+        Ok(Box::new(UnixStream::connect(&self.path)?))
+    }
+}
+
+#[cfg(unix)]
+impl TransportStream for UnixStream {
+    fn try_clone(&self) -> io::Result<Box<dyn TransportStream>> {
+        Ok(Box::new(UnixStream::try_clone(self)?))
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        UnixStream::set_read_timeout(self, timeout)
+    }
+
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        UnixStream::read_timeout(self)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        UnixStream::shutdown(self, Shutdown::Both)
+    }
+}
+
+/// Transport connecting to an in-process server, e.g. a fake TWS in tests.
+///
+/// Each connection made by the client is handed to the server by [accept](MemoryTransport::accept). Clones share connections.
+///
+/// ```no_run
+/// use std::thread;
+///
+/// use ibapi::client::MemoryTransport;
+/// use ibapi::Client;
+///
+/// let transport = MemoryTransport::new();
+///
+/// let server = transport.clone();
+/// thread::spawn(move || {
+///     let stream = server.accept();
+///     // exchange messages with the client over stream
+/// });
+///
+/// let client = Client::builder().transport(transport).connect().expect("connection failed");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MemoryTransport {
+    sender: Sender<MemoryStream>,
+    receiver: Receiver<MemoryStream>,
+}
+
+impl MemoryTransport {
+    /// Creates a transport without connections.
+    pub fn new() -> Self {
+        let (sender, receiver) = channel::unbounded();
+        MemoryTransport { sender, receiver }
+    }
+
+    /// Waits for the client to connect and returns the server end of the connection.
+    pub fn accept(&self) -> MemoryStream {
+        // the transport holds a sender, so the channel is never disconnected
+        self.receiver.recv().unwrap()
+    }
+}
+
+impl Default for MemoryTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn connect(&self, _address: &str, _timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error> {
+        let (client, server) = MemoryStream::pair();
+        if self.sender.send(server).is_err() {
+            return Err(Error::ConnectionFailed);
+        }
+        Ok(Box::new(client))
+    }
+}
+
+/// One end of an in-memory connection. Bytes written to one end are read from the other.
+#[derive(Clone)]
+pub struct MemoryStream {
+    incoming: Arc<Pipe>,
+    outgoing: Arc<Pipe>,
+    read_timeout: Arc<Mutex<Option<Duration>>>,
+}
+
+#[derive(Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    ready: Condvar,
+}
+
+#[derive(Default)]
+struct PipeState {
+    data: VecDeque<u8>,
+    closed: bool,
+}
+
+impl Pipe {
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.ready.notify_all();
+    }
+}
+
+impl MemoryStream {
+    /// Creates both ends of a connection.
+    pub fn pair() -> (MemoryStream, MemoryStream) {
+        let a = Arc::new(Pipe::default());
+        let b = Arc::new(Pipe::default());
+
+        let first = MemoryStream {
+            incoming: Arc::clone(&a),
+            outgoing: Arc::clone(&b),
+            read_timeout: Arc::default(),
+        };
+        let second = MemoryStream {
+            incoming: b,
+            outgoing: a,
+            read_timeout: Arc::default(),
+        };
+
+        (first, second)
+    }
+}
+
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let deadline = self.read_timeout.lock().unwrap().map(|timeout| Instant::now() + timeout);
+
+        let mut state = self.incoming.state.lock().unwrap();
+        while state.data.is_empty() && !state.closed {
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(io::ErrorKind::WouldBlock.into());
+                    }
+                    self.incoming.ready.wait_timeout(state, deadline - now).unwrap().0
+                }
+                None => self.incoming.ready.wait(state).unwrap(),
+            };
+        }
+
+        // reads from a closed pipe return the remaining data, then end of file
+        let count = buf.len().min(state.data.len());
+        for (byte, value) in buf.iter_mut().zip(state.data.drain(..count)) {
+            *byte = value;
+        }
+        Ok(count)
+    }
+}
+
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.outgoing.state.lock().unwrap();
+        if state.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+
+        state.data.extend(buf);
+        self.outgoing.ready.notify_all();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl TransportStream for MemoryStream {
+    fn try_clone(&self) -> io::Result<Box<dyn TransportStream>> {
+        Ok(Box::new(self.clone()))
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        *self.read_timeout.lock().unwrap() = timeout;
+        Ok(())
+    }
+
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(*self.read_timeout.lock().unwrap())
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        self.incoming.close();
+        self.outgoing.close();
+        Ok(())
+    }
+}
+
+impl Debug for MemoryStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryStream").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,254 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream};
+use std::time::Duration;
+
+use super::{connect_tcp, Transport, TransportStream};
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+const SOCKS_VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const USERNAME_PASSWORD: u8 = 2;
+const CONNECT: u8 = 1;
+const IPV4: u8 = 1;
+const DOMAIN_NAME: u8 = 3;
+const IPV6: u8 = 4;
+
+// Longest response header accepted from an HTTP proxy.
+const MAX_HTTP_HEADER_LENGTH: usize = 8192;
+
+/// Proxy used to reach TWS or IB Gateway on a remote host. Register with [ConnectionBuilder::proxy](crate::client::ConnectionBuilder::proxy).
+///
+/// ```no_run
+/// use ibapi::client::Proxy;
+/// use ibapi::Client;
+///
+/// let proxy = Proxy::socks5("proxy.internal:1080").with_credentials("trader", "secret");
+///
+/// let client = Client::builder()
+///     .host("gateway.internal")
+///     .port(4002)
+///     .proxy(proxy)
+///     .connect()
+///     .expect("connection failed");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proxy {
+    protocol: ProxyProtocol,
+    address: String,
+    credentials: Option<(String, String)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ProxyProtocol {
+    Socks5,
+    Http,
+}
+
+impl Proxy {
+    /// SOCKS5 proxy listening on `address`, e.g. proxy.internal:1080.
+    pub fn socks5(address: &str) -> Self {
+        Self::new(ProxyProtocol::Socks5, address)
+    }
+
+    /// HTTP proxy listening on `address` and supporting the CONNECT method, e.g. proxy.internal:3128.
+    pub fn http(address: &str) -> Self {
+        Self::new(ProxyProtocol::Http, address)
+    }
+
+    fn new(protocol: ProxyProtocol, address: &str) -> Self {
+        Proxy {
+            protocol,
+            address: address.into(),
+            credentials: None,
+        }
+    }
+
+    /// Authenticates with the proxy using `username` and `password`.
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Address of the proxy.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl Transport for Proxy {
+    fn connect(&self, address: &str, timeout: Option<Duration>) -> Result<Box<dyn TransportStream>, Error> {
+        let mut stream = connect_tcp(&self.address, timeout)?;
+        // a proxy that stops responding should not hang the connection attempt
+        stream.set_read_timeout(timeout)?;
+
+        match self.protocol {
+            ProxyProtocol::Socks5 => self.socks5_connect(&mut stream, address)?,
+            ProxyProtocol::Http => self.http_connect(&mut stream, address)?,
+        }
+
+        Ok(Box::new(stream))
+    }
+}
+
+impl Proxy {
+    fn socks5_connect(&self, stream: &mut TcpStream, address: &str) -> Result<(), Error> {
+        let (host, port) = split_address(address)?;
+
+        let method = if self.credentials.is_some() {
+            USERNAME_PASSWORD
+        } else {
+            NO_AUTHENTICATION
+        };
+        stream.write_all(&[SOCKS_VERSION, 1, method])?;
+
+        let mut reply = [0_u8; 2];
+        stream.read_exact(&mut reply)?;
+        if reply[0] != SOCKS_VERSION {
+            return Err(self.error(format!("unexpected SOCKS version {}", reply[0])));
+        }
+        // proxies answer 0xFF when no offered method is acceptable
+        if reply[1] != method {
+            return Err(self.error("authentication method not supported".into()));
+        }
+
+        if let Some((username, password)) = &self.credentials {
+            let mut request = vec![1];
+            push_length_prefixed(&mut request, "username", username)?;
+            push_length_prefixed(&mut request, "password", password)?;
+            stream.write_all(&request)?;
+
+            stream.read_exact(&mut reply)?;
+            if reply[1] != 0 {
+                return Err(self.error("authentication failed".into()));
+            }
+        }
+
+        let mut request = vec![SOCKS_VERSION, CONNECT, 0];
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                request.push(IPV4);
+                request.extend(ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                request.push(IPV6);
+                request.extend(ip.octets());
+            }
+            Err(_) => {
+                request.push(DOMAIN_NAME);
+                push_length_prefixed(&mut request, "host name", host)?;
+            }
+        }
+        request.extend(port.to_be_bytes());
+        stream.write_all(&request)?;
+
+        let mut reply = [0_u8; 4];
+        stream.read_exact(&mut reply)?;
+        if reply[1] != 0 {
+            return Err(self.error(format!("connection to {address} refused: {}", socks5_reply(reply[1]))));
+        }
+
+        // bound address and port, which are not needed
+        let length = match reply[3] {
+            IPV4 => 4,
+            IPV6 => 16,
+            DOMAIN_NAME => {
+                let mut length = [0_u8; 1];
+                stream.read_exact(&mut length)?;
+                length[0] as usize
+            }
+            address_type => return Err(self.error(format!("unexpected address type {address_type}"))),
+        };
+        let mut bound = vec![0_u8; length + 2];
+        stream.read_exact(&mut bound)?;
+
+        Ok(())
+    }
+
+    fn http_connect(&self, stream: &mut TcpStream, address: &str) -> Result<(), Error> {
+        let mut request = format!("CONNECT {address} HTTP/1.1\r\nHost: {address}\r\n");
+        if let Some((username, password)) = &self.credentials {
+            request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", base64(&format!("{username}:{password}"))));
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes())?;
+
+        // reads the header a byte at a time, so no bytes sent by TWS after it are consumed
+        let mut header = Vec::new();
+        while !header.ends_with(b"\r\n\r\n") {
+            if header.len() >= MAX_HTTP_HEADER_LENGTH {
+                return Err(self.error("response header too long".into()));
+            }
+            let mut byte = [0_u8; 1];
+            stream.read_exact(&mut byte)?;
+            header.push(byte[0]);
+        }
+
+        let header = String::from_utf8_lossy(&header);
+        let status_line = header.lines().next().unwrap_or_default();
+        match status_line.split_whitespace().nth(1) {
+            Some(status) if status.starts_with('2') => Ok(()),
+            _ => Err(self.error(format!("connection to {address} refused: {status_line}"))),
+        }
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::Proxy {
+            address: self.address.clone(),
+            reason: message,
+        }
+    }
+}
+
+// Splits host:port, where IPv6 hosts are enclosed in brackets, e.g. [::1]:4002.
+fn split_address(address: &str) -> Result<(&str, u16), Error> {
+    let invalid = || Error::InvalidArgument(format!("invalid address: {address}"));
+
+    let (host, port) = address.rsplit_once(':').ok_or_else(invalid)?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = port.parse().map_err(|_| invalid())?;
+
+    Ok((host, port))
+}
+
+fn push_length_prefixed(buffer: &mut Vec<u8>, name: &str, value: &str) -> Result<(), Error> {
+    let length = u8::try_from(value.len()).map_err(|_| Error::InvalidArgument(format!("SOCKS5 {name} is longer than 255 bytes")))?;
+    buffer.push(length);
+    buffer.extend(value.as_bytes());
+    Ok(())
+}
+
+fn socks5_reply(code: u8) -> &'static str {
+    match code {
+        1 => "general failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+fn base64(value: &str) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::new();
+    for chunk in value.as_bytes().chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,151 @@
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+use std::thread;
+
+use super::*;
+
+#[test]
+fn connects_through_socks5_proxy() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy_address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        let mut greeting = [0_u8; 3];
+        stream.read_exact(&mut greeting).unwrap();
+        assert_eq!(greeting, [5, 1, 2], "greeting");
+        stream.write_all(&[5, 2]).unwrap();
+
+        let mut authentication = [0_u8; 13];
+        stream.read_exact(&mut authentication).unwrap();
+        assert_eq!(&authentication, b"\x01\x06trader\x04pass", "authentication");
+        stream.write_all(&[1, 0]).unwrap();
+
+        let mut request = [0_u8; 23];
+        stream.read_exact(&mut request).unwrap();
+        assert_eq!(&request, b"\x05\x01\x00\x03\x10gateway.internal\x0f\xa2", "connect request");
+        stream.write_all(&[5, 0, 0, 1, 10, 0, 0, 6, 0x0f, 0xa2]).unwrap();
+
+        stream.write_all(b"173\0").unwrap();
+    });
+
+    let proxy = Proxy::socks5(&proxy_address).with_credentials("trader", "pass");
+    let mut stream = proxy
+        .connect("gateway.internal:4002", Some(Duration::from_secs(5)))
+        .expect("connection failed");
+
+    let mut version = [0_u8; 4];
+    stream.read_exact(&mut version).unwrap();
+    assert_eq!(&version, b"173\0", "data relayed after the handshake");
+
+    server.join().unwrap();
+}
+
+#[test]
+fn reports_socks5_connection_refused() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy_address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        let mut greeting = [0_u8; 3];
+        stream.read_exact(&mut greeting).unwrap();
+        stream.write_all(&[5, 0]).unwrap();
+
+        let mut request = [0_u8; 10];
+        stream.read_exact(&mut request).unwrap();
+        assert_eq!(request, [5, 1, 0, 1, 10, 0, 0, 6, 0x0f, 0xa2], "connect request");
+        stream.write_all(&[5, 5, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+    });
+
+    let result = Proxy::socks5(&proxy_address).connect("10.0.0.6:4002", Some(Duration::from_secs(5)));
+    match result {
+        Err(Error::Proxy { address, reason }) => {
+            assert_eq!(address, proxy_address);
+            assert_eq!(reason, "connection to 10.0.0.6:4002 refused: connection refused");
+        }
+        result => panic!("expected error, got {result:?}"),
+    }
+
+    server.join().unwrap();
+}
+
+#[test]
+fn connects_through_http_proxy() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy_address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut header = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            header.push(line.trim_end().to_string());
+        }
+
+        let mut stream = stream;
+        stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n173\0").unwrap();
+        header
+    });
+
+    let proxy = Proxy::http(&proxy_address).with_credentials("trader", "pass");
+    let mut stream = proxy.connect("gateway.internal:4002", None).expect("connection failed");
+
+    let mut version = [0_u8; 4];
+    stream.read_exact(&mut version).unwrap();
+    assert_eq!(&version, b"173\0", "data relayed after the response header");
+
+    let header = server.join().unwrap();
+    assert_eq!(
+        header,
+        vec![
+            "CONNECT gateway.internal:4002 HTTP/1.1",
+            "Host: gateway.internal:4002",
+            "Proxy-Authorization: Basic dHJhZGVyOnBhc3M=",
+        ]
+    );
+}
+
+#[test]
+fn reports_http_proxy_rejection() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy_address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0_u8; 16];
+        stream.read_exact(&mut request).unwrap();
+        stream
+            .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\nContent-Length: 0\r\n\r\n")
+            .unwrap();
+    });
+
+    let result = Proxy::http(&proxy_address).connect("gateway.internal:4002", Some(Duration::from_secs(5)));
+    match result {
+        Err(Error::Proxy { reason, .. }) => assert!(reason.ends_with("refused: HTTP/1.1 407 Proxy Authentication Required"), "{reason}"),
+        result => panic!("expected error, got {result:?}"),
+    }
+
+    server.join().unwrap();
+}
+
+#[test]
+fn encodes_base64() {
+    for (value, expected) in [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("trader:pass", "dHJhZGVyOnBhc3M="),
+    ] {
+        assert_eq!(base64(value), expected, "base64({value:?})");
+    }
+}
@@ -0,0 +1,56 @@
+use std::thread;
+
+use super::*;
+
+#[test]
+fn memory_streams_exchange_bytes() {
+    let (mut client, mut server) = MemoryStream::pair();
+
+    client.write_all(b"API\0").unwrap();
+    let mut prefix = [0_u8; 4];
+    server.read_exact(&mut prefix).unwrap();
+    assert_eq!(&prefix, b"API\0");
+
+    let writer = thread::spawn(move || server.write_all(b"173\0").unwrap());
+    let mut version = [0_u8; 4];
+    client.read_exact(&mut version).unwrap();
+    assert_eq!(&version, b"173\0");
+    writer.join().unwrap();
+}
+
+#[test]
+fn memory_stream_reads_time_out() {
+    let (mut client, _server) = MemoryStream::pair();
+    client.set_read_timeout(Some(Duration::from_millis(10))).unwrap();
+
+    let mut buffer = [0_u8; 4];
+    let error = client.read(&mut buffer).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+}
+
+#[test]
+fn memory_stream_shutdown_closes_both_ends() {
+    let (mut client, mut server) = MemoryStream::pair();
+    let reader = TransportStream::try_clone(&client).unwrap();
+
+    server.write_all(b"9\0").unwrap();
+    TransportStream::shutdown(reader.as_ref()).unwrap();
+
+    let mut buffer = Vec::new();
+    client.read_to_end(&mut buffer).unwrap();
+    assert_eq!(buffer, b"9\0", "data written before shutdown");
+    assert_eq!(server.write(b"x").unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn memory_transport_hands_connections_to_server() {
+    let transport = MemoryTransport::new();
+
+    let mut client = transport.connect("127.0.0.1:4002", None).unwrap();
+    let mut server = transport.accept();
+
+    client.write_all(b"71\0").unwrap();
+    let mut buffer = [0_u8; 3];
+    server.read_exact(&mut buffer).unwrap();
+    assert_eq!(&buffer, b"71\0");
+}
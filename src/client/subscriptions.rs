@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::CancelHandle;
+use crate::contracts::Contract;
+
+/// Kind of data a subscription receives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SubscriptionKind {
+    Notices,
+    AccountSummary,
+    AccountUpdates,
+    AccountUpdatesMulti,
+    Positions,
+    PositionsMulti,
+    PnL,
+    PnLSingle,
+    OptionChain,
+    OptionComputation,
+    PlaceOrder,
+    CancelOrder,
+    Orders,
+    Executions,
+    ExerciseOptions,
+    MarketData,
+    MarketDepth,
+    RealtimeBars,
+    TickByTickTrades,
+    TickByTickBidAsk,
+    TickByTickMidPoint,
+    NewsBulletins,
+    News,
+    Scanner,
+    WshMetadata,
+    WshEventData,
+    DisplayGroupEvents,
+}
+
+/// Describes a subscription that has not been cancelled or dropped, returned by [Client::active_subscriptions](crate::Client::active_subscriptions).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubscriptionInfo {
+    pub kind: SubscriptionKind,
+    /// Contract the data was requested for, if the request names a contract.
+    pub contract: Option<Contract>,
+    /// Request id of the subscription. None for subscriptions routed by order id or message type, e.g. open orders.
+    pub request_id: Option<i32>,
+    /// Order id of the subscription, for order placement, cancellation and exercise.
+    pub order_id: Option<i32>,
+    /// Time since the subscription was created.
+    pub age: Duration,
+}
+
+// Tracks the subscriptions of a client until they are dropped, so they can be listed and cancelled together.
+#[derive(Debug, Default)]
+pub(crate) struct SubscriptionRegistry {
+    next_key: AtomicU64,
+    entries: Mutex<BTreeMap<u64, Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    kind: SubscriptionKind,
+    contract: Option<Contract>,
+    request_id: Option<i32>,
+    order_id: Option<i32>,
+    created: Instant,
+    cancel_handle: CancelHandle,
+}
+
+impl SubscriptionRegistry {
+    // Registers a subscription, returning the key that unregisters it.
+    pub(crate) fn register(
+        &self,
+        kind: SubscriptionKind,
+        contract: Option<Contract>,
+        request_id: Option<i32>,
+        order_id: Option<i32>,
+        cancel_handle: CancelHandle,
+    ) -> u64 {
+        let key = self.next_key.fetch_add(1, Ordering::Relaxed);
+        let entry = Entry {
+            kind,
+            contract,
+            request_id,
+            order_id,
+            created: Instant::now(),
+            cancel_handle,
+        };
+        self.entries.lock().unwrap().insert(key, entry);
+        key
+    }
+
+    pub(crate) fn unregister(&self, key: u64) {
+        self.entries.lock().unwrap().remove(&key);
+    }
+
+    // Subscriptions not cancelled, in the order they were created.
+    pub(crate) fn active(&self) -> Vec<SubscriptionInfo> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .values()
+            .filter(|entry| !entry.cancel_handle.is_cancelled())
+            .map(|entry| SubscriptionInfo {
+                kind: entry.kind,
+                contract: entry.contract.clone(),
+                request_id: entry.request_id,
+                order_id: entry.order_id,
+                age: entry.created.elapsed(),
+            })
+            .collect()
+    }
+
+    // Cancels every subscription, returning the number of subscriptions cancelled.
+    pub(crate) fn cancel_all(&self) -> usize {
+        // cancelling sends messages to TWS, so the handles are cancelled without holding the lock
+        let handles: Vec<CancelHandle> = self
+            .entries
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|entry| !entry.cancel_handle.is_cancelled())
+            .map(|entry| entry.cancel_handle.clone())
+            .collect();
+
+        for handle in &handles {
+            handle.cancel();
+        }
+        handles.len()
+    }
+}
@@ -1 +1,126 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::thread;
 
+use super::*;
+use crate::contracts::Contract;
+use crate::server_versions;
+use crate::stubs::MessageBusStub;
+use crate::tests::assert_send_and_sync;
+
+#[test]
+fn client_is_send_and_sync() {
+    assert_send_and_sync::<Client>();
+}
+
+#[test]
+fn concurrent_requests_share_connection() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["52|1|9000||".to_string()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    thread::scope(|scope| {
+        for symbol in ["AAPL", "NVDA", "TSLA", "MSFT"] {
+            let client = &client;
+            scope.spawn(move || {
+                client
+                    .contract_details(&Contract::stock(symbol))
+                    .expect("request contract details failed");
+            });
+        }
+    });
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages.len(), 4, "requests sent");
+
+    let request_ids: HashSet<String> = request_messages.iter().map(|message| message[2].to_string()).collect();
+    assert_eq!(request_ids.len(), 4, "unique request ids {request_ids:?}");
+}
+
+#[test]
+fn notices_skip_other_messages() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "4|2|-1|2104|Market data farm connection is OK:usfarm|".to_string(),
+            "49|1|1678323335|".to_string(),
+            "4|2|-1|1100|Connectivity between IB and Trader Workstation has been lost.|".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let notices = client.notices().expect("error subscribing to notices");
+    let codes: Vec<i32> = notices.iter().map(|notice| notice.code).collect();
+
+    assert_eq!(codes, vec![2104, 1100]);
+}
+
+#[test]
+#[cfg(feature = "market-data")]
+fn lists_and_cancels_active_subscriptions() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![],
+    });
+
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    let contract = Contract::stock("AAPL");
+    let bars = client
+        .realtime_bars(&contract, realtime::BarSize::Sec5, WhatToShow::Trades, false)
+        .expect("error requesting realtime bars");
+    let positions = client.positions().expect("error requesting positions");
+    let pnl = client.pnl("DU1234567", None).expect("error requesting pnl");
+    drop(pnl);
+
+    assert_eq!(bars.request_id(), Some(9000), "bars request id");
+    assert_eq!(positions.request_id(), None, "positions request id");
+
+    let active = client.active_subscriptions();
+    assert_eq!(active.len(), 2, "{active:?}");
+    assert_eq!(active[0].kind, SubscriptionKind::RealtimeBars);
+    assert_eq!(active[0].contract, Some(contract));
+    assert_eq!(active[0].request_id, Some(9000));
+    assert_eq!(active[1].kind, SubscriptionKind::Positions);
+    assert_eq!(active[1].contract, None);
+    assert_eq!(active[1].request_id, None);
+    assert!(active[0].age >= active[1].age, "ages");
+
+    let sent = message_bus.request_messages().len();
+    assert_eq!(client.cancel_all(), 2, "cancelled subscriptions");
+    assert!(client.active_subscriptions().is_empty(), "active after cancel_all");
+    assert_eq!(message_bus.request_messages().len(), sent + 2, "cancel messages sent");
+    assert_eq!(client.cancel_all(), 0, "cancelled twice");
+
+    drop(bars);
+    drop(positions);
+    assert_eq!(message_bus.request_messages().len(), sent + 2, "cancel messages sent on drop");
+}
+
+#[test]
+fn reports_connection_info() {
+    let tws = crate::testing::MockTws::new()
+        .managed_accounts("DU1234,DU5678")
+        .start()
+        .expect("error starting mock TWS");
+
+    let client = Client::builder()
+        .port(tws.address().rsplit_once(':').unwrap().1.parse().unwrap())
+        .optional_capabilities("E")
+        .connect()
+        .expect("connection failed");
+
+    assert_eq!(
+        client.connection_info(),
+        &ConnectionInfo {
+            server_version: 173,
+            connection_time: "20230405 22:20:39 PST".into(),
+            optional_capabilities: Some("E".into()),
+            managed_accounts: vec!["DU1234".into(), "DU5678".into()],
+        }
+    );
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use time::OffsetDateTime;
+
+use super::Client;
+use crate::contracts::{self, Contract, ContractDescription, ContractDetails, MarketRule};
+#[cfg(feature = "fundamentals")]
+use crate::fundamentals::{self, FundamentalReportType};
+#[cfg(feature = "historical")]
+use crate::market_data::historical::{self, BarSize, HistoricalData, Schedule, WhatToShow};
+use crate::{accounts, Error};
+
+#[cfg(all(test, feature = "historical"))]
+mod tests;
+
+/// Makes blocking requests that fail with [Error::Timeout] if TWS does not respond in time. Created with [Client::with_timeout].
+///
+/// The timeout applies to each response awaited from TWS. Historical data, historical schedule, head timestamp and fundamental data
+/// requests that time out are cancelled in TWS; late replies to other requests are discarded when they arrive.
+/// See the [Client] methods of the same names for details on each request.
+pub struct WithTimeout<'a> {
+    client: &'a Client,
+    timeout: Option<Duration>,
+}
+
+impl<'a> WithTimeout<'a> {
+    pub(crate) fn new(client: &'a Client, timeout: Duration) -> Self {
+        WithTimeout {
+            client,
+            timeout: Some(timeout),
+        }
+    }
+
+    /// Requests the current server time. See [Client::server_time].
+    pub fn server_time(&self) -> Result<OffsetDateTime, Error> {
+        accounts::server_time(self.client, self.timeout)
+    }
+
+    /// Requests the white branding id of the logged user. See [Client::user_info].
+    pub fn user_info(&self) -> Result<String, Error> {
+        accounts::user_info(self.client, self.timeout)
+    }
+
+    /// Requests contract information. See [Client::contract_details].
+    pub fn contract_details(&self, contract: &Contract) -> Result<Vec<ContractDetails>, Error> {
+        contracts::contract_details(self.client, contract, self.timeout)
+    }
+
+    /// Requests details about a given market rule. See [Client::market_rule].
+    pub fn market_rule(&self, market_rule_id: i32) -> Result<MarketRule, Error> {
+        contracts::market_rule(self.client, market_rule_id, self.timeout)
+    }
+
+    /// Requests matching stock symbols. See [Client::matching_symbols].
+    pub fn matching_symbols(&self, pattern: &str) -> Result<impl Iterator<Item = ContractDescription>, Error> {
+        Ok(contracts::matching_symbols(self.client, pattern, self.timeout)?.into_iter())
+    }
+
+    /// Returns the timestamp of earliest available historical data. See [Client::head_timestamp].
+    #[cfg(feature = "historical")]
+    pub fn head_timestamp(&self, contract: &Contract, what_to_show: WhatToShow, use_rth: bool) -> Result<OffsetDateTime, Error> {
+        historical::head_timestamp(self.client, contract, what_to_show, use_rth, self.timeout)
+    }
+
+    /// Requests interval of historical data ending at specified time. See [Client::historical_data].
+    #[cfg(feature = "historical")]
+    pub fn historical_data(
+        &self,
+        contract: &Contract,
+        interval_end: OffsetDateTime,
+        duration: historical::Duration,
+        bar_size: BarSize,
+        what_to_show: WhatToShow,
+        use_rth: bool,
+    ) -> Result<HistoricalData, Error> {
+        historical::historical_data(
+            self.client,
+            contract,
+            Some(interval_end),
+            duration,
+            bar_size,
+            Some(what_to_show),
+            use_rth,
+            self.timeout,
+        )
+    }
+
+    /// Requests interval of historical data ending now. See [Client::historical_data_ending_now].
+    #[cfg(feature = "historical")]
+    pub fn historical_data_ending_now(
+        &self,
+        contract: &Contract,
+        duration: historical::Duration,
+        bar_size: BarSize,
+        what_to_show: WhatToShow,
+        use_rth: bool,
+    ) -> Result<HistoricalData, Error> {
+        historical::historical_data(self.client, contract, None, duration, bar_size, Some(what_to_show), use_rth, self.timeout)
+    }
+
+    /// Requests the trading schedule for an interval ending at specified date. See [Client::historical_schedules].
+    #[cfg(feature = "historical")]
+    pub fn historical_schedules(&self, contract: &Contract, interval_end: OffsetDateTime, duration: historical::Duration) -> Result<Schedule, Error> {
+        historical::historical_schedule(self.client, contract, Some(interval_end), duration, self.timeout)
+    }
+
+    /// Requests the trading schedule for an interval ending now. See [Client::historical_schedules_ending_now].
+    #[cfg(feature = "historical")]
+    pub fn historical_schedules_ending_now(&self, contract: &Contract, duration: historical::Duration) -> Result<Schedule, Error> {
+        historical::historical_schedule(self.client, contract, None, duration, self.timeout)
+    }
+
+    /// Requests a fundamental data report. See [Client::fundamental_data].
+    #[cfg(feature = "fundamentals")]
+    pub fn fundamental_data(&self, contract: &Contract, report_type: FundamentalReportType) -> Result<String, Error> {
+        fundamentals::fundamental_data(self.client, contract, report_type, self.timeout)
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::server_versions;
+use crate::stubs::MessageBusStub;
+
+#[test]
+fn requests_with_timeout() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["88|9000|1678323335|".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let head_timestamp = client
+        .with_timeout(Duration::from_secs(5))
+        .head_timestamp(&Contract::stock("MSFT"), WhatToShow::Trades, true)
+        .expect("head timestamp request failed");
+
+    assert_eq!(head_timestamp, OffsetDateTime::from_unix_timestamp(1678323335).unwrap());
+    assert_eq!(client.message_bus.request_messages().len(), 1, "requests sent to TWS");
+}
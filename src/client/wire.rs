@@ -0,0 +1,130 @@
+use std::fmt::{self, Debug};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use log::warn;
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Direction of a message exchanged with TWS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent by the client to TWS.
+    Outgoing,
+    /// Received by the client from TWS.
+    Incoming,
+}
+
+/// Raw message exchanged with TWS, as seen on the wire without the length prefix.
+#[derive(Clone, Copy, Debug)]
+pub struct WireMessage<'a> {
+    pub direction: Direction,
+    /// Time the message was sent or received.
+    pub timestamp: OffsetDateTime,
+    /// Message fields, each terminated by a NUL character.
+    pub raw: &'a str,
+}
+
+impl WireMessage<'_> {
+    /// Fields of the message.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.raw.strip_suffix('\0').unwrap_or(self.raw).split('\0')
+    }
+}
+
+/// Observes every message sent to and received from TWS. Register with [ConnectionBuilder::message_observer](crate::client::ConnectionBuilder::message_observer).
+///
+/// Observers are called on the threads reading and writing messages and should return quickly.
+/// Closures taking a [WireMessage] implement the trait.
+pub trait MessageObserver: Send + Sync {
+    fn observe(&self, message: &WireMessage);
+}
+
+impl<F> MessageObserver for F
+where
+    F: Fn(&WireMessage) + Send + Sync,
+{
+    fn observe(&self, message: &WireMessage) {
+        self(message)
+    }
+}
+
+/// Writes messages to a file in the format of the TWS API logs, e.g. `14:31:02:123 -> 71-2-100--`.
+///
+/// ```no_run
+/// use ibapi::client::TwsLogFile;
+/// use ibapi::Client;
+///
+/// let log = TwsLogFile::create("api.100.log").expect("error creating log file");
+///
+/// let client = Client::builder().message_observer(log).connect().expect("connection failed");
+/// ```
+pub struct TwsLogFile {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl TwsLogFile {
+    /// Opens `path` for appending, creating the file if it does not exist.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(TwsLogFile {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    pub(crate) fn format(message: &WireMessage) -> String {
+        let format = format_description!("[hour]:[minute]:[second]:[subsecond digits:3]");
+        let timestamp = message.timestamp.format(&format).unwrap_or_default();
+
+        let arrow = match message.direction {
+            Direction::Outgoing => "->",
+            Direction::Incoming => "<-",
+        };
+
+        format!("{timestamp} {arrow} {}", message.raw.replace('\0', "-"))
+    }
+}
+
+impl MessageObserver for TwsLogFile {
+    fn observe(&self, message: &WireMessage) {
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+
+        let result = writeln!(writer, "{}", Self::format(message)).and_then(|_| writer.flush());
+        if let Err(e) = result {
+            warn!("error writing message log: {e}");
+        }
+    }
+}
+
+// Shared handle to an observer, so connection settings stay cloneable and printable.
+#[derive(Clone)]
+pub(crate) struct SharedObserver(Arc<dyn MessageObserver>);
+
+impl SharedObserver {
+    pub(crate) fn new(observer: impl MessageObserver + 'static) -> Self {
+        SharedObserver(Arc::new(observer))
+    }
+
+    pub(crate) fn observe(&self, direction: Direction, raw: &str) {
+        self.0.observe(&WireMessage {
+            direction,
+            timestamp: OffsetDateTime::now_utc(),
+            raw,
+        });
+    }
+}
+
+impl Debug for SharedObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedObserver")
+    }
+}
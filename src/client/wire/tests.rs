@@ -0,0 +1,37 @@
+use time::macros::datetime;
+
+use super::*;
+
+#[test]
+fn formats_tws_log_lines() {
+    let outgoing = WireMessage {
+        direction: Direction::Outgoing,
+        timestamp: datetime!(2024-03-01 14:31:02.123 UTC),
+        raw: "71\x002\x00100\x00\x00",
+    };
+    assert_eq!(TwsLogFile::format(&outgoing), "14:31:02:123 -> 71-2-100--");
+    assert_eq!(outgoing.fields().collect::<Vec<_>>(), vec!["71", "2", "100", ""]);
+
+    let incoming = WireMessage {
+        direction: Direction::Incoming,
+        timestamp: datetime!(2024-03-01 14:31:02.5 UTC),
+        raw: "9\x001\x0090\x00",
+    };
+    assert_eq!(TwsLogFile::format(&incoming), "14:31:02:500 <- 9-1-90-");
+}
+
+#[test]
+fn appends_to_log_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("api.100.log");
+
+    let log = TwsLogFile::create(&path).expect("error creating log file");
+    log.observe(&WireMessage {
+        direction: Direction::Incoming,
+        timestamp: datetime!(2024-03-01 14:31:02 UTC),
+        raw: "9\x001\x0090\x00",
+    });
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents, "14:31:02:000 <- 9-1-90-\n");
+}
@@ -1,15 +1,16 @@
 use std::convert::From;
 use std::fmt::Debug;
 use std::string::ToString;
+use std::time::Duration;
 
 use log::{error, info};
-use serde::Deserialize;
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use tick_types::TickType;
 
-use crate::client::DataStream;
 use crate::client::ResponseContext;
 use crate::client::Subscription;
+use crate::client::{DataStream, SubscriptionKind};
 use crate::encode_option_field;
 use crate::messages::IncomingMessages;
 use crate::messages::OutgoingMessages;
@@ -18,8 +19,21 @@ use crate::messages::ResponseMessage;
 use crate::Client;
 use crate::{server_versions, Error, ToField};
 
+pub use cache::ContractCache;
+pub use exchange::Exchange;
+pub use expiration::ExpirationDate;
+pub use hours::{TradingHours, TradingSession};
+pub use key::ContractKey;
+pub use resolver::ContractResolver;
+
+mod cache;
 pub(crate) mod decoders;
 mod encoders;
+mod exchange;
+mod expiration;
+mod hours;
+mod key;
+mod resolver;
 pub mod tick_types;
 
 #[cfg(test)]
@@ -29,7 +43,8 @@ mod tests;
 
 // Models
 
-#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// SecurityType enumerates available security types
 pub enum SecurityType {
     /// Stock (or ETF)
@@ -59,6 +74,14 @@ pub enum SecurityType {
     MutualFund,
     /// Crypto currency
     Crypto,
+    /// Contract for difference
+    Cfd,
+    /// Dutch warrant or structured product
+    StructuredProduct,
+    /// Continuous futures
+    ContinuousFuture,
+    /// Security type not known to this crate, holding the code reported by TWS.
+    Other(String),
 }
 
 impl ToField for SecurityType {
@@ -89,11 +112,28 @@ impl std::fmt::Display for SecurityType {
             SecurityType::News => write!(f, "NEWS"),
             SecurityType::MutualFund => write!(f, "FUND"),
             SecurityType::Crypto => write!(f, "CRYPTO"),
+            SecurityType::Cfd => write!(f, "CFD"),
+            SecurityType::StructuredProduct => write!(f, "IOPT"),
+            SecurityType::ContinuousFuture => write!(f, "CONTFUT"),
+            SecurityType::Other(code) => write!(f, "{code}"),
+        }
+    }
+}
+
+impl std::str::FromStr for SecurityType {
+    type Err = Error;
+
+    /// Parses a security type code, e.g. STK or FUT. Unknown codes are rejected.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match SecurityType::from(code) {
+            SecurityType::Other(code) => Err(Error::InvalidArgument(format!("unknown security type: {code}"))),
+            security_type => Ok(security_type),
         }
     }
 }
 
 impl SecurityType {
+    /// Converts a security type code as sent by TWS, e.g. STK or FUT. Unknown codes are kept as [SecurityType::Other].
     pub fn from(name: &str) -> SecurityType {
         match name {
             "STK" => SecurityType::Stock,
@@ -109,12 +149,16 @@ impl SecurityType {
             "NEWS" => SecurityType::News,
             "FUND" => SecurityType::MutualFund,
             "CRYPTO" => SecurityType::Crypto,
-            unsupported => todo!("Unimplemented security type: {unsupported}"),
+            "CFD" => SecurityType::Cfd,
+            "IOPT" => SecurityType::StructuredProduct,
+            "CONTFUT" => SecurityType::ContinuousFuture,
+            other => SecurityType::Other(other.to_string()),
         }
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Contract describes an instrument's definition
 pub struct Contract {
     /// The unique IB contract identifier.
@@ -182,6 +226,45 @@ impl Contract {
         }
     }
 
+    /// Creates bond contract from its CUSIP or ISIN.
+    /// Defaults currency to USD and exchange to SMART.
+    pub fn bond(cusip_or_isin: &str) -> Contract {
+        Contract {
+            symbol: cusip_or_isin.to_string(),
+            security_type: SecurityType::Bond,
+            currency: "USD".to_string(),
+            exchange: "SMART".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates bond contract identified by a security id, e.g. `("CUSIP", "912828C57")` or `("ISIN", "US912828C570")`.
+    /// Defaults currency to USD and exchange to SMART.
+    pub fn bond_by_security_id(security_id_type: &str, security_id: &str) -> Contract {
+        Contract {
+            security_type: SecurityType::Bond,
+            security_id_type: security_id_type.to_string(),
+            security_id: security_id.to_string(),
+            currency: "USD".to_string(),
+            exchange: "SMART".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates continuous futures contract from specified symbol and exchange.
+    ///
+    /// Continuous futures can be used for historical data and contract details but not for trading.
+    /// Use [Client::front_month_futures] to find the futures contract currently backing the series.
+    pub fn continuous_futures(symbol: &str, exchange: &str) -> Contract {
+        Contract {
+            symbol: symbol.to_string(),
+            security_type: SecurityType::ContinuousFuture,
+            exchange: exchange.to_string(),
+            currency: "USD".to_string(),
+            ..Default::default()
+        }
+    }
+
     /// Creates Crypto contract from specified symbol
     pub fn crypto(symbol: &str) -> Contract {
         Contract {
@@ -193,6 +276,82 @@ impl Contract {
         }
     }
 
+    /// Creates contract for difference on the specified underlying symbol.
+    /// Defaults currency to USD and exchange to SMART.
+    pub fn cfd(symbol: &str) -> Contract {
+        Contract {
+            symbol: symbol.to_string(),
+            security_type: SecurityType::Cfd,
+            currency: "USD".to_string(),
+            exchange: "SMART".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates index contract from specified symbol, exchange and currency.
+    ///
+    /// # Arguments
+    /// * `symbol`   - Symbol of the index, e.g. SPX.
+    /// * `exchange` - Exchange publishing the index, e.g. CBOE.
+    /// * `currency` - Currency of the index.
+    pub fn index(symbol: &str, exchange: &str, currency: &str) -> Contract {
+        Contract {
+            symbol: symbol.to_string(),
+            security_type: SecurityType::Index,
+            exchange: exchange.to_string(),
+            currency: currency.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates warrant contract. The multiplier can be set with [Contract::with_multiplier].
+    ///
+    /// # Arguments
+    /// * `symbol`          - Symbol of the underlying asset.
+    /// * `exchange`        - Exchange the warrant trades on, e.g. FWB.
+    /// * `currency`        - Currency of the warrant.
+    /// * `expiration_date` - Expiration date of the warrant (YYYYMMDD)
+    /// * `strike`          - Strike price of the warrant.
+    /// * `right`           - Warrant type: "C" for Call, "P" for Put
+    pub fn warrant(symbol: &str, exchange: &str, currency: &str, expiration_date: &str, strike: f64, right: &str) -> Contract {
+        Contract {
+            symbol: symbol.to_string(),
+            security_type: SecurityType::Warrant,
+            exchange: exchange.to_string(),
+            currency: currency.to_string(),
+            last_trade_date_or_contract_month: expiration_date.to_string(),
+            strike,
+            right: right.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates Dutch warrant or structured product (IOPT) contract. Takes the same arguments as [Contract::warrant].
+    pub fn structured_product(symbol: &str, exchange: &str, currency: &str, expiration_date: &str, strike: f64, right: &str) -> Contract {
+        Contract {
+            security_type: SecurityType::StructuredProduct,
+            ..Contract::warrant(symbol, exchange, currency, expiration_date, strike, right)
+        }
+    }
+
+    /// Creates combo (BAG) contract from the specified legs.
+    ///
+    /// # Arguments
+    /// * `symbol`   - Symbol of the combo, typically the symbol of the underlying or of the first leg.
+    /// * `exchange` - Exchange the combo is routed to, e.g. SMART.
+    /// * `currency` - Currency of the combo.
+    /// * `legs`     - Legs of the combo. See [ComboLeg::buy] and [ComboLeg::sell].
+    pub fn combo(symbol: &str, exchange: &str, currency: &str, legs: Vec<ComboLeg>) -> Contract {
+        Contract {
+            symbol: symbol.to_string(),
+            security_type: SecurityType::Spread,
+            exchange: exchange.to_string(),
+            currency: currency.to_string(),
+            combo_legs: legs,
+            ..Default::default()
+        }
+    }
+
     /// Creates News contract from specified provider code.
     pub fn news(provider_code: &str) -> Contract {
         Contract {
@@ -224,11 +383,62 @@ impl Contract {
         }
     }
 
+    /// Sets the exchange the contract is routed to, e.g. [Exchange::SMART].
+    pub fn with_exchange(mut self, exchange: impl Into<Exchange>) -> Contract {
+        self.exchange = exchange.into().into();
+        self
+    }
+
+    /// Sets the primary exchange of the contract, used to tell apart smart routed contracts listed on several exchanges.
+    pub fn with_primary_exchange(mut self, primary_exchange: impl Into<Exchange>) -> Contract {
+        self.primary_exchange = primary_exchange.into().into();
+        self
+    }
+
+    /// Sets the contract multiplier, e.g. to select between standard and mini futures.
+    pub fn with_multiplier(mut self, multiplier: &str) -> Contract {
+        self.multiplier = multiplier.to_string();
+        self
+    }
+
+    /// Sets the trading class of the contract.
+    pub fn with_trading_class(mut self, trading_class: &str) -> Contract {
+        self.trading_class = trading_class.to_string();
+        self
+    }
+
+    /// Identifies the contract by a security id, e.g. `("ISIN", "US5949181045")`, `("CUSIP", "594918104")` or `("FIGI", "BBG000BPH459")`.
+    pub fn with_security_id(mut self, security_id_type: &str, security_id: &str) -> Contract {
+        self.security_id_type = security_id_type.to_string();
+        self.security_id = security_id.to_string();
+        self
+    }
+
+    /// Sets whether requests for the contract include expired contracts.
+    ///
+    /// Contract details and historical data can only be requested for expired futures. Expired options and other instrument types are not available.
+    pub fn with_include_expired(mut self, include_expired: bool) -> Contract {
+        self.include_expired = include_expired;
+        self
+    }
+
+    /// Sets the underlying hedge of a delta-neutral combo or VOL order.
+    ///
+    /// # Arguments
+    /// * `contract_id` - Contract id of the underlying stock or future.
+    /// * `delta`       - Delta of the underlying.
+    /// * `price`       - Price of the underlying.
+    pub fn with_delta_neutral_contract(mut self, contract_id: i32, delta: f64, price: f64) -> Contract {
+        self.delta_neutral_contract = Some(DeltaNeutralContract { contract_id, delta, price });
+        self
+    }
+
     /// Is Bag request
     pub fn is_bag(&self) -> bool {
         self.security_type == SecurityType::Spread
     }
 
+    #[cfg(feature = "historical")]
     pub(crate) fn push_fields(&self, message: &mut RequestMessage) {
         message.push_field(&self.contract_id);
         message.push_field(&self.symbol);
@@ -244,9 +454,22 @@ impl Contract {
         message.push_field(&self.trading_class);
         message.push_field(&self.include_expired);
     }
+
+    // Encodes the "has delta neutral contract" marker, followed by the delta-neutral contract when present.
+    pub(crate) fn push_delta_neutral_contract(&self, message: &mut RequestMessage) {
+        if let Some(delta_neutral_contract) = &self.delta_neutral_contract {
+            message.push_field(&true);
+            message.push_field(&delta_neutral_contract.contract_id);
+            message.push_field(&delta_neutral_contract.delta);
+            message.push_field(&delta_neutral_contract.price);
+        } else {
+            message.push_field(&false);
+        }
+    }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 // ComboLeg represents a leg within combo orders.
 pub struct ComboLeg {
     /// The Contract's IB's unique id.
@@ -268,7 +491,57 @@ pub struct ComboLeg {
     pub exempt_code: i32,
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+impl Default for ComboLeg {
+    fn default() -> Self {
+        Self {
+            contract_id: 0,
+            ratio: 0,
+            action: String::default(),
+            exchange: String::default(),
+            open_close: ComboLegOpenClose::default(),
+            short_sale_slot: 0,
+            designated_location: String::default(),
+            exempt_code: -1,
+        }
+    }
+}
+
+impl ComboLeg {
+    /// Creates leg buying `ratio` units of the contract with id `contract_id`, routed to `exchange`.
+    pub fn buy(contract_id: i32, ratio: i32, exchange: &str) -> ComboLeg {
+        ComboLeg {
+            contract_id,
+            ratio,
+            action: "BUY".to_string(),
+            exchange: exchange.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates leg selling `ratio` units of the contract with id `contract_id`, routed to `exchange`.
+    pub fn sell(contract_id: i32, ratio: i32, exchange: &str) -> ComboLeg {
+        ComboLeg {
+            action: "SELL".to_string(),
+            ..ComboLeg::buy(contract_id, ratio, exchange)
+        }
+    }
+
+    /// Sets whether the leg opens or closes a position.
+    pub fn with_open_close(mut self, open_close: ComboLegOpenClose) -> ComboLeg {
+        self.open_close = open_close;
+        self
+    }
+
+    /// Sets the short sale slot of the leg: 1 for clearing broker, 2 for third party at `designated_location`.
+    pub fn with_short_sale_slot(mut self, short_sale_slot: i32, designated_location: &str) -> ComboLeg {
+        self.short_sale_slot = short_sale_slot;
+        self.designated_location = designated_location.to_string();
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// OpenClose specifies whether an order is an open or closing order.
 pub enum ComboLegOpenClose {
     /// 0 - Same as the parent security. This is the only option for retail customers.
@@ -288,20 +561,28 @@ impl ToField for ComboLegOpenClose {
     }
 }
 
+impl ComboLegOpenClose {
+    // Returns None for unsupported values, so decoders can reject them.
+    pub(crate) fn from_i32(val: i32) -> Option<Self> {
+        match val {
+            0 => Some(Self::Same),
+            1 => Some(Self::Open),
+            2 => Some(Self::Close),
+            3 => Some(Self::Unknown),
+            _ => None,
+        }
+    }
+}
+
 impl From<i32> for ComboLegOpenClose {
     // TODO - verify these values
     fn from(val: i32) -> Self {
-        match val {
-            0 => Self::Same,
-            1 => Self::Open,
-            2 => Self::Close,
-            3 => Self::Unknown,
-            _ => panic!("unsupported value: {val}"),
-        }
+        Self::from_i32(val).unwrap_or_else(|| panic!("unsupported value: {val}"))
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Delta and underlying price for Delta-Neutral combo orders.
 /// Underlying (STK or FUT), delta and underlying price goes into this attribute.
 pub struct DeltaNeutralContract {
@@ -314,7 +595,8 @@ pub struct DeltaNeutralContract {
 }
 
 /// ContractDetails provides extended contract details.
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContractDetails {
     /// A fully-defined Contract object.
     pub contract: Contract,
@@ -405,8 +687,70 @@ pub struct ContractDetails {
     pub suggested_size_increment: f64,
 }
 
+impl ContractDetails {
+    /// Security id of the given type reported for the contract, e.g. the ISIN of a stock.
+    pub fn security_id(&self, security_id_type: &str) -> Option<&str> {
+        self.sec_id_list
+            .iter()
+            .find(|tag_value| tag_value.tag.eq_ignore_ascii_case(security_id_type))
+            .map(|tag_value| tag_value.value.as_str())
+    }
+
+    /// Rounds `price` to the nearest multiple of [ContractDetails::min_tick].
+    ///
+    /// Use [ContractDetails::market_rule] for instruments whose tick size varies with price.
+    pub fn round_price_to_tick(&self, price: f64) -> f64 {
+        round_to_increment(price, self.min_tick, f64::round)
+    }
+
+    /// Rounds `size` down to a multiple of [ContractDetails::size_increment], so orders never exceed the requested size.
+    ///
+    /// Returns 0 if the rounded size is smaller than [ContractDetails::min_size].
+    pub fn round_size(&self, size: f64) -> f64 {
+        let rounded = round_to_increment(size, self.size_increment, f64::floor);
+        if rounded < self.min_size {
+            0.0
+        } else {
+            rounded
+        }
+    }
+
+    /// Market rule id in effect for the contract on `exchange`.
+    ///
+    /// [ContractDetails::market_rule_ids] are reported in the same order as [ContractDetails::valid_exchanges].
+    pub fn market_rule_id(&self, exchange: &str) -> Option<i32> {
+        let position = self.valid_exchanges.iter().position(|valid_exchange| valid_exchange == exchange)?;
+        self.market_rule_ids.get(position)?.parse().ok()
+    }
+
+    /// Requests the [MarketRule] in effect for the contract on `exchange`.
+    pub fn market_rule(&self, client: &Client, exchange: &str) -> Result<MarketRule, Error> {
+        match self.market_rule_id(exchange) {
+            Some(market_rule_id) => market_rule(client, market_rule_id, client.request_timeout),
+            None => Err(Error::InvalidArgument(format!(
+                "no market rule for {} on exchange {exchange}",
+                self.contract.symbol
+            ))),
+        }
+    }
+}
+
+// Rounds `value` to a multiple of `increment`. Values are returned unchanged if the increment is not known.
+fn round_to_increment(value: f64, increment: f64, round: fn(f64) -> f64) -> f64 {
+    if increment <= 0.0 {
+        return value;
+    }
+
+    // snap values within floating point noise of a multiple before rounding
+    let steps = ((value / increment) * 1e8).round() / 1e8;
+    let rounded = round(steps) * increment;
+    // remove floating point noise introduced by the increment
+    (rounded * 1e10).round() / 1e10
+}
+
 /// TagValue is a convenience struct to define key-value pairs.
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TagValue {
     pub tag: String,
     pub value: String,
@@ -425,6 +769,7 @@ impl ToField for Vec<TagValue> {
 /// Receives option specific market data.
 /// TWS’s options model volatility, prices, and deltas, along with the present value of dividends expected on that options underlier.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OptionComputation {
     /// Specifies the type of option computation.
     pub field: TickType,
@@ -450,6 +795,7 @@ pub struct OptionComputation {
 
 impl DataStream<OptionComputation> for OptionComputation {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::TickOptionComputation];
+    const KIND: SubscriptionKind = SubscriptionKind::OptionComputation;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -470,7 +816,9 @@ impl DataStream<OptionComputation> for OptionComputation {
     }
 }
 
-#[derive(Debug, Default)]
+/// Option chain parameters of an underlying on one exchange.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OptionChain {
     /// The contract ID of the underlying security.
     pub underlying_contract_id: i32,
@@ -486,7 +834,146 @@ pub struct OptionChain {
     pub strikes: Vec<f64>,
 }
 
+impl OptionChain {
+    /// Expands the chain into an option contract for every expiration, strike and right.
+    ///
+    /// # Arguments
+    /// * `symbol`   - Symbol of the underlying.
+    /// * `currency` - Currency the options trade in.
+    pub fn expand(&self, symbol: &str, currency: &str) -> ExpandedOptionChain {
+        let mut options = Vec::with_capacity(self.expirations.len() * self.strikes.len() * 2);
+
+        for expiration in &self.expirations {
+            for strike in &self.strikes {
+                for right in ["C", "P"] {
+                    options.push(Contract {
+                        symbol: symbol.to_owned(),
+                        security_type: SecurityType::Option,
+                        exchange: self.exchange.clone(),
+                        currency: currency.to_owned(),
+                        last_trade_date_or_contract_month: expiration.clone(),
+                        strike: *strike,
+                        right: right.to_owned(),
+                        multiplier: self.multiplier.clone(),
+                        trading_class: self.trading_class.clone(),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        ExpandedOptionChain {
+            underlying_contract_id: self.underlying_contract_id,
+            exchange: self.exchange.clone(),
+            trading_class: self.trading_class.clone(),
+            multiplier: self.multiplier.clone(),
+            options,
+        }
+    }
+
+    /// Expands the chain like [OptionChain::expand], keeping only the options confirmed by contract details.
+    ///
+    /// Contract details are requested once per expiration and the returned options are fully specified, including their
+    /// contract ids. Combinations of expiration, strike and right that are not listed are dropped.
+    pub fn expand_verified(&self, client: &Client, symbol: &str, currency: &str) -> Result<ExpandedOptionChain, Error> {
+        let mut chain = self.expand(symbol, currency);
+
+        let mut listed = Vec::new();
+        for expiration in &self.expirations {
+            let contract = Contract {
+                symbol: symbol.to_owned(),
+                security_type: SecurityType::Option,
+                exchange: self.exchange.clone(),
+                currency: currency.to_owned(),
+                last_trade_date_or_contract_month: expiration.clone(),
+                multiplier: self.multiplier.clone(),
+                trading_class: self.trading_class.clone(),
+                ..Default::default()
+            };
+            listed.extend(
+                contract_details(client, &contract, client.request_timeout)?
+                    .into_iter()
+                    .map(|details| details.contract),
+            );
+        }
+
+        chain.options = chain
+            .options
+            .iter()
+            .filter_map(|option| listed.iter().find(|contract| is_same_option(contract, option)).cloned())
+            .collect();
+
+        Ok(chain)
+    }
+}
+
+fn is_same_option(a: &Contract, b: &Contract) -> bool {
+    a.last_trade_date_or_contract_month == b.last_trade_date_or_contract_month && a.right == b.right && (a.strike - b.strike).abs() < 1e-9
+}
+
+/// Options of an [OptionChain] expanded to fully specified contracts.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpandedOptionChain {
+    /// The contract ID of the underlying security.
+    pub underlying_contract_id: i32,
+    /// Exchange the options trade on.
+    pub exchange: String,
+    /// The option trading class.
+    pub trading_class: String,
+    /// The option multiplier.
+    pub multiplier: String,
+    /// Option contracts, ordered by expiration, strike and right.
+    pub options: Vec<Contract>,
+}
+
+impl ExpandedOptionChain {
+    /// Expirations with at least one option, in chain order.
+    pub fn expirations(&self) -> Vec<&str> {
+        let mut expirations: Vec<&str> = Vec::new();
+        for option in &self.options {
+            if !expirations.contains(&option.last_trade_date_or_contract_month.as_str()) {
+                expirations.push(&option.last_trade_date_or_contract_month);
+            }
+        }
+        expirations
+    }
+
+    /// Strikes of the options expiring on `expiration`.
+    pub fn strikes(&self, expiration: &str) -> Vec<f64> {
+        let mut strikes: Vec<f64> = Vec::new();
+        for option in self
+            .options
+            .iter()
+            .filter(|option| option.last_trade_date_or_contract_month == expiration)
+        {
+            if !strikes.contains(&option.strike) {
+                strikes.push(option.strike);
+            }
+        }
+        strikes
+    }
+
+    /// Option with the given expiration, strike and right ("C" or "P").
+    pub fn option(&self, expiration: &str, strike: f64, right: &str) -> Option<&Contract> {
+        self.options
+            .iter()
+            .find(|option| option.last_trade_date_or_contract_month == expiration && option.right == right && (option.strike - strike).abs() < 1e-9)
+    }
+
+    /// Call options of the chain.
+    pub fn calls(&self) -> impl Iterator<Item = &Contract> {
+        self.options.iter().filter(|option| option.right == "C")
+    }
+
+    /// Put options of the chain.
+    pub fn puts(&self) -> impl Iterator<Item = &Contract> {
+        self.options.iter().filter(|option| option.right == "P")
+    }
+}
+
 impl DataStream<OptionChain> for OptionChain {
+    const KIND: SubscriptionKind = SubscriptionKind::OptionChain;
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<OptionChain, Error> {
         match message.message_type() {
             IncomingMessages::SecurityDefinitionOptionParameter => Ok(decoders::decode_option_chain(message)?),
@@ -496,6 +983,71 @@ impl DataStream<OptionChain> for OptionChain {
     }
 }
 
+/// Creates futures contract for the specified contract month.
+/// Defaults currency to USD.
+///
+/// The multiplier and trading class can be set with [Contract::with_multiplier] and [Contract::with_trading_class]
+/// when the symbol, exchange and month alone are ambiguous.
+///
+/// # Arguments
+/// * `symbol`         - Symbol of the futures contract, e.g. ES.
+/// * `exchange`       - Exchange the contract trades on, e.g. CME.
+/// * `contract_month` - Contract month (YYYYMM) or last trading day (YYYYMMDD).
+///
+/// # Examples
+///
+/// ```
+/// use ibapi::contracts;
+///
+/// let contract = contracts::futures("ES", "CME", "202412").with_multiplier("50");
+/// ```
+pub fn futures(symbol: &str, exchange: &str, contract_month: &str) -> Contract {
+    Contract {
+        exchange: exchange.to_string(),
+        last_trade_date_or_contract_month: contract_month.to_string(),
+        ..Contract::futures(symbol)
+    }
+}
+
+/// Creates a stock contract identified by its ISIN, routed to SMART.
+///
+/// The currency and symbol are left blank so that [Client::contract_details] resolves them.
+/// Set [Contract::security_type] to look up other instruments.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::contracts;
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let details = client.contract_details(&contracts::by_isin("US5949181045")).expect("request failed");
+/// println!("symbol: {}", details[0].contract.symbol);
+/// ```
+pub fn by_isin(isin: &str) -> Contract {
+    by_security_id("ISIN", isin)
+}
+
+/// Creates a stock contract identified by its CUSIP, routed to SMART. See [by_isin].
+pub fn by_cusip(cusip: &str) -> Contract {
+    by_security_id("CUSIP", cusip)
+}
+
+/// Creates a stock contract identified by its FIGI, routed to SMART. See [by_isin].
+pub fn by_figi(figi: &str) -> Contract {
+    by_security_id("FIGI", figi)
+}
+
+fn by_security_id(security_id_type: &str, security_id: &str) -> Contract {
+    Contract {
+        security_type: SecurityType::Stock,
+        exchange: "SMART".to_string(),
+        ..Contract::default()
+    }
+    .with_security_id(security_id_type, security_id)
+}
+
 // === API ===
 
 // Requests contract information.
@@ -505,7 +1057,8 @@ impl DataStream<OptionChain> for OptionChain {
 // # Arguments
 // * `client` - [Client] with an active connection to gateway.
 // * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
-pub(super) fn contract_details(client: &Client, contract: &Contract) -> Result<Vec<ContractDetails>, Error> {
+// * `timeout` - Maximum time to wait for each response from TWS.
+pub(super) fn contract_details(client: &Client, contract: &Contract, timeout: Option<Duration>) -> Result<Vec<ContractDetails>, Error> {
     verify_contract(client, contract)?;
 
     let request_id = client.next_request_id();
@@ -516,18 +1069,28 @@ pub(super) fn contract_details(client: &Client, contract: &Contract) -> Result<V
     let mut contract_details: Vec<ContractDetails> = Vec::default();
 
     // TODO create iterator
-    while let Some(Ok(mut message)) = responses.next() {
+    loop {
+        // only the end marker completes the list, anything else would return a partial list
+        let mut message = match responses.next_within(timeout) {
+            Some(Ok(message)) => message,
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::UnexpectedEndOfStream),
+        };
+
         match message.message_type() {
             IncomingMessages::ContractData => {
                 let decoded = decoders::decode_contract_details(client.server_version(), &mut message)?;
                 contract_details.push(decoded);
             }
+            IncomingMessages::BondContractData => {
+                let decoded = decoders::decode_bond_contract_details(client.server_version(), &mut message)?;
+                contract_details.push(decoded);
+            }
             IncomingMessages::ContractDataEnd => {
                 break;
             }
             IncomingMessages::Error => {
-                error!("error: {message:?}");
-                return Err(Error::Simple(format!("contract_details {message:?}")));
+                return Err(Error::from_response(message));
             }
             _ => {
                 error!("unexpected message: {:?}", message);
@@ -538,7 +1101,45 @@ pub(super) fn contract_details(client: &Client, contract: &Contract) -> Result<V
     Ok(contract_details)
 }
 
+// Resolves a continuous futures contract to the futures contract currently backing the series.
+pub(super) fn front_month_futures(client: &Client, contract: &Contract) -> Result<Contract, Error> {
+    let continuous = Contract {
+        security_type: SecurityType::ContinuousFuture,
+        ..contract.clone()
+    };
+
+    let front = contract_details(client, &continuous, client.request_timeout)?
+        .into_iter()
+        .map(|details| details.contract)
+        .min_by(|a, b| a.last_trade_date_or_contract_month.cmp(&b.last_trade_date_or_contract_month));
+
+    match front {
+        Some(front) => Ok(Contract {
+            security_type: SecurityType::Future,
+            ..front
+        }),
+        None => Err(Error::ContractNotFound(format!("continuous futures {}", contract.symbol))),
+    }
+}
+
+// Lists the contracts matching `contract`, including expired contracts, ordered by last trade date.
+pub(super) fn expired_contracts(client: &Client, contract: &Contract) -> Result<Vec<Contract>, Error> {
+    let contract = contract.clone().with_include_expired(true);
+
+    let mut contracts: Vec<Contract> = contract_details(client, &contract, client.request_timeout)?
+        .into_iter()
+        .map(|details| details.contract.with_include_expired(true))
+        .collect();
+    contracts.sort_by(|a, b| a.last_trade_date_or_contract_month.cmp(&b.last_trade_date_or_contract_month));
+
+    Ok(contracts)
+}
+
 fn verify_contract(client: &Client, contract: &Contract) -> Result<(), Error> {
+    for exchange in [&contract.exchange, &contract.primary_exchange] {
+        Exchange::validate(exchange)?;
+    }
+
     if !contract.security_id_type.is_empty() || !contract.security_id.is_empty() {
         client.check_server_version(
             server_versions::SEC_ID_TYPE,
@@ -571,9 +1172,12 @@ fn verify_contract(client: &Client, contract: &Contract) -> Result<(), Error> {
 }
 
 /// Contract data and list of derivative security types
-#[derive(Debug)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContractDescription {
+    /// The matching contract. Only contract id, symbol, security type, primary exchange and currency are populated.
     pub contract: Contract,
+    /// Security types of the derivatives available on the contract, e.g. OPT or WAR.
     pub derivative_security_types: Vec<String>,
 }
 
@@ -582,22 +1186,26 @@ pub struct ContractDescription {
 // # Arguments
 // * `client` - [Client] with an active connection to gateway.
 // * `pattern` - Either start of ticker symbol or (for larger strings) company name.
-pub(super) fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<ContractDescription>, Error> {
+// * `timeout` - Maximum time to wait for the response from TWS.
+pub(super) fn matching_symbols(client: &Client, pattern: &str, timeout: Option<Duration>) -> Result<Vec<ContractDescription>, Error> {
     client.check_server_version(server_versions::REQ_MATCHING_SYMBOLS, "It does not support matching symbols requests.")?;
 
     let request_id = client.next_request_id();
     let request = encoders::encode_request_matching_symbols(request_id, pattern)?;
     let subscription = client.send_request(request_id, request)?;
 
-    if let Some(Ok(mut message)) = subscription.next() {
+    let response = subscription.next_within(timeout);
+    if let Some(Err(Error::Timeout)) = response {
+        return Err(Error::Timeout);
+    }
+
+    if let Some(Ok(mut message)) = response {
         match message.message_type() {
             IncomingMessages::SymbolSamples => {
                 return decoders::decode_contract_descriptions(client.server_version(), &mut message);
             }
             IncomingMessages::Error => {
-                // TODO custom error
-                error!("unexpected error: {:?}", message);
-                return Err(Error::Simple(format!("unexpected error: {message:?}")));
+                return Err(Error::from_response(message));
             }
             _ => {
                 info!("unexpected message: {:?}", message);
@@ -609,7 +1217,8 @@ pub(super) fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<Con
     Ok(Vec::default())
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Minimum price increment structure for a particular market rule ID.
 pub struct MarketRule {
     /// Market Rule ID requested.
@@ -618,9 +1227,37 @@ pub struct MarketRule {
     pub price_increments: Vec<PriceIncrement>,
 }
 
-#[derive(Debug, Default)]
+impl MarketRule {
+    /// Minimum price increment at `price`, or None if the rule has no price increments.
+    pub fn increment_at(&self, price: f64) -> Option<f64> {
+        let price = price.abs();
+
+        let mut increment = self.price_increments.first()?.increment;
+        for price_increment in &self.price_increments {
+            if price_increment.low_edge <= price {
+                increment = price_increment.increment;
+            }
+        }
+
+        Some(increment)
+    }
+
+    /// Rounds `price` to the nearest valid price under the rule.
+    pub fn round_price(&self, price: f64) -> f64 {
+        match self.increment_at(price) {
+            Some(increment) => round_to_increment(price, increment, f64::round),
+            None => price,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// Price increment that applies from a price level upwards.
 pub struct PriceIncrement {
+    /// Lowest price the increment applies to.
     pub low_edge: f64,
+    /// Minimum price increment.
     pub increment: f64,
 }
 
@@ -628,13 +1265,13 @@ pub struct PriceIncrement {
 //
 // The market rule for an instrument on a particular exchange provides details about how the minimum price increment changes with price.
 // A list of market rule ids can be obtained by invoking [request_contract_details] on a particular contract. The returned market rule ID list will provide the market rule ID for the instrument in the correspond valid exchange list in [ContractDetails].
-pub(super) fn market_rule(client: &Client, market_rule_id: i32) -> Result<MarketRule, Error> {
+pub(super) fn market_rule(client: &Client, market_rule_id: i32, timeout: Option<Duration>) -> Result<MarketRule, Error> {
     client.check_server_version(server_versions::MARKET_RULES, "It does not support market rule requests.")?;
 
     let request = encoders::encode_request_market_rule(market_rule_id)?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestMarketRule, request)?;
 
-    match subscription.next() {
+    match subscription.next_within(timeout) {
         Some(Ok(mut message)) => Ok(decoders::decode_market_rule(&mut message)?),
         Some(Err(e)) => Err(e),
         None => Err(Error::Simple("no market rule found".into())),
@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use time::{Date, Duration, Month, OffsetDateTime};
+
+use super::{decoders, Contract, ContractDetails, SecurityType};
+use crate::messages::ResponseMessage;
+use crate::{server_versions, Client, Error};
+
+#[cfg(test)]
+mod tests;
+
+// Cached records are stored in the layout of the contract data messages of this server version.
+const RECORD_SERVER_VERSION: i32 = server_versions::SIZE_RULES;
+
+const CONTRACT_DATA: &str = "10";
+const BOND_CONTRACT_DATA: &str = "18";
+
+#[derive(Clone, Debug)]
+struct CachedDetails {
+    details: ContractDetails,
+    fetched_at: OffsetDateTime,
+}
+
+#[derive(Clone, Debug)]
+struct CachedQuery {
+    contract_ids: Vec<i32>,
+    fetched_at: OffsetDateTime,
+}
+
+/// Caches [ContractDetails] by contract id, in memory and optionally on disk, so repeated lookups do not hit TWS.
+///
+/// Entries expire after a time to live. Details of derivatives change as they approach expiry, so entries for options,
+/// futures and warrants expiring within the expiry window are always refreshed.
+///
+/// ```no_run
+/// use ibapi::contracts::{Contract, ContractCache};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut cache = ContractCache::with_file("contracts.cache").expect("error opening contract cache");
+///
+/// let contract = Contract::stock("AAPL");
+/// let details = cache.contract_details(&client, &contract).expect("error requesting contract details");
+/// // served from the cache
+/// let details = cache.contract_details(&client, &contract).expect("error requesting contract details");
+/// ```
+#[derive(Debug)]
+pub struct ContractCache {
+    ttl: Duration,
+    expiry_window: Duration,
+    path: Option<PathBuf>,
+    details: HashMap<i32, CachedDetails>,
+    queries: HashMap<String, CachedQuery>,
+}
+
+impl Default for ContractCache {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::days(1),
+            expiry_window: Duration::days(7),
+            path: None,
+            details: HashMap::new(),
+            queries: HashMap::new(),
+        }
+    }
+}
+
+impl ContractCache {
+    /// Creates an in memory cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a cache persisted to the file at `path`, loading any entries already in the file.
+    pub fn with_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let mut cache = Self {
+            path: Some(path.to_path_buf()),
+            ..Self::default()
+        };
+        if path.exists() {
+            cache.load(path)?;
+        }
+
+        Ok(cache)
+    }
+
+    /// Sets how long entries are served from the cache. Defaults to one day.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how close to expiry derivative entries stop being served from the cache. Defaults to seven days.
+    pub fn with_expiry_window(mut self, window: Duration) -> Self {
+        self.expiry_window = window;
+        self
+    }
+
+    /// Number of contracts in the cache, including expired entries.
+    pub fn len(&self) -> usize {
+        self.details.len()
+    }
+
+    /// Returns true if the cache holds no contracts.
+    pub fn is_empty(&self) -> bool {
+        self.details.is_empty()
+    }
+
+    /// Returns the cached details for `contract_id`, unless the entry has expired.
+    pub fn get(&self, contract_id: i32) -> Option<&ContractDetails> {
+        self.get_at(OffsetDateTime::now_utc(), contract_id)
+    }
+
+    fn get_at(&self, now: OffsetDateTime, contract_id: i32) -> Option<&ContractDetails> {
+        match self.details.get(&contract_id) {
+            Some(entry) if self.is_fresh(now, entry) => Some(&entry.details),
+            _ => None,
+        }
+    }
+
+    /// Adds `details` to the cache.
+    pub fn insert(&mut self, details: ContractDetails) {
+        self.insert_at(OffsetDateTime::now_utc(), details);
+    }
+
+    fn insert_at(&mut self, fetched_at: OffsetDateTime, details: ContractDetails) {
+        self.details.insert(details.contract.contract_id, CachedDetails { details, fetched_at });
+    }
+
+    /// Removes the entry for `contract_id`, and any lookups that resolved to it.
+    pub fn invalidate(&mut self, contract_id: i32) {
+        self.details.remove(&contract_id);
+        self.queries.retain(|_, query| !query.contract_ids.contains(&contract_id));
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&mut self) {
+        self.details.clear();
+        self.queries.clear();
+    }
+
+    /// Returns the details of the contracts matching `contract`, requesting them from TWS if they are not cached.
+    ///
+    /// Contracts with a contract id are looked up by id; otherwise the contract's other fields identify the lookup.
+    /// When the cache is backed by a file, the file is updated after each request to TWS.
+    pub fn contract_details(&mut self, client: &Client, contract: &Contract) -> Result<Vec<ContractDetails>, Error> {
+        let now = OffsetDateTime::now_utc();
+
+        if let Some(details) = self.lookup_at(now, contract) {
+            return Ok(details);
+        }
+
+        let details = super::contract_details(client, contract, client.request_timeout)?;
+        self.store_at(now, contract, &details);
+
+        if let Some(path) = &self.path {
+            self.save(path)?;
+        }
+
+        Ok(details)
+    }
+
+    fn lookup_at(&self, now: OffsetDateTime, contract: &Contract) -> Option<Vec<ContractDetails>> {
+        if contract.contract_id > 0 {
+            return self.get_at(now, contract.contract_id).map(|details| vec![details.clone()]);
+        }
+
+        let query = self.queries.get(&query_key(contract))?;
+        if now - query.fetched_at > self.ttl {
+            return None;
+        }
+
+        query
+            .contract_ids
+            .iter()
+            .map(|contract_id| self.get_at(now, *contract_id).cloned())
+            .collect()
+    }
+
+    fn store_at(&mut self, now: OffsetDateTime, contract: &Contract, details: &[ContractDetails]) {
+        if contract.contract_id <= 0 {
+            let query = CachedQuery {
+                contract_ids: details.iter().map(|details| details.contract.contract_id).collect(),
+                fetched_at: now,
+            };
+            self.queries.insert(query_key(contract), query);
+        }
+
+        for details in details {
+            self.insert_at(now, details.clone());
+        }
+    }
+
+    fn is_fresh(&self, now: OffsetDateTime, entry: &CachedDetails) -> bool {
+        if now - entry.fetched_at > self.ttl {
+            return false;
+        }
+
+        match expiration(&entry.details) {
+            Some(expiration) if is_derivative(&entry.details.contract.security_type) => expiration - now.date() > self.expiry_window,
+            _ => true,
+        }
+    }
+
+    /// Writes the cache to the file at `path`.
+    ///
+    /// Contracts are stored one per line in the layout of the TWS contract data messages.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let staging = path.with_extension("tmp");
+
+        let mut writer = BufWriter::new(File::create(&staging)?);
+        for entry in self.details.values() {
+            writeln!(writer, "D\t{}\t{}", entry.fetched_at.unix_timestamp(), encode_record(&entry.details))?;
+        }
+        for (key, query) in &self.queries {
+            let contract_ids: Vec<String> = query.contract_ids.iter().map(|id| id.to_string()).collect();
+            writeln!(writer, "Q\t{}\t{}\t{}", query.fetched_at.unix_timestamp(), key, contract_ids.join(","))?;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        fs::rename(&staging, path)?;
+
+        Ok(())
+    }
+
+    fn load(&mut self, path: &Path) -> Result<(), Error> {
+        let reader = BufReader::new(File::open(path)?);
+
+        for line in reader.lines() {
+            let line = line?;
+            let fields: Vec<&str> = line.split('\t').collect();
+
+            match fields.as_slice() {
+                ["D", fetched_at, record] => {
+                    let details = decode_record(record)?;
+                    self.insert_at(parse_timestamp(fetched_at)?, details);
+                }
+                ["Q", fetched_at, key, contract_ids] => {
+                    let contract_ids = if contract_ids.is_empty() {
+                        Vec::new()
+                    } else {
+                        contract_ids.split(',').map(|id| id.parse()).collect::<Result<Vec<i32>, _>>()?
+                    };
+                    let query = CachedQuery {
+                        contract_ids,
+                        fetched_at: parse_timestamp(fetched_at)?,
+                    };
+                    self.queries.insert(key.to_string(), query);
+                }
+                [""] => {}
+                _ => {
+                    return Err(Error::InvalidFormat {
+                        kind: "contract cache entry",
+                        reason: line.to_owned(),
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_timestamp(timestamp: &str) -> Result<OffsetDateTime, Error> {
+    OffsetDateTime::from_unix_timestamp(timestamp.parse()?).map_err(|err| Error::InvalidFormat {
+        kind: "contract cache timestamp",
+        reason: err.to_string(),
+    })
+}
+
+fn query_key(contract: &Contract) -> String {
+    [
+        contract.symbol.clone(),
+        contract.security_type.to_string(),
+        contract.last_trade_date_or_contract_month.clone(),
+        contract.strike.to_string(),
+        contract.right.clone(),
+        contract.multiplier.clone(),
+        contract.exchange.clone(),
+        contract.primary_exchange.clone(),
+        contract.currency.clone(),
+        contract.local_symbol.clone(),
+        contract.trading_class.clone(),
+        contract.include_expired.to_string(),
+        contract.security_id_type.clone(),
+        contract.security_id.clone(),
+    ]
+    .join("\x00")
+}
+
+fn is_derivative(security_type: &SecurityType) -> bool {
+    matches!(
+        security_type,
+        SecurityType::Option
+            | SecurityType::Future
+            | SecurityType::FuturesOption
+            | SecurityType::Warrant
+            | SecurityType::StructuredProduct
+            | SecurityType::ContinuousFuture
+    )
+}
+
+// Expiration date of the contract. Contract months are taken as the first day of the month.
+fn expiration(details: &ContractDetails) -> Option<Date> {
+    let date = if details.real_expiration_date.is_empty() {
+        &details.contract.last_trade_date_or_contract_month
+    } else {
+        &details.real_expiration_date
+    };
+
+    let year = date.get(0..4)?.parse().ok()?;
+    let month = Month::try_from(date.get(4..6)?.parse::<u8>().ok()?).ok()?;
+    let day = date.get(6..8).and_then(|day| day.parse().ok()).unwrap_or(1);
+
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+fn encode_record(details: &ContractDetails) -> String {
+    let fields = if details.contract.security_type == SecurityType::Bond {
+        encode_bond_fields(details)
+    } else {
+        encode_fields(details)
+    };
+    fields.join("\x00")
+}
+
+fn decode_record(record: &str) -> Result<ContractDetails, Error> {
+    let mut message = ResponseMessage::from(record);
+
+    match record.split('\x00').next() {
+        Some(BOND_CONTRACT_DATA) => decoders::decode_bond_contract_details(RECORD_SERVER_VERSION, &mut message),
+        _ => decoders::decode_contract_details(RECORD_SERVER_VERSION, &mut message),
+    }
+}
+
+fn join_trade_date(parts: &[&str]) -> String {
+    let parts: Vec<&str> = parts.iter().copied().take_while(|part| !part.is_empty()).collect();
+    parts.join(" ")
+}
+
+fn encode_sec_ids(details: &ContractDetails, fields: &mut Vec<String>) {
+    fields.push(details.sec_id_list.len().to_string());
+    for tag_value in &details.sec_id_list {
+        fields.push(tag_value.tag.clone());
+        fields.push(tag_value.value.clone());
+    }
+}
+
+fn encode_bool(value: bool) -> String {
+    if value { "1" } else { "0" }.to_string()
+}
+
+fn encode_fields(details: &ContractDetails) -> Vec<String> {
+    let contract = &details.contract;
+
+    let mut fields = vec![
+        CONTRACT_DATA.to_string(),
+        "-1".to_string(),
+        contract.symbol.clone(),
+        contract.security_type.to_string(),
+        join_trade_date(&[&contract.last_trade_date_or_contract_month, &details.last_trade_time]),
+        contract.strike.to_string(),
+        contract.right.clone(),
+        contract.exchange.clone(),
+        contract.currency.clone(),
+        contract.local_symbol.clone(),
+        details.market_name.clone(),
+        contract.trading_class.clone(),
+        contract.contract_id.to_string(),
+        details.min_tick.to_string(),
+        contract.multiplier.clone(),
+        details.order_types.join(","),
+        details.valid_exchanges.join(","),
+        details.price_magnifier.to_string(),
+        details.under_contract_id.to_string(),
+        details.long_name.clone(),
+        contract.primary_exchange.clone(),
+        details.contract_month.clone(),
+        details.industry.clone(),
+        details.category.clone(),
+        details.subcategory.clone(),
+        details.time_zone_id.clone(),
+        details.trading_hours.join(";"),
+        details.liquid_hours.join(";"),
+        details.ev_rule.clone(),
+        details.ev_multiplier.to_string(),
+    ];
+    encode_sec_ids(details, &mut fields);
+    fields.extend([
+        details.agg_group.to_string(),
+        details.under_symbol.clone(),
+        details.under_security_type.clone(),
+        details.market_rule_ids.join(","),
+        details.real_expiration_date.clone(),
+        details.stock_type.clone(),
+        details.min_size.to_string(),
+        details.size_increment.to_string(),
+        details.suggested_size_increment.to_string(),
+    ]);
+
+    fields
+}
+
+fn encode_bond_fields(details: &ContractDetails) -> Vec<String> {
+    let contract = &details.contract;
+
+    let mut fields = vec![
+        BOND_CONTRACT_DATA.to_string(),
+        "-1".to_string(),
+        contract.symbol.clone(),
+        contract.security_type.to_string(),
+        details.cusip.clone(),
+        details.coupon.to_string(),
+        join_trade_date(&[&details.maturity, &details.last_trade_time, &details.time_zone_id]),
+        details.issue_date.clone(),
+        details.ratings.clone(),
+        details.bond_type.clone(),
+        details.coupon_type.clone(),
+        encode_bool(details.convertible),
+        encode_bool(details.callable),
+        encode_bool(details.putable),
+        details.desc_append.clone(),
+        contract.exchange.clone(),
+        contract.currency.clone(),
+        details.market_name.clone(),
+        contract.trading_class.clone(),
+        contract.contract_id.to_string(),
+        details.min_tick.to_string(),
+        details.order_types.join(","),
+        details.valid_exchanges.join(","),
+        details.next_option_date.clone(),
+        details.next_option_type.clone(),
+        encode_bool(details.next_option_partial),
+        details.notes.clone(),
+        details.long_name.clone(),
+        details.ev_rule.clone(),
+        details.ev_multiplier.to_string(),
+    ];
+    encode_sec_ids(details, &mut fields);
+    fields.extend([
+        details.agg_group.to_string(),
+        details.market_rule_ids.join(","),
+        details.min_size.to_string(),
+        details.size_increment.to_string(),
+        details.suggested_size_increment.to_string(),
+    ]);
+
+    fields
+}
@@ -0,0 +1,105 @@
+use std::sync::{Arc, RwLock};
+
+use time::macros::datetime;
+
+use super::*;
+use crate::stubs::MessageBusStub;
+
+const STOCK_RECORD: &str = "10|9001|TSLA|STK||0||SMART|USD|TSLA|NMS|NMS|76792991|0.01||LMT,MKT|SMART,AMEX|1|0|TESLA INC|NASDAQ||Consumer, Cyclical|Auto Manufacturers|Auto-Cars/Light Trucks|US/Eastern|20221229:0400-20221229:2000;20221230:0400-20221230:2000|20221229:0930-20221229:1600|||1|ISIN|US88160R1014|1|||26,26||COMMON|1|1|100|";
+const FUTURE_RECORD: &str = "10|9001|ES|FUT|20231215 08:30|0||CME|USD|ESZ3|ES|ES|495512563|0.25|50|LMT,MKT|CME,QBALGO|1|11004968|E-mini S&P 500||||||US/Central|||||0|1|ES|IND|67|20231215||1|1|1|";
+const BOND_RECORD: &str = "18|9000|IBCID12345|BOND|912828C57|2.25|20240331-16:00-US/Eastern|20170331|AAA|GOVT|FIXED|0|1|0|US T-Note|SMART|USD|US Treasury|IBCID|123456789|0.0001|LMT,MKT|SMART,BONDDESK|20230331|CALL|0|callable at par|UNITED STATES TREASURY|||1|CUSIP|912828C57|0|26|1000|1000|1000|";
+
+fn decode(record: &str) -> ContractDetails {
+    let record = record.replace('|', "\x00");
+    decode_record(&record).expect("error decoding record")
+}
+
+#[test]
+fn encode_and_decode_records() {
+    for record in [STOCK_RECORD, FUTURE_RECORD, BOND_RECORD] {
+        let details = decode(record);
+        let encoded = encode_record(&details);
+        let decoded = decode_record(&encoded).expect("error decoding encoded record");
+
+        assert_eq!(decoded, details, "record {record}");
+    }
+
+    let bond = decode(BOND_RECORD);
+    assert_eq!(bond.maturity, "20240331", "bond.maturity");
+    assert_eq!(bond.time_zone_id, "US/Eastern", "bond.time_zone_id");
+    assert!(bond.callable, "bond.callable");
+}
+
+#[test]
+fn expires_entries_after_ttl() {
+    let fetched_at = datetime!(2023-11-01 10:00 UTC);
+
+    let mut cache = ContractCache::new().with_ttl(Duration::hours(12));
+    cache.insert_at(fetched_at, decode(STOCK_RECORD));
+
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get_at(fetched_at + Duration::hours(11), 76792991).is_some(), "fresh entry");
+    assert!(cache.get_at(fetched_at + Duration::hours(13), 76792991).is_none(), "expired entry");
+    assert!(cache.get_at(fetched_at, 1).is_none(), "unknown contract");
+}
+
+#[test]
+fn refreshes_derivatives_near_expiry() {
+    let cache = {
+        let mut cache = ContractCache::new().with_ttl(Duration::days(30)).with_expiry_window(Duration::days(7));
+        cache.insert_at(datetime!(2023-11-20 10:00 UTC), decode(FUTURE_RECORD));
+        cache
+    };
+
+    assert!(cache.get_at(datetime!(2023-11-21 10:00 UTC), 495512563).is_some(), "far from expiry");
+    assert!(cache.get_at(datetime!(2023-12-10 10:00 UTC), 495512563).is_none(), "near expiry");
+}
+
+#[test]
+fn invalidate_removes_lookups() {
+    let now = datetime!(2023-11-01 10:00 UTC);
+    let contract = Contract::stock("TSLA");
+
+    let mut cache = ContractCache::new();
+    cache.store_at(now, &contract, &[decode(STOCK_RECORD)]);
+
+    let details = cache.lookup_at(now, &contract).expect("lookup should be cached");
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].contract.contract_id, 76792991);
+
+    cache.invalidate(76792991);
+
+    assert!(cache.is_empty());
+    assert!(cache.lookup_at(now, &contract).is_none(), "lookup after invalidate");
+}
+
+#[test]
+fn serves_repeated_lookups_from_cache() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![STOCK_RECORD.to_string(), "52|1|9001||".to_string()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("contracts.cache");
+
+    let contract = Contract::stock("TSLA");
+
+    let mut cache = ContractCache::with_file(&path).expect("error creating cache");
+    let first = cache.contract_details(&client, &contract).expect("error requesting contract details");
+    let second = cache.contract_details(&client, &contract).expect("error requesting contract details");
+
+    assert_eq!(first, second);
+    assert_eq!(client.message_bus.request_messages().len(), 1, "requests sent to TWS");
+
+    let by_id = Contract {
+        contract_id: 76792991,
+        ..Contract::default()
+    };
+    let reopened = ContractCache::with_file(&path).expect("error reopening cache");
+    assert_eq!(reopened.len(), 1);
+    assert_eq!(reopened.lookup_at(OffsetDateTime::now_utc(), &by_id), Some(first.clone()));
+    assert_eq!(reopened.lookup_at(OffsetDateTime::now_utc(), &contract), Some(first));
+}
@@ -3,6 +3,7 @@ use super::*;
 // https://github.com/InteractiveBrokers/tws-api/blob/5cb24aea5cef9d315985a7b13dea7efbcfe2b16a/samples/CSharp/Testbed/ContractSamples.cs
 
 // Future contracts also require an expiration date but are less complicated than options.
+#[cfg(feature = "market-data")]
 pub fn simple_future() -> Contract {
     Contract {
         symbol: "GBL".to_owned(),
@@ -27,6 +28,7 @@ pub fn future_with_local_symbol() -> Contract {
     }
 }
 
+#[allow(dead_code)]
 pub fn future_with_multiplier() -> Contract {
     Contract {
         symbol: "DAX".to_owned(),
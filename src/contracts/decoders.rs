@@ -98,15 +98,96 @@ pub(super) fn decode_contract_details(server_version: i32, message: &mut Respons
     Ok(contract)
 }
 
+pub(super) fn decode_bond_contract_details(server_version: i32, message: &mut ResponseMessage) -> Result<ContractDetails, Error> {
+    message.skip(); // message type
+
+    let mut message_version = 6;
+    if server_version < server_versions::SIZE_RULES {
+        message_version = message.next_int()?;
+    }
+
+    if message_version >= 3 {
+        // request id
+        message.skip();
+    }
+
+    let mut contract = ContractDetails::default();
+
+    contract.contract.symbol = message.next_string()?;
+    contract.contract.security_type = SecurityType::from(&message.next_string()?);
+    contract.cusip = message.next_string()?;
+    contract.coupon = message.next_double()?;
+    read_last_trade_date(&mut contract, &message.next_string()?, true)?;
+    contract.issue_date = message.next_string()?;
+    contract.ratings = message.next_string()?;
+    contract.bond_type = message.next_string()?;
+    contract.coupon_type = message.next_string()?;
+    contract.convertible = message.next_bool()?;
+    contract.callable = message.next_bool()?;
+    contract.putable = message.next_bool()?;
+    contract.desc_append = message.next_string()?;
+    contract.contract.exchange = message.next_string()?;
+    contract.contract.currency = message.next_string()?;
+    contract.market_name = message.next_string()?;
+    contract.contract.trading_class = message.next_string()?;
+    contract.contract.contract_id = message.next_int()?;
+    contract.min_tick = message.next_double()?;
+    if (server_versions::MD_SIZE_MULTIPLIER..server_versions::SIZE_RULES).contains(&server_version) {
+        message.next_int()?; // mdSizeMultiplier no longer used
+    }
+    contract.order_types = split_to_vec(&message.next_string()?);
+    contract.valid_exchanges = split_to_vec(&message.next_string()?);
+    if message_version >= 2 {
+        contract.next_option_date = message.next_string()?;
+        contract.next_option_type = message.next_string()?;
+        contract.next_option_partial = message.next_bool()?;
+        contract.notes = message.next_string()?;
+    }
+    if message_version >= 4 {
+        contract.long_name = message.next_string()?;
+    }
+    if message_version >= 6 {
+        contract.ev_rule = message.next_string()?;
+        contract.ev_multiplier = message.next_double()?;
+    }
+    if message_version >= 5 {
+        let sec_id_list_count = message.next_int()?;
+        for _ in 0..sec_id_list_count {
+            let tag = message.next_string()?;
+            let value = message.next_string()?;
+            contract.sec_id_list.push(TagValue { tag, value });
+        }
+    }
+    if server_version >= server_versions::AGG_GROUP {
+        contract.agg_group = message.next_int()?;
+    }
+    if server_version >= server_versions::MARKET_RULES {
+        contract.market_rule_ids = split_to_vec(&message.next_string()?);
+    }
+    if server_version >= server_versions::SIZE_RULES {
+        contract.min_size = message.next_double()?;
+        contract.size_increment = message.next_double()?;
+        contract.suggested_size_increment = message.next_double()?;
+    }
+
+    Ok(contract)
+}
+
 fn split_hours(hours: &str) -> Vec<String> {
+    if hours.is_empty() {
+        return Vec::default();
+    }
     hours.split(";").map(|s| s.to_string()).collect()
 }
 
 fn split_to_vec(s: &str) -> Vec<String> {
+    if s.is_empty() {
+        return Vec::default();
+    }
     s.split(",").map(|s| s.to_string()).collect()
 }
 
-fn read_last_trade_date(contract: &mut ContractDetails, last_trade_date_or_contract_month: &str, is_bond: bool) -> Result<(), Error> {
+pub(super) fn read_last_trade_date(contract: &mut ContractDetails, last_trade_date_or_contract_month: &str, is_bond: bool) -> Result<(), Error> {
     if last_trade_date_or_contract_month.is_empty() {
         return Ok(());
     }
@@ -145,7 +226,7 @@ pub(super) fn decode_contract_descriptions(server_version: i32, message: &mut Re
         return Ok(Vec::default());
     }
 
-    let mut contract_descriptions: Vec<ContractDescription> = Vec::with_capacity(contract_descriptions_count as usize);
+    let mut contract_descriptions: Vec<ContractDescription> = Vec::new();
 
     for _ in 0..contract_descriptions_count {
         let mut contract = Contract {
@@ -158,7 +239,7 @@ pub(super) fn decode_contract_descriptions(server_version: i32, message: &mut Re
         };
 
         let derivative_security_types_count = message.next_int()?;
-        let mut derivative_security_types: Vec<String> = Vec::with_capacity(derivative_security_types_count as usize);
+        let mut derivative_security_types: Vec<String> = Vec::new();
         for _ in 0..derivative_security_types_count {
             derivative_security_types.push(message.next_string()?);
         }
@@ -213,7 +294,7 @@ pub(crate) fn decode_option_computation(server_version: i32, message: &mut Respo
     };
 
     if server_version >= server_versions::PRICE_BASED_VOLATILITY {
-        computation.tick_attribute = Some(message.next_int()?);
+        computation.tick_attribute = message.next_optional_int()?;
     }
 
     computation.implied_volatility = next_optional_double(message, -1.0)?;
@@ -234,13 +315,10 @@ pub(crate) fn decode_option_computation(server_version: i32, message: &mut Respo
     Ok(computation)
 }
 
+// Values are absent if unset or equal to `none_value`, e.g. -1 for prices.
 fn next_optional_double(message: &mut ResponseMessage, none_value: f64) -> Result<Option<f64>, Error> {
-    let value = message.next_double()?;
-    if value == none_value {
-        Ok(None)
-    } else {
-        Ok(Some(value))
-    }
+    let value = message.next_optional_double()?;
+    Ok(value.filter(|value| *value != none_value))
 }
 
 pub(super) fn decode_option_chain(message: &mut ResponseMessage) -> Result<OptionChain, Error> {
@@ -256,13 +334,11 @@ pub(super) fn decode_option_chain(message: &mut ResponseMessage) -> Result<Optio
     };
 
     let expirations_count = message.next_int()?;
-    option_chain.expirations.reserve(expirations_count as usize);
     for _ in 0..expirations_count {
         option_chain.expirations.push(message.next_string()?);
     }
 
     let strikes_count = message.next_int()?;
-    option_chain.strikes.reserve(strikes_count as usize);
     for _ in 0..strikes_count {
         option_chain.strikes.push(message.next_double()?);
     }
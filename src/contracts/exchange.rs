@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Name of an exchange or routing destination, e.g. SMART or CME.
+///
+/// TWS rejects contracts with unknown exchanges with "No security definition has been found for the request".
+/// Constants are provided for commonly used venues to avoid typos.
+///
+/// ```
+/// use ibapi::contracts::{Contract, Exchange};
+///
+/// let contract = Contract::stock("AAPL").with_exchange(Exchange::SMART).with_primary_exchange(Exchange::ISLAND);
+/// assert_eq!(contract.exchange, "SMART");
+/// assert_eq!(contract.primary_exchange, "ISLAND");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Exchange(Cow<'static, str>);
+
+impl Exchange {
+    /// IB smart routing.
+    pub const SMART: Exchange = Exchange::from_static("SMART");
+    /// IB forex dealing system.
+    pub const IDEALPRO: Exchange = Exchange::from_static("IDEALPRO");
+    /// Chicago Mercantile Exchange, including Globex.
+    pub const CME: Exchange = Exchange::from_static("CME");
+    /// Chicago Board of Trade.
+    pub const CBOT: Exchange = Exchange::from_static("CBOT");
+    /// New York Mercantile Exchange.
+    pub const NYMEX: Exchange = Exchange::from_static("NYMEX");
+    /// Commodity Exchange.
+    pub const COMEX: Exchange = Exchange::from_static("COMEX");
+    /// CBOE Futures Exchange.
+    pub const CFE: Exchange = Exchange::from_static("CFE");
+    /// Eurex.
+    pub const EUREX: Exchange = Exchange::from_static("EUREX");
+    /// ICE Futures Europe.
+    pub const ICEEU: Exchange = Exchange::from_static("ICEEU");
+    /// Chicago Board Options Exchange.
+    pub const CBOE: Exchange = Exchange::from_static("CBOE");
+    /// New York Stock Exchange.
+    pub const NYSE: Exchange = Exchange::from_static("NYSE");
+    /// NYSE Arca.
+    pub const ARCA: Exchange = Exchange::from_static("ARCA");
+    /// Nasdaq, as reported in the primary exchange of contract details.
+    pub const NASDAQ: Exchange = Exchange::from_static("NASDAQ");
+    /// Nasdaq routing destination, used to define the primary exchange of Nasdaq listed contracts.
+    pub const ISLAND: Exchange = Exchange::from_static("ISLAND");
+    /// London Stock Exchange.
+    pub const LSE: Exchange = Exchange::from_static("LSE");
+    /// Frankfurt Stock Exchange.
+    pub const FWB: Exchange = Exchange::from_static("FWB");
+    /// Deutsche Boerse Xetra.
+    pub const IBIS: Exchange = Exchange::from_static("IBIS");
+    /// Toronto Stock Exchange.
+    pub const TSE: Exchange = Exchange::from_static("TSE");
+    /// Hong Kong Stock Exchange.
+    pub const SEHK: Exchange = Exchange::from_static("SEHK");
+    /// Paxos cryptocurrency exchange.
+    pub const PAXOS: Exchange = Exchange::from_static("PAXOS");
+    /// Zero Hash cryptocurrency exchange.
+    pub const ZEROHASH: Exchange = Exchange::from_static("ZEROHASH");
+    /// IB CFD and commodity dealing.
+    pub const IBCMDTY: Exchange = Exchange::from_static("IBCMDTY");
+
+    /// Venues with constants defined above.
+    pub const KNOWN: &'static [Exchange] = &[
+        Exchange::SMART,
+        Exchange::IDEALPRO,
+        Exchange::CME,
+        Exchange::CBOT,
+        Exchange::NYMEX,
+        Exchange::COMEX,
+        Exchange::CFE,
+        Exchange::EUREX,
+        Exchange::ICEEU,
+        Exchange::CBOE,
+        Exchange::NYSE,
+        Exchange::ARCA,
+        Exchange::NASDAQ,
+        Exchange::ISLAND,
+        Exchange::LSE,
+        Exchange::FWB,
+        Exchange::IBIS,
+        Exchange::TSE,
+        Exchange::SEHK,
+        Exchange::PAXOS,
+        Exchange::ZEROHASH,
+        Exchange::IBCMDTY,
+    ];
+
+    const fn from_static(name: &'static str) -> Exchange {
+        Exchange(Cow::Borrowed(name))
+    }
+
+    /// Creates an exchange from its name. Names are trimmed and uppercased, and GLOBEX is mapped to CME.
+    pub fn new(name: &str) -> Exchange {
+        let name = name.trim().to_uppercase();
+        match name.as_str() {
+            "GLOBEX" => Exchange::CME,
+            _ => Exchange(Cow::Owned(name)),
+        }
+    }
+
+    /// Name of the exchange as sent to TWS.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if a constant is defined for the exchange.
+    pub fn is_known(&self) -> bool {
+        Exchange::KNOWN.contains(self)
+    }
+
+    /// Checks that `name` is usable as an exchange name.
+    ///
+    /// Rejects names with whitespace or lowercase letters, which TWS does not match against any contract.
+    pub fn validate(name: &str) -> Result<(), Error> {
+        if name.chars().any(|c| c.is_whitespace() || c.is_lowercase()) {
+            return Err(Error::InvalidArgument(format!(
+                "invalid exchange \"{name}\": exchange names are uppercase without whitespace, e.g. {}",
+                Exchange::new(name)
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Exchange {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Exchange {
+    fn from(name: &str) -> Self {
+        Exchange::new(name)
+    }
+}
+
+impl From<String> for Exchange {
+    fn from(name: String) -> Self {
+        Exchange::new(&name)
+    }
+}
+
+impl From<Exchange> for String {
+    fn from(exchange: Exchange) -> Self {
+        exchange.0.into_owned()
+    }
+}
+
+impl PartialEq<str> for Exchange {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Exchange {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn normalizes_names() {
+    assert_eq!(Exchange::new(" smart "), Exchange::SMART);
+    assert_eq!(Exchange::new("globex"), Exchange::CME);
+    assert_eq!(Exchange::from("idealpro").to_string(), "IDEALPRO");
+    assert_eq!(String::from(Exchange::PAXOS), "PAXOS");
+    assert_eq!(Exchange::CBOE, "CBOE");
+}
+
+#[test]
+fn known_exchanges() {
+    assert!(Exchange::new("arca").is_known());
+    assert!(!Exchange::new("BVME").is_known());
+}
+
+#[test]
+fn validate_names() {
+    assert!(Exchange::validate("SMART").is_ok());
+    assert!(Exchange::validate("").is_ok(), "unset exchange");
+    assert!(Exchange::validate("ENEXT.BE").is_ok());
+
+    match Exchange::validate("SMART ") {
+        Err(Error::InvalidArgument(message)) => assert!(message.contains("e.g. SMART"), "{message}"),
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert!(Exchange::validate("Smart").is_err());
+}
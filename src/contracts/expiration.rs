@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use time::{Date, Month};
+
+use super::Contract;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Expiration of a derivative, parsed from [Contract::last_trade_date_or_contract_month].
+///
+/// TWS accepts and reports either the last trading day (YYYYMMDD) or the contract month (YYYYMM).
+///
+/// ```
+/// use ibapi::contracts::{futures, ExpirationDate};
+/// use time::macros::date;
+///
+/// let contract = futures("ES", "CME", "202412");
+///
+/// let expiration = contract.expiration().expect("contract month should be valid");
+/// assert_eq!(expiration, ExpirationDate::Month { year: 2024, month: time::Month::December });
+/// assert!(expiration.contains(date!(2024 - 12 - 20)));
+/// assert!(expiration.is_expired_at(date!(2025 - 01 - 02)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExpirationDate {
+    /// Last trading day.
+    Date(Date),
+    /// Contract month, without a specific day.
+    Month { year: i32, month: Month },
+}
+
+impl ExpirationDate {
+    /// Parses YYYYMMDD or YYYYMM. A time of day following the date, e.g. `20231215 08:30`, is ignored.
+    pub fn parse(text: &str) -> Result<ExpirationDate, Error> {
+        let invalid = || Error::InvalidArgument(format!("invalid expiration date: {text}"));
+
+        let digits = text.split_whitespace().next().ok_or_else(invalid)?;
+        if !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let number = |range: std::ops::Range<usize>| digits[range].parse::<u8>().map_err(|_| invalid());
+
+        match digits.len() {
+            6 | 8 => {
+                let year: i32 = digits[0..4].parse().map_err(|_| invalid())?;
+                let month = Month::try_from(number(4..6)?).map_err(|_| invalid())?;
+
+                if digits.len() == 6 {
+                    return Ok(ExpirationDate::Month { year, month });
+                }
+
+                let date = Date::from_calendar_date(year, month, number(6..8)?).map_err(|_| invalid())?;
+                Ok(ExpirationDate::Date(date))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Year of the expiration.
+    pub fn year(&self) -> i32 {
+        match self {
+            ExpirationDate::Date(date) => date.year(),
+            ExpirationDate::Month { year, .. } => *year,
+        }
+    }
+
+    /// Month of the expiration.
+    pub fn month(&self) -> Month {
+        match self {
+            ExpirationDate::Date(date) => date.month(),
+            ExpirationDate::Month { month, .. } => *month,
+        }
+    }
+
+    /// Last trading day if known.
+    pub fn date(&self) -> Option<Date> {
+        match self {
+            ExpirationDate::Date(date) => Some(*date),
+            ExpirationDate::Month { .. } => None,
+        }
+    }
+
+    /// Latest date the contract can expire on: the last trading day, or the last day of the contract month.
+    pub fn latest_date(&self) -> Date {
+        match self {
+            ExpirationDate::Date(date) => *date,
+            ExpirationDate::Month { year, month } => {
+                Date::from_calendar_date(*year, *month, month.length(*year)).expect("last day of month is valid")
+            }
+        }
+    }
+
+    /// Returns true if `date` is the last trading day, or falls in the contract month.
+    pub fn contains(&self, date: Date) -> bool {
+        match self {
+            ExpirationDate::Date(expiration) => *expiration == date,
+            ExpirationDate::Month { year, month } => date.year() == *year && date.month() == *month,
+        }
+    }
+
+    /// Returns true if the contract has expired by `date`, i.e. `date` is after [ExpirationDate::latest_date].
+    pub fn is_expired_at(&self, date: Date) -> bool {
+        date > self.latest_date()
+    }
+
+    // contract months sort before the days within them
+    fn sort_key(&self) -> (i32, u8, u8) {
+        match self {
+            ExpirationDate::Date(date) => (date.year(), date.month() as u8, date.day()),
+            ExpirationDate::Month { year, month } => (*year, *month as u8, 0),
+        }
+    }
+}
+
+impl Ord for ExpirationDate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for ExpirationDate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for ExpirationDate {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        ExpirationDate::parse(text)
+    }
+}
+
+impl fmt::Display for ExpirationDate {
+    /// Formats the expiration as sent to TWS.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpirationDate::Date(date) => write!(f, "{:04}{:02}{:02}", date.year(), date.month() as u8, date.day()),
+            ExpirationDate::Month { year, month } => write!(f, "{year:04}{:02}", *month as u8),
+        }
+    }
+}
+
+impl Contract {
+    /// Parses [Contract::last_trade_date_or_contract_month]. Returns None if it is blank or malformed.
+    pub fn expiration(&self) -> Option<ExpirationDate> {
+        ExpirationDate::parse(&self.last_trade_date_or_contract_month).ok()
+    }
+}
@@ -0,0 +1,54 @@
+use time::macros::date;
+
+use super::*;
+
+#[test]
+fn parse_expirations() {
+    assert_eq!(ExpirationDate::parse("20231215").unwrap(), ExpirationDate::Date(date!(2023 - 12 - 15)));
+    assert_eq!(
+        ExpirationDate::parse("20231215 08:30 US/Central").unwrap(),
+        ExpirationDate::Date(date!(2023 - 12 - 15))
+    );
+    assert_eq!(
+        ExpirationDate::parse("202402").unwrap(),
+        ExpirationDate::Month {
+            year: 2024,
+            month: Month::February
+        }
+    );
+
+    for invalid in ["", "2024", "20241301", "20240230", "2024-02-01", "+20401"] {
+        assert!(ExpirationDate::parse(invalid).is_err(), "parse({invalid:?})");
+    }
+}
+
+#[test]
+fn expiration_helpers() {
+    let month: ExpirationDate = "202402".parse().unwrap();
+    assert_eq!(month.latest_date(), date!(2024 - 02 - 29));
+    assert_eq!(month.date(), None);
+    assert!(month.contains(date!(2024 - 02 - 16)));
+    assert!(!month.is_expired_at(date!(2024 - 02 - 29)));
+    assert!(month.is_expired_at(date!(2024 - 03 - 01)));
+    assert_eq!(month.to_string(), "202402");
+
+    let day = ExpirationDate::parse("20240216").unwrap();
+    assert_eq!((day.year(), day.month()), (2024, Month::February));
+    assert_eq!(day.to_string(), "20240216");
+
+    let mut expirations = [day, ExpirationDate::parse("20240115").unwrap(), month];
+    expirations.sort();
+    assert_eq!(expirations[0].to_string(), "20240115");
+    assert_eq!(expirations[1], month, "contract month sorts before its days");
+}
+
+#[test]
+fn contract_expiration() {
+    assert_eq!(Contract::stock("AAPL").expiration(), None);
+
+    let contract = Contract {
+        last_trade_date_or_contract_month: "20240119".to_string(),
+        ..Contract::default()
+    };
+    assert_eq!(contract.expiration(), Some(ExpirationDate::Date(date!(2024 - 01 - 19))));
+}
@@ -0,0 +1,179 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use time::macros::format_description;
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+use time_tz::Tz;
+
+use super::ContractDetails;
+use crate::{assume_time_zone, parse_time_zone, Error};
+
+#[cfg(test)]
+mod tests;
+
+/// A trading session parsed from the trading or liquid hours of a contract.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TradingSession {
+    /// Trading date the session belongs to.
+    pub date: Date,
+    /// Time the session opens, in the time zone of the contract. None if the market is closed on the date.
+    pub open: Option<OffsetDateTime>,
+    /// Time the session closes, in the time zone of the contract. None if the market is closed on the date.
+    pub close: Option<OffsetDateTime>,
+}
+
+impl TradingSession {
+    /// Returns true if the market is closed for the whole date.
+    pub fn is_closed(&self) -> bool {
+        self.open.is_none()
+    }
+
+    /// Returns true if `at` falls between the session open (inclusive) and close (exclusive).
+    pub fn contains(&self, at: OffsetDateTime) -> bool {
+        match (self.open, self.close) {
+            (Some(open), Some(close)) => open <= at && at < close,
+            _ => false,
+        }
+    }
+}
+
+/// Trading sessions of a contract, parsed from [ContractDetails::trading_hours] or [ContractDetails::liquid_hours].
+///
+/// ```no_run
+/// use ibapi::contracts::Contract;
+/// use ibapi::Client;
+/// use time::OffsetDateTime;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let details = client.contract_details(&Contract::stock("AAPL")).expect("error requesting contract details");
+/// let hours = details[0].liquid_sessions().expect("error parsing liquid hours");
+///
+/// let now = OffsetDateTime::now_utc();
+/// if !hours.is_open_at(now) {
+///     println!("next session: {:?}", hours.next_session_after(now));
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TradingHours {
+    /// Time zone the hours are expressed in, e.g. US/Eastern.
+    pub time_zone_id: String,
+    /// Sessions ordered by date and open time. Dates the market is closed are included as closed sessions.
+    pub sessions: Vec<TradingSession>,
+}
+
+impl TradingHours {
+    /// Parses trading hours as reported by TWS, e.g. `20180323:0400-20180323:2000` or `20090507:0700-1830,1830-2330`, with one entry per date.
+    ///
+    /// # Arguments
+    /// * `hours`        - Trading hours entries, one per date.
+    /// * `time_zone_id` - Time zone of the trading hours, e.g. US/Eastern.
+    pub fn parse(hours: &[String], time_zone_id: &str) -> Result<TradingHours, Error> {
+        let time_zone = parse_time_zone(time_zone_id)?;
+
+        let mut sessions = Vec::new();
+        for entry in hours.iter().map(|entry| entry.trim()).filter(|entry| !entry.is_empty()) {
+            parse_entry(entry, time_zone, &mut sessions)?;
+        }
+        sessions.sort_by_key(|session| (session.date, session.open));
+
+        Ok(TradingHours {
+            time_zone_id: time_zone_id.to_string(),
+            sessions,
+        })
+    }
+
+    /// Returns true if the market is open at `at`.
+    pub fn is_open_at(&self, at: OffsetDateTime) -> bool {
+        self.session_at(at).is_some()
+    }
+
+    /// Returns the session open at `at`, if any.
+    pub fn session_at(&self, at: OffsetDateTime) -> Option<&TradingSession> {
+        self.sessions.iter().find(|session| session.contains(at))
+    }
+
+    /// Returns the first session opening after `at`, if any.
+    pub fn next_session_after(&self, at: OffsetDateTime) -> Option<&TradingSession> {
+        self.sessions
+            .iter()
+            .filter(|session| matches!(session.open, Some(open) if open > at))
+            .min_by_key(|session| session.open)
+    }
+
+    /// Dates on which the market is closed.
+    pub fn closed_dates(&self) -> Vec<Date> {
+        self.sessions
+            .iter()
+            .filter(|session| session.is_closed())
+            .map(|session| session.date)
+            .collect()
+    }
+}
+
+impl ContractDetails {
+    /// Parses [ContractDetails::trading_hours] into typed sessions.
+    pub fn trading_sessions(&self) -> Result<TradingHours, Error> {
+        TradingHours::parse(&self.trading_hours, &self.time_zone_id)
+    }
+
+    /// Parses [ContractDetails::liquid_hours], the regular trading hours, into typed sessions.
+    pub fn liquid_sessions(&self) -> Result<TradingHours, Error> {
+        TradingHours::parse(&self.liquid_hours, &self.time_zone_id)
+    }
+}
+
+// Parses the sessions of a single date, e.g. 20180323:0400-20180323:2000, 20090507:0700-1830,1830-2330 or 20221231:CLOSED.
+fn parse_entry(entry: &str, time_zone: &Tz, sessions: &mut Vec<TradingSession>) -> Result<(), Error> {
+    let invalid = || Error::InvalidFormat {
+        kind: "trading hours",
+        reason: entry.to_owned(),
+    };
+
+    let (date, ranges) = entry.split_once(':').ok_or_else(invalid)?;
+    let date = parse_date(date)?;
+
+    if ranges == "CLOSED" {
+        sessions.push(TradingSession {
+            date,
+            open: None,
+            close: None,
+        });
+        return Ok(());
+    }
+
+    for range in ranges.split(',') {
+        let (open, close) = range.split_once('-').ok_or_else(invalid)?;
+
+        let open = parse_date_time(date, open)?;
+        let mut close = parse_date_time(date, close)?;
+        if close <= open {
+            // sessions spanning midnight in the format without close dates
+            close += Duration::days(1);
+        }
+
+        sessions.push(TradingSession {
+            date,
+            open: Some(assume_time_zone(open, time_zone)?),
+            close: Some(assume_time_zone(close, time_zone)?),
+        });
+    }
+
+    Ok(())
+}
+
+// Parses HHMM on `date`, or YYYYMMDD:HHMM.
+fn parse_date_time(date: Date, text: &str) -> Result<PrimitiveDateTime, Error> {
+    let (date, time) = match text.split_once(':') {
+        Some((date, time)) => (parse_date(date)?, time),
+        None => (date, text),
+    };
+
+    let time = Time::parse(time, format_description!("[hour][minute]"))?;
+    Ok(date.with_time(time))
+}
+
+fn parse_date(text: &str) -> Result<Date, Error> {
+    Ok(Date::parse(text, format_description!("[year][month][day]"))?)
+}
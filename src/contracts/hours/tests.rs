@@ -0,0 +1,95 @@
+use time::macros::{date, datetime};
+
+use super::*;
+
+fn hours(entries: &[&str]) -> Vec<String> {
+    entries.iter().map(|entry| entry.to_string()).collect()
+}
+
+#[test]
+fn parse_trading_hours() {
+    let entries = hours(&[
+        "20221229:0400-20221229:2000",
+        "20221230:0400-20221230:2000",
+        "20221231:CLOSED",
+        "20230101:CLOSED",
+    ]);
+
+    let hours = TradingHours::parse(&entries, "US/Eastern").expect("error parsing trading hours");
+
+    assert_eq!(hours.time_zone_id, "US/Eastern");
+    assert_eq!(hours.sessions.len(), 4);
+    assert_eq!(
+        hours.sessions[0],
+        TradingSession {
+            date: date!(2022 - 12 - 29),
+            open: Some(datetime!(2022-12-29 04:00 -5)),
+            close: Some(datetime!(2022-12-29 20:00 -5)),
+        }
+    );
+    assert!(hours.sessions[2].is_closed(), "2022-12-31 closed");
+    assert_eq!(hours.closed_dates(), vec![date!(2022 - 12 - 31), date!(2023 - 01 - 01)]);
+}
+
+#[test]
+fn parse_sessions_spanning_midnight() {
+    let entries = hours(&["20231015:1700-20231016:1600", "20090507:0700-1830,1830-2330,2330-0100"]);
+
+    let hours = TradingHours::parse(&entries, "US/Central").expect("error parsing trading hours");
+
+    assert_eq!(hours.sessions.len(), 4);
+    assert_eq!(hours.sessions[0].date, date!(2009 - 05 - 07));
+    assert_eq!(hours.sessions[1].open, Some(datetime!(2009-05-07 18:30 -5)));
+    assert_eq!(hours.sessions[1].close, Some(datetime!(2009-05-07 23:30 -5)));
+    // the last session of the date closes the next day
+    assert_eq!(hours.sessions[2].close, Some(datetime!(2009-05-08 01:00 -5)));
+    assert_eq!(hours.sessions[3].open, Some(datetime!(2023-10-15 17:00 -5)));
+    assert_eq!(hours.sessions[3].close, Some(datetime!(2023-10-16 16:00 -5)));
+}
+
+#[test]
+fn is_open_at_and_next_session() {
+    let entries = hours(&[
+        "20221230:0930-20221230:1600",
+        "20221231:CLOSED",
+        "20230101:CLOSED",
+        "20230103:0930-20230103:1600",
+    ]);
+
+    let hours = TradingHours::parse(&entries, "US/Eastern").expect("error parsing trading hours");
+
+    assert!(hours.is_open_at(datetime!(2022-12-30 10:00 -5)), "open mid session");
+    assert!(hours.is_open_at(datetime!(2022-12-30 14:30 UTC)), "open at session open");
+    assert!(!hours.is_open_at(datetime!(2022-12-30 16:00 -5)), "closed at session close");
+    assert!(!hours.is_open_at(datetime!(2022-12-31 12:00 -5)), "closed on holiday");
+
+    let next = hours.next_session_after(datetime!(2022-12-30 16:00 -5)).expect("next session");
+    assert_eq!(next.date, date!(2023 - 01 - 03));
+    assert!(hours.next_session_after(datetime!(2023-01-03 10:00 -5)).is_none(), "no later session");
+}
+
+#[test]
+fn parse_invalid_trading_hours() {
+    assert!(
+        TradingHours::parse(&hours(&["20221230:0930-20221230:1600"]), "Mars/Olympus").is_err(),
+        "unknown time zone"
+    );
+    assert!(TradingHours::parse(&hours(&["20221230"]), "US/Eastern").is_err(), "missing sessions");
+    assert!(TradingHours::parse(&hours(&["20221230:0930"]), "US/Eastern").is_err(), "missing close");
+}
+
+#[test]
+fn contract_details_sessions() {
+    let details = ContractDetails {
+        time_zone_id: "US/Eastern".to_owned(),
+        trading_hours: hours(&["20221229:0400-20221229:2000"]),
+        liquid_hours: hours(&["20221229:0930-20221229:1600"]),
+        ..ContractDetails::default()
+    };
+
+    let trading = details.trading_sessions().expect("error parsing trading hours");
+    let liquid = details.liquid_sessions().expect("error parsing liquid hours");
+
+    assert!(trading.is_open_at(datetime!(2022-12-29 08:00 -5)));
+    assert!(!liquid.is_open_at(datetime!(2022-12-29 08:00 -5)));
+}
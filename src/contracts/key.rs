@@ -0,0 +1,89 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Contract, Exchange, SecurityType};
+
+#[cfg(test)]
+mod tests;
+
+/// Canonical identity of a [Contract], suitable as a map key.
+///
+/// Contracts with a contract id are identified by it. Other contracts are identified by their normalized
+/// symbol, security type, exchange, currency, expiration, strike and right, so that e.g. `smart` and `SMART`
+/// or `CALL` and `C` describe the same contract.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use ibapi::contracts::Contract;
+///
+/// let mut positions = HashMap::new();
+/// positions.insert(Contract::stock("AAPL").canonical_key(), 100.0);
+///
+/// let contract = Contract::stock("aapl").with_exchange("smart");
+/// assert_eq!(positions.get(&contract.canonical_key()), Some(&100.0));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContractKey {
+    /// Contract identified by its contract id.
+    Id(i32),
+    /// Contract identified by its description.
+    Description {
+        symbol: String,
+        security_type: SecurityType,
+        exchange: String,
+        currency: String,
+        /// Last trade date or contract month, without the time of day.
+        expiration: String,
+        /// Bits of the strike price, 0 if not set.
+        strike: u64,
+        /// C or P for options, blank otherwise.
+        right: String,
+    },
+}
+
+impl Contract {
+    /// Returns the canonical identity of the contract. See [ContractKey].
+    pub fn canonical_key(&self) -> ContractKey {
+        if self.contract_id > 0 {
+            return ContractKey::Id(self.contract_id);
+        }
+
+        // 0.0 and -0.0 compare equal but have different bits
+        let strike = if self.strike == 0.0 || self.strike.is_nan() {
+            0
+        } else {
+            self.strike.to_bits()
+        };
+
+        ContractKey::Description {
+            symbol: self.symbol.trim().to_uppercase(),
+            security_type: self.security_type.clone(),
+            exchange: Exchange::new(&self.exchange).into(),
+            currency: self.currency.trim().to_uppercase(),
+            expiration: self
+                .last_trade_date_or_contract_month
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            strike,
+            right: normalize_right(&self.right),
+        }
+    }
+
+    /// Returns true if both contracts have the same [ContractKey].
+    pub fn is_same_contract(&self, other: &Contract) -> bool {
+        self.canonical_key() == other.canonical_key()
+    }
+}
+
+fn normalize_right(right: &str) -> String {
+    let right = right.trim().to_uppercase();
+    match right.as_str() {
+        "CALL" => "C".to_string(),
+        "PUT" => "P".to_string(),
+        _ => right,
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use super::*;
+
+#[test]
+fn keys_by_contract_id() {
+    let contract = Contract {
+        contract_id: 265598,
+        ..Contract::stock("AAPL")
+    };
+    let other = Contract {
+        contract_id: 265598,
+        exchange: "ISLAND".to_string(),
+        ..Contract::default()
+    };
+
+    assert_eq!(contract.canonical_key(), ContractKey::Id(265598));
+    assert!(contract.is_same_contract(&other));
+}
+
+#[test]
+fn keys_by_normalized_description() {
+    let option = Contract {
+        symbol: "spy".to_string(),
+        security_type: SecurityType::Option,
+        exchange: "smart".to_string(),
+        currency: "usd".to_string(),
+        last_trade_date_or_contract_month: "20240119".to_string(),
+        strike: 470.0,
+        right: "CALL".to_string(),
+        ..Contract::default()
+    };
+    let same = Contract {
+        symbol: "SPY".to_string(),
+        exchange: "SMART".to_string(),
+        currency: "USD".to_string(),
+        last_trade_date_or_contract_month: "20240119 16:00".to_string(),
+        right: "C".to_string(),
+        ..option.clone()
+    };
+    let put = Contract {
+        right: "P".to_string(),
+        ..same.clone()
+    };
+
+    assert!(option.is_same_contract(&same));
+    assert!(!option.is_same_contract(&put));
+
+    let keys: HashSet<ContractKey> = [&option, &same, &put].iter().map(|contract| contract.canonical_key()).collect();
+    assert_eq!(keys.len(), 2);
+
+    let no_strike = Contract {
+        strike: -0.0,
+        ..Contract::stock("AAPL")
+    };
+    assert_eq!(no_strike.canonical_key(), Contract::stock("AAPL").canonical_key(), "negative zero strike");
+}
@@ -0,0 +1,112 @@
+use super::{Contract, ContractDetails};
+use crate::{Client, Error};
+
+#[cfg(test)]
+mod tests;
+
+/// Resolves contract details requests that match several contracts, such as the same ticker listed on several exchanges, to a single [Contract].
+///
+/// Candidates are narrowed by each preference in turn: preferred primary exchanges, then currencies, then routing exchanges.
+/// A preference that matches none of the remaining candidates is skipped. If more than one contract remains,
+/// resolution fails with [Error::AmbiguousContract] listing the candidates.
+///
+/// ```no_run
+/// use ibapi::contracts::{Contract, ContractResolver};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let resolver = ContractResolver::new().prefer_primary_exchange("NASDAQ").prefer_currency("USD");
+///
+/// let contract = resolver.resolve_contract(&client, &Contract::stock("NVDA")).expect("error resolving contract");
+/// println!("resolved contract id: {}", contract.contract_id);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ContractResolver {
+    primary_exchanges: Vec<String>,
+    currencies: Vec<String>,
+    exchanges: Vec<String>,
+}
+
+impl ContractResolver {
+    /// Creates a resolver without preferences.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefers contracts listed on `exchange`, e.g. NASDAQ. Preferences added first take precedence.
+    ///
+    /// ISLAND is treated as NASDAQ.
+    pub fn prefer_primary_exchange(mut self, exchange: &str) -> Self {
+        self.primary_exchanges.push(normalize_exchange(exchange));
+        self
+    }
+
+    /// Prefers contracts denominated in `currency`. Preferences added first take precedence.
+    pub fn prefer_currency(mut self, currency: &str) -> Self {
+        self.currencies.push(currency.to_uppercase());
+        self
+    }
+
+    /// Prefers contracts routed to `exchange`, e.g. SMART. Preferences added first take precedence.
+    pub fn prefer_exchange(mut self, exchange: &str) -> Self {
+        self.exchanges.push(normalize_exchange(exchange));
+        self
+    }
+
+    /// Requests the contract details matching `contract` and resolves them to a single contract.
+    pub fn resolve_contract(&self, client: &Client, contract: &Contract) -> Result<Contract, Error> {
+        let candidates = super::contract_details(client, contract, client.request_timeout)?;
+
+        if candidates.is_empty() {
+            return Err(Error::ContractNotFound(contract.symbol.clone()));
+        }
+
+        self.resolve(&candidates)
+    }
+
+    /// Resolves `candidates` to a single contract.
+    pub fn resolve(&self, candidates: &[ContractDetails]) -> Result<Contract, Error> {
+        let mut remaining: Vec<&Contract> = candidates.iter().map(|details| &details.contract).collect();
+
+        remaining = narrow(remaining, &self.primary_exchanges, |contract| {
+            normalize_exchange(&contract.primary_exchange)
+        });
+        remaining = narrow(remaining, &self.currencies, |contract| contract.currency.to_uppercase());
+        remaining = narrow(remaining, &self.exchanges, |contract| normalize_exchange(&contract.exchange));
+
+        // the same contract may be reported once per exchange it trades on
+        let mut distinct: Vec<&Contract> = Vec::new();
+        for contract in remaining {
+            if !distinct.iter().any(|other| other.contract_id == contract.contract_id) {
+                distinct.push(contract);
+            }
+        }
+
+        match distinct.as_slice() {
+            [] => Err(Error::ContractNotFound("an empty list of candidates".into())),
+            [contract] => Ok((*contract).clone()),
+            _ => Err(Error::AmbiguousContract(distinct.into_iter().cloned().collect())),
+        }
+    }
+}
+
+// Keeps the candidates matching the first preference that matches any candidate.
+fn narrow<'a>(candidates: Vec<&'a Contract>, preferences: &[String], key: impl Fn(&Contract) -> String) -> Vec<&'a Contract> {
+    for preference in preferences {
+        let matching: Vec<&Contract> = candidates.iter().copied().filter(|contract| key(contract) == *preference).collect();
+        if !matching.is_empty() {
+            return matching;
+        }
+    }
+    candidates
+}
+
+// TWS reports NASDAQ listings with primary exchange NASDAQ, while contracts are often defined with ISLAND.
+fn normalize_exchange(exchange: &str) -> String {
+    let exchange = exchange.trim().to_uppercase();
+    match exchange.as_str() {
+        "ISLAND" => "NASDAQ".to_string(),
+        _ => exchange,
+    }
+}
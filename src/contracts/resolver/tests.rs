@@ -0,0 +1,95 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::contracts::SecurityType;
+use crate::server_versions;
+use crate::stubs::MessageBusStub;
+
+fn candidate(contract_id: i32, exchange: &str, primary_exchange: &str, currency: &str) -> ContractDetails {
+    ContractDetails {
+        contract: Contract {
+            contract_id,
+            symbol: "NVDA".to_owned(),
+            security_type: SecurityType::Stock,
+            exchange: exchange.to_owned(),
+            primary_exchange: primary_exchange.to_owned(),
+            currency: currency.to_owned(),
+            ..Contract::default()
+        },
+        ..ContractDetails::default()
+    }
+}
+
+fn candidates() -> Vec<ContractDetails> {
+    vec![
+        candidate(4815747, "SMART", "NASDAQ", "USD"),
+        candidate(4815747, "ISLAND", "NASDAQ", "USD"),
+        candidate(81203821, "MEXI", "MEXI", "MXN"),
+        candidate(13104788, "IBIS", "IBIS", "EUR"),
+    ]
+}
+
+#[test]
+fn resolve_by_primary_exchange() {
+    let resolver = ContractResolver::new().prefer_primary_exchange("ISLAND");
+
+    let contract = resolver.resolve(&candidates()).expect("error resolving contract");
+
+    assert_eq!(contract.contract_id, 4815747);
+    assert_eq!(contract.exchange, "SMART", "first listing of the contract");
+}
+
+#[test]
+fn resolve_by_currency_and_exchange() {
+    let resolver = ContractResolver::new().prefer_currency("cad").prefer_currency("EUR");
+    assert_eq!(resolver.resolve(&candidates()).expect("error resolving contract").contract_id, 13104788);
+
+    let resolver = ContractResolver::new().prefer_currency("USD").prefer_exchange("ISLAND");
+    let contract = resolver.resolve(&candidates()).expect("error resolving contract");
+    assert_eq!(contract.contract_id, 4815747);
+    assert_eq!(contract.exchange, "ISLAND");
+}
+
+#[test]
+fn resolve_ambiguous_contract() {
+    let resolver = ContractResolver::new().prefer_primary_exchange("LSE");
+
+    match resolver.resolve(&candidates()) {
+        Err(Error::AmbiguousContract(candidates)) => {
+            let contract_ids: Vec<i32> = candidates.iter().map(|contract| contract.contract_id).collect();
+            assert_eq!(contract_ids, vec![4815747, 81203821, 13104788]);
+        }
+        result => panic!("expected ambiguous contract error, got {result:?}"),
+    }
+}
+
+#[test]
+fn resolve_contract_details_request() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "10|9000|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS|4815747|0.01||LMT,MKT|SMART,ISLAND|1|0|NVIDIA CORP|NASDAQ||Technology|Semiconductors|Electronic Components-Semicon|US/Eastern|||||1|ISIN|US67066G1040|1|||26,26||COMMON|1|1|100|".to_owned(),
+            "10|9000|NVDA|STK||0||MEXI|MXN|NVDA|NVDA|NVDA|81203821|0.01||LMT,MKT|MEXI|1|0|NVIDIA CORP|MEXI||Technology|Semiconductors|Electronic Components-Semicon|America/Mexico_City|||||1|ISIN|US67066G1040|1|||26||COMMON|1|1|100|".to_owned(),
+            "52|1|9000||".to_owned(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract {
+        currency: String::new(),
+        ..Contract::stock("NVDA")
+    };
+
+    let resolved = ContractResolver::new()
+        .prefer_currency("USD")
+        .resolve_contract(&client, &contract)
+        .expect("error resolving contract");
+    assert_eq!(resolved.contract_id, 4815747);
+
+    let result = ContractResolver::new().resolve_contract(&client, &contract);
+    assert!(
+        matches!(result, Err(Error::AmbiguousContract(ref candidates)) if candidates.len() == 2),
+        "{result:?}"
+    );
+}
@@ -170,23 +170,581 @@ fn request_stock_contract_details() {
 }
 
 #[test]
-fn request_bond_contract_details() {}
+fn request_bond_contract_details() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "18|9000|IBCID12345|BOND|912828C57|2.25|20240331-16:00-US/Eastern|20170331|AAA|GOVT|FIXED|0|1|0|US T-Note|SMART|USD|US Treasury|IBCID|123456789|0.0001|LMT,MKT|SMART,BONDDESK|20230331|CALL|0|callable at par|UNITED STATES TREASURY|||1|CUSIP|912828C57|0|26|1000|1000|1000|".to_string(),
+            "52|1|9000||".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::bond_by_security_id("CUSIP", "912828C57");
+    let contracts = client.contract_details(&contract).expect("request contract details failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(
+        request_messages[0].encode_simple(),
+        "9|8|9000|0||BOND||0|||SMART||USD|||0|CUSIP|912828C57|"
+    );
+
+    assert_eq!(contracts.len(), 1, "contracts.len()");
+
+    let details = &contracts[0];
+    assert_eq!(details.contract.symbol, "IBCID12345", "contract.symbol");
+    assert_eq!(details.contract.security_type, SecurityType::Bond, "contract.security_type");
+    assert_eq!(details.contract.contract_id, 123456789, "contract.contract_id");
+    assert_eq!(details.contract.exchange, "SMART", "contract.exchange");
+    assert_eq!(details.cusip, "912828C57", "cusip");
+    assert_eq!(details.coupon, 2.25, "coupon");
+    assert_eq!(details.maturity, "20240331", "maturity");
+    assert_eq!(details.last_trade_time, "16:00", "last_trade_time");
+    assert_eq!(details.time_zone_id, "US/Eastern", "time_zone_id");
+    assert_eq!(details.issue_date, "20170331", "issue_date");
+    assert_eq!(details.ratings, "AAA", "ratings");
+    assert_eq!(details.bond_type, "GOVT", "bond_type");
+    assert_eq!(details.coupon_type, "FIXED", "coupon_type");
+    assert!(!details.convertible, "convertible");
+    assert!(details.callable, "callable");
+    assert!(!details.putable, "putable");
+    assert_eq!(details.desc_append, "US T-Note", "desc_append");
+    assert_eq!(details.min_tick, 0.0001, "min_tick");
+    assert_eq!(details.order_types, vec!["LMT", "MKT"], "order_types");
+    assert_eq!(details.next_option_date, "20230331", "next_option_date");
+    assert_eq!(details.next_option_type, "CALL", "next_option_type");
+    assert!(!details.next_option_partial, "next_option_partial");
+    assert_eq!(details.notes, "callable at par", "notes");
+    assert_eq!(details.long_name, "UNITED STATES TREASURY", "long_name");
+    assert_eq!(
+        details.sec_id_list,
+        vec![TagValue {
+            tag: "CUSIP".to_owned(),
+            value: "912828C57".to_owned()
+        }],
+        "sec_id_list"
+    );
+    assert_eq!(details.market_rule_ids, vec!["26"], "market_rule_ids");
+    assert_eq!(details.min_size, 1000.0, "min_size");
+}
 
 #[test]
-fn request_future_contract_details() {}
+fn request_future_contract_details() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "10|9000|ES|FUT|20240315 08:30 US/Central|0||CME|USD|ESH4|ES|ES|551601561|0.25|50|ACTIVETIM,LMT,MKT|CME,QBALGO|1|11004968|E-mini S&P 500||202403||||US/Central|20240314:1700-20240315:0830|20240315:0830-20240315:0830|||0|2|ES|IND|67,67|20240315||1|1|1|".to_string(),
+            "52|1|9000||".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::futures("ES");
+    let contracts = client.contract_details(&contract).expect("request contract details failed");
+
+    assert_eq!(contracts.len(), 1, "contracts.len()");
+
+    let details = &contracts[0];
+    assert_eq!(details.contract.symbol, "ES", "contract.symbol");
+    assert_eq!(details.contract.security_type, SecurityType::Future, "contract.security_type");
+    assert_eq!(
+        details.contract.last_trade_date_or_contract_month, "20240315",
+        "contract.last_trade_date_or_contract_month"
+    );
+    assert_eq!(details.last_trade_time, "08:30", "last_trade_time");
+    assert_eq!(details.contract.multiplier, "50", "contract.multiplier");
+    assert_eq!(details.contract.local_symbol, "ESH4", "contract.local_symbol");
+    assert_eq!(details.min_tick, 0.25, "min_tick");
+    assert_eq!(details.order_types, vec!["ACTIVETIM", "LMT", "MKT"], "order_types");
+    assert_eq!(details.valid_exchanges, vec!["CME", "QBALGO"], "valid_exchanges");
+    assert_eq!(details.under_contract_id, 11004968, "under_contract_id");
+    assert_eq!(details.long_name, "E-mini S&P 500", "long_name");
+    assert_eq!(details.contract_month, "202403", "contract_month");
+    assert_eq!(details.time_zone_id, "US/Central", "time_zone_id");
+    assert_eq!(details.trading_hours, vec!["20240314:1700-20240315:0830"], "trading_hours");
+    assert_eq!(details.sec_id_list, vec![], "sec_id_list");
+    assert_eq!(details.under_symbol, "ES", "under_symbol");
+    assert_eq!(details.under_security_type, "IND", "under_security_type");
+    assert_eq!(details.market_rule_ids, vec!["67", "67"], "market_rule_ids");
+    assert_eq!(details.real_expiration_date, "20240315", "real_expiration_date");
+    assert_eq!(details.stock_type, "", "stock_type");
+}
+
+#[test]
+fn futures_builder() {
+    let contract = futures("ES", "CME", "202403").with_multiplier("50").with_trading_class("ES");
+
+    assert_eq!(contract.symbol, "ES", "contract.symbol");
+    assert_eq!(contract.security_type, SecurityType::Future, "contract.security_type");
+    assert_eq!(contract.exchange, "CME", "contract.exchange");
+    assert_eq!(contract.currency, "USD", "contract.currency");
+    assert_eq!(
+        contract.last_trade_date_or_contract_month, "202403",
+        "contract.last_trade_date_or_contract_month"
+    );
+    assert_eq!(contract.multiplier, "50", "contract.multiplier");
+    assert_eq!(contract.trading_class, "ES", "contract.trading_class");
+
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["52|1|9000||".to_string()],
+    });
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let _ = client.contract_details(&contract).expect("request contract details failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "9|8|9000|0|ES|FUT|202403|0||50|CME||USD||ES|0|||");
+}
+
+#[test]
+fn request_contract_details_error() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["4|2|9000|200|No security definition has been found for the request|".to_string()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let result = client.contract_details(&Contract::stock("XXXX"));
+
+    match result {
+        Err(Error::Tws { code, message, request_id }) => {
+            assert_eq!(code, 200, "code");
+            assert_eq!(message, "No security definition has been found for the request", "message");
+            assert_eq!(request_id, 9000, "request_id");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
 
 #[test]
 fn test_read_last_trade_date() {
-    // let mut contract = ContractDetails::default();
+    let mut contract = ContractDetails::default();
 
     // handles blank string
-    // let result = read_last_trade_date(&mut contract, "", false);
-    // assert!(!result.is_err(), "unexpected error {:?}", result);
+    let result = decoders::read_last_trade_date(&mut contract, "", false);
+    assert!(result.is_ok(), "unexpected error {:?}", result);
+    assert_eq!(contract.contract.last_trade_date_or_contract_month, "");
 
     // handles non bond contracts
+    decoders::read_last_trade_date(&mut contract, "20240315 08:30", false).unwrap();
+    assert_eq!(contract.contract.last_trade_date_or_contract_month, "20240315");
+    assert_eq!(contract.last_trade_time, "08:30");
 
     // handles bond contracts
+    let mut contract = ContractDetails::default();
+    decoders::read_last_trade_date(&mut contract, "20320115-16:00-US/Eastern", true).unwrap();
+    assert_eq!(contract.maturity, "20320115");
+    assert_eq!(contract.last_trade_time, "16:00");
+    assert_eq!(contract.time_zone_id, "US/Eastern");
+}
+
+#[test]
+fn request_matching_symbols() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["79|9000|2|265598|AAPL|STK|NASDAQ|USD|3|OPT|WAR|CFD|38708077|AAPL|STK|MEXI|MXN|0|".to_string()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contracts: Vec<ContractDescription> = client.matching_symbols("AAPL").expect("request matching symbols failed").collect();
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "81|9000|AAPL|");
+
+    assert_eq!(contracts.len(), 2, "contracts.len()");
+
+    assert_eq!(contracts[0].contract.contract_id, 265598, "contracts[0].contract.contract_id");
+    assert_eq!(contracts[0].contract.symbol, "AAPL", "contracts[0].contract.symbol");
+    assert_eq!(
+        contracts[0].contract.security_type,
+        SecurityType::Stock,
+        "contracts[0].contract.security_type"
+    );
+    assert_eq!(contracts[0].contract.primary_exchange, "NASDAQ", "contracts[0].contract.primary_exchange");
+    assert_eq!(contracts[0].contract.currency, "USD", "contracts[0].contract.currency");
+    assert_eq!(
+        contracts[0].derivative_security_types,
+        vec!["OPT", "WAR", "CFD"],
+        "contracts[0].derivative_security_types"
+    );
+
+    assert_eq!(contracts[1].contract.currency, "MXN", "contracts[1].contract.currency");
+    assert!(
+        contracts[1].derivative_security_types.is_empty(),
+        "contracts[1].derivative_security_types"
+    );
+}
+
+#[test]
+fn request_option_chain() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "75|9000|SMART|265598|AAPL|100|2|20240119|20240216|3|180|185|190|".to_string(),
+            "75|9000|CBOE|265598|AAPL|100|1|20240119|2|185|190|".to_string(),
+            "76|9000|".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let subscription = client
+        .option_chain("AAPL", "", SecurityType::Stock, 265598)
+        .expect("request option chain failed");
+    let chains: Vec<OptionChain> = subscription.iter().collect();
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "78|9000|AAPL||STK|265598|");
+
+    assert_eq!(chains.len(), 2, "chains.len()");
+    assert_eq!(
+        chains[0],
+        OptionChain {
+            underlying_contract_id: 265598,
+            trading_class: "AAPL".to_owned(),
+            multiplier: "100".to_owned(),
+            exchange: "SMART".to_owned(),
+            expirations: vec!["20240119".to_owned(), "20240216".to_owned()],
+            strikes: vec![180.0, 185.0, 190.0],
+        },
+        "chains[0]"
+    );
+    assert_eq!(chains[1].exchange, "CBOE", "chains[1].exchange");
+    assert_eq!(chains[1].strikes, vec![185.0, 190.0], "chains[1].strikes");
+    assert!(subscription.error().is_none(), "subscription.error()");
+}
+
+fn sample_option_chain() -> OptionChain {
+    OptionChain {
+        underlying_contract_id: 265598,
+        trading_class: "AAPL".to_owned(),
+        multiplier: "100".to_owned(),
+        exchange: "SMART".to_owned(),
+        expirations: vec!["20240119".to_owned(), "20240216".to_owned()],
+        strikes: vec![185.0, 190.0],
+    }
+}
+
+#[test]
+fn expand_option_chain() {
+    let chain = sample_option_chain().expand("AAPL", "USD");
+
+    assert_eq!(chain.options.len(), 8, "expirations x strikes x rights");
+    assert_eq!(chain.expirations(), vec!["20240119", "20240216"], "chain.expirations()");
+    assert_eq!(chain.strikes("20240216"), vec![185.0, 190.0], "chain.strikes()");
+    assert_eq!(chain.calls().count(), 4, "chain.calls()");
+    assert_eq!(chain.puts().count(), 4, "chain.puts()");
+
+    let option = chain.option("20240216", 190.0, "P").expect("option not found");
+    assert_eq!(option.symbol, "AAPL", "option.symbol");
+    assert_eq!(option.security_type, SecurityType::Option, "option.security_type");
+    assert_eq!(option.exchange, "SMART", "option.exchange");
+    assert_eq!(option.currency, "USD", "option.currency");
+    assert_eq!(option.multiplier, "100", "option.multiplier");
+    assert_eq!(option.trading_class, "AAPL", "option.trading_class");
+
+    assert!(chain.option("20240216", 195.0, "P").is_none(), "strike not in chain");
+}
+
+#[test]
+fn expand_verified_option_chain() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "10|9000|AAPL|OPT|20240119|185|C|SMART|USD|AAPL  240119C00185000|AAPL|AAPL|654321|0.01|100|LMT|SMART|1|265598|APPLE INC||202401||||US/Eastern|||||0|1|AAPL|STK|32|20240119||1|1|1|".to_string(),
+            "10|9000|AAPL|OPT|20240119|185|P|SMART|USD|AAPL  240119P00185000|AAPL|AAPL|654322|0.01|100|LMT|SMART|1|265598|APPLE INC||202401||||US/Eastern|||||0|1|AAPL|STK|32|20240119||1|1|1|".to_string(),
+            "52|1|9000||".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let chain = OptionChain {
+        expirations: vec!["20240119".to_owned()],
+        ..sample_option_chain()
+    };
+    let chain = chain.expand_verified(&client, "AAPL", "USD").expect("expand verified failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(
+        request_messages[0].encode_simple(),
+        "9|8|9000|0|AAPL|OPT|20240119|0||100|SMART||USD||AAPL|0|||"
+    );
+
+    assert_eq!(chain.options.len(), 2, "only listed options are kept");
+    assert_eq!(chain.strikes("20240119"), vec![185.0], "chain.strikes()");
+    assert_eq!(chain.option("20240119", 185.0, "C").unwrap().contract_id, 654321, "call contract id");
+    assert_eq!(chain.option("20240119", 185.0, "P").unwrap().contract_id, 654322, "put contract id");
 }
 
 #[test]
-fn request_matching_symbols() {}
+fn bond_contracts() {
+    let contract = Contract::bond("912828C57");
+    assert_eq!(contract.symbol, "912828C57", "contract.symbol");
+    assert_eq!(contract.security_type, SecurityType::Bond, "contract.security_type");
+
+    let contract = Contract::bond_by_security_id("ISIN", "US912828C570");
+    assert_eq!(contract.symbol, "", "contract.symbol");
+    assert_eq!(contract.security_id_type, "ISIN", "contract.security_id_type");
+    assert_eq!(contract.security_id, "US912828C570", "contract.security_id");
+}
+
+#[test]
+fn cfd_warrant_and_index_contracts() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["52|1|9000||".to_string()],
+    });
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contracts = [
+        Contract::cfd("IBDE30"),
+        Contract::index("SPX", "CBOE", "USD"),
+        Contract::warrant("DAX", "FWB", "EUR", "20241220", 18000.0, "C").with_multiplier("0.01"),
+        Contract::structured_product("AEX", "FTA", "EUR", "20241220", 800.0, "P"),
+    ];
+    for contract in &contracts {
+        let _ = client.contract_details(contract).expect("request contract details failed");
+    }
+
+    let request_messages = client.message_bus.request_messages();
+
+    assert_eq!(request_messages[0].encode_simple(), "9|8|9000|0|IBDE30|CFD||0|||SMART||USD|||0|||", "cfd");
+    assert_eq!(request_messages[1].encode_simple(), "9|8|9001|0|SPX|IND||0|||CBOE||USD|||0|||", "index");
+    assert_eq!(
+        request_messages[2].encode_simple(),
+        "9|8|9002|0|DAX|WAR|20241220|18000|C|0.01|FWB||EUR|||0|||",
+        "warrant"
+    );
+    assert_eq!(
+        request_messages[3].encode_simple(),
+        "9|8|9003|0|AEX|IOPT|20241220|800|P||FTA||EUR|||0|||",
+        "structured product"
+    );
+}
+
+#[test]
+fn security_type_codes() {
+    for (code, security_type) in [
+        ("CFD", SecurityType::Cfd),
+        ("IOPT", SecurityType::StructuredProduct),
+        ("WAR", SecurityType::Warrant),
+    ] {
+        assert_eq!(SecurityType::from(code), security_type, "SecurityType::from({code})");
+        assert_eq!(security_type.to_string(), code, "{security_type:?}.to_string()");
+    }
+}
+
+#[test]
+fn parse_security_types() {
+    assert_eq!("FOP".parse::<SecurityType>().unwrap(), SecurityType::FuturesOption);
+    assert_eq!("BAG".parse::<SecurityType>().unwrap(), SecurityType::Spread);
+    assert!(matches!("XYZ".parse::<SecurityType>(), Err(Error::InvalidArgument(_))));
+
+    // unknown codes from TWS round trip
+    let other = SecurityType::from("SLB");
+    assert_eq!(other, SecurityType::Other("SLB".to_string()));
+    assert_eq!(other.to_string(), "SLB");
+}
+
+#[test]
+fn request_front_month_futures() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "10|9000|ES|CONTFUT|20240315 08:30 US/Central|0||CME|USD|ESH4|ES|ES|551601561|0.25|50|ACTIVETIM,LMT,MKT|CME,QBALGO|1|11004968|E-mini S&P 500||202403||||US/Central|20240314:1700-20240315:0830|20240315:0830-20240315:0830|||0|2|ES|IND|67,67|20240315||1|1|1|".to_string(),
+            "52|1|9000||".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let front = client
+        .front_month_futures(&Contract::continuous_futures("ES", "CME"))
+        .expect("request front month futures failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "9|8|9000|0|ES|CONTFUT||0|||CME||USD|||0|||");
+
+    assert_eq!(front.security_type, SecurityType::Future, "front.security_type");
+    assert_eq!(front.contract_id, 551601561, "front.contract_id");
+    assert_eq!(front.local_symbol, "ESH4", "front.local_symbol");
+    assert_eq!(
+        front.last_trade_date_or_contract_month, "20240315",
+        "front.last_trade_date_or_contract_month"
+    );
+}
+
+#[test]
+fn combo_contract() {
+    let contract = Contract::combo(
+        "WTI",
+        "SMART",
+        "USD",
+        vec![ComboLeg::buy(55928698, 1, "IPE"), ComboLeg::sell(55850663, 1, "IPE")],
+    );
+
+    assert!(contract.is_bag(), "is_bag");
+    assert_eq!(contract.combo_legs.len(), 2);
+    assert_eq!(contract.combo_legs[0].action, "BUY");
+    assert_eq!(contract.combo_legs[1].action, "SELL");
+    assert_eq!(contract.combo_legs[1].exempt_code, -1, "exempt_code");
+    assert_eq!(contract.combo_legs[1].open_close, ComboLegOpenClose::Same, "open_close");
+}
+
+#[test]
+fn request_market_rule_for_exchange() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["93|239|3|0|0.01|10|0.05|100|0.1|".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let details = ContractDetails {
+        valid_exchanges: vec!["SMART".to_owned(), "ISLAND".to_owned()],
+        market_rule_ids: vec!["26".to_owned(), "239".to_owned()],
+        ..ContractDetails::default()
+    };
+
+    assert_eq!(details.market_rule_id("SMART"), Some(26));
+    assert_eq!(details.market_rule_id("NYSE"), None);
+    assert!(matches!(details.market_rule(&client, "NYSE"), Err(Error::InvalidArgument(_))));
+
+    let market_rule = details.market_rule(&client, "ISLAND").expect("request market rule failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "91|239|");
+
+    assert_eq!(market_rule.market_rule_id, 239);
+    assert_eq!(market_rule.increment_at(5.0), Some(0.01));
+    assert_eq!(market_rule.increment_at(10.0), Some(0.05));
+    assert_eq!(market_rule.increment_at(250.0), Some(0.1));
+    assert_eq!(market_rule.round_price(12.33), 12.35);
+    assert_eq!(market_rule.round_price(185.37), 185.4);
+    assert_eq!(market_rule.round_price(3.333), 3.33);
+
+    assert_eq!(MarketRule::default().increment_at(10.0), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn contract_types_implement_serde() {
+    fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>() {}
+
+    assert_serde::<Contract>();
+    assert_serde::<ContractDetails>();
+    assert_serde::<ContractDescription>();
+    assert_serde::<OptionChain>();
+    assert_serde::<ExpandedOptionChain>();
+    assert_serde::<MarketRule>();
+}
+
+#[test]
+fn request_expired_contracts() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "10|9000|ES|FUT|20221216 08:30 US/Central|0||CME|USD|ESZ2|ES|ES|495512552|0.25|50|LMT,MKT|CME|1|11004968|E-mini S&P 500||202212||||US/Central|||||0|2|ES|IND|67|20221216||1|1|1|".to_string(),
+            "10|9000|ES|FUT|20220916 08:30 US/Central|0||CME|USD|ESU2|ES|ES|457646261|0.25|50|LMT,MKT|CME|1|11004968|E-mini S&P 500||202209||||US/Central|||||0|2|ES|IND|67|20220916||1|1|1|".to_string(),
+            "52|1|9000||".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contracts = client
+        .expired_contracts(&futures("ES", "CME", "2022"))
+        .expect("request expired contracts failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "9|8|9000|0|ES|FUT|2022|0|||CME||USD|||1|||");
+
+    assert_eq!(contracts.len(), 2, "contracts.len()");
+    assert_eq!(contracts[0].local_symbol, "ESU2", "contracts[0].local_symbol");
+    assert_eq!(contracts[1].local_symbol, "ESZ2", "contracts[1].local_symbol");
+    assert!(contracts.iter().all(|contract| contract.include_expired), "include_expired");
+}
+
+#[test]
+fn request_contract_details_by_isin() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "10|9000|MSFT|STK||0||SMART|USD|MSFT|NMS|NMS|272093|0.01||LMT,MKT|SMART,ISLAND|1|0|MICROSOFT CORP|NASDAQ||Technology|Software|Applications Software|US/Eastern|||||1|ISIN|US5949181045|1|||26,26||COMMON|1|1|100|".to_string(),
+            "52|1|9000||".to_string(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let details = client
+        .contract_details(&by_isin("US5949181045"))
+        .expect("request contract details failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "9|8|9000|0||STK||0|||SMART|||||0|ISIN|US5949181045|");
+
+    assert_eq!(details.len(), 1, "details.len()");
+    assert_eq!(details[0].contract.symbol, "MSFT", "contract.symbol");
+    assert_eq!(details[0].contract.contract_id, 272093, "contract.contract_id");
+    assert_eq!(details[0].security_id("isin"), Some("US5949181045"), "security_id(ISIN)");
+    assert_eq!(details[0].security_id("FIGI"), None, "security_id(FIGI)");
+
+    assert_eq!(by_cusip("594918104").security_id_type, "CUSIP");
+    assert_eq!(by_figi("BBG000BPH459").security_id_type, "FIGI");
+}
+
+#[test]
+fn round_price_and_size_to_increments() {
+    let details = ContractDetails {
+        min_tick: 0.25,
+        min_size: 1.0,
+        size_increment: 0.1,
+        ..ContractDetails::default()
+    };
+
+    assert_eq!(details.round_price_to_tick(4512.37), 4512.25);
+    assert_eq!(details.round_price_to_tick(4512.38), 4512.5);
+    assert_eq!(details.round_size(2.37), 2.3);
+    assert_eq!(details.round_size(0.3), 0.0, "below minimum size");
+    assert_eq!(details.round_size(1.3), 1.3, "exact multiple");
+
+    let unknown = ContractDetails::default();
+    assert_eq!(unknown.round_price_to_tick(1.234), 1.234, "unknown min tick");
+    assert_eq!(unknown.round_size(1.5), 1.5, "unknown size increment");
+}
+
+#[test]
+fn request_contract_details_without_end_marker() {
+    let message_bus = Arc::new(MessageBusStub::default());
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let result = client.contract_details(&Contract::stock("AAPL"));
+    assert!(matches!(result, Err(Error::UnexpectedEndOfStream)), "{result:?}");
+}
+
+#[test]
+fn request_contract_details_invalid_exchange() {
+    let message_bus = Arc::new(MessageBusStub::default());
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let result = client.contract_details(&Contract::stock("AAPL").with_primary_exchange("NASDAQ ").with_exchange("smart "));
+    assert!(
+        result.is_ok() || !matches!(result, Err(Error::InvalidArgument(_))),
+        "builders normalize names"
+    );
+
+    let contract = Contract {
+        exchange: "smart".to_string(),
+        ..Contract::stock("AAPL")
+    };
+    let result = client.contract_details(&contract);
+    assert!(matches!(result, Err(Error::InvalidArgument(_))), "{result:?}");
+    assert_eq!(client.message_bus.request_messages().len(), 1, "invalid request not sent");
+}
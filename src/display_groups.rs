@@ -0,0 +1,175 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{DataStream, ResponseContext, Subscription, SubscriptionKind},
+    messages::{IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage},
+    server_versions, Client, Error,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Contract displayed in a TWS window group, received when the group changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayGroupUpdate {
+    /// Contract displayed in the group as `contract_id@exchange`, e.g. `265598@SMART`, or `none` if there is no contract.
+    pub contract_info: String,
+}
+
+impl DisplayGroupUpdate {
+    /// Contract id of the displayed contract, or None if no contract is displayed.
+    pub fn contract_id(&self) -> Option<i32> {
+        self.contract_info.split('@').next()?.parse().ok()
+    }
+
+    /// Exchange of the displayed contract, or None if no exchange is given.
+    pub fn exchange(&self) -> Option<&str> {
+        self.contract_info.split_once('@').map(|(_, exchange)| exchange)
+    }
+}
+
+impl DataStream<DisplayGroupUpdate> for DisplayGroupUpdate {
+    const KIND: SubscriptionKind = SubscriptionKind::DisplayGroupEvents;
+
+    fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<DisplayGroupUpdate, Error> {
+        match message.message_type() {
+            IncomingMessages::DisplayGroupUpdated => Ok(decoders::decode_display_group_updated(message.clone())?),
+            _ => Err(Error::UnexpectedResponse(message.clone())),
+        }
+    }
+
+    fn cancel_message(_server_version: i32, request_id: Option<i32>, _context: &ResponseContext) -> Result<RequestMessage, Error> {
+        let request_id = request_id.expect("Request ID required to encode unsubscribe from group events");
+        encoders::encode_unsubscribe_from_group_events(request_id)
+    }
+}
+
+impl Subscription<'_, DisplayGroupUpdate> {
+    /// Displays a contract in the subscribed window group.
+    ///
+    /// # Arguments
+    /// * `contract_info` - Contract to display as `contract_id@exchange`, e.g. `265598@SMART`, or `none` to clear the group.
+    ///
+    /// ```no_run
+    /// use ibapi::Client;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let subscription = client.subscribe_to_group_events(1).expect("error subscribing to group events");
+    /// subscription.update("265598@SMART").expect("error updating display group");
+    /// ```
+    pub fn update(&self, contract_info: &str) -> Result<(), Error> {
+        let request_id = self.request_id().expect("display group subscriptions have a request id");
+        let request = encoders::encode_update_display_group(request_id, contract_info)?;
+        self.client().message_bus.send_message(Some(request_id), &request)
+    }
+}
+
+// Requests the ids of the window groups available in TWS.
+pub(super) fn display_groups(client: &Client) -> Result<Vec<i32>, Error> {
+    client.check_server_version(server_versions::LINKING, "It does not support display groups.")?;
+
+    let request_id = client.next_request_id();
+    let request = encoders::encode_query_display_groups(request_id)?;
+    let subscription = client.send_request(request_id, request)?;
+
+    match subscription.next() {
+        Some(Ok(message)) if message.message_type() == IncomingMessages::DisplayGroupList => decoders::decode_display_group_list(message),
+        Some(Ok(message)) => Err(Error::from_response(message)),
+        Some(Err(Error::ConnectionReset)) => display_groups(client),
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfStream),
+    }
+}
+
+// Subscribes to changes of the contract displayed in a window group.
+pub(super) fn subscribe_to_group_events(client: &Client, group_id: i32) -> Result<Subscription<'_, DisplayGroupUpdate>, Error> {
+    client.check_server_version(server_versions::LINKING, "It does not support display groups.")?;
+
+    let request_id = client.next_request_id();
+    let request = encoders::encode_subscribe_to_group_events(request_id, group_id)?;
+    let subscription = client.send_request(request_id, request)?;
+
+    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+}
+
+mod encoders {
+    use super::*;
+
+    const VERSION: i32 = 1;
+
+    pub(super) fn encode_query_display_groups(request_id: i32) -> Result<RequestMessage, Error> {
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::QueryDisplayGroups);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+
+        Ok(message)
+    }
+
+    pub(super) fn encode_subscribe_to_group_events(request_id: i32, group_id: i32) -> Result<RequestMessage, Error> {
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::SubscribeToGroupEvents);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+        message.push_field(&group_id);
+
+        Ok(message)
+    }
+
+    pub(super) fn encode_update_display_group(request_id: i32, contract_info: &str) -> Result<RequestMessage, Error> {
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::UpdateDisplayGroup);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+        message.push_field(&contract_info);
+
+        Ok(message)
+    }
+
+    pub(super) fn encode_unsubscribe_from_group_events(request_id: i32) -> Result<RequestMessage, Error> {
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::UnsubscribeFromGroupEvents);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+
+        Ok(message)
+    }
+}
+
+mod decoders {
+    use super::*;
+
+    pub(super) fn decode_display_group_list(mut message: ResponseMessage) -> Result<Vec<i32>, Error> {
+        message.skip(); // message type
+        message.skip(); // message version
+        message.skip(); // request id
+
+        let groups = message.next_string()?;
+        groups
+            .split('|')
+            .filter(|group| !group.is_empty())
+            .map(|group| {
+                group
+                    .parse()
+                    .map_err(|e| message.decode_error(format!("invalid display group {group}: {e}")))
+            })
+            .collect()
+    }
+
+    pub(super) fn decode_display_group_updated(mut message: ResponseMessage) -> Result<DisplayGroupUpdate, Error> {
+        message.skip(); // message type
+        message.skip(); // message version
+        message.skip(); // request id
+
+        Ok(DisplayGroupUpdate {
+            contract_info: message.next_string()?,
+        })
+    }
+}
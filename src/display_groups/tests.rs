@@ -0,0 +1,56 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::stubs::MessageBusStub;
+
+#[test]
+fn decodes_display_group_list() {
+    let message = ResponseMessage::from("67\01\09000\01|2|3\0");
+    assert_eq!(decoders::decode_display_group_list(message).unwrap(), vec![1, 2, 3]);
+
+    let message = ResponseMessage::from("67\01\09000\0\0");
+    assert_eq!(decoders::decode_display_group_list(message).unwrap(), Vec::<i32>::new());
+
+    let message = ResponseMessage::from("67\01\09000\01|x\0");
+    assert!(decoders::decode_display_group_list(message).is_err());
+}
+
+#[test]
+fn queries_display_groups() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["67|1|9000|4|".to_owned()],
+    });
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    assert_eq!(client.display_groups().unwrap(), vec![4]);
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests[0].encode_simple(), "67|1|9000|");
+}
+
+#[test]
+fn subscribes_to_group_events() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["68|1|9000|265598@SMART|".to_owned(), "68|1|9000|none|".to_owned()],
+    });
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    let subscription = client.subscribe_to_group_events(1).unwrap();
+
+    let update = subscription.next().unwrap();
+    assert_eq!(update.contract_id(), Some(265598), "contract id");
+    assert_eq!(update.exchange(), Some("SMART"), "exchange");
+
+    let update = subscription.next().unwrap();
+    assert_eq!((update.contract_id(), update.exchange()), (None, None), "no contract");
+
+    subscription.update("8314@SMART").unwrap();
+    drop(subscription);
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests[0].encode_simple(), "68|1|9000|1|", "subscribe");
+    assert_eq!(requests[1].encode_simple(), "69|1|9000|8314@SMART|", "update");
+    assert_eq!(requests[2].encode_simple(), "70|1|9000|", "unsubscribe");
+}
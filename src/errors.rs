@@ -1,6 +1,7 @@
 use std::{num::ParseIntError, string::FromUtf8Error, sync::Arc};
 
-use crate::messages::ResponseMessage;
+use crate::contracts::Contract;
+use crate::messages::{IncomingMessages, Notice, ResponseMessage};
 
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -20,11 +21,96 @@ pub enum Error {
     InvalidArgument(String),
     ConnectionFailed,
     ConnectionReset,
+    /// TWS rejected the connection because another client is connected with the client id.
+    ClientIdInUse(i32),
+    /// The connection to TWS was lost. The client is attempting to reconnect.
+    Disconnected,
+    /// The client reconnected to another TWS or Gateway endpoint, given by its address, after the connection was lost.
+    Failover(String),
     Cancelled,
+    /// TWS did not respond to a request within the configured timeout.
+    Timeout,
+    /// A blocking request fell behind and the given number of messages were dropped because its queue was full.
+    Lagged(u64),
     Shutdown,
     EndOfStream,
     UnexpectedResponse(ResponseMessage),
     UnexpectedEndOfStream,
+    /// TWS announced a message longer than the maximum message length. The connection can no longer be read reliably.
+    MessageTooLong(usize),
+    /// A message could not be decoded, e.g. TWS sent fewer fields than expected for the message version.
+    Decode {
+        /// Index of the offending field.
+        index: usize,
+        reason: String,
+        /// The message as received.
+        message: ResponseMessage,
+    },
+    AmbiguousContract(Vec<Contract>),
+    /// No contract matched the description, e.g. the symbol of the requested contract.
+    ContractNotFound(String),
+    /// Text that is not a TWS message could not be parsed, e.g. an XML document or a file written by this crate.
+    InvalidFormat {
+        /// What was being parsed, e.g. `trading hours`.
+        kind: &'static str,
+        /// The offending text, or why it could not be parsed.
+        reason: String,
+    },
+    /// TWS reported a time zone name that is not in the time zone database.
+    UnknownTimeZone(String),
+    /// The proxy, given by its address, failed to open a connection to TWS.
+    Proxy {
+        address: String,
+        reason: String,
+    },
+    /// Error reported by TWS for a request.
+    Tws {
+        code: i32,
+        message: String,
+        request_id: i32,
+    },
+}
+
+impl Error {
+    /// TWS error code, if the error was reported by TWS.
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Error::Tws { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Category of the TWS error code, if the error was reported by TWS.
+    pub fn category(&self) -> Option<ErrorCategory> {
+        self.code().map(ErrorCategory::from_code)
+    }
+
+    /// Returns true if the request may succeed when retried later, e.g. after a pacing violation or a connection reset.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::ConnectionReset | Error::Timeout => true,
+            _ => self.category().is_some_and(|category| category.is_retryable()),
+        }
+    }
+
+    /// Returns true if TWS reported a warning rather than a failed request.
+    pub fn is_warning(&self) -> bool {
+        self.category().is_some_and(|category| category.is_warning())
+    }
+
+    // Converts an error message from TWS into [Error::Tws]. Other messages are unexpected responses.
+    pub(crate) fn from_response(message: ResponseMessage) -> Error {
+        if message.message_type() != IncomingMessages::Error {
+            return Error::UnexpectedResponse(message);
+        }
+
+        let notice = Notice::from(&message);
+        Error::Tws {
+            code: notice.code,
+            message: notice.message,
+            request_id: message.peek_int(2).unwrap_or(-1),
+        }
+    }
 }
 
 impl std::error::Error for Error {}
@@ -43,11 +129,36 @@ impl std::fmt::Display for Error {
             Error::ServerVersion(wanted, have, message) => write!(f, "server version {wanted} required, got {have}: {message}"),
             Error::ConnectionFailed => write!(f, "ConnectionFailed"),
             Error::ConnectionReset => write!(f, "ConnectionReset"),
+            Error::ClientIdInUse(client_id) => write!(f, "client id {client_id} is already in use"),
+            Error::Disconnected => write!(f, "Disconnected"),
+            Error::Failover(address) => write!(f, "failed over to {address}"),
             Error::Cancelled => write!(f, "Cancelled"),
+            Error::Timeout => write!(f, "Timeout"),
+            Error::Lagged(dropped) => write!(f, "subscription fell behind, {dropped} messages were dropped"),
             Error::Shutdown => write!(f, "Shutdown"),
             Error::EndOfStream => write!(f, "EndOfStream"),
             Error::UnexpectedResponse(message) => write!(f, "UnexpectedResponse: {:?}", message),
             Error::UnexpectedEndOfStream => write!(f, "UnexpectedEndOfStream"),
+            Error::MessageTooLong(length) => write!(f, "message of {length} bytes exceeds the maximum message length"),
+            Error::Decode { index, reason, message } => write!(f, "decode error at field {index}: {reason}: {message:?}"),
+            Error::AmbiguousContract(candidates) => {
+                let candidates: Vec<String> = candidates
+                    .iter()
+                    .map(|contract| {
+                        format!(
+                            "{} {} {} {} ({})",
+                            contract.symbol, contract.security_type, contract.primary_exchange, contract.currency, contract.contract_id
+                        )
+                    })
+                    .collect();
+                write!(f, "AmbiguousContract: {}", candidates.join(", "))
+            }
+
+            Error::ContractNotFound(description) => write!(f, "no contract found for {description}"),
+            Error::InvalidFormat { kind, reason } => write!(f, "invalid {kind}: {reason}"),
+            Error::UnknownTimeZone(name) => write!(f, "unknown time zone: {name}"),
+            Error::Proxy { address, reason } => write!(f, "proxy {address}: {reason}"),
+            Error::Tws { code, message, request_id } => write!(f, "TWS error [{code}] {message} (request {request_id})"),
 
             Error::Simple(ref err) => write!(f, "error occurred: {err}"),
             Error::InvalidArgument(ref err) => write!(f, "InvalidArgument: {err}"),
@@ -55,6 +166,52 @@ impl std::fmt::Display for Error {
     }
 }
 
+/// Category of a TWS error code.
+///
+/// See [TWS error codes](https://interactivebrokers.github.io/tws-api/message_codes.html) for the full list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Connection between the API client, TWS and IB servers was lost or restored, e.g. 1100-1102.
+    Connectivity,
+    /// Request rate limits were exceeded, e.g. historical data pacing violations (162) and real-time bar limits (420).
+    Pacing,
+    /// The account lacks market data subscriptions or trading permissions.
+    Permission,
+    /// The order was rejected or cancelled.
+    OrderRejected,
+    /// The request was malformed or referenced an unknown contract, e.g. no security definition found (200).
+    InvalidRequest,
+    /// Informational message, e.g. market data farm connection status (2100-2169).
+    Warning,
+    /// Code not categorized by this crate.
+    Other,
+}
+
+impl ErrorCategory {
+    /// Categorizes a TWS error code.
+    pub fn from_code(code: i32) -> ErrorCategory {
+        match code {
+            502 | 504 | 507 | 1100..=1102 | 1300 | 2110 => ErrorCategory::Connectivity,
+            100 | 162 | 420 => ErrorCategory::Pacing,
+            203 | 354 | 10089 | 10090 | 10168 | 10186 => ErrorCategory::Permission,
+            103..=111 | 113 | 116..=118 | 201 | 202 | 10147 | 10148 => ErrorCategory::OrderRejected,
+            200 | 300 | 320..=322 | 10187 => ErrorCategory::InvalidRequest,
+            399 | 2100..=2169 | 10167 => ErrorCategory::Warning,
+            _ => ErrorCategory::Other,
+        }
+    }
+
+    /// Returns true if requests failing with the category may succeed when retried later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ErrorCategory::Connectivity | ErrorCategory::Pacing)
+    }
+
+    /// Returns true for informational messages that do not fail a request.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, ErrorCategory::Warning)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
         Error::Io(Arc::new(err))
@@ -94,6 +251,42 @@ mod tests {
     use time::macros::format_description;
     use time::Time;
 
+    #[test]
+    fn test_error_categories() {
+        for (code, category) in [
+            (1100, ErrorCategory::Connectivity),
+            (162, ErrorCategory::Pacing),
+            (420, ErrorCategory::Pacing),
+            (354, ErrorCategory::Permission),
+            (201, ErrorCategory::OrderRejected),
+            (200, ErrorCategory::InvalidRequest),
+            (2104, ErrorCategory::Warning),
+            (9999, ErrorCategory::Other),
+        ] {
+            assert_eq!(ErrorCategory::from_code(code), category, "ErrorCategory::from_code({code})");
+        }
+
+        let pacing = Error::Tws {
+            code: 162,
+            message: "Historical Market Data Service error message:API historical data query cancelled".into(),
+            request_id: 9000,
+        };
+        assert_eq!(pacing.code(), Some(162));
+        assert!(pacing.is_retryable());
+        assert!(!pacing.is_warning());
+
+        let warning = Error::Tws {
+            code: 2106,
+            message: "HMDS data farm connection is OK:ushmds".into(),
+            request_id: -1,
+        };
+        assert!(warning.is_warning());
+        assert!(!warning.is_retryable());
+
+        assert!(Error::ConnectionReset.is_retryable());
+        assert_eq!(Error::Cancelled.category(), None);
+    }
+
     #[test]
     fn test_error_debug() {
         let error = Error::Simple("test error".to_string());
@@ -127,8 +320,68 @@ mod tests {
                 "server version 2 required, got 1: old version",
             ),
             (Error::ConnectionFailed, "ConnectionFailed"),
+            (Error::ClientIdInUse(100), "client id 100 is already in use"),
             (Error::Cancelled, "Cancelled"),
+            (Error::Disconnected, "Disconnected"),
+            (Error::Failover("10.0.0.6:4002".to_string()), "failed over to 10.0.0.6:4002"),
+            (Error::Timeout, "Timeout"),
+            (Error::Lagged(3), "subscription fell behind, 3 messages were dropped"),
+            (
+                Error::MessageTooLong(16777216),
+                "message of 16777216 bytes exceeds the maximum message length",
+            ),
+            (
+                Error::Decode {
+                    index: 3,
+                    reason: "expected field 3 of message with 3 fields".into(),
+                    message: ResponseMessage::from("4\x002\x009000"),
+                },
+                r#"decode error at field 3: expected field 3 of message with 3 fields: ResponseMessage { i: 0, fields: ["4", "2", "9000"] }"#,
+            ),
             (Error::Simple("simple error".to_string()), "error occurred: simple error"),
+            (Error::ContractNotFound("AAPL".to_string()), "no contract found for AAPL"),
+            (
+                Error::InvalidFormat {
+                    kind: "trading hours",
+                    reason: "20221231".to_string(),
+                },
+                "invalid trading hours: 20221231",
+            ),
+            (Error::UnknownTimeZone("MST7".to_string()), "unknown time zone: MST7"),
+            (
+                Error::Proxy {
+                    address: "10.0.0.5:1080".to_string(),
+                    reason: "authentication failed".to_string(),
+                },
+                "proxy 10.0.0.5:1080: authentication failed",
+            ),
+            (
+                Error::AmbiguousContract(vec![
+                    Contract {
+                        contract_id: 4815747,
+                        symbol: "NVDA".to_string(),
+                        primary_exchange: "NASDAQ".to_string(),
+                        currency: "USD".to_string(),
+                        ..Contract::default()
+                    },
+                    Contract {
+                        contract_id: 81203821,
+                        symbol: "NVDA".to_string(),
+                        primary_exchange: "MEXI".to_string(),
+                        currency: "MXN".to_string(),
+                        ..Contract::default()
+                    },
+                ]),
+                "AmbiguousContract: NVDA STK NASDAQ USD (4815747), NVDA STK MEXI MXN (81203821)",
+            ),
+            (
+                Error::Tws {
+                    code: 200,
+                    message: "No security definition has been found for the request".into(),
+                    request_id: 9000,
+                },
+                "TWS error [200] No security definition has been found for the request (request 9000)",
+            ),
         ];
 
         for (error, expected) in cases {
@@ -144,7 +397,7 @@ mod tests {
 
     #[test]
     fn test_from_io_error() {
-        let io_error = io::Error::new(io::ErrorKind::Other, "io error");
+        let io_error = io::Error::other("io error");
         let error: Error = io_error.into();
         assert!(matches!(error, Error::Io(_)));
     }
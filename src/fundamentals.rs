@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    contracts::Contract,
+    messages::{IncomingMessages, RequestMessage, ResponseMessage},
+    server_versions, Client, Error, ToField,
+};
+
+pub use reports::{CompanySnapshot, FundamentalRatios, Industry};
+
+mod reports;
+#[cfg(test)]
+mod tests;
+
+/// Type of fundamental data report, from Refinitiv (formerly Reuters). Requires a fundamental data subscription.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FundamentalReportType {
+    /// Company overview, e.g. market cap, sector and latest financials.
+    ReportSnapshot,
+    /// Financial summary.
+    ReportsFinSummary,
+    /// Financial ratios.
+    ReportRatios,
+    /// Financial statements.
+    ReportsFinStatements,
+    /// Analyst estimates.
+    Resc,
+}
+
+impl FundamentalReportType {
+    /// Name of the report type used by TWS.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FundamentalReportType::ReportSnapshot => "ReportSnapshot",
+            FundamentalReportType::ReportsFinSummary => "ReportsFinSummary",
+            FundamentalReportType::ReportRatios => "ReportRatios",
+            FundamentalReportType::ReportsFinStatements => "ReportsFinStatements",
+            FundamentalReportType::Resc => "RESC",
+        }
+    }
+}
+
+impl ToField for FundamentalReportType {
+    fn to_field(&self) -> String {
+        self.as_str().to_owned()
+    }
+}
+
+// Requests a fundamental data report as an XML document, cancelling the request if it times out.
+pub(super) fn fundamental_data(
+    client: &Client,
+    contract: &Contract,
+    report_type: FundamentalReportType,
+    timeout: Option<Duration>,
+) -> Result<String, Error> {
+    client.check_server_version(server_versions::FUNDAMENTAL_DATA, "It does not support fundamental data requests.")?;
+
+    let request_id = client.next_request_id();
+    let request = encoders::encode_request_fundamental_data(client.server_version(), request_id, contract, report_type)?;
+    let subscription = client.send_request(request_id, request)?;
+
+    match subscription.next_within(timeout) {
+        Some(Ok(message)) if message.message_type() == IncomingMessages::FundamentalData => decoders::decode_fundamental_data(message),
+        Some(Ok(message)) => Err(Error::from_response(message)),
+        Some(Err(Error::ConnectionReset)) => fundamental_data(client, contract, report_type, timeout),
+        Some(Err(Error::Timeout)) => {
+            client
+                .message_bus
+                .cancel_subscription(request_id, &encoders::encode_cancel_fundamental_data(request_id)?)?;
+            Err(Error::Timeout)
+        }
+        Some(Err(e)) => Err(e),
+        None => Err(Error::UnexpectedEndOfStream),
+    }
+}
+
+mod encoders {
+    use super::*;
+    use crate::messages::OutgoingMessages;
+
+    pub(super) fn encode_request_fundamental_data(
+        server_version: i32,
+        request_id: i32,
+        contract: &Contract,
+        report_type: FundamentalReportType,
+    ) -> Result<RequestMessage, Error> {
+        const VERSION: i32 = 2;
+
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::RequestFundamentalData);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+        if server_version >= server_versions::TRADING_CLASS {
+            message.push_field(&contract.contract_id);
+        }
+        message.push_field(&contract.symbol);
+        message.push_field(&contract.security_type);
+        message.push_field(&contract.exchange);
+        message.push_field(&contract.primary_exchange);
+        message.push_field(&contract.currency);
+        message.push_field(&contract.local_symbol);
+        message.push_field(&report_type);
+        if server_version >= server_versions::LINKING {
+            message.push_field(&""); // ignore fundamental data options
+        }
+
+        Ok(message)
+    }
+
+    pub(super) fn encode_cancel_fundamental_data(request_id: i32) -> Result<RequestMessage, Error> {
+        const VERSION: i32 = 1;
+
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::CancelFundamentalData);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+
+        Ok(message)
+    }
+}
+
+mod decoders {
+    use super::*;
+
+    pub(super) fn decode_fundamental_data(mut message: ResponseMessage) -> Result<String, Error> {
+        message.skip(); // message type
+        message.skip(); // message version
+        message.skip(); // request id
+
+        message.next_string()
+    }
+}
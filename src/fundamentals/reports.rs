@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::xml::Element;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+// Value reported by Refinitiv when a ratio is not available.
+const NOT_AVAILABLE: &str = "-99999.99";
+
+/// Financial ratios from a [ReportRatios](super::FundamentalReportType::ReportRatios) or [ReportSnapshot](super::FundamentalReportType::ReportSnapshot) report.
+///
+/// Ratios are identified by their Refinitiv field names, e.g. MKTCAP. Accessors are provided for the most common ratios.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FundamentalRatios {
+    /// Currency of price based ratios.
+    pub price_currency: String,
+    /// Currency the company reports in.
+    pub reporting_currency: String,
+    /// Date of the latest data used, e.g. 2023-04-06.
+    pub latest_available_date: String,
+    /// Raw ratio values by field name.
+    pub values: BTreeMap<String, String>,
+}
+
+impl FundamentalRatios {
+    /// Parses the XML document of a [ReportRatios](super::FundamentalReportType::ReportRatios) report.
+    pub fn parse(xml: &str) -> Result<FundamentalRatios, Error> {
+        let root = Element::parse(xml)?;
+        Ok(root.child("Ratios").map(parse_ratios).unwrap_or_default())
+    }
+
+    /// Numeric value of the ratio with the given field name, or None if the ratio is missing or not available.
+    pub fn value(&self, field_name: &str) -> Option<f64> {
+        let value = self.values.get(field_name)?.trim();
+        if value == NOT_AVAILABLE {
+            return None;
+        }
+        value.replace(',', "").parse().ok()
+    }
+
+    /// Last price.
+    pub fn price(&self) -> Option<f64> {
+        self.value("NPRICE")
+    }
+
+    /// Market capitalization, in millions.
+    pub fn market_cap(&self) -> Option<f64> {
+        self.value("MKTCAP")
+    }
+
+    /// Price to earnings ratio, excluding extraordinary items, for the trailing twelve months.
+    pub fn price_to_earnings(&self) -> Option<f64> {
+        self.value("PEEXCLXOR")
+    }
+
+    /// Earnings per share, excluding extraordinary items, for the trailing twelve months.
+    pub fn earnings_per_share(&self) -> Option<f64> {
+        self.value("TTMEPSXCLX")
+    }
+
+    /// Dividend yield, in percent.
+    pub fn dividend_yield(&self) -> Option<f64> {
+        self.value("YIELD")
+    }
+
+    /// Price to book ratio for the most recent quarter.
+    pub fn price_to_book(&self) -> Option<f64> {
+        self.value("PRICE2BK")
+    }
+
+    /// Beta.
+    pub fn beta(&self) -> Option<f64> {
+        self.value("BETA")
+    }
+}
+
+/// Company overview from a [ReportSnapshot](super::FundamentalReportType::ReportSnapshot) report.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompanySnapshot {
+    /// Name of the company.
+    pub company_name: String,
+    /// Number of employees.
+    pub employees: Option<i64>,
+    /// Number of shares outstanding.
+    pub shares_outstanding: Option<f64>,
+    /// Currency the company reports in, e.g. USD.
+    pub reporting_currency: String,
+    /// Industry classifications of the company.
+    pub industries: Vec<Industry>,
+    /// Summary of the company's business.
+    pub business_summary: String,
+    /// Financial ratios.
+    pub ratios: FundamentalRatios,
+}
+
+impl CompanySnapshot {
+    /// Parses the XML document of a [ReportSnapshot](super::FundamentalReportType::ReportSnapshot) report.
+    pub fn parse(xml: &str) -> Result<CompanySnapshot, Error> {
+        let root = Element::parse(xml)?;
+
+        let company_name = root
+            .child("CoIDs")
+            .into_iter()
+            .flat_map(|ids| ids.children_named("CoID"))
+            .find(|id| id.attribute("Type") == Some("CompanyName"))
+            .map(|id| id.text.trim().to_owned())
+            .unwrap_or_default();
+
+        let general = root.child("CoGeneralInfo");
+        let employees = general.map(|general| general.child_text("Employees")).unwrap_or_default();
+        let shares_outstanding = general.map(|general| general.child_text("SharesOut")).unwrap_or_default();
+        let reporting_currency = general
+            .and_then(|general| general.child("ReportingCurrency"))
+            .and_then(|currency| currency.attribute("Code"))
+            .unwrap_or_default();
+
+        let industries = root
+            .child("peerInfo")
+            .and_then(|peers| peers.child("IndustryInfo"))
+            .map(|info| info.children_named("Industry").map(parse_industry).collect())
+            .unwrap_or_default();
+
+        let business_summary = root
+            .child("TextInfo")
+            .into_iter()
+            .flat_map(|info| info.children_named("Text"))
+            .find(|text| text.attribute("Type") == Some("Business Summary"))
+            .map(|text| text.text.trim().to_owned())
+            .unwrap_or_default();
+
+        Ok(CompanySnapshot {
+            company_name,
+            employees: parse_optional(employees, "employees")?,
+            shares_outstanding: parse_optional(shares_outstanding, "shares outstanding")?,
+            reporting_currency: reporting_currency.to_owned(),
+            industries,
+            business_summary,
+            ratios: root.child("Ratios").map(parse_ratios).unwrap_or_default(),
+        })
+    }
+
+    /// Returns the primary industry of the given classification, e.g. TRBC, NAICS or SIC.
+    pub fn industry(&self, classification: &str) -> Option<&Industry> {
+        self.industries
+            .iter()
+            .filter(|industry| industry.classification == classification)
+            .min_by_key(|industry| industry.order)
+    }
+}
+
+/// An industry classification of a company.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Industry {
+    /// Classification scheme, e.g. TRBC, NAICS or SIC.
+    pub classification: String,
+    /// Rank of the industry among the company's industries of the same classification, starting from 1 for the primary industry.
+    pub order: i32,
+    /// Industry code within the classification.
+    pub code: String,
+    /// Description of the industry, e.g. Phones & Handheld Devices.
+    pub description: String,
+}
+
+fn parse_ratios(element: &Element) -> FundamentalRatios {
+    let attribute = |name| element.attribute(name).unwrap_or_default().to_owned();
+
+    let values = element
+        .children_named("Group")
+        .flat_map(|group| group.children_named("Ratio"))
+        .filter_map(|ratio| Some((ratio.attribute("FieldName")?.to_owned(), ratio.text.trim().to_owned())))
+        .collect();
+
+    FundamentalRatios {
+        price_currency: attribute("PriceCurrency"),
+        reporting_currency: attribute("ReportingCurrency"),
+        latest_available_date: attribute("LatestAvailableDate"),
+        values,
+    }
+}
+
+fn parse_industry(element: &Element) -> Industry {
+    Industry {
+        classification: element.attribute("type").unwrap_or_default().to_owned(),
+        order: element.attribute("order").and_then(|order| order.parse().ok()).unwrap_or_default(),
+        code: element.attribute("code").unwrap_or_default().to_owned(),
+        description: element.text.trim().to_owned(),
+    }
+}
+
+fn parse_optional<T: std::str::FromStr>(text: &str, name: &str) -> Result<Option<T>, Error>
+where
+    T::Err: std::fmt::Display,
+{
+    match text {
+        "" => Ok(None),
+        text => text.parse().map(Some).map_err(|e| Error::InvalidFormat {
+            kind: "fundamental data",
+            reason: format!("{name} {text}: {e}"),
+        }),
+    }
+}
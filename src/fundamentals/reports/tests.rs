@@ -0,0 +1,105 @@
+use super::*;
+
+const RATIOS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ReportRatios Major="1" Minor="0" Revision="1">
+  <CoIDs><CoID Type="CompanyName">Apple Inc</CoID></CoIDs>
+  <Ratios PriceCurrency="USD" ReportingCurrency="USD" ExchangeRate="1.00000" LatestAvailableDate="2023-04-01">
+    <Group ID="Price and Volume">
+      <Ratio FieldName="NPRICE" Type="N">164.66000</Ratio>
+      <Ratio FieldName="BETA" Type="N">-99999.99</Ratio>
+    </Group>
+    <Group ID="Income Statement">
+      <Ratio FieldName="MKTCAP" Type="N">2,605,047.00000</Ratio>
+    </Group>
+    <Group ID="Per share data">
+      <Ratio FieldName="TTMEPSXCLX" Type="N">5.89551</Ratio>
+    </Group>
+    <Group ID="Other Ratios">
+      <Ratio FieldName="PEEXCLXOR" Type="N">27.92972</Ratio>
+      <Ratio FieldName="YIELD" Type="N">0.55873</Ratio>
+      <Ratio FieldName="LATESTADATE" Type="D">2022-12-31T00:00:00</Ratio>
+    </Group>
+  </Ratios>
+</ReportRatios>"#;
+
+const SNAPSHOT_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ReportSnapshot Major="1" Minor="0" Revision="1">
+  <CoIDs>
+    <CoID Type="RepNo">05680</CoID>
+    <CoID Type="CompanyName">Apple Inc.</CoID>
+  </CoIDs>
+  <CoGeneralInfo>
+    <CoStatus Code="1">Active</CoStatus>
+    <Employees LastUpdated="2022-09-24">164000</Employees>
+    <SharesOut Date="2023-01-20" TotalFloat="15805738107.0">15821946000.0</SharesOut>
+    <ReportingCurrency Code="USD">U.S. Dollars</ReportingCurrency>
+  </CoGeneralInfo>
+  <TextInfo>
+    <Text Type="Financial Summary" lastModified="2023-02-03">Revenue fell.</Text>
+    <Text Type="Business Summary" lastModified="2023-02-03">Apple designs smartphones &amp; computers.</Text>
+  </TextInfo>
+  <peerInfo lastUpdated="2023-02-03">
+    <IndustryInfo>
+      <Industry type="TRBC" order="2" reported="0" code="5710" mnem="">Computer Hardware</Industry>
+      <Industry type="TRBC" order="1" reported="0" code="5710601010" mnem="">Phones &amp; Handheld Devices</Industry>
+      <Industry type="NAICS" order="1" reported="0" code="334220" mnem="">Radio and Television Broadcasting</Industry>
+    </IndustryInfo>
+  </peerInfo>
+  <Ratios PriceCurrency="USD" ReportingCurrency="USD" ExchangeRate="1.00000" LatestAvailableDate="2022-12-31">
+    <Group ID="Price and Volume">
+      <Ratio FieldName="NPRICE" Type="N">164.66000</Ratio>
+    </Group>
+  </Ratios>
+</ReportSnapshot>"#;
+
+#[test]
+fn parses_fundamental_ratios() {
+    let ratios = FundamentalRatios::parse(RATIOS_XML).unwrap();
+
+    assert_eq!(ratios.price_currency, "USD");
+    assert_eq!(ratios.reporting_currency, "USD");
+    assert_eq!(ratios.latest_available_date, "2023-04-01");
+
+    assert_eq!(ratios.price(), Some(164.66));
+    assert_eq!(ratios.market_cap(), Some(2_605_047.0));
+    assert_eq!(ratios.earnings_per_share(), Some(5.89551));
+    assert_eq!(ratios.price_to_earnings(), Some(27.92972));
+    assert_eq!(ratios.dividend_yield(), Some(0.55873));
+    assert_eq!(ratios.beta(), None, "not available");
+    assert_eq!(ratios.price_to_book(), None, "missing");
+    assert_eq!(ratios.value("LATESTADATE"), None, "not numeric");
+    assert_eq!(ratios.values["LATESTADATE"], "2022-12-31T00:00:00");
+}
+
+#[test]
+fn parses_company_snapshot() {
+    let snapshot = CompanySnapshot::parse(SNAPSHOT_XML).unwrap();
+
+    assert_eq!(snapshot.company_name, "Apple Inc.");
+    assert_eq!(snapshot.employees, Some(164000));
+    assert_eq!(snapshot.shares_outstanding, Some(15821946000.0));
+    assert_eq!(snapshot.reporting_currency, "USD");
+    assert_eq!(snapshot.business_summary, "Apple designs smartphones & computers.");
+    assert_eq!(snapshot.industries.len(), 3, "industries");
+    assert_eq!(
+        snapshot.industry("TRBC"),
+        Some(&Industry {
+            classification: "TRBC".into(),
+            order: 1,
+            code: "5710601010".into(),
+            description: "Phones & Handheld Devices".into(),
+        })
+    );
+    assert_eq!(snapshot.industry("SIC"), None);
+    assert_eq!(snapshot.ratios.price(), Some(164.66));
+}
+
+#[test]
+fn rejects_malformed_company_snapshot() {
+    let xml = "<ReportSnapshot><CoGeneralInfo><Employees>many</Employees></CoGeneralInfo></ReportSnapshot>";
+    assert_eq!(
+        CompanySnapshot::parse(xml).unwrap_err().to_string(),
+        "invalid fundamental data: employees many: invalid digit found in string"
+    );
+    assert!(FundamentalRatios::parse("<ReportRatios>").is_err());
+}
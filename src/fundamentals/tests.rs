@@ -0,0 +1,56 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use super::*;
+use crate::stubs::MessageBusStub;
+
+#[test]
+fn requests_fundamental_data() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["51|1|9000|<ReportSnapshot><CoIDs/></ReportSnapshot>|".to_owned()],
+    });
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    let contract = Contract {
+        contract_id: 265598,
+        ..Contract::stock("AAPL")
+    };
+    let xml = client.fundamental_data(&contract, FundamentalReportType::ReportSnapshot).unwrap();
+    assert_eq!(xml, "<ReportSnapshot><CoIDs/></ReportSnapshot>");
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests[0].encode_simple(), "52|2|9000|265598|AAPL|STK|SMART||USD||ReportSnapshot||");
+}
+
+#[test]
+fn reports_fundamental_data_errors() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["4|2|9000|430|We are sorry, but fundamentals data for the security specified is not available.|".to_owned()],
+    });
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let result = client.fundamental_data(&Contract::stock("AAPL"), FundamentalReportType::Resc);
+    assert!(matches!(result, Err(Error::Tws { code: 430, .. })), "{result:?}");
+}
+
+#[test]
+fn cancels_fundamental_data_on_timeout() {
+    let tws = crate::testing::MockTws::new().start().expect("error starting mock TWS");
+    let client = Client::connect(&tws.address(), 100).expect("connection failed");
+
+    let result = client
+        .with_timeout(Duration::from_millis(100))
+        .fundamental_data(&Contract::stock("AAPL"), FundamentalReportType::ReportRatios);
+    assert!(matches!(result, Err(Error::Timeout)), "{result:?}");
+
+    // the cancel is written after the timeout, so it may not have been received yet
+    for _ in 0..50 {
+        if tws.requests().len() == 2 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(tws.requests()[1], "53|1|9000|", "cancel fundamental data");
+}
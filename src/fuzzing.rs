@@ -0,0 +1,184 @@
+use std::io::Cursor;
+use std::sync::{Arc, RwLock};
+
+#[cfg(any(feature = "historical", feature = "news"))]
+use time::macros::datetime;
+
+#[cfg(feature = "fa")]
+use crate::accounts::fa::{FaConfiguration, FaDataType};
+use crate::contracts::{Contract, SecurityType};
+#[cfg(feature = "fundamentals")]
+use crate::fundamentals::FundamentalReportType;
+#[cfg(feature = "historical")]
+use crate::market_data::historical::{self, ToDuration};
+#[cfg(feature = "market-data")]
+use crate::market_data::realtime::{BarSize, WhatToShow};
+use crate::messages::ResponseMessage;
+use crate::orders::{order_builder, Action, ExecutionFilter, ExerciseAction};
+#[cfg(feature = "scanner")]
+use crate::scanner::ScannerSubscription;
+use crate::stubs::MessageBusStub;
+use crate::transport::{read_packet, BufferPool, MIN_SERVER_VERSION};
+use crate::{server_versions, Client};
+
+#[cfg(test)]
+mod tests;
+
+/// Splits `data` into length prefixed messages, as read from the connection to TWS, and reads the fields used to route each message.
+pub fn framing(data: &[u8]) {
+    let pool = BufferPool::default();
+    let mut reader = Cursor::new(data);
+    while let Ok(raw) = read_packet(&mut reader, &pool) {
+        let message = ResponseMessage::from_data(raw);
+        let _ = message.message_type();
+        let _ = message.request_id();
+        let _ = message.order_id();
+        let _ = message.execution_id();
+        let _ = message.peek_int(2);
+    }
+}
+
+/// Answers every request of a client with the messages in `data`, one per line with `|` separating fields, and decodes the responses.
+///
+/// The first byte selects the server version the client reports, as decoders read different fields for different versions.
+pub fn decode(data: &[u8]) {
+    let Some((version, data)) = data.split_first() else {
+        return;
+    };
+    let server_version = MIN_SERVER_VERSION + i32::from(*version) % (server_versions::FA_PROFILE_DESUPPORT - MIN_SERVER_VERSION + 1);
+
+    let text = String::from_utf8_lossy(data);
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: text.lines().map(|line| line.to_owned()).collect(),
+    });
+    let client = Client::stubbed(message_bus, server_version);
+
+    decode_responses(&client);
+}
+
+// Iterates a subscription until the stubbed responses run out.
+macro_rules! drain {
+    ($subscription:expr) => {
+        if let Ok(subscription) = $subscription {
+            for _ in subscription.iter() {}
+        }
+    };
+}
+
+fn decode_responses(client: &Client) {
+    let stock = Contract::stock("AAPL");
+    #[cfg(any(feature = "historical", feature = "news"))]
+    let start = datetime!(2023-04-10 14:00 UTC);
+    #[cfg(any(feature = "historical", feature = "news"))]
+    let end = datetime!(2023-04-11 14:00 UTC);
+    let order = order_builder::limit_order(Action::Buy, 100.0, 150.0);
+
+    // accounts
+    let _ = client.server_time();
+    drain!(client.positions());
+    drain!(client.positions_multi(Some("DU1234567"), None));
+    drain!(client.pnl("DU1234567", None));
+    drain!(client.pnl_single("DU1234567", 265598, None));
+    drain!(client.account_summary("All", &["NetLiquidation"]));
+    drain!(client.account_updates("DU1234567"));
+    drain!(client.account_updates_multi(Some("DU1234567"), None));
+    let _ = client.managed_accounts();
+    let _ = client.user_info();
+    let _ = client.family_codes();
+    let _ = client.model("DU1234567", "MODEL");
+    #[cfg(feature = "fa")]
+    {
+        let _ = client.request_fa(FaDataType::Groups);
+        let _ = client.request_fa(FaDataType::Profiles);
+        let _ = client.request_fa(FaDataType::Aliases);
+    }
+
+    // contracts
+    let _ = client.contract_details(&stock);
+    let _ = client.market_rule(26);
+    let _ = client.matching_symbols("AA");
+    let _ = client.calculate_option_price(&stock, 0.25, 150.0);
+    let _ = client.calculate_implied_volatility(&stock, 5.0, 150.0);
+    drain!(client.option_chain("AAPL", "", SecurityType::Stock, 265598));
+
+    // orders
+    drain!(client.all_open_orders());
+    drain!(client.auto_open_orders(false));
+    drain!(client.cancel_order(90, ""));
+    drain!(client.completed_orders(false));
+    drain!(client.executions(ExecutionFilter::default()));
+    let _ = client.soft_dollar_tiers();
+    drain!(client.open_orders());
+    drain!(client.place_order(90, &stock, &order));
+    let _ = client.what_if_margin(&[(stock.clone(), order.clone())]);
+    drain!(client.exercise_options(&stock, ExerciseAction::Exercise, 1, "DU1234567", false, None));
+
+    #[cfg(feature = "historical")]
+    {
+        let _ = client.head_timestamp(&stock, historical::WhatToShow::Trades, true);
+        let _ = client.historical_data(&stock, end, 1.days(), historical::BarSize::Hour, historical::WhatToShow::Trades, true);
+        let _ = client.historical_schedules(&stock, end, 1.days());
+        if let Ok(ticks) = client.historical_ticks_bid_ask(&stock, Some(start), None, 100, true, false) {
+            for _ in ticks.iter() {}
+        }
+        if let Ok(ticks) = client.historical_ticks_mid_point(&stock, Some(start), None, 100, true) {
+            for _ in ticks.iter() {}
+        }
+        if let Ok(ticks) = client.historical_ticks_trade(&stock, Some(start), None, 100, true) {
+            for _ in ticks.iter() {}
+        }
+        let _ = client.histogram_data(&stock, true, historical::BarSize::Week);
+    }
+
+    #[cfg(feature = "market-data")]
+    {
+        drain!(client.realtime_bars(&stock, BarSize::Sec5, WhatToShow::Trades, false));
+        drain!(client.tick_by_tick_all_last(&stock, 0, false));
+        drain!(client.tick_by_tick_bid_ask(&stock, 0, false));
+        drain!(client.tick_by_tick_last(&stock, 0, false));
+        drain!(client.tick_by_tick_midpoint(&stock, 0, false));
+        drain!(client.market_depth(&stock, 5, true));
+        let _ = client.market_depth_exchanges();
+        drain!(client.market_data(&stock, &["233"], false, false));
+    }
+
+    #[cfg(feature = "news")]
+    {
+        let _ = client.news_providers();
+        drain!(client.news_bulletins(true));
+        drain!(client.historical_news(8314, &["BZ"], start, end, 10));
+        let _ = client.historical_news_page(8314, &["BZ"], start, end, 10);
+        let _ = client.historical_news_all(8314, &["BZ"], start, end);
+        let _ = client.news_article("BZ", "BZ$04507322");
+        drain!(client.contract_news(&stock, &["BZ"]));
+        drain!(client.broad_tape_news("BZ"));
+    }
+
+    #[cfg(feature = "fundamentals")]
+    let _ = client.fundamental_data(&stock, FundamentalReportType::ReportSnapshot);
+
+    #[cfg(feature = "scanner")]
+    {
+        let _ = client.scanner_parameters();
+        drain!(client.scanner_subscription(&ScannerSubscription::default(), &Vec::default()));
+    }
+
+    // wall street horizon
+    let _ = client.wsh_metadata();
+    let _ = client.wsh_event_data_by_contract(8314, None, None, None, None);
+    drain!(client.wsh_event_data_by_filter("{}", None, None));
+
+    // display groups
+    let _ = client.display_groups();
+    drain!(client.subscribe_to_group_events(1));
+}
+
+/// Parses `data` as a financial advisor configuration of each type, as returned by TWS.
+#[cfg(feature = "fa")]
+pub fn fa_configuration(data: &[u8]) {
+    let xml = String::from_utf8_lossy(data);
+    for data_type in [FaDataType::Groups, FaDataType::Profiles, FaDataType::Aliases] {
+        let _ = FaConfiguration::parse(data_type, &xml);
+    }
+}
@@ -0,0 +1,163 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use super::*;
+
+// Responses from the unit tests, one for each message type, mutated by the tests below.
+const SEEDS: &[&str] = &[
+    "1|2|9001|1|185.50|100|7|",
+    "2|2|9000|0|100.5|170141183460469231731687303715884105727||",
+    "3|12|0|WTI|BAG||0|||SMART||USD|||||SELL|150|MKT|||||||0||1|0|0|0|0|0|0|0|2|55928698|1|BUY|IPE|0|0||-1|55850663|1|SELL|IPE|0|0||-1|0|1|NonGuaranteed|1||0||||||||0||-1|0|||0|||0|0||0||||||0|||||0|||||||||||0|||0|0|||0||0|0|0|0|||||||0|||||||||0|0|0|0|||0|",
+    "4|2|-1|399|Order Message: Warning: your order will not be placed at the exchange until 2024-01-02 09:30:00 US/Eastern|",
+    "5|13|76792991|TSLA|STK||0|?||SMART|USD|TSLA|NMS|BUY|100|MKT|0.0|0.0|DAY||DU1234567||0||100|1376327563|0|0|0||1376327563.0/DU1234567/100||||||||||0||-1|0||||||2147483647|0|0|0||3|0|0||0|0||0|None||0||||?|0|0||0|0||||||0|0|0|2147483647|2147483647|||0||IB|0|0||0|0|Filled|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.0|||USD||0|0|0|None|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|0||||0|1|0|0|0|||0||",
+    "6|2|ExcessLiquidity|1000.00|USD|DU1234567|",
+    "7|3|9000|100|xyz|yyyymmdd hh:mm:ss EST|TSLA|STK|ISLAND|BUY|",
+    "8|1|12:30|",
+    "9|8|9000|0|AAPL|OPT|20240119|0||100|SMART||USD||AAPL|0|||",
+    "10|9001|TSLA|STK||0||SMART|USD|TSLA|NMS|NMS|76792991|0.01||LMT,MKT|SMART,AMEX|1|0|TESLA INC|NASDAQ||Consumer, Cyclical|Auto Manufacturers|Auto-Cars/Light Trucks|US/Eastern|20221229:0400-20221229:2000;20221230:0400-20221230:2000|20221229:0930-20221229:1600|||1|ISIN|US88160R1014|1|||26,26||COMMON|1|1|100|",
+    "11|-1|13|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|00025b46.63f8f39c.01.01|20230224  12:04:56|DU1234567|ISLAND|BOT|100|196.52|1376327563|100|0|100|196.52|||||2||",
+    "12|2|9001|1|1|0|185.45|200|",
+    "13||9000|0|ISLAND|1|1|185.50|100|1|",
+    "14|1|1|1|Trading halted|NYSE|",
+    "15|1|DU1234567,DU7654321||",
+    "16|1|3|<?xml version=\"1.0\" encoding=\"UTF-8\"?><ListOfAccountAliases><AccountAlias><account>DU1234567</account><alias>Growth</alias></AccountAlias></ListOfAccountAliases>|",
+    "17|9000|20230413  16:31:22|20230415  16:31:22|2|20230413|182.9400|186.5000|180.9400|185.9000|948837.22|184.869|324891|20230414|183.8800|186.2800|182.0100|185.0000|810998.27|183.9865|277547|",
+    "18|9000|IBCID12345|BOND|912828C57|2.25|20240331-16:00-US/Eastern|20170331|AAA|GOVT|FIXED|0|1|0|US T-Note|SMART|USD|US Treasury|IBCID|123456789|0.0001|LMT,MKT|SMART,BONDDESK|20230331|CALL|0|callable at par|UNITED STATES TREASURY|||1|CUSIP|912828C57|0|26|1000|1000|1000|",
+    "19|1|<ScanParameterResponse><InstrumentList/></ScanParameterResponse>|",
+    "20|3|9000|10|0|667434000|ELAB|STK||0||SMART|USD|ELAB|SCM|SCM|||||1|689954925|XTIA|STK||0||SMART|USD|XTIA|SCM|SCM|||||2|647805811|MTEM|STK||0||SMART|USD|MTEM|SCM|SCM|||||3|670777621|SVMH|STK||0||SMART|USD|SVMH|NMS|NMS|||||4|324651164|QUBT|STK||0||SMART|USD|QUBT|SCM|SCM|||||5|504717050|MVST|STK||0||SMART|USD|MVST|SCM|SCM|||||6|733727297|UAVS|STK||0||SMART|USD|UAVS|UAVS|UAVS|||||7|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS|||||8|76792991|TSLA|STK||0||SMART|USD|TSLA|NMS|NMS|||||9|531212348|NU|STK||0||SMART|USD|NU|NU|NU|||||",
+    "21|9000|13|1|0.25|0.5|5.25|0|0.1|0.02|-0.03|0.15|150.0|",
+    "45|2|9001|23|20.5|",
+    "46|2|9001|45|2023-03-13 09:30:00|",
+    "49|1|1678323335|",
+    "50||9000|invalid_timestamp|4028.75|4029.00|4028.25|4028.50|2|4026.75|1|",
+    "52|1|9000||",
+    "54|1|DU1234567|",
+    "56|1|9000|12345|0.52|101.25|",
+    "58|1|",
+    "59|1|00025b46.63f8f39c.01.01|1.0|USD|1.7976931348623157E308|1.7976931348623157E308|||",
+    "61|3|6|DU1234567|76792991|TSLA|STK||0.0|||NASDAQ|USD|TSLA|NMS|500|196.77|",
+    "62|1|9000|All|AccountType|",
+    "63|9000|",
+    "64|1|",
+    "71|2|ISLAND|STK|NASDAQ|DEEP2|1|NYSE|STK|NYSE|DEEP|1|",
+    "73|1|9000|DU1234567||StockMarketValue|0.00|BASE||",
+    "74|1|9000|DU1234567|TARGET2024|",
+    "75|9000|SMART|265598|AAPL|100|2|20240119|20240216|3|180|185|190|",
+    "76|1|9001|DU1234567|TARGET2024|1|",
+    "77|9000|2|Tier1|1|Tier One|Tier2|2|Tier Two|",
+    "78|9000|AAPL||STK|265598|",
+    "79|9000|2|265598|AAPL|STK|NASDAQ|USD|3|OPT|WAR|CFD|38708077|AAPL|STK|MEXI|MXN|0|",
+    "80|2|ISLAND|STK|NASDAQ|DEEP2|1|ARCA|STK|NYSE|DEEP|2|",
+    "81|9000|AAPL|",
+    "83|9000|0|Article text|",
+    "84|9000|1678323335000|BZ|BZ$123|Headline|extra|",
+    "85|2|BZ|Benzinga|FLY|Fly on the Wall|",
+    "86|9000|2023-03-13 09:30:00.0|BZ|BZ$123|Headline|",
+    "87|9000|0|",
+    "88|9000|1560346200|",
+    "89|9000|2|185.50|100|186.00|200|",
+    "91|239|",
+    "92|9002|DU1234567|TARGET2024|",
+    "93|239|3|0|0.01|10|0.05|100|0.1|",
+    "94|1|9000|DU1234567|AccountType|FA|",
+    "95|9000|100|-12.5|1.7976931348623157E308|1.7976931348623157E308|4550.0|",
+    "96|9000|24|1681133398|0|91.36|0|1681133400|0|91.355|0|1681133400|0|91.35|0|1681133400|0|91.345|0|1681133400|0|91.35|0|1681133400|0|91.355|0|1681133400|0|91.35|0|1681133400|0|91.34|0|1681133400|0|91.345|0|1681133400|0|91.34|0|1681133400|0|91.345|0|1681133400|0|91.34|0|1681133400|0|91.335|0|1681133400|0|91.33|0|1681133400|0|91.325|0|1681133400|0|91.32|0|1681133400|0|91.325|0|1681133400|0|91.32|0|1681133400|0|91.315|0|1681133400|0|91.32|0|1681133400|0|91.325|0|1681133400|0|91.32|0|1681133400|0|91.315|0|1681133400|0|91.31|0|1|",
+    "97|9000|4|1681133399|0|11.63|11.83|2800|100|1681133400|0|11.63|11.83|2800|200|1681133400|0|11.63|11.72|2800|100|1681133400|0|11.63|11.83|2800|200|1|",
+    "98|9000|7|1681133400|0|11.63|24547|ISLAND| O X|1681133400|2|11.73|1|DRCTEDGE|   I|1681133401|0|11.63|179|FINRA||1681133401|2|11.73|1|FINRA|   I|1681133402|2|11.63|1|FINRA| 4 I|1681133402|2|11.73|1|FINRA|   I|1681133402|2|11.73|1|FINRA|   I|1|",
+    "99|9001|1|1678740829|3895.25|7|2|NASDAQ|Regular|",
+    "101|265598|AAPL|STK||0|?||SMART|USD|AAPL|NMS|BUY|0|MKT|0.0|0.0|DAY||DU1234567||0||1824933227|0|0|0|||||||||||0||-1||||||2147483647|0|0||3|0||0|None||0|0|0||0|0||||0|0|0|2147483647|2147483647||||IB|0|0||0|Filled|0|0|0|1.7976931348623157E308|1.7976931348623157E308|0|1|0||100|2147483647|0|Not an insider or substantial shareholder|0|0|9223372036854775807|20230306 12:28:30 America/Los_Angeles|Filled Size: 100|",
+    "103|9000|FA data replaced|",
+    "104|9000|{\"validated\":true}|",
+    "105|9000|{\"results\":[]}|",
+    "106|9000|20230414-09:30:00|20230414-16:00:00|US/Eastern|1|20230414-09:30:00|20230414-16:00:00|20230414|",
+    "109|1678323335123|",
+];
+
+// Field values that decoders are likely to mishandle.
+const SUBSTITUTIONS: &[&str] = &[
+    "",
+    "x",
+    "-1",
+    "-2147483648",
+    "2147483647",
+    "99999999999999999999",
+    "1e400",
+    "NaN",
+    "20230413  99:99:99",
+];
+
+// Truncations of each seed, each seed with one field replaced, and the fields of each seed sent as every other message type.
+fn mutations(seed: &str) -> Vec<String> {
+    let fields: Vec<&str> = seed.strip_suffix('|').unwrap_or(seed).split('|').collect();
+
+    let mut mutations = Vec::new();
+    for other in SEEDS {
+        let message_type = other.split('|').next().unwrap();
+        mutations.push(format!("{message_type}|{}|", fields[1..].join("|")));
+    }
+
+    for i in 1..fields.len() {
+        mutations.push(format!("{}|", fields[..i].join("|")));
+
+        for substitution in SUBSTITUTIONS {
+            let mut mutated = fields.clone();
+            mutated[i] = substitution;
+            mutations.push(format!("{}|", mutated.join("|")));
+        }
+    }
+    mutations
+}
+
+fn assert_no_panics(target: fn(&[u8]), inputs: impl Iterator<Item = Vec<u8>>) {
+    let mut panics = Vec::new();
+    for input in inputs {
+        if panic::catch_unwind(AssertUnwindSafe(|| target(&input))).is_err() {
+            panics.push(String::from_utf8_lossy(&input).into_owned());
+        }
+    }
+    assert!(panics.is_empty(), "{} inputs panicked: {panics:#?}", panics.len());
+}
+
+#[test]
+fn decoders_do_not_panic() {
+    let inputs = SEEDS.iter().flat_map(|seed| mutations(seed)).enumerate().map(|(i, message)| {
+        // cycles through the supported server versions
+        let mut input = vec![i as u8];
+        input.extend(message.as_bytes());
+        input
+    });
+
+    assert_no_panics(decode, inputs);
+}
+
+#[test]
+fn framing_does_not_panic() {
+    let mut inputs = vec![
+        vec![],
+        vec![0, 0],
+        vec![0xFF, 0xFF, 0xFF, 0xFF],
+        vec![0, 0, 0, 10, b'4', 0],
+        vec![0, 0, 0, 2, 0xC3, 0x28],
+    ];
+    for seed in SEEDS {
+        let message = seed.replace('|', "\0");
+        let mut input = (message.len() as u32).to_be_bytes().to_vec();
+        input.extend(message.as_bytes());
+        inputs.push(input);
+    }
+
+    assert_no_panics(framing, inputs.into_iter());
+}
+
+#[test]
+#[cfg(feature = "fa")]
+fn fa_configuration_does_not_panic() {
+    let inputs = [
+        "",
+        "<",
+        "<ListOfGroups><Group><name>Growth</name><ListOfAccts/><defaultMethod>x</defaultMethod></Group></ListOfGroups>",
+        "<ListOfAllocationProfiles><AllocationProfile><type>99</type></AllocationProfile></ListOfAllocationProfiles>",
+        "<ListOfAccountAliases><AccountAlias/></ListOfAccountAliases>",
+    ];
+
+    assert_no_panics(fa_configuration, inputs.iter().map(|input| input.as_bytes().to_vec()));
+}
@@ -15,6 +15,9 @@
 //!
 //! For an overview of API usage, refer to the [README](https://github.com/wboayue/rust-ibapi/blob/main/README.md).
 
+// Test fixtures encode messages with NUL separators directly followed by digits.
+#![cfg_attr(test, allow(clippy::octal_escapes))]
+
 /// Describes items present in an account.
 pub mod accounts;
 
@@ -23,6 +26,8 @@ pub mod accounts;
 /// The Client establishes the connection to TWS or the Gateway.
 /// It manages the routing of messages between TWS and the application.
 pub mod client;
+/// APIs for linking the contract displayed in TWS window groups.
+pub mod display_groups;
 
 pub(crate) mod transport;
 
@@ -32,31 +37,55 @@ pub(crate) mod transport;
 pub mod contracts;
 // Describes primary data structures used by the model.
 pub mod errors;
+/// APIs for requesting fundamental data reports.
+#[cfg(feature = "fundamentals")]
+pub mod fundamentals;
 /// APIs for retrieving market data
 pub mod market_data;
 mod messages;
+/// Typed currency amounts.
+pub mod money;
+#[cfg(feature = "news")]
 pub mod news;
 /// Data types for building and placing orders.
 pub mod orders;
 /// APIs for working with the market scanner.
+#[cfg(feature = "scanner")]
 pub mod scanner;
 /// APIs for working with Wall Street Horizon: Earnings Calendar & Event Data.
 pub mod wsh;
 
+/// Entry points for the benchmarks in `benches/`. Enabled with the `bench` feature.
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
+/// Entry points for the fuzz targets in `fuzz/`. Enabled with the `fuzzing` feature.
+#[cfg(any(test, feature = "fuzzing"))]
+#[doc(hidden)]
+pub mod fuzzing;
 mod server_versions;
+/// Support for testing applications without TWS. Enabled with the `test-utils` feature.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+#[cfg(any(feature = "fa", feature = "scanner", feature = "fundamentals"))]
+mod xml;
 
 #[doc(inline)]
 pub use errors::Error;
 
 #[doc(inline)]
 pub use client::Client;
+
+#[doc(inline)]
+pub use messages::Notice;
 use std::sync::LazyLock;
 use time::{
-    format_description::{self, BorrowedFormatItem},
-    Date,
+    format_description::{self, BorrowedFormatItem, FormatItem},
+    Date, OffsetDateTime, PrimitiveDateTime,
 };
+use time_tz::{timezones, OffsetDateTimeExt, PrimitiveDateTimeExt, TimeZone, Tz};
 
-#[cfg(test)]
+#[cfg(any(test, feature = "fuzzing"))]
 pub(crate) mod stubs;
 
 #[cfg(test)]
@@ -69,6 +98,11 @@ pub(crate) mod testdata;
 
 pub(crate) trait ToField {
     fn to_field(&self) -> String;
+
+    // Appends the field to `buffer`. Implemented by frequently sent types to encode without allocating.
+    fn write_field(&self, buffer: &mut String) {
+        buffer.push_str(&self.to_field());
+    }
 }
 
 impl ToField for bool {
@@ -79,64 +113,104 @@ impl ToField for bool {
             String::from("0")
         }
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        buffer.push(if *self { '1' } else { '0' });
+    }
 }
 
 impl ToField for String {
     fn to_field(&self) -> String {
         self.clone()
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        buffer.push_str(self);
+    }
 }
 
 impl ToField for Option<String> {
     fn to_field(&self) -> String {
         encode_option_field(self)
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_option_field(self, buffer);
+    }
 }
 
 impl ToField for &str {
     fn to_field(&self) -> String {
         <&str>::clone(self).to_string()
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        buffer.push_str(self);
+    }
 }
 
 impl ToField for Option<&str> {
     fn to_field(&self) -> String {
         encode_option_field(self)
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_option_field(self, buffer);
+    }
 }
 
 impl ToField for usize {
     fn to_field(&self) -> String {
         self.to_string()
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_display_field(self, buffer);
+    }
 }
 
 impl ToField for i32 {
     fn to_field(&self) -> String {
         self.to_string()
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_display_field(self, buffer);
+    }
 }
 
 impl ToField for Option<i32> {
     fn to_field(&self) -> String {
         encode_option_field(self)
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_option_field(self, buffer);
+    }
 }
 
 impl ToField for f64 {
     fn to_field(&self) -> String {
         self.to_string()
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_display_field(self, buffer);
+    }
 }
 
 impl ToField for Option<f64> {
     fn to_field(&self) -> String {
         encode_option_field(self)
     }
+
+    fn write_field(&self, buffer: &mut String) {
+        write_option_field(self, buffer);
+    }
 }
 
 fn date_format() -> Vec<BorrowedFormatItem<'static>> {
-    format_description::parse("YYYYMMDD").unwrap()
+    format_description::parse_borrowed::<2>("[year][month][day]").unwrap()
 }
 
 static DATE_FORMAT: LazyLock<Vec<BorrowedFormatItem<'static>>> = LazyLock::new(date_format);
@@ -153,9 +227,61 @@ impl ToField for Option<Date> {
     }
 }
 
+const END_DATE_FORMAT: &[FormatItem] = time::macros::format_description!("[year][month][day] [hour]:[minute]:[second]");
+
+impl ToField for OffsetDateTime {
+    fn to_field(&self) -> String {
+        let adjusted = self.to_timezone(timezones::db::UTC);
+        let formatted = adjusted.format(END_DATE_FORMAT).unwrap();
+        format!("{formatted} UTC")
+    }
+}
+
+impl ToField for Option<OffsetDateTime> {
+    fn to_field(&self) -> String {
+        match self {
+            Some(date_time) => date_time.to_field(),
+            None => "".into(),
+        }
+    }
+}
+
+// Looks up a time zone reported by TWS, either an IANA name such as US/Eastern or an abbreviation such as EST.
+pub(crate) fn parse_time_zone(name: &str) -> Result<&'static Tz, Error> {
+    if let Some(time_zone) = timezones::get_by_name(name) {
+        return Ok(time_zone);
+    }
+
+    match timezones::find_by_name(name).first() {
+        Some(time_zone) => Ok(time_zone),
+        None => Err(Error::UnknownTimeZone(name.to_owned())),
+    }
+}
+
+// Converts a local time in `time_zone` to an offset time. Times skipped by a daylight saving transition are rejected, rather than panicking.
+pub(crate) fn assume_time_zone(date_time: PrimitiveDateTime, time_zone: &Tz) -> Result<OffsetDateTime, Error> {
+    date_time.assume_timezone(time_zone).take_first().ok_or_else(|| Error::InvalidFormat {
+        kind: "local time",
+        reason: format!("{date_time} does not exist in time zone {}", time_zone.name()),
+    })
+}
+
 fn encode_option_field<T: ToField>(val: &Option<T>) -> String {
     match val {
         Some(val) => val.to_field(),
         None => String::from(""),
     }
 }
+
+fn write_option_field<T: ToField>(val: &Option<T>, buffer: &mut String) {
+    if let Some(val) = val {
+        val.write_field(buffer);
+    }
+}
+
+fn write_display_field<T: std::fmt::Display>(val: &T, buffer: &mut String) {
+    use std::fmt::Write;
+
+    // writing to a String cannot fail
+    let _ = write!(buffer, "{val}");
+}
@@ -1,10 +1,16 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{messages::OutgoingMessages, server_versions, Client, Error};
 
+#[cfg(feature = "historical")]
 pub mod historical;
+#[cfg(feature = "market-data")]
 pub mod realtime;
 
 /// By default only Realtime market data is enabled sending.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MarketDataType {
     /// Disables frozen, delayed and delayed-frozen market data sending.
     Live = 1,
@@ -4,10 +4,11 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
 use log::{debug, warn};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use time::{Date, OffsetDateTime};
 
-use crate::contracts::Contract;
+use crate::contracts::{Contract, SecurityType};
 use crate::messages::{IncomingMessages, RequestMessage, ResponseMessage};
 use crate::transport::{InternalSubscription, Response};
 use crate::{server_versions, Client, Error, ToField};
@@ -18,7 +19,8 @@ mod encoders;
 mod tests;
 
 /// Bar describes the historical data bar.
-#[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bar {
     /// The bar's date and time (either as a yyyymmss hh:mm:ss formatted string or as system time according to the request). Time zone is the TWS time zone chosen on login.
     // pub time: OffsetDateTime,
@@ -39,7 +41,8 @@ pub struct Bar {
     pub count: i32,
 }
 
-#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BarSize {
     Sec,
     Sec5,
@@ -94,7 +97,8 @@ impl ToField for BarSize {
     }
 }
 
-#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Duration {
     value: i32,
     unit: char,
@@ -169,20 +173,23 @@ impl ToDuration for i32 {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HistogramEntry {
     pub price: f64,
     pub size: i32,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HistoricalData {
     pub start: OffsetDateTime,
     pub end: OffsetDateTime,
     pub bars: Vec<Bar>,
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Schedule {
     pub start: OffsetDateTime,
     pub end: OffsetDateTime,
@@ -190,7 +197,8 @@ pub struct Schedule {
     pub sessions: Vec<Session>,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Session {
     pub reference: Date,
     pub start: OffsetDateTime,
@@ -198,7 +206,8 @@ pub struct Session {
 }
 
 /// The historical tick's description. Used when requesting historical tick data with whatToShow = MIDPOINT
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickMidpoint {
     /// timestamp of the historical tick.
     pub timestamp: OffsetDateTime,
@@ -209,7 +218,8 @@ pub struct TickMidpoint {
 }
 
 /// The historical tick's description. Used when requesting historical tick data with whatToShow = BID_ASK.
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickBidAsk {
     /// Timestamp of the historical tick.
     pub timestamp: OffsetDateTime,
@@ -225,14 +235,16 @@ pub struct TickBidAsk {
     pub size_ask: i32,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickAttributeBidAsk {
     pub bid_past_low: bool,
     pub ask_past_high: bool,
 }
 
 /// The historical last tick's description. Used when requesting historical tick data with whatToShow = TRADES.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickLast {
     /// Timestamp of the historical tick.
     pub timestamp: OffsetDateTime,
@@ -248,13 +260,15 @@ pub struct TickLast {
     pub special_conditions: String,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickAttributeLast {
     pub past_limit: bool,
     pub unreported: bool,
 }
 
 #[derive(Clone, Debug, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WhatToShow {
     Trades,
     MidPoint,
@@ -301,23 +315,36 @@ impl ToField for Option<WhatToShow> {
 }
 
 // Returns the timestamp of earliest available historical data for a contract and data type.
-pub(crate) fn head_timestamp(client: &Client, contract: &Contract, what_to_show: WhatToShow, use_rth: bool) -> Result<OffsetDateTime, Error> {
+pub(crate) fn head_timestamp(
+    client: &Client,
+    contract: &Contract,
+    what_to_show: WhatToShow,
+    use_rth: bool,
+    timeout: Option<std::time::Duration>,
+) -> Result<OffsetDateTime, Error> {
     client.check_server_version(server_versions::REQ_HEAD_TIMESTAMP, "It does not support head time stamp requests.")?;
 
     let request_id = client.next_request_id();
     let request = encoders::encode_request_head_timestamp(request_id, contract, what_to_show, use_rth)?;
     let subscription = client.send_request(request_id, request)?;
 
-    match subscription.next() {
+    match subscription.next_within(timeout) {
         Some(Ok(mut message)) if message.message_type() == IncomingMessages::HeadTimestamp => Ok(decoders::decode_head_timestamp(&mut message)?),
-        Some(Ok(message)) => Err(Error::UnexpectedResponse(message)),
-        Some(Err(Error::ConnectionReset)) => head_timestamp(client, contract, what_to_show, use_rth),
+        Some(Ok(message)) => Err(Error::from_response(message)),
+        Some(Err(Error::ConnectionReset)) => head_timestamp(client, contract, what_to_show, use_rth, timeout),
+        Some(Err(Error::Timeout)) => {
+            client
+                .message_bus
+                .cancel_subscription(request_id, &encoders::encode_cancel_head_timestamp(request_id)?)?;
+            Err(Error::Timeout)
+        }
         Some(Err(e)) => Err(e),
         None => Err(Error::UnexpectedEndOfStream),
     }
 }
 
 // https://interactivebrokers.github.io/tws-api/historical_bars.html#hd_duration
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn historical_data(
     client: &Client,
     contract: &Contract,
@@ -326,6 +353,7 @@ pub(crate) fn historical_data(
     bar_size: BarSize,
     what_to_show: Option<WhatToShow>,
     use_rth: bool,
+    timeout: Option<std::time::Duration>,
 ) -> Result<HistoricalData, Error> {
     if !contract.trading_class.is_empty() || contract.contract_id > 0 {
         client.check_server_version(
@@ -341,6 +369,18 @@ pub(crate) fn historical_data(
         )?;
     }
 
+    if end_date.is_some() && contract.security_type == SecurityType::ContinuousFuture {
+        return Err(Error::InvalidArgument(
+            "end_date must be None when requesting historical data for continuous futures.".into(),
+        ));
+    }
+
+    if contract.include_expired && contract.contract_id == 0 && contract.last_trade_date_or_contract_month.is_empty() {
+        return Err(Error::InvalidArgument(
+            "expired contracts must specify contract_id or last_trade_date_or_contract_month when requesting historical data.".into(),
+        ));
+    }
+
     if end_date.is_some() && what_to_show == Some(WhatToShow::AdjustedLast) {
         return Err(Error::InvalidArgument("end_date must be None when requesting WhatToShow::AdjustedLast. You might have called Client::historical_data with WhatToShow::AdjustedLast".into()));
     }
@@ -362,18 +402,27 @@ pub(crate) fn historical_data(
 
         let subscription = client.send_request(request_id, request)?;
 
-        match subscription.next() {
+        match subscription.next_within(timeout) {
             Some(Ok(mut message)) if message.message_type() == IncomingMessages::HistoricalData => {
                 return decoders::decode_historical_data(client.server_version, time_zone(client), &mut message)
             }
-            Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
+            Some(Ok(message)) => return Err(Error::from_response(message)),
             Some(Err(Error::ConnectionReset)) => continue,
+            Some(Err(Error::Timeout)) => return cancel_historical_data(client, request_id),
             Some(Err(e)) => return Err(e),
             None => return Err(Error::UnexpectedEndOfStream),
         }
     }
 }
 
+// Cancels a historical data request TWS did not answer in time.
+fn cancel_historical_data<T>(client: &Client, request_id: i32) -> Result<T, Error> {
+    client
+        .message_bus
+        .cancel_subscription(request_id, &encoders::encode_cancel_historical_data(request_id)?)?;
+    Err(Error::Timeout)
+}
+
 fn time_zone(client: &Client) -> &time_tz::Tz {
     if let Some(tz) = client.time_zone {
         tz
@@ -388,6 +437,7 @@ pub(crate) fn historical_schedule(
     contract: &Contract,
     end_date: Option<OffsetDateTime>,
     duration: Duration,
+    timeout: Option<std::time::Duration>,
 ) -> Result<Schedule, Error> {
     if !contract.trading_class.is_empty() || contract.contract_id > 0 {
         client.check_server_version(
@@ -418,12 +468,13 @@ pub(crate) fn historical_schedule(
 
         let subscription = client.send_request(request_id, request)?;
 
-        match subscription.next() {
+        match subscription.next_within(timeout) {
             Some(Ok(mut message)) if message.message_type() == IncomingMessages::HistoricalSchedule => {
                 return decoders::decode_historical_schedule(&mut message)
             }
-            Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
+            Some(Ok(message)) => return Err(Error::from_response(message)),
             Some(Err(Error::ConnectionReset)) => continue,
+            Some(Err(Error::Timeout)) => return cancel_historical_data(client, request_id),
             Some(Err(e)) => return Err(e),
             None => return Err(Error::UnexpectedEndOfStream),
         }
@@ -554,15 +605,15 @@ impl<T: TickDecoder<T>> TickSubscription<T> {
         }
     }
 
-    pub fn iter(&self) -> TickSubscriptionIter<T> {
+    pub fn iter(&self) -> TickSubscriptionIter<'_, T> {
         TickSubscriptionIter { subscription: self }
     }
 
-    pub fn try_iter(&self) -> TickSubscriptionTryIter<T> {
+    pub fn try_iter(&self) -> TickSubscriptionTryIter<'_, T> {
         TickSubscriptionTryIter { subscription: self }
     }
 
-    pub fn timeout_iter(&self, duration: std::time::Duration) -> TickSubscriptionTimeoutIter<T> {
+    pub fn timeout_iter(&self, duration: std::time::Duration) -> TickSubscriptionTimeoutIter<'_, T> {
         TickSubscriptionTimeoutIter {
             subscription: self,
             timeout: duration,
@@ -606,10 +657,15 @@ impl<T: TickDecoder<T>> TickSubscription<T> {
     fn fill_buffer(&self, response: Option<Response>) -> Result<(), ()> {
         match response {
             Some(Ok(mut message)) if message.message_type() == T::MESSAGE_TYPE => {
-                let mut buffer = self.buffer.lock().unwrap();
-
-                let (ticks, done) = T::decode(&mut message).unwrap();
+                let (ticks, done) = match T::decode(&mut message) {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        self.set_error(e);
+                        return Err(());
+                    }
+                };
 
+                let mut buffer = self.buffer.lock().unwrap();
                 buffer.append(&mut ticks.into());
                 self.done.store(done, Ordering::Relaxed);
 
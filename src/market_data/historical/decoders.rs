@@ -1,8 +1,9 @@
 use time::macros::{format_description, time};
 use time::{Date, PrimitiveDateTime};
-use time_tz::{timezones, OffsetDateTimeExt, PrimitiveDateTimeExt, Tz};
+use time_tz::{OffsetDateTimeExt, PrimitiveDateTimeExt, Tz};
 
 use super::*;
+use crate::{assume_time_zone, parse_time_zone};
 
 pub(super) fn decode_head_timestamp(message: &mut ResponseMessage) -> Result<OffsetDateTime, Error> {
     message.skip(); // message type
@@ -28,12 +29,8 @@ pub(super) fn decode_historical_data(server_version: i32, time_zone: &Tz, messag
     let mut start = OffsetDateTime::now_utc();
     let mut end = OffsetDateTime::now_utc();
     if message_version > 2 {
-        start = PrimitiveDateTime::parse(&message.next_string()?, slice_format)?
-            .assume_timezone(time_zone)
-            .unwrap();
-        end = PrimitiveDateTime::parse(&message.next_string()?, slice_format)?
-            .assume_timezone(time_zone)
-            .unwrap();
+        start = assume_time_zone(PrimitiveDateTime::parse(&message.next_string()?, slice_format)?, time_zone)?;
+        end = assume_time_zone(PrimitiveDateTime::parse(&message.next_string()?, slice_format)?, time_zone)?;
     }
 
     let mut bars = Vec::new();
@@ -81,10 +78,10 @@ pub(super) fn decode_historical_schedule(message: &mut ResponseMessage) -> Resul
     let end = message.next_string()?;
     let time_zone_name = message.next_string()?;
 
-    let time_zone = parse_time_zone(&time_zone_name);
+    let time_zone = parse_time_zone(&time_zone_name)?;
 
     let sessions_count = message.next_int()?;
-    let mut sessions = Vec::<Session>::with_capacity(sessions_count as usize);
+    let mut sessions = Vec::<Session>::new();
     for _ in 0..sessions_count {
         let session_start = message.next_string()?;
         let session_end = message.next_string()?;
@@ -110,7 +107,7 @@ pub(super) fn decode_historical_ticks_bid_ask(message: &mut ResponseMessage) ->
     message.skip(); // request_id
 
     let number_of_ticks = message.next_int()?;
-    let mut ticks = Vec::with_capacity(number_of_ticks as usize);
+    let mut ticks = Vec::new();
 
     for _ in 0..number_of_ticks {
         let timestamp = message.next_date_time()?;
@@ -146,7 +143,7 @@ pub(super) fn decode_historical_ticks_mid_point(message: &mut ResponseMessage) -
     message.skip(); // request_id
 
     let number_of_ticks = message.next_int()?;
-    let mut ticks = Vec::with_capacity(number_of_ticks as usize);
+    let mut ticks = Vec::new();
 
     for _ in 0..number_of_ticks {
         let timestamp = message.next_date_time()?;
@@ -167,7 +164,7 @@ pub(super) fn decode_historical_ticks_last(message: &mut ResponseMessage) -> Res
     message.skip(); // request_id
 
     let number_of_ticks = message.next_int()?;
-    let mut ticks = Vec::with_capacity(number_of_ticks as usize);
+    let mut ticks = Vec::new();
 
     for _ in 0..number_of_ticks {
         let timestamp = message.next_date_time()?;
@@ -203,7 +200,7 @@ pub(super) fn decode_histogram_data(message: &mut ResponseMessage) -> Result<Vec
     message.skip(); // request id
 
     let count = message.next_int()?;
-    let mut items = Vec::with_capacity(count as usize);
+    let mut items = Vec::new();
 
     for _ in 0..count {
         items.push(HistogramEntry {
@@ -215,18 +212,10 @@ pub(super) fn decode_histogram_data(message: &mut ResponseMessage) -> Result<Vec
     Ok(items)
 }
 
-fn parse_time_zone(name: &str) -> &Tz {
-    let zones = timezones::find_by_name(name);
-    if zones.is_empty() {
-        panic!("timezone not found for: {}", name)
-    }
-    zones[0]
-}
-
 fn parse_schedule_date_time(text: &str, time_zone: &Tz) -> Result<OffsetDateTime, Error> {
     let schedule_date_time_format = format_description!("[year][month][day]-[hour]:[minute]:[second]");
     let schedule_date_time = PrimitiveDateTime::parse(text, schedule_date_time_format)?;
-    Ok(schedule_date_time.assume_timezone(time_zone).unwrap())
+    assume_time_zone(schedule_date_time, time_zone)
 }
 
 fn parse_schedule_date(text: &str) -> Result<Date, Error> {
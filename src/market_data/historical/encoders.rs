@@ -1,29 +1,8 @@
-use time::{format_description::FormatItem, macros::format_description};
-use time_tz::OffsetDateTimeExt;
-
 use crate::messages::OutgoingMessages;
 
 use super::*;
 
 const DATE_FORMAT: i32 = 2; // 1 for yyyyMMdd HH:mm:ss, 2 for system time format in seconds.
-const END_DATE_FORMAT: &[FormatItem] = format_description!("[year][month][day] [hour]:[minute]:[second]");
-
-impl ToField for OffsetDateTime {
-    fn to_field(&self) -> String {
-        let adjusted = self.to_timezone(time_tz::timezones::db::UTC);
-        let formatted = adjusted.format(END_DATE_FORMAT).unwrap();
-        format!("{formatted} UTC")
-    }
-}
-
-impl ToField for Option<OffsetDateTime> {
-    fn to_field(&self) -> String {
-        match self {
-            Some(date_time) => date_time.to_field(),
-            None => "".into(),
-        }
-    }
-}
 
 // Encodes the head timestamp request
 pub(super) fn encode_request_head_timestamp(
@@ -44,6 +23,16 @@ pub(super) fn encode_request_head_timestamp(
     Ok(packet)
 }
 
+// Encodes the cancel head timestamp request
+pub(super) fn encode_cancel_head_timestamp(request_id: i32) -> Result<RequestMessage, Error> {
+    let mut packet = RequestMessage::default();
+
+    packet.push_field(&OutgoingMessages::CancelHeadTimestamp);
+    packet.push_field(&request_id);
+
+    Ok(packet)
+}
+
 // Encodes the historical data request
 #[allow(clippy::too_many_arguments)]
 pub(super) fn encode_request_historical_data(
@@ -122,6 +111,19 @@ pub(super) fn encode_request_historical_data(
     Ok(message)
 }
 
+// Encodes the cancel historical data request
+pub(super) fn encode_cancel_historical_data(request_id: i32) -> Result<RequestMessage, Error> {
+    const VERSION: i32 = 1;
+
+    let mut packet = RequestMessage::default();
+
+    packet.push_field(&OutgoingMessages::CancelHistoricalData);
+    packet.push_field(&VERSION);
+    packet.push_field(&request_id);
+
+    Ok(packet)
+}
+
 // Encodes message to request historical ticks
 #[allow(clippy::too_many_arguments)]
 pub(super) fn encode_request_historical_ticks(
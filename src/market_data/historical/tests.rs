@@ -5,6 +5,7 @@ use time::macros::datetime;
 use crate::market_data::historical::ToDuration;
 use crate::messages::OutgoingMessages;
 use crate::stubs::MessageBusStub;
+use crate::testing::{assert_fields, fixture};
 
 use super::*;
 
@@ -12,7 +13,13 @@ use super::*;
 fn test_head_timestamp() {
     let message_bus = Arc::new(MessageBusStub {
         request_messages: RwLock::new(vec![]),
-        response_messages: vec!["88|9000|1678323335|".to_owned()],
+        response_messages: vec![fixture(
+            "
+            88          # head timestamp
+            9000        # request id
+            1678323335  # head timestamp
+            ",
+        )],
     });
 
     let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
@@ -28,38 +35,46 @@ fn test_head_timestamp() {
     assert_eq!(head_timestamp, OffsetDateTime::from_unix_timestamp(1678323335).unwrap(), "bar.date");
 
     let request_messages = client.message_bus.request_messages();
-
-    let head_timestamp_request = &request_messages[0];
-    assert_eq!(
-        head_timestamp_request[0],
-        OutgoingMessages::RequestHeadTimestamp.to_field(),
-        "message.message_type"
-    );
-    assert_eq!(head_timestamp_request[1], "9000", "message.request_id");
-    assert_eq!(head_timestamp_request[2], contract.contract_id.to_field(), "message.contract_id");
-    assert_eq!(head_timestamp_request[3], contract.symbol.to_field(), "message.symbol");
-    assert_eq!(head_timestamp_request[4], contract.security_type.to_field(), "message.security_type");
-    assert_eq!(
-        head_timestamp_request[5],
-        contract.last_trade_date_or_contract_month.to_field(),
-        "message.last_trade_date_or_contract_month"
-    );
-    assert_eq!(head_timestamp_request[6], contract.strike.to_field(), "message.strike");
-    assert_eq!(head_timestamp_request[7], contract.right.to_field(), "message.right");
-    assert_eq!(head_timestamp_request[8], contract.multiplier.to_field(), "message.multiplier");
-    assert_eq!(head_timestamp_request[9], contract.exchange.to_field(), "message.exchange");
-    assert_eq!(
-        head_timestamp_request[10],
-        contract.primary_exchange.to_field(),
-        "message.primary_exchange"
+    assert_fields(
+        &request_messages[0].encode_simple(),
+        &[
+            ("message_type", "87"),
+            ("request_id", "9000"),
+            ("contract_id", "0"),
+            ("symbol", "MSFT"),
+            ("security_type", "STK"),
+            ("last_trade_date_or_contract_month", ""),
+            ("strike", "0"),
+            ("right", ""),
+            ("multiplier", ""),
+            ("exchange", "SMART"),
+            ("primary_exchange", ""),
+            ("currency", "USD"),
+            ("local_symbol", ""),
+            ("trading_class", ""),
+            ("include_expired", "0"),
+            ("use_rth", "1"),
+            ("what_to_show", "TRADES"),
+            ("date_format", "2"),
+        ],
     );
-    assert_eq!(head_timestamp_request[11], contract.currency.to_field(), "message.currency");
-    assert_eq!(head_timestamp_request[12], contract.local_symbol.to_field(), "message.local_symbol");
-    assert_eq!(head_timestamp_request[13], contract.trading_class.to_field(), "message.trading_class");
-    assert_eq!(head_timestamp_request[14], contract.include_expired.to_field(), "message.include_expired");
-    assert_eq!(head_timestamp_request[15], use_rth.to_field(), "message.use_rth");
-    assert_eq!(head_timestamp_request[16], what_to_show.to_field(), "message.what_to_show");
-    assert_eq!(head_timestamp_request[17], "2", "message.date_format");
+}
+
+#[test]
+fn test_head_timestamp_error() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["4|2|9000|162|Historical Market Data Service error message:No market data permissions.|".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let result = client.head_timestamp(&Contract::stock("MSFT"), WhatToShow::Trades, true);
+
+    match result {
+        Err(Error::Tws { code, request_id, .. }) => assert_eq!((code, request_id), (162, 9000)),
+        other => panic!("unexpected result: {other:?}"),
+    }
 }
 
 #[test]
@@ -147,6 +162,73 @@ fn test_historical_data() {
     assert_eq!(head_timestamp_request[22], "", "message.chart_options");
 }
 
+#[test]
+fn test_historical_data_continuous_futures() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "17\09000\020230413  16:31:22\020230415  16:31:22\01\020230413\04100.25\04120.50\04090.00\04110.75\01000\04105.5\0500\0".to_owned(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::continuous_futures("ES", "CME");
+
+    let result = client.historical_data(
+        &contract,
+        datetime!(2023-04-15 16:31:22 UTC),
+        2.days(),
+        BarSize::Hour,
+        WhatToShow::Trades,
+        true,
+    );
+    assert!(
+        matches!(result, Err(Error::InvalidArgument(_))),
+        "end date not allowed for continuous futures"
+    );
+
+    let historical_data = client
+        .historical_data_ending_now(&contract, 2.days(), BarSize::Hour, WhatToShow::Trades, true)
+        .expect("historical data request failed");
+    assert_eq!(historical_data.bars.len(), 1, "historical_data.bars.len()");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0][4], "CONTFUT", "message.security_type");
+    assert_eq!(request_messages[0][15], "", "message.end_date");
+}
+
+#[test]
+fn test_historical_data_expired_futures() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "17\09000\020221115  16:00:00\020221215  16:00:00\01\020221215\04000.25\04020.50\03990.00\04010.75\01000\04005.5\0500\0".to_owned(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let end_date = datetime!(2022-12-15 16:00 UTC);
+
+    let contract = Contract::futures("ES").with_include_expired(true);
+    let result = client.historical_data(&contract, end_date, 1.months(), BarSize::Day, WhatToShow::Trades, true);
+    assert!(
+        matches!(result, Err(Error::InvalidArgument(_))),
+        "expired contracts need a last trade date"
+    );
+
+    let contract = crate::contracts::futures("ES", "CME", "20221216").with_include_expired(true);
+    let historical_data = client
+        .historical_data(&contract, end_date, 1.months(), BarSize::Day, WhatToShow::Trades, true)
+        .expect("historical data request failed");
+    assert_eq!(historical_data.bars.len(), 1, "historical_data.bars.len()");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0][5], "20221216", "message.last_trade_date_or_contract_month");
+    assert_eq!(request_messages[0][14], "1", "message.include_expired");
+}
+
 #[test]
 fn test_bar_size() {
     assert_eq!(BarSize::Sec.to_string(), "1 sec");
@@ -191,3 +273,16 @@ fn test_duration() {
     assert_eq!(5.months().to_field(), "5 M");
     assert_eq!(6.years().to_field(), "6 Y");
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn historical_types_implement_serde() {
+    fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>() {}
+
+    assert_serde::<HistoricalData>();
+    assert_serde::<Schedule>();
+    assert_serde::<HistogramEntry>();
+    assert_serde::<TickBidAsk>();
+    assert_serde::<TickLast>();
+    assert_serde::<TickMidpoint>();
+}
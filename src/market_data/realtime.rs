@@ -1,10 +1,11 @@
 use log::debug;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::client::{DataStream, ResponseContext, Subscription};
+use crate::client::{DataStream, ResponseContext, Subscription, SubscriptionKind};
 use crate::contracts::tick_types::TickType;
-use crate::contracts::{Contract, OptionComputation};
+use crate::contracts::{Contract, DeltaNeutralContract, OptionComputation};
 use crate::messages::{IncomingMessages, Notice, OutgoingMessages, RequestMessage, ResponseMessage};
 use crate::orders::TagValue;
 use crate::server_versions;
@@ -18,7 +19,8 @@ mod tests;
 
 // === Models ===
 
-#[derive(Clone, Debug, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BarSize {
     // Sec,
     Sec5,
@@ -34,7 +36,8 @@ pub enum BarSize {
     // Day,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BidAsk {
     /// The spread's date and time (either as a yyyymmss hh:mm:ss formatted string or as system time according to the request). Time zone is the TWS time zone chosen on login.
     pub time: OffsetDateTime,
@@ -52,6 +55,7 @@ pub struct BidAsk {
 
 impl DataStream<BidAsk> for BidAsk {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::TickByTick];
+    const KIND: SubscriptionKind = SubscriptionKind::TickByTickBidAsk;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         decoders::decode_bid_ask_tick(message)
@@ -63,13 +67,15 @@ impl DataStream<BidAsk> for BidAsk {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BidAskAttribute {
     pub bid_past_low: bool,
     pub ask_past_high: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MidPoint {
     /// The trade's date and time (either as a yyyymmss hh:mm:ss formatted string or as system time according to the request). Time zone is the TWS time zone chosen on login.
     pub time: OffsetDateTime,
@@ -79,6 +85,7 @@ pub struct MidPoint {
 
 impl DataStream<MidPoint> for MidPoint {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::TickByTick];
+    const KIND: SubscriptionKind = SubscriptionKind::TickByTickMidPoint;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         decoders::decode_mid_point_tick(message)
@@ -91,7 +98,8 @@ impl DataStream<MidPoint> for MidPoint {
 }
 
 /// Represents a real-time bar with OHLCV data
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bar {
     /// The timestamp of the bar in market timezone
     pub date: OffsetDateTime,
@@ -113,6 +121,7 @@ pub struct Bar {
 
 impl DataStream<Bar> for Bar {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::RealTimeBars];
+    const KIND: SubscriptionKind = SubscriptionKind::RealtimeBars;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         decoders::decode_realtime_bar(message)
@@ -124,7 +133,8 @@ impl DataStream<Bar> for Bar {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Trade {
     /// Tick type: "Last" or "AllLast"
     pub tick_type: String,
@@ -144,6 +154,7 @@ pub struct Trade {
 
 impl DataStream<Trade> for Trade {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::TickByTick];
+    const KIND: SubscriptionKind = SubscriptionKind::TickByTickTrades;
 
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         decoders::decode_trade_tick(message)
@@ -155,13 +166,15 @@ impl DataStream<Trade> for Trade {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TradeAttribute {
     pub past_limit: bool,
     pub unreported: bool,
 }
 
 #[derive(Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WhatToShow {
     Trades,
     MidPoint,
@@ -186,14 +199,16 @@ impl ToField for WhatToShow {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MarketDepths {
     MarketDepth(MarketDepth),
     MarketDepthL2(MarketDepthL2),
     Notice(Notice),
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Returns the order book.
 pub struct MarketDepth {
     /// The order book's row being updated
@@ -209,7 +224,8 @@ pub struct MarketDepth {
 }
 
 /// Returns the order book.
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MarketDepthL2 {
     /// The order book's row being updated
     pub position: i32,
@@ -229,6 +245,7 @@ pub struct MarketDepthL2 {
 
 impl DataStream<MarketDepths> for MarketDepths {
     const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::MarketDepth, IncomingMessages::MarketDepthL2, IncomingMessages::Error];
+    const KIND: SubscriptionKind = SubscriptionKind::MarketDepth;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -250,6 +267,7 @@ impl DataStream<MarketDepths> for MarketDepths {
 
 /// Stores depth market data description.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DepthMarketDataDescription {
     /// The exchange name
     pub exchange_name: String,
@@ -264,6 +282,7 @@ pub struct DepthMarketDataDescription {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TickTypes {
     Price(TickPrice),
     Size(TickSize),
@@ -275,6 +294,7 @@ pub enum TickTypes {
     Notice(Notice),
     RequestParameters(TickRequestParameters),
     PriceSize(TickPriceSize),
+    DeltaNeutralValidation(DeltaNeutralContract),
 }
 
 impl DataStream<TickTypes> for TickTypes {
@@ -288,7 +308,9 @@ impl DataStream<TickTypes> for TickTypes {
         IncomingMessages::TickSnapshotEnd,
         IncomingMessages::Error,
         IncomingMessages::TickReqParams,
+        IncomingMessages::DeltaNeutralValidation,
     ];
+    const KIND: SubscriptionKind = SubscriptionKind::MarketData;
 
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
@@ -303,6 +325,7 @@ impl DataStream<TickTypes> for TickTypes {
             )?)),
             IncomingMessages::TickReqParams => Ok(TickTypes::RequestParameters(decoders::decode_tick_request_parameters(message)?)),
             IncomingMessages::TickSnapshotEnd => Ok(TickTypes::SnapshotEnd),
+            IncomingMessages::DeltaNeutralValidation => Ok(TickTypes::DeltaNeutralValidation(decoders::decode_delta_neutral_validation(message)?)),
             IncomingMessages::Error => Ok(TickTypes::Notice(Notice::from(message))),
             _ => Err(Error::NotImplemented),
         }
@@ -315,6 +338,7 @@ impl DataStream<TickTypes> for TickTypes {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickPrice {
     pub tick_type: TickType,
     pub price: f64,
@@ -322,6 +346,7 @@ pub struct TickPrice {
 }
 
 #[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickAttribute {
     pub can_auto_execute: bool,
     pub past_limit: bool,
@@ -329,12 +354,14 @@ pub struct TickAttribute {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickSize {
     pub tick_type: TickType,
     pub size: f64,
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickPriceSize {
     pub price_tick_type: TickType,
     pub price: f64,
@@ -344,12 +371,14 @@ pub struct TickPriceSize {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickString {
     pub tick_type: TickType,
     pub value: String,
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickEFP {
     pub tick_type: TickType,
     pub basis_points: f64,
@@ -362,12 +391,14 @@ pub struct TickEFP {
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickGeneric {
     pub tick_type: TickType,
     pub value: f64,
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TickRequestParameters {
     pub min_tick: f64,
     pub bbo_exchange: String,
@@ -389,7 +420,7 @@ pub(crate) fn realtime_bars<'a>(
     let request = encoders::encode_request_realtime_bars(client.server_version(), request_id, contract, bar_size, what_to_show, use_rth, options)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 // Requests tick by tick AllLast ticks.
@@ -407,7 +438,7 @@ pub(crate) fn tick_by_tick_all_last<'a>(
     let request = encoders::encode_tick_by_tick(server_version, request_id, contract, "AllLast", number_of_ticks, ignore_size)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 // Validates that server supports the given request.
@@ -439,7 +470,7 @@ pub(crate) fn tick_by_tick_last<'a>(
     let request = encoders::encode_tick_by_tick(server_version, request_id, contract, "Last", number_of_ticks, ignore_size)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 // Requests tick by tick BidAsk ticks.
@@ -457,7 +488,7 @@ pub(crate) fn tick_by_tick_bid_ask<'a>(
     let request = encoders::encode_tick_by_tick(server_version, request_id, contract, "BidAsk", number_of_ticks, ignore_size)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 // Requests tick by tick MidPoint ticks.
@@ -475,7 +506,7 @@ pub(crate) fn tick_by_tick_midpoint<'a>(
     let request = encoders::encode_tick_by_tick(server_version, request_id, contract, "MidPoint", number_of_ticks, ignore_size)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 pub(crate) fn market_depth<'a>(
@@ -498,7 +529,7 @@ pub(crate) fn market_depth<'a>(
     let request = encoders::encode_request_market_depth(client.server_version, request_id, contract, number_of_rows, is_smart_depth)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 // Requests venues for which market data is returned to market_depth (those with market makers)
@@ -533,6 +564,10 @@ pub fn market_data<'a>(
     snapshot: bool,
     regulatory_snapshot: bool,
 ) -> Result<Subscription<'a, TickTypes>, Error> {
+    if contract.is_bag() && contract.combo_legs.is_empty() {
+        return Err(Error::InvalidArgument("combo contract must have at least one leg".into()));
+    }
+
     let request_id = client.next_request_id();
     let request = encoders::encode_request_market_data(
         client.server_version(),
@@ -544,5 +579,5 @@ pub fn market_data<'a>(
     )?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
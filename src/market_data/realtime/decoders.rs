@@ -1,6 +1,6 @@
 use crate::contracts::decoders::decode_option_computation;
 use crate::contracts::tick_types::TickType;
-use crate::contracts::OptionComputation;
+use crate::contracts::{DeltaNeutralContract, OptionComputation};
 use crate::Error;
 use crate::{messages::ResponseMessage, server_versions};
 
@@ -27,7 +27,7 @@ pub(super) fn decode_realtime_bar(message: &mut ResponseMessage) -> Result<Bar,
         high: message.next_double()?,
         low: message.next_double()?,
         close: message.next_double()?,
-        volume: message.next_double()?,
+        volume: message.next_decimal()?,
         wap: message.next_double()?,
         count: message.next_int()?,
     })
@@ -117,7 +117,7 @@ pub(super) fn decode_market_depth(message: &mut ResponseMessage) -> Result<Marke
         operation: message.next_int()?,
         side: message.next_int()?,
         price: message.next_double()?,
-        size: message.next_double()?,
+        size: message.next_decimal()?,
     };
 
     Ok(depth)
@@ -134,7 +134,7 @@ pub(super) fn decode_market_depth_l2(server_version: i32, message: &mut Response
         operation: message.next_int()?,
         side: message.next_int()?,
         price: message.next_double()?,
-        size: message.next_double()?,
+        size: message.next_decimal()?,
         ..Default::default()
     };
 
@@ -149,7 +149,7 @@ pub(super) fn decode_market_depth_exchanges(server_version: i32, message: &mut R
     message.skip(); // message type
 
     let count = message.next_int()?;
-    let mut descriptions = Vec::with_capacity(count as usize);
+    let mut descriptions = Vec::new();
 
     for _ in 0..count {
         let description = if server_version >= server_versions::SERVICE_DATA_TYPE {
@@ -232,7 +232,7 @@ pub(super) fn decode_tick_size(message: &mut ResponseMessage) -> Result<TickSize
 
     Ok(TickSize {
         tick_type: TickType::from(message.next_int()?),
-        size: message.next_double()?,
+        size: message.next_decimal()?,
     })
 }
 
@@ -279,6 +279,18 @@ pub(super) fn decode_tick_option_computation(server_version: i32, message: &mut
     decode_option_computation(server_version, message)
 }
 
+pub(super) fn decode_delta_neutral_validation(message: &mut ResponseMessage) -> Result<DeltaNeutralContract, Error> {
+    message.skip(); // message type
+    message.skip(); // message version
+    message.skip(); // message request id
+
+    Ok(DeltaNeutralContract {
+        contract_id: message.next_int()?,
+        delta: message.next_double()?,
+        price: message.next_double()?,
+    })
+}
+
 pub(super) fn decode_tick_request_parameters(message: &mut ResponseMessage) -> Result<TickRequestParameters, Error> {
     message.skip(); // message type
     message.skip(); // message request id
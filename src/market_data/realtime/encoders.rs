@@ -1,6 +1,5 @@
 use super::{BarSize, WhatToShow};
 use crate::contracts::Contract;
-use crate::contracts::SecurityType;
 use crate::messages::OutgoingMessages;
 use crate::messages::RequestMessage;
 use crate::orders::TagValue;
@@ -191,7 +190,7 @@ pub(crate) fn encode_request_market_data(
     message.push_field(&contract.local_symbol);
     message.push_field(&contract.trading_class);
 
-    if contract.security_type == SecurityType::Spread {
+    if contract.is_bag() {
         message.push_field(&contract.combo_legs.len());
 
         for leg in &contract.combo_legs {
@@ -202,14 +201,7 @@ pub(crate) fn encode_request_market_data(
         }
     }
 
-    if let Some(delta_neutral_contract) = &contract.delta_neutral_contract {
-        message.push_field(&true);
-        message.push_field(&delta_neutral_contract.contract_id);
-        message.push_field(&delta_neutral_contract.delta);
-        message.push_field(&delta_neutral_contract.price);
-    } else {
-        message.push_field(&false);
-    }
+    contract.push_delta_neutral_contract(&mut message);
 
     message.push_field(&generic_ticks.join(","));
     message.push_field(&snapshot);
@@ -223,7 +215,7 @@ pub(crate) fn encode_request_market_data(
     Ok(message)
 }
 
-pub(super) fn encode_cancel_market_data(request_id: i32) -> Result<RequestMessage, Error> {
+pub(crate) fn encode_cancel_market_data(request_id: i32) -> Result<RequestMessage, Error> {
     let mut message = RequestMessage::new();
 
     const VERSION: i32 = 1;
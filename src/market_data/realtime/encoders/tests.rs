@@ -1,5 +1,5 @@
 use super::*;
-use crate::{contracts::contract_samples, contracts::Contract, messages::OutgoingMessages, orders::TagValue, ToField};
+use crate::{contracts::contract_samples, contracts::ComboLeg, contracts::Contract, messages::OutgoingMessages, orders::TagValue, ToField};
 
 /// Helper function to create a basic test contract
 fn create_test_contract() -> Contract {
@@ -183,6 +183,24 @@ mod market_data_tests {
         assert_eq!(message[18], regulatory_snapshot.to_field(), "Wrong regulatory snapshot flag");
     }
 
+    #[test]
+    fn test_encode_request_market_data_combo() {
+        let legs = vec![ComboLeg::buy(55928698, 1, "IPE"), ComboLeg::sell(55850663, 1, "IPE")];
+        let contract = Contract::combo("WTI", "SMART", "USD", legs);
+
+        let message = encode_request_market_data(server_versions::SIZE_RULES, 9000, &contract, &[], false, false)
+            .expect("Failed to encode market data request");
+
+        assert_eq!(message[5], "BAG", "Wrong security type");
+        assert_eq!(message[15], "2", "Wrong combo leg count");
+        assert_eq!(message[16], "55928698", "Wrong first leg contract ID");
+        assert_eq!(message[18], "BUY", "Wrong first leg action");
+        assert_eq!(message[20], "55850663", "Wrong second leg contract ID");
+        assert_eq!(message[22], "SELL", "Wrong second leg action");
+        assert_eq!(message[23], "IPE", "Wrong second leg exchange");
+        assert_eq!(message[24], false.to_field(), "Wrong delta neutral flag");
+    }
+
     #[test]
     fn test_encode_cancel_market_data() {
         let request_id = 9000;
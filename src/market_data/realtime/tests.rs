@@ -22,3 +22,16 @@ mod market_data_tests;
 
 #[cfg(test)]
 mod tick_by_tick_last_tests;
+
+#[cfg(feature = "serde")]
+#[test]
+fn realtime_types_implement_serde() {
+    fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>() {}
+
+    assert_serde::<Bar>();
+    assert_serde::<BidAsk>();
+    assert_serde::<MidPoint>();
+    assert_serde::<Trade>();
+    assert_serde::<MarketDepths>();
+    assert_serde::<TickTypes>();
+}
@@ -38,7 +38,7 @@ fn test_basic_market_data() {
             TickTypes::Price(tick) => {
                 assert_eq!(tick.tick_type, TickType::Bid, "Wrong tick type");
                 assert_eq!(tick.price, 185.50, "Wrong price");
-                assert_eq!(tick.attributes.can_auto_execute, true, "Wrong can auto execute flag");
+                assert!(tick.attributes.can_auto_execute, "Wrong can auto execute flag");
             }
             TickTypes::Size(tick) => {
                 assert_eq!(tick.tick_type, TickType::BidSize, "Wrong tick type");
@@ -47,7 +47,7 @@ fn test_basic_market_data() {
             TickTypes::PriceSize(tick) => {
                 assert_eq!(tick.price_tick_type, TickType::Bid, "Wrong tick type");
                 assert_eq!(tick.price, 185.50, "Wrong price");
-                assert_eq!(tick.attributes.can_auto_execute, false, "Wrong can auto execute flag");
+                assert!(!tick.attributes.can_auto_execute, "Wrong can auto execute flag");
                 assert_eq!(tick.size_tick_type, TickType::BidSize, "Wrong tick type");
                 assert_eq!(tick.size, 100.0, "Wrong size");
             }
@@ -106,7 +106,7 @@ fn test_market_data_with_combo_legs() {
     let snapshot = false;
     let regulatory_snapshot = false;
 
-    let market_data = client
+    let _market_data = client
         .market_data(&contract, generic_ticks, snapshot, regulatory_snapshot)
         .expect("Failed to create market data subscription");
 
@@ -167,6 +167,37 @@ fn test_market_data_with_delta_neutral() {
     assert_eq!(request[delta_neutral_index + 3], "100", "Wrong price");
 }
 
+#[test]
+fn test_market_data_delta_neutral_validation() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["56|1|9000|12345|0.52|101.25|".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::stock("AAPL").with_delta_neutral_contract(12345, 0.0, 0.0);
+
+    let subscription = client
+        .market_data(&contract, &[], false, false)
+        .expect("Failed to create market data subscription");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0][15], "1", "Wrong delta neutral marker");
+
+    match subscription.next() {
+        Some(TickTypes::DeltaNeutralValidation(delta_neutral_contract)) => assert_eq!(
+            delta_neutral_contract,
+            DeltaNeutralContract {
+                contract_id: 12345,
+                delta: 0.52,
+                price: 101.25,
+            }
+        ),
+        tick => panic!("Unexpected tick type received: {:?}", tick),
+    }
+}
+
 #[test]
 fn test_market_data_regulatory_snapshot() {
     let message_bus = Arc::new(MessageBusStub {
@@ -1,11 +1,17 @@
 use std::fmt::Display;
-use std::ops::Index;
+use std::ops::{Deref, Index, Range};
 use std::str::{self, FromStr};
+use std::sync::Arc;
 
 use log::debug;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
+use crate::client::{DataStream, SubscriptionKind};
+use crate::errors::ErrorCategory;
+use crate::transport::BufferPool;
+use crate::Client;
 use crate::{Error, ToField};
 
 pub(crate) mod shared_channel_configuration;
@@ -15,6 +21,7 @@ mod tests;
 const INFINITY_STR: &str = "Infinity";
 const UNSET_DOUBLE: &str = "1.7976931348623157E308";
 const UNSET_INTEGER: &str = "2147483647";
+const UNSET_DECIMAL: &str = "170141183460469231731687303715884105727";
 const UNSET_LONG: &str = "9223372036854775807";
 
 // Index of message text in the response message
@@ -213,8 +220,12 @@ pub fn request_id_index(kind: IncomingMessages) -> Option<usize> {
         IncomingMessages::AccountSummaryEnd => Some(2),
         IncomingMessages::AccountUpdateMulti => Some(2),
         IncomingMessages::AccountUpdateMultiEnd => Some(2),
+        IncomingMessages::BondContractData => Some(1),
         IncomingMessages::ContractData => Some(1),
         IncomingMessages::ContractDataEnd => Some(2),
+        IncomingMessages::DeltaNeutralValidation => Some(2),
+        IncomingMessages::DisplayGroupList => Some(2),
+        IncomingMessages::DisplayGroupUpdated => Some(2),
         IncomingMessages::Error => Some(2),
         IncomingMessages::ExecutionData => Some(1),
         IncomingMessages::ExecutionDataEnd => Some(2),
@@ -234,9 +245,11 @@ pub fn request_id_index(kind: IncomingMessages) -> Option<usize> {
         IncomingMessages::PnL => Some(1),
         IncomingMessages::PnLSingle => Some(1),
         IncomingMessages::RealTimeBars => Some(2),
+        IncomingMessages::ReplaceFAEnd => Some(1),
         IncomingMessages::ScannerData => Some(2),
         IncomingMessages::SecurityDefinitionOptionParameter => Some(1),
         IncomingMessages::SecurityDefinitionOptionParameterEnd => Some(1),
+        IncomingMessages::SoftDollarTier => Some(1),
         IncomingMessages::SymbolSamples => Some(1),
         IncomingMessages::TickByTick => Some(1),
         IncomingMessages::TickEFP => Some(2),
@@ -248,6 +261,7 @@ pub fn request_id_index(kind: IncomingMessages) -> Option<usize> {
         IncomingMessages::TickSize => Some(2),
         IncomingMessages::TickSnapshotEnd => Some(2),
         IncomingMessages::TickString => Some(2),
+        IncomingMessages::UserInfo => Some(1),
         IncomingMessages::WshEventData => Some(1),
         IncomingMessages::WshMetaData => Some(1),
 
@@ -349,9 +363,12 @@ impl ToField for OutgoingMessages {
     }
 }
 
+// A message sent to TWS. Fields are written directly into a single buffer, each followed by a null separator.
 #[derive(Default, Debug, Clone)]
 pub(crate) struct RequestMessage {
-    fields: Vec<String>,
+    data: String,
+    // byte offsets in data where each field ends
+    ends: Vec<usize>,
 }
 
 impl RequestMessage {
@@ -360,54 +377,126 @@ impl RequestMessage {
     }
 
     pub fn push_field<T: ToField>(&mut self, val: &T) -> &RequestMessage {
-        let field = val.to_field();
-        self.fields.push(field);
+        val.write_field(&mut self.data);
+        self.ends.push(self.data.len());
+        self.data.push('\0');
         self
     }
 
+    #[cfg(test)]
     pub fn encode(&self) -> String {
-        let mut data = self.fields.join("\0");
-        data.push('\0');
-        data
+        self.data.clone()
+    }
+
+    // Encoded message, borrowed from the message buffer.
+    pub(crate) fn as_str(&self) -> &str {
+        &self.data
     }
 
     #[cfg(test)]
     pub(crate) fn len(&self) -> usize {
-        self.fields.len()
+        self.ends.len()
     }
 
     #[cfg(test)]
     pub(crate) fn encode_simple(&self) -> String {
-        let mut data = self.fields.join("|");
-        data.push('|');
-        data
+        self.data.replace('\0', "|")
+    }
+
+    fn field(&self, i: usize) -> &str {
+        let start = if i == 0 { 0 } else { self.ends[i - 1] + 1 };
+        &self.data[start..self.ends[i]]
+    }
+}
+
+impl RequestMessage {
+    // Numeric id of the message type, or -1 for empty messages.
+    pub(crate) fn message_id(&self) -> i32 {
+        if self.ends.is_empty() {
+            return -1;
+        }
+        self.field(0).parse().unwrap_or(-1)
     }
 }
 
 impl Index<usize> for RequestMessage {
-    type Output = String;
+    type Output = RequestField;
 
     fn index(&self, i: usize) -> &Self::Output {
-        &self.fields[i]
+        RequestField::new(self.field(i))
+    }
+}
+
+// A field of a request message, borrowed from the message buffer. Compares equal to string slices and strings.
+#[derive(PartialEq, Eq)]
+#[repr(transparent)]
+pub(crate) struct RequestField(str);
+
+impl RequestField {
+    fn new(field: &str) -> &RequestField {
+        // SAFETY: RequestField is a transparent wrapper around str, so both references have the same layout.
+        unsafe { &*(field as *const str as *const RequestField) }
+    }
+}
+
+impl Deref for RequestField {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for RequestField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq<str> for RequestField {
+    fn eq(&self, other: &str) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<&str> for RequestField {
+    fn eq(&self, other: &&str) -> bool {
+        &self.0 == *other
+    }
+}
+
+impl PartialEq<String> for RequestField {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
     }
 }
 
-#[derive(Clone, Default, Debug)]
+// A message received from TWS, holding the null separated fields as received.
+// Fields are parsed on demand from the buffer, so decoding allocates only for fields decoded into owned values.
+#[derive(Clone, Default)]
 pub struct ResponseMessage {
+    // index of the next field
     pub i: usize,
-    pub fields: Vec<String>,
+    data: String,
+    // byte offset of the next field
+    position: usize,
+    // pool the buffer is returned to when the message is dropped
+    pool: Option<Arc<BufferPool>>,
 }
 
 impl ResponseMessage {
     pub fn len(&self) -> usize {
-        self.fields.len()
+        if self.data.is_empty() {
+            return 0;
+        }
+        self.data.split('\0').count()
     }
 
     pub fn message_type(&self) -> IncomingMessages {
-        if self.fields.is_empty() {
+        if self.data.is_empty() {
             IncomingMessages::NotValid
         } else {
-            let message_id = i32::from_str(&self.fields[0]).unwrap_or(-1);
+            let message_id = self.field(0).ok().and_then(|field| i32::from_str(field).ok()).unwrap_or(-1);
             IncomingMessages::from(message_id)
         }
     }
@@ -438,21 +527,69 @@ impl ResponseMessage {
         }
     }
 
+    fn field(&self, i: usize) -> Result<&str, Error> {
+        match self.data.split('\0').nth(i) {
+            Some(field) => Ok(field),
+            None => Err(self.missing_field(i)),
+        }
+    }
+
+    // Returns the byte range of the next field and advances to the field after it.
+    fn next_range(&mut self) -> Result<Range<usize>, Error> {
+        let start = self.position;
+        if start > self.data.len() {
+            return Err(self.missing_field(self.i));
+        }
+
+        let end = match self.data[start..].find('\0') {
+            Some(length) => start + length,
+            None => self.data.len(),
+        };
+
+        self.i += 1;
+        self.position = end + 1;
+        Ok(start..end)
+    }
+
+    // Decoders expecting more fields than TWS sent, e.g. for a newer message version, fail with the message attached.
+    fn missing_field(&self, i: usize) -> Error {
+        Error::Decode {
+            index: i,
+            reason: format!("expected field {i} of message with {} fields", self.len()),
+            message: self.clone(),
+        }
+    }
+
+    // Error for a field that was read but holds an unsupported value.
+    pub(crate) fn decode_error(&self, reason: impl Into<String>) -> Error {
+        Error::Decode {
+            index: self.i.saturating_sub(1),
+            reason: reason.into(),
+            message: self.clone(),
+        }
+    }
+
     pub fn peek_int(&self, i: usize) -> Result<i32, Error> {
-        let field = &self.fields[i];
+        let field = self.field(i)?;
         match field.parse() {
             Ok(val) => Ok(val),
             Err(err) => Err(Error::Parse(i, field.into(), err.to_string())),
         }
     }
 
+    // Missing fields peek as empty strings.
     pub fn peek_string(&self, i: usize) -> String {
-        self.fields[i].to_owned()
+        self.peek_str(i).to_owned()
+    }
+
+    // Borrows field `i` without copying it. Missing fields peek as empty strings.
+    pub fn peek_str(&self, i: usize) -> &str {
+        self.field(i).unwrap_or_default()
     }
 
     pub fn next_int(&mut self) -> Result<i32, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         match field.parse() {
             Ok(val) => Ok(val),
@@ -461,8 +598,8 @@ impl ResponseMessage {
     }
 
     pub fn next_optional_int(&mut self) -> Result<Option<i32>, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         if field.is_empty() || field == UNSET_INTEGER {
             return Ok(None);
@@ -475,15 +612,15 @@ impl ResponseMessage {
     }
 
     pub fn next_bool(&mut self) -> Result<bool, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         Ok(field == "1")
     }
 
     pub fn next_long(&mut self) -> Result<i64, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         match field.parse() {
             Ok(val) => Ok(val),
@@ -492,8 +629,8 @@ impl ResponseMessage {
     }
 
     pub fn next_optional_long(&mut self) -> Result<Option<i64>, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         if field.is_empty() || field == UNSET_LONG {
             return Ok(None);
@@ -506,8 +643,8 @@ impl ResponseMessage {
     }
 
     pub fn next_date_time(&mut self) -> Result<OffsetDateTime, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         if field.is_empty() {
             return Err(Error::Simple("expected timestamp and found empty string".into()));
@@ -521,15 +658,36 @@ impl ResponseMessage {
         }
     }
 
+    // Missing timestamps, sent as empty or 0, decode as None.
+    pub fn next_optional_date_time(&mut self) -> Result<Option<OffsetDateTime>, Error> {
+        let range = self.next_range()?;
+        let field = &self.data[range];
+
+        if field.is_empty() || field == "0" {
+            return Ok(None);
+        }
+
+        let timestamp: i64 = field.parse()?;
+        match OffsetDateTime::from_unix_timestamp(timestamp) {
+            Ok(val) => Ok(Some(val)),
+            Err(err) => Err(Error::Parse(self.i, field.into(), err.to_string())),
+        }
+    }
+
     pub fn next_string(&mut self) -> Result<String, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
-        Ok(String::from(field))
+        let range = self.next_range()?;
+        Ok(String::from(&self.data[range]))
+    }
+
+    // Borrows the next field without copying it.
+    pub fn next_str(&mut self) -> Result<&str, Error> {
+        let range = self.next_range()?;
+        Ok(&self.data[range])
     }
 
     pub fn next_double(&mut self) -> Result<f64, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         if field.is_empty() || field == "0" || field == "0.0" {
             return Ok(0.0);
@@ -541,9 +699,38 @@ impl ResponseMessage {
         }
     }
 
+    // Decimal values, such as sizes. Missing values decode as 0.
+    pub fn next_decimal(&mut self) -> Result<f64, Error> {
+        let range = self.next_range()?;
+        let field = &self.data[range];
+
+        if field.is_empty() || field == UNSET_DECIMAL {
+            return Ok(0.0);
+        }
+
+        match field.parse() {
+            Ok(val) => Ok(val),
+            Err(err) => Err(Error::Parse(self.i, field.into(), err.to_string())),
+        }
+    }
+
+    pub fn next_optional_decimal(&mut self) -> Result<Option<f64>, Error> {
+        let range = self.next_range()?;
+        let field = &self.data[range];
+
+        if field.is_empty() || field == UNSET_DECIMAL {
+            return Ok(None);
+        }
+
+        match field.parse() {
+            Ok(val) => Ok(Some(val)),
+            Err(err) => Err(Error::Parse(self.i, field.into(), err.to_string())),
+        }
+    }
+
     pub fn next_optional_double(&mut self) -> Result<Option<f64>, Error> {
-        let field = &self.fields[self.i];
-        self.i += 1;
+        let range = self.next_range()?;
+        let field = &self.data[range];
 
         if field.is_empty() || field == UNSET_DOUBLE {
             return Ok(None);
@@ -560,38 +747,78 @@ impl ResponseMessage {
     }
 
     pub fn from(fields: &str) -> ResponseMessage {
+        Self::from_data(fields.to_owned())
+    }
+
+    // Creates a message from null separated fields, taking ownership of the buffer.
+    pub(crate) fn from_data(data: String) -> ResponseMessage {
+        ResponseMessage {
+            i: 0,
+            data,
+            position: 0,
+            pool: None,
+        }
+    }
+
+    // Creates a message from a buffer taken from `pool`, returning the buffer to the pool when the message is dropped.
+    pub(crate) fn from_pooled(data: String, pool: Arc<BufferPool>) -> ResponseMessage {
         ResponseMessage {
             i: 0,
-            fields: fields.split('\x00').map(|x| x.to_string()).collect(),
+            data,
+            position: 0,
+            pool: Some(pool),
         }
     }
 
     #[cfg(test)]
     pub fn from_simple(fields: &str) -> ResponseMessage {
         let fields = fields.replace("|", "\0");
-        Self::from(&fields)
+        Self::from_data(fields)
     }
 
+    // Skipping past the end of the message is allowed, so unused trailing fields may be absent.
     pub fn skip(&mut self) {
-        self.i += 1;
+        if self.next_range().is_err() {
+            self.i += 1;
+        }
     }
 
     pub fn encode(&self) -> String {
-        let mut data = self.fields.join("\0");
+        let mut data = self.data.clone();
         data.push('\0');
         data
     }
 
     #[cfg(test)]
     pub fn encode_simple(&self) -> String {
-        let mut data = self.fields.join("|");
+        let mut data = self.data.replace('\0', "|");
         data.push('|');
         data
     }
 }
 
+impl Drop for ResponseMessage {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            pool.give(std::mem::take(&mut self.data).into_bytes());
+        }
+    }
+}
+
+impl std::fmt::Debug for ResponseMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<&str> = if self.data.is_empty() {
+            Vec::new()
+        } else {
+            self.data.split('\0').collect()
+        };
+        f.debug_struct("ResponseMessage").field("i", &self.i).field("fields", &fields).finish()
+    }
+}
+
 /// An error message from the TWS API.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Notice {
     pub code: i32,
     pub message: String,
@@ -604,6 +831,33 @@ impl Notice {
         let message = message.peek_string(MESSAGE_INDEX);
         Notice { code, message }
     }
+
+    /// Category of the notice code.
+    pub fn category(&self) -> ErrorCategory {
+        ErrorCategory::from_code(self.code)
+    }
+
+    /// Returns true for informational notices, e.g. market data farm connection status.
+    pub fn is_warning(&self) -> bool {
+        self.category().is_warning()
+    }
+
+    /// Returns true if a request failing with this notice may succeed when retried later.
+    pub fn is_retryable(&self) -> bool {
+        self.category().is_retryable()
+    }
+}
+
+impl DataStream<Notice> for Notice {
+    const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::Error];
+    const KIND: SubscriptionKind = SubscriptionKind::Notices;
+
+    fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<Notice, Error> {
+        match message.message_type() {
+            IncomingMessages::Error => Ok(Notice::from(message)),
+            _ => Err(Error::UnexpectedResponse(message.clone())),
+        }
+    }
 }
 
 impl Display for Notice {
@@ -80,4 +80,8 @@ pub(crate) const CHANNEL_MAPPINGS: &[ChannelMapping] = &[
         request: OutgoingMessages::RequestScannerParameters,
         responses: &[IncomingMessages::ScannerParameters],
     },
+    ChannelMapping {
+        request: OutgoingMessages::RequestFA,
+        responses: &[IncomingMessages::ReceiveFA],
+    },
 ];
@@ -10,7 +10,7 @@ fn test_message_encodes_bool() {
     message.push_field(&false);
     message.push_field(&true);
 
-    assert_eq!(2, message.fields.len());
+    assert_eq!(2, message.len());
     assert_eq!("0\01\0", message.encode());
 }
 
@@ -22,7 +22,7 @@ fn test_message_encodes_i32() {
     message.push_field(&Some(2));
     message.push_field(&Option::<i32>::None);
 
-    assert_eq!(3, message.fields.len());
+    assert_eq!(3, message.len());
     assert_eq!("1\02\0\0", message.encode());
 }
 
@@ -34,7 +34,7 @@ fn test_message_encodes_f64() {
     message.push_field(&Some(3.0));
     message.push_field(&Option::<f64>::None);
 
-    assert_eq!(3, message.fields.len());
+    assert_eq!(3, message.len());
     // assert_eq!("2.0\03.0\0\0", message.encode());
 }
 
@@ -45,7 +45,7 @@ fn test_message_encodes_string() {
     message.push_field(&"interactive");
     message.push_field(&"brokers");
 
-    assert_eq!(2, message.fields.len());
+    assert_eq!(2, message.len());
     assert_eq!("interactive\0brokers\0", message.encode());
 }
 
@@ -64,7 +64,7 @@ fn test_message_encodes_rule_80_a() {
     message.push_field(&Some(Rule80A::AgentOtherMemberPT));
     message.push_field(&Option::<Rule80A>::None);
 
-    assert_eq!(10, message.fields.len());
+    assert_eq!(10, message.len());
     assert_eq!("I\0A\0W\0J\0U\0M\0K\0Y\0N\0\0", message.encode());
 }
 
@@ -79,7 +79,7 @@ fn test_message_encodes_order_condition() {
     message.push_field(&OrderCondition::Volume);
     message.push_field(&OrderCondition::PercentChange);
 
-    assert_eq!(6, message.fields.len());
+    assert_eq!(6, message.len());
     assert_eq!("1\03\04\05\06\07\0", message.encode());
 }
 
@@ -92,7 +92,7 @@ fn test_message_encodes_action() {
     message.push_field(&Action::SellShort);
     message.push_field(&Action::SellLong);
 
-    assert_eq!(4, message.fields.len());
+    assert_eq!(4, message.len());
     assert_eq!("BUY\0SELL\0SSHORT\0SLONG\0", message.encode());
 }
 
@@ -113,7 +113,7 @@ fn test_message_encodes_security_type() {
     message.push_field(&SecurityType::News);
     message.push_field(&SecurityType::MutualFund);
 
-    assert_eq!(12, message.fields.len());
+    assert_eq!(12, message.len());
     assert_eq!("STK\0OPT\0FUT\0IND\0FOP\0CASH\0BAG\0WAR\0BOND\0CMDTY\0NEWS\0FUND\0", message.encode());
 }
 
@@ -126,7 +126,7 @@ fn test_message_encodes_outgoing_message() {
     message.push_field(&OutgoingMessages::PlaceOrder);
     message.push_field(&OutgoingMessages::RequestUserInfo);
 
-    assert_eq!(4, message.fields.len());
+    assert_eq!(4, message.len());
     assert_eq!("1\02\03\0104\0", message.encode());
 }
 
@@ -138,7 +138,7 @@ fn test_message_encodes_order_open_close() {
     message.push_field(&OrderOpenClose::Open);
     message.push_field(&OrderOpenClose::Close);
 
-    assert_eq!(3, message.fields.len());
+    assert_eq!(3, message.len());
     assert_eq!("\0O\0C\0", message.encode());
 }
 
@@ -151,7 +151,7 @@ fn test_message_encodes_combo_leg_open_close() {
     message.push_field(&ComboLegOpenClose::Close);
     message.push_field(&ComboLegOpenClose::Unknown);
 
-    assert_eq!(4, message.fields.len());
+    assert_eq!(4, message.len());
     assert_eq!("0\01\02\03\0", message.encode());
 }
 
@@ -285,3 +285,78 @@ fn test_notice() {
     assert_eq!(notice.message, "HMDS data farm connection is inactive.");
     assert_eq!(format!("{notice}"), "[2107] HMDS data farm connection is inactive.");
 }
+
+#[test]
+fn test_response_message_fields() {
+    let mut message = ResponseMessage::from_data("1\x002\x009000\x00\x00150.25\x00".to_string());
+
+    assert_eq!(message.len(), 6, "fields including the trailing empty field");
+    assert_eq!(message.message_type(), IncomingMessages::TickPrice);
+    assert_eq!(message.peek_str(2), "9000");
+
+    message.skip();
+    assert_eq!(message.next_int().unwrap(), 2);
+    assert_eq!(message.next_str().unwrap(), "9000");
+    assert_eq!(message.next_optional_int().unwrap(), None);
+    assert_eq!(message.next_double().unwrap(), 150.25);
+    assert_eq!(message.next_string().unwrap(), "");
+
+    assert_eq!(message.encode(), "1\x002\x009000\x00\x00150.25\x00\x00");
+    assert_eq!(
+        format!("{message:?}"),
+        r#"ResponseMessage { i: 6, fields: ["1", "2", "9000", "", "150.25", ""] }"#
+    );
+}
+
+#[test]
+fn test_response_message_missing_fields() {
+    let mut message = ResponseMessage::from("4\x002\x009000\x00");
+
+    message.skip(); // message type
+    message.skip(); // message version
+    assert_eq!(message.next_int().unwrap(), 9000);
+    assert_eq!(message.next_string().unwrap(), "", "trailing empty field");
+
+    match message.next_int() {
+        Err(Error::Decode { index, message, .. }) => {
+            assert_eq!(index, 4, "index");
+            assert_eq!(message.encode(), "4\x002\x009000\x00\x00", "payload");
+        }
+        result => panic!("expected decode error, got {result:?}"),
+    }
+
+    assert!(matches!(message.peek_int(7), Err(Error::Decode { index: 7, .. })));
+    assert_eq!(message.peek_str(7), "");
+}
+
+#[test]
+fn test_response_message_decimals() {
+    let mut message = ResponseMessage::from_simple("2|2|9000|0|100.5|170141183460469231731687303715884105727||");
+
+    message.skip(); // message type
+    message.skip(); // message version
+    message.skip(); // request id
+
+    assert_eq!(message.next_decimal().unwrap(), 0.0, "zero");
+    assert_eq!(message.next_decimal().unwrap(), 100.5, "size");
+    assert_eq!(message.next_decimal().unwrap(), 0.0, "unset");
+    assert_eq!(message.next_decimal().unwrap(), 0.0, "empty");
+    assert_eq!(message.peek_int(2).unwrap(), 9000, "peek after reading");
+}
+
+#[test]
+fn test_response_message_optional_fields() {
+    let mut message = ResponseMessage::from_simple("5|170141183460469231731687303715884105727|||0|1681133400|");
+
+    message.skip(); // message type
+
+    assert_eq!(message.next_optional_decimal().unwrap(), None, "unset decimal");
+    assert_eq!(message.next_optional_decimal().unwrap(), None, "empty decimal");
+    assert_eq!(message.next_optional_date_time().unwrap(), None, "empty timestamp");
+    assert_eq!(message.next_optional_date_time().unwrap(), None, "zero timestamp");
+    assert_eq!(
+        message.next_optional_date_time().unwrap(),
+        Some(OffsetDateTime::from_unix_timestamp(1681133400).unwrap()),
+        "timestamp"
+    );
+}
@@ -0,0 +1,145 @@
+//! # Money
+//!
+//! Typed currency amounts. Arithmetic between [Money] values checks that both amounts are expressed in the same
+//! [Currency], so values reported in different currencies cannot be combined by mistake.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Mul, Neg};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// ISO 4217 currency code, e.g. `USD`.
+///
+/// TWS also reports account values in the pseudo currency [Currency::BASE], the base currency of the account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Currency(Cow<'static, str>);
+
+impl Currency {
+    /// Account base currency.
+    pub const BASE: Currency = Currency(Cow::Borrowed("BASE"));
+    pub const AUD: Currency = Currency(Cow::Borrowed("AUD"));
+    pub const CAD: Currency = Currency(Cow::Borrowed("CAD"));
+    pub const CHF: Currency = Currency(Cow::Borrowed("CHF"));
+    pub const EUR: Currency = Currency(Cow::Borrowed("EUR"));
+    pub const GBP: Currency = Currency(Cow::Borrowed("GBP"));
+    pub const HKD: Currency = Currency(Cow::Borrowed("HKD"));
+    pub const JPY: Currency = Currency(Cow::Borrowed("JPY"));
+    pub const USD: Currency = Currency(Cow::Borrowed("USD"));
+
+    /// Creates a currency from its code. Codes are normalized to upper case.
+    pub fn new(code: &str) -> Self {
+        Currency(Cow::Owned(code.trim().to_uppercase()))
+    }
+
+    /// The currency code.
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for Currency {
+    fn from(code: &str) -> Self {
+        Currency::new(code)
+    }
+}
+
+impl From<String> for Currency {
+    fn from(code: String) -> Self {
+        Currency::new(&code)
+    }
+}
+
+/// An amount expressed in a [Currency].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Money {
+    /// The amount.
+    pub amount: f64,
+    /// The currency the amount is expressed in.
+    pub currency: Currency,
+}
+
+impl Money {
+    /// Creates an amount in `currency`.
+    pub fn new(amount: f64, currency: impl Into<Currency>) -> Self {
+        Money {
+            amount,
+            currency: currency.into(),
+        }
+    }
+
+    /// Zero in `currency`.
+    pub fn zero(currency: impl Into<Currency>) -> Self {
+        Money::new(0.0, currency)
+    }
+
+    /// Adds `other`. Returns an error if the amounts are in different currencies.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, Error> {
+        self.check_currency(other, "add")?;
+        Ok(Money::new(self.amount + other.amount, self.currency.clone()))
+    }
+
+    /// Subtracts `other`. Returns an error if the amounts are in different currencies.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, Error> {
+        self.check_currency(other, "subtract")?;
+        Ok(Money::new(self.amount - other.amount, self.currency.clone()))
+    }
+
+    /// Converts the amount to `currency` at `rate` units of `currency` per unit of this amount's currency.
+    pub fn convert(&self, currency: impl Into<Currency>, rate: f64) -> Money {
+        Money::new(self.amount * rate, currency)
+    }
+
+    /// Sums `amounts`, all of which must be in `currency`.
+    pub fn sum<'a>(currency: impl Into<Currency>, amounts: impl IntoIterator<Item = &'a Money>) -> Result<Money, Error> {
+        amounts
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, amount| total.checked_add(amount))
+    }
+
+    fn check_currency(&self, other: &Money, operation: &str) -> Result<(), Error> {
+        if self.currency != other.currency {
+            return Err(Error::InvalidArgument(format!(
+                "cannot {operation} amounts in different currencies: {} and {}",
+                self.currency, other.currency
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::new(-self.amount, self.currency)
+    }
+}
+
+impl Mul<f64> for Money {
+    type Output = Money;
+
+    fn mul(self, factor: f64) -> Money {
+        Money::new(self.amount * factor, self.currency)
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn currency_normalization() {
+    assert_eq!(Currency::new("usd"), Currency::USD, "lower case code");
+    assert_eq!(Currency::from(" EUR "), Currency::EUR, "padded code");
+    assert_eq!(Currency::new("BASE").code(), "BASE", "base currency code");
+    assert_eq!(Currency::GBP.to_string(), "GBP", "display");
+}
+
+#[test]
+fn money_arithmetic() {
+    let a = Money::new(10.5, Currency::USD);
+    let b = Money::new(2.5, "USD");
+
+    assert_eq!(a.checked_add(&b).unwrap(), Money::new(13.0, Currency::USD), "checked_add");
+    assert_eq!(a.checked_sub(&b).unwrap(), Money::new(8.0, Currency::USD), "checked_sub");
+    assert_eq!(-b.clone(), Money::new(-2.5, Currency::USD), "neg");
+    assert_eq!(b * 2.0, Money::new(5.0, Currency::USD), "mul");
+    assert_eq!(a.to_string(), "10.5 USD", "display");
+}
+
+#[test]
+fn money_rejects_mixed_currencies() {
+    let usd = Money::new(10.0, Currency::USD);
+    let eur = Money::new(10.0, Currency::EUR);
+
+    assert!(matches!(usd.checked_add(&eur), Err(Error::InvalidArgument(_))), "checked_add");
+    assert!(matches!(usd.checked_sub(&eur), Err(Error::InvalidArgument(_))), "checked_sub");
+    assert!(Money::sum(Currency::USD, [&usd, &eur]).is_err(), "sum of mixed currencies");
+
+    assert_eq!(
+        usd.convert(Currency::EUR, 0.9).checked_add(&eur).unwrap(),
+        Money::new(19.0, Currency::EUR),
+        "converted"
+    );
+}
+
+#[test]
+fn money_sum() {
+    let amounts = vec![Money::new(1.0, Currency::JPY), Money::new(2.0, Currency::JPY)];
+    assert_eq!(Money::sum(Currency::JPY, &amounts).unwrap(), Money::new(3.0, Currency::JPY), "sum");
+}
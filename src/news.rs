@@ -1,17 +1,25 @@
 use crate::market_data::realtime;
 use crate::{
-    client::{DataStream, ResponseContext, SharesChannel, Subscription},
+    client::{DataStream, ResponseContext, SharesChannel, Subscription, SubscriptionKind},
     contracts::Contract,
     messages::{IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage},
     server_versions, Client, Error,
 };
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use time::OffsetDateTime;
 
 mod decoders;
 mod encoders;
+mod feed;
+#[cfg(test)]
+mod tests;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub use feed::{Headline, HeadlineDeduplicator, HeadlineFilter, NewsFeed, DEFAULT_DEDUP_WINDOW};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NewsProvider {
     pub code: String,
     pub name: String,
@@ -35,7 +43,8 @@ pub(super) fn news_providers(client: &Client) -> Result<Vec<NewsProvider>, Error
 impl SharesChannel for Vec<NewsProvider> {}
 
 /// IB News Bulletin
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NewsBulletin {
     /// The unique identifier of the news bulletin.
     pub message_id: i32,
@@ -47,7 +56,38 @@ pub struct NewsBulletin {
     pub exchange: String,
 }
 
+impl NewsBulletin {
+    /// The type of the news bulletin, e.g. an exchange halting trading.
+    pub fn bulletin_type(&self) -> NewsBulletinType {
+        NewsBulletinType::from(self.message_type)
+    }
+}
+
+/// The type of a [NewsBulletin].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NewsBulletinType {
+    /// Regular news bulletin.
+    #[default]
+    Regular = 1,
+    /// Exchange is no longer available for trading, e.g. trading was halted.
+    ExchangeUnavailable = 2,
+    /// Exchange is available for trading again.
+    ExchangeAvailable = 3,
+}
+
+impl From<i32> for NewsBulletinType {
+    fn from(value: i32) -> Self {
+        match value {
+            2 => NewsBulletinType::ExchangeUnavailable,
+            3 => NewsBulletinType::ExchangeAvailable,
+            _ => NewsBulletinType::Regular,
+        }
+    }
+}
+
 impl DataStream<NewsBulletin> for NewsBulletin {
+    const KIND: SubscriptionKind = SubscriptionKind::NewsBulletins;
     fn decode(_client: &Client, message: &mut ResponseMessage) -> Result<NewsBulletin, Error> {
         match message.message_type() {
             IncomingMessages::NewsBulletins => Ok(decoders::decode_news_bulletin(message.clone())?),
@@ -61,7 +101,7 @@ impl DataStream<NewsBulletin> for NewsBulletin {
 }
 
 // Subscribes to IB's News Bulletins.
-pub(super) fn news_bulletins(client: &Client, all_messages: bool) -> Result<Subscription<NewsBulletin>, Error> {
+pub(super) fn news_bulletins(client: &Client, all_messages: bool) -> Result<Subscription<'_, NewsBulletin>, Error> {
     let request = encoders::encode_request_news_bulletins(all_messages)?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestNewsBulletins, request)?;
 
@@ -71,7 +111,8 @@ pub(super) fn news_bulletins(client: &Client, all_messages: bool) -> Result<Subs
 impl SharesChannel for Subscription<'_, NewsBulletin> {}
 
 /// Returns news headlines for requested contracts.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NewsArticle {
     /// The article’s published time.
     pub time: OffsetDateTime,
@@ -86,6 +127,7 @@ pub struct NewsArticle {
 }
 
 impl DataStream<NewsArticle> for NewsArticle {
+    const KIND: SubscriptionKind = SubscriptionKind::News;
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<NewsArticle, Error> {
         match message.message_type() {
             IncomingMessages::HistoricalNews => Ok(decoders::decode_historical_news(client.time_zone, message.clone())?),
@@ -94,6 +136,14 @@ impl DataStream<NewsArticle> for NewsArticle {
             _ => Err(Error::UnexpectedResponse(message.clone())),
         }
     }
+
+    fn cancel_message(_server_version: i32, request_id: Option<i32>, context: &ResponseContext) -> Result<RequestMessage, Error> {
+        // news ticks are requested as market data for the contract, historical news cannot be cancelled
+        match (request_id, &context.contract) {
+            (Some(request_id), Some(_)) => realtime::encoders::encode_cancel_market_data(request_id),
+            _ => Err(Error::NotImplemented),
+        }
+    }
 }
 
 // Historical News Headlines
@@ -115,14 +165,102 @@ pub(super) fn historical_news<'a>(
         provider_codes,
         start_time,
         end_time,
-        total_results,
+        total_results.into(),
     )?;
     let subscription = client.send_request(request_id, request)?;
 
     Ok(Subscription::new(client, subscription, ResponseContext::default()))
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Default)]
+/// The most headlines TWS returns for a single historical news request.
+pub const MAX_HISTORICAL_NEWS: u16 = 300;
+
+/// Historical news headlines returned by a single request.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HistoricalNews {
+    /// The headlines returned.
+    pub headlines: Vec<NewsArticle>,
+    /// True if more headlines are available in the requested range than were returned.
+    pub has_more: bool,
+}
+
+// Requests a single page of historical news headlines, reporting whether more are available.
+pub(super) fn historical_news_page(
+    client: &Client,
+    contract_id: i32,
+    provider_codes: &[&str],
+    start_time: OffsetDateTime,
+    end_time: OffsetDateTime,
+    total_results: u16,
+) -> Result<HistoricalNews, Error> {
+    client.check_server_version(server_versions::REQ_HISTORICAL_NEWS, "It does not support historical news requests.")?;
+
+    let request_id = client.next_request_id();
+    let request = encoders::encode_request_historical_news(
+        client.server_version(),
+        request_id,
+        contract_id,
+        provider_codes,
+        start_time,
+        end_time,
+        total_results,
+    )?;
+    let subscription = client.send_request(request_id, request)?;
+
+    let mut headlines = Vec::new();
+    loop {
+        match subscription.next() {
+            Some(Ok(message)) if message.message_type() == IncomingMessages::HistoricalNews => {
+                headlines.push(decoders::decode_historical_news(client.time_zone, message)?);
+            }
+            Some(Ok(message)) if message.message_type() == IncomingMessages::HistoricalNewsEnd => {
+                let has_more = decoders::decode_historical_news_end(message)?;
+                return Ok(HistoricalNews { headlines, has_more });
+            }
+            Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
+            Some(Err(Error::ConnectionReset)) => {
+                return historical_news_page(client, contract_id, provider_codes, start_time, end_time, total_results)
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::UnexpectedEndOfStream),
+        }
+    }
+}
+
+// Requests pages of historical news headlines until all headlines in the range are returned.
+pub(super) fn historical_news_all(
+    client: &Client,
+    contract_id: i32,
+    provider_codes: &[&str],
+    start_time: OffsetDateTime,
+    end_time: OffsetDateTime,
+) -> Result<Vec<NewsArticle>, Error> {
+    let mut headlines = Vec::new();
+    let mut seen = HashSet::new();
+    let mut end_time = end_time;
+
+    loop {
+        let page = historical_news_page(client, contract_id, provider_codes, start_time, end_time, MAX_HISTORICAL_NEWS)?;
+        let oldest = page.headlines.iter().map(|headline| headline.time).min();
+
+        // the end of the range is inclusive, so each page repeats the oldest headlines of the previous page
+        let count = headlines.len();
+        for headline in page.headlines {
+            if seen.insert((headline.provider_code.clone(), headline.article_id.clone())) {
+                headlines.push(headline);
+            }
+        }
+
+        match oldest {
+            Some(oldest) if page.has_more && headlines.len() > count => end_time = oldest,
+            _ => return Ok(headlines),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// The type of news article ([ArticleType::Text] - plain text or html, [ArticleType::Binary] - binary data / pdf)
 pub enum ArticleType {
     /// plain text or html
@@ -142,12 +280,67 @@ impl From<i32> for ArticleType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NewsArticleBody {
     /// The type of news article ([ArticleType::Text] - plain text or html, [ArticleType::Binary] - binary data / pdf)
-    article_type: ArticleType,
+    pub article_type: ArticleType,
     /// The body of article (if [ArticleType::Binary], the binary data is encoded using the Base64 scheme)
-    article_text: String,
+    pub article_text: String,
+}
+
+impl NewsArticleBody {
+    /// Content of the article, with binary articles decoded from Base64.
+    pub fn content(&self) -> Result<ArticleContent, Error> {
+        match self.article_type {
+            ArticleType::Text => Ok(ArticleContent::Text(self.article_text.clone())),
+            ArticleType::Binary => Ok(ArticleContent::Binary(decode_base64(&self.article_text)?)),
+        }
+    }
+}
+
+/// Content of a news article, returned by [NewsArticleBody::content].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArticleContent {
+    /// Plain text or html.
+    Text(String),
+    /// Binary data, e.g. a PDF document.
+    Binary(Vec<u8>),
+}
+
+// Decodes standard Base64, ignoring whitespace.
+fn decode_base64(text: &str) -> Result<Vec<u8>, Error> {
+    let invalid = || Error::InvalidFormat {
+        kind: "Base64 in news article",
+        reason: format!("{text:.40}"),
+    };
+
+    let value = |symbol: u8| match symbol {
+        b'A'..=b'Z' => Some(symbol - b'A'),
+        b'a'..=b'z' => Some(symbol - b'a' + 26),
+        b'0'..=b'9' => Some(symbol - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+
+    let symbols: Vec<u8> = text.bytes().filter(|symbol| !symbol.is_ascii_whitespace()).collect();
+    let data = symbols.strip_suffix(b"==").or_else(|| symbols.strip_suffix(b"=")).unwrap_or(&symbols);
+    if !symbols.len().is_multiple_of(4) {
+        return Err(invalid());
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.chunks(4) {
+        let mut bits = 0_u32;
+        for (i, symbol) in chunk.iter().enumerate() {
+            bits |= u32::from(value(*symbol).ok_or_else(invalid)?) << (18 - 6 * i);
+        }
+        let bytes = bits.to_be_bytes();
+        decoded.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Ok(decoded)
 }
 
 pub(super) fn news_article(client: &Client, provider_code: &str, article_id: &str) -> Result<NewsArticleBody, Error> {
@@ -177,7 +370,7 @@ pub fn contract_news<'a>(client: &'a Client, contract: &Contract, provider_codes
         realtime::encoders::encode_request_market_data(client.server_version(), request_id, contract, generic_ticks.as_slice(), false, false)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
 
 pub fn broad_tape_news<'a>(client: &'a Client, provider_code: &str) -> Result<Subscription<'a, NewsArticle>, Error> {
@@ -188,5 +381,5 @@ pub fn broad_tape_news<'a>(client: &'a Client, provider_code: &str) -> Result<Su
     let request = realtime::encoders::encode_request_market_data(client.server_version(), request_id, &contract, generic_ticks, false, false)?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(&contract)))
 }
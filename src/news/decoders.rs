@@ -2,16 +2,17 @@ use std::str;
 
 use time::macros::format_description;
 use time::{OffsetDateTime, PrimitiveDateTime};
-use time_tz::{timezones, PrimitiveDateTimeExt, Tz};
+use time_tz::{timezones, Tz};
 
 use super::{ArticleType, Error, NewsArticle, NewsArticleBody, NewsBulletin, NewsProvider};
+use crate::assume_time_zone;
 use crate::messages::ResponseMessage;
 
 pub(super) fn decode_news_providers(mut message: ResponseMessage) -> Result<Vec<NewsProvider>, Error> {
     message.skip(); // message type
 
     let num_providers = message.next_int()?;
-    let mut news_providers = Vec::with_capacity(num_providers as usize);
+    let mut news_providers = Vec::new();
 
     for _ in 0..num_providers {
         news_providers.push(NewsProvider {
@@ -35,12 +36,19 @@ pub(super) fn decode_news_bulletin(mut message: ResponseMessage) -> Result<NewsB
     })
 }
 
+pub(super) fn decode_historical_news_end(mut message: ResponseMessage) -> Result<bool, Error> {
+    message.skip(); // message type
+    message.skip(); // request id
+
+    message.next_bool()
+}
+
 pub(super) fn decode_historical_news(time_zone: Option<&'static Tz>, mut message: ResponseMessage) -> Result<NewsArticle, Error> {
     message.skip(); // message type
     message.skip(); // request id
 
     let time = message.next_string()?;
-    let time = parse_time(time_zone, &time);
+    let time = parse_time(time_zone, &time)?;
 
     Ok(NewsArticle {
         time,
@@ -51,13 +59,13 @@ pub(super) fn decode_historical_news(time_zone: Option<&'static Tz>, mut message
     })
 }
 
-fn parse_time(time_zone: Option<&'static Tz>, time: &str) -> OffsetDateTime {
+fn parse_time(time_zone: Option<&'static Tz>, time: &str) -> Result<OffsetDateTime, Error> {
     let timezone = time_zone.unwrap_or(timezones::db::UTC);
 
     let format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]");
-    let time = PrimitiveDateTime::parse(time, format).unwrap();
+    let time = PrimitiveDateTime::parse(time, format)?;
 
-    time.assume_timezone(timezone).unwrap()
+    assume_time_zone(time, timezone)
 }
 
 pub(super) fn decode_news_article(mut message: ResponseMessage) -> Result<NewsArticleBody, Error> {
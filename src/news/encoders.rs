@@ -43,7 +43,7 @@ pub(super) fn encode_request_historical_news(
     provider_codes: &[&str],
     start_time: OffsetDateTime,
     end_time: OffsetDateTime,
-    total_results: u8,
+    total_results: u16,
 ) -> Result<RequestMessage, Error> {
     let mut message = RequestMessage::new();
 
@@ -53,7 +53,7 @@ pub(super) fn encode_request_historical_news(
     message.push_field(&provider_codes.join("+"));
     message.push_field(&start_time);
     message.push_field(&end_time);
-    message.push_field(&(total_results as i32));
+    message.push_field(&i32::from(total_results));
     if server_version >= server_versions::NEWS_QUERY_ORIGINS {
         message.push_field(&"");
     }
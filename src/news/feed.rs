@@ -0,0 +1,349 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::Select;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::NewsArticle;
+use crate::client::Subscription;
+use crate::contracts::Contract;
+use crate::{Client, Error};
+
+#[cfg(test)]
+mod tests;
+
+/// How long a headline is remembered when looking for copies from other providers, unless set with [NewsFeed::dedup_window].
+pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+// Upper bound on remembered headlines, in case article times are far apart or out of order.
+const MAX_REMEMBERED: usize = 10_000;
+
+/// A headline received by a [NewsFeed].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Headline {
+    /// Contract id of the contract news subscription the headline was received on, None for broad tape news.
+    pub contract_id: Option<i32>,
+    /// The headline.
+    pub article: NewsArticle,
+}
+
+impl Headline {
+    /// Headline text without the metadata TWS prefixes to some headlines, e.g. `{A:800015:L:en:K:-0.97:C:0.97}`.
+    pub fn text(&self) -> &str {
+        strip_metadata(&self.article.headline)
+    }
+}
+
+/// Selects the headlines passed on by a [NewsFeed].
+///
+/// Each criteria matches if any of its values match, and a headline must match every criteria given. A filter without criteria matches all headlines.
+///
+/// ```
+/// use ibapi::news::HeadlineFilter;
+///
+/// // earnings or guidance headlines from Benzinga or Dow Jones about Apple
+/// let filter = HeadlineFilter::new().keyword("earnings").keyword("guidance").provider("BZ").provider("DJ-N").contract_id(265598);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeadlineFilter {
+    keywords: Vec<String>,
+    providers: Vec<String>,
+    contract_ids: Vec<i32>,
+}
+
+impl HeadlineFilter {
+    /// Creates a filter matching all headlines.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches headlines containing `keyword`, ignoring case.
+    pub fn keyword(mut self, keyword: &str) -> Self {
+        self.keywords.push(keyword.to_lowercase());
+        self
+    }
+
+    /// Matches headlines from the provider with code `provider_code`, e.g. BZ.
+    pub fn provider(mut self, provider_code: &str) -> Self {
+        self.providers.push(provider_code.to_owned());
+        self
+    }
+
+    /// Matches headlines received for the contract with id `contract_id`.
+    pub fn contract_id(mut self, contract_id: i32) -> Self {
+        self.contract_ids.push(contract_id);
+        self
+    }
+
+    /// Returns true if `headline` matches the filter.
+    pub fn matches(&self, headline: &Headline) -> bool {
+        let text = headline.text().to_lowercase();
+
+        (self.keywords.is_empty() || self.keywords.iter().any(|keyword| text.contains(keyword.as_str())))
+            && (self.providers.is_empty() || self.providers.contains(&headline.article.provider_code))
+            && (self.contract_ids.is_empty() || headline.contract_id.is_some_and(|contract_id| self.contract_ids.contains(&contract_id)))
+    }
+}
+
+/// Remembers recent headlines to recognize repeats of the same story.
+///
+/// A headline repeats an earlier one if it has the same provider and article id, or if its text matches, ignoring case and punctuation,
+/// a headline published within the dedup window, e.g. the same wire story carried by several providers.
+#[derive(Clone, Debug)]
+pub struct HeadlineDeduplicator {
+    window: Duration,
+    seen: HashSet<StoryKey>,
+    remembered: VecDeque<(OffsetDateTime, StoryKey)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum StoryKey {
+    Article(String, String),
+    Text(String),
+}
+
+impl HeadlineDeduplicator {
+    /// Creates a deduplicator remembering headlines published within `window` of the latest headline.
+    pub fn new(window: Duration) -> Self {
+        HeadlineDeduplicator {
+            window,
+            seen: HashSet::new(),
+            remembered: VecDeque::new(),
+        }
+    }
+
+    /// Records `article`, returning false if it repeats a remembered headline.
+    pub fn insert(&mut self, article: &NewsArticle) -> bool {
+        self.forget_before(article.time - self.window);
+
+        let keys = [
+            StoryKey::Article(article.provider_code.clone(), article.article_id.clone()),
+            StoryKey::Text(normalize(&article.headline)),
+        ];
+        if keys.iter().any(|key| self.seen.contains(key)) {
+            return false;
+        }
+
+        for key in keys {
+            self.seen.insert(key.clone());
+            self.remembered.push_back((article.time, key));
+        }
+        while self.remembered.len() > MAX_REMEMBERED {
+            self.forget_oldest();
+        }
+
+        true
+    }
+
+    fn forget_before(&mut self, cutoff: OffsetDateTime) {
+        while self.remembered.front().is_some_and(|(time, _)| *time < cutoff) {
+            self.forget_oldest();
+        }
+    }
+
+    fn forget_oldest(&mut self) {
+        if let Some((_, key)) = self.remembered.pop_front() {
+            self.seen.remove(&key);
+        }
+    }
+}
+
+impl Default for HeadlineDeduplicator {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEDUP_WINDOW)
+    }
+}
+
+/// Consolidates headlines from several news subscriptions into a single stream, dropping repeats of the same story and headlines not matching a [HeadlineFilter].
+///
+/// Headlines are returned in the order they are received. Dropping the feed cancels its subscriptions.
+///
+/// ```no_run
+/// use ibapi::contracts::Contract;
+/// use ibapi::news::{HeadlineFilter, NewsFeed};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut contract = Contract::stock("AAPL");
+/// contract.contract_id = 265598;
+///
+/// let feed = NewsFeed::new(&client)
+///     .filter(HeadlineFilter::new().keyword("earnings"))
+///     .contract_news(&contract, &["BZ", "DJ-N"])
+///     .expect("request contract news failed")
+///     .broad_tape_news("BRFG")
+///     .expect("request broad tape news failed");
+///
+/// for headline in feed {
+///     println!("{} {}: {}", headline.article.time, headline.article.provider_code, headline.text());
+/// }
+/// ```
+pub struct NewsFeed<'a> {
+    client: &'a Client,
+    sources: Vec<Source<'a>>,
+    filter: HeadlineFilter,
+    deduplicator: HeadlineDeduplicator,
+    // source polled first, rotated so a busy source does not starve the others
+    next_source: usize,
+}
+
+struct Source<'a> {
+    contract_id: Option<i32>,
+    subscription: Subscription<'a, NewsArticle>,
+    ended: bool,
+}
+
+impl<'a> NewsFeed<'a> {
+    /// Creates a feed without subscriptions, passing on all headlines.
+    pub fn new(client: &'a Client) -> Self {
+        NewsFeed {
+            client,
+            sources: Vec::new(),
+            filter: HeadlineFilter::default(),
+            deduplicator: HeadlineDeduplicator::default(),
+            next_source: 0,
+        }
+    }
+
+    /// Passes on only headlines matching `filter`.
+    pub fn filter(mut self, filter: HeadlineFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// How long a headline is remembered when looking for copies from other providers. Defaults to [DEFAULT_DEDUP_WINDOW].
+    pub fn dedup_window(mut self, window: Duration) -> Self {
+        self.deduplicator = HeadlineDeduplicator::new(window);
+        self
+    }
+
+    /// Adds headlines for `contract` from the providers listed. See [Client::contract_news].
+    pub fn contract_news(mut self, contract: &Contract, provider_codes: &[&str]) -> Result<Self, Error> {
+        let subscription = self.client.contract_news(contract, provider_codes)?;
+        self.add(Some(contract.contract_id), subscription);
+        Ok(self)
+    }
+
+    /// Adds broad tape headlines from `provider_code`. See [Client::broad_tape_news].
+    pub fn broad_tape_news(mut self, provider_code: &str) -> Result<Self, Error> {
+        let subscription = self.client.broad_tape_news(provider_code)?;
+        self.add(None, subscription);
+        Ok(self)
+    }
+
+    fn add(&mut self, contract_id: Option<i32>, subscription: Subscription<'a, NewsArticle>) {
+        self.sources.push(Source {
+            contract_id,
+            subscription,
+            ended: false,
+        });
+    }
+
+    /// Returns the next headline if one was already received, without waiting.
+    pub fn try_next(&mut self) -> Option<Headline> {
+        for _ in 0..self.sources.len() {
+            let index = self.next_source;
+            self.next_source = (self.next_source + 1) % self.sources.len();
+
+            if let Some(headline) = self.receive(index, false) {
+                return Some(headline);
+            }
+        }
+        None
+    }
+
+    /// Waits up to `timeout` for the next headline.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<Headline> {
+        self.next_before(Some(Instant::now() + timeout))
+    }
+
+    // Waits until `deadline` for the next headline, or until all subscriptions end if no deadline is given.
+    fn next_before(&mut self, deadline: Option<Instant>) -> Option<Headline> {
+        loop {
+            if let Some(headline) = self.try_next() {
+                return Some(headline);
+            }
+
+            let mut live = Vec::new();
+            let mut select = Select::new();
+            for (index, source) in self.sources.iter().enumerate().filter(|(_, source)| !source.ended) {
+                if let Some(receiver) = source.subscription.receiver() {
+                    select.recv(receiver);
+                    live.push(index);
+                }
+            }
+            if live.is_empty() {
+                return None;
+            }
+
+            let ready = match deadline {
+                Some(deadline) => select.ready_deadline(deadline).ok()?,
+                None => select.ready(),
+            };
+
+            if let Some(headline) = self.receive(live[ready], true) {
+                return Some(headline);
+            }
+        }
+    }
+
+    // Reads the messages waiting on a source until one yields a new headline matching the filter.
+    // A source found `ready` by a select without a headline has ended, unless it reported an error it recovers from, e.g. a connection reset.
+    fn receive(&mut self, index: usize, ready: bool) -> Option<Headline> {
+        let source = &mut self.sources[index];
+        if source.ended {
+            return None;
+        }
+
+        loop {
+            let Some(article) = source.subscription.try_next() else {
+                source.ended = match source.subscription.error() {
+                    None => ready,
+                    Some(Error::Cancelled) => true,
+                    Some(_) => false,
+                };
+                return None;
+            };
+
+            let headline = Headline {
+                contract_id: source.contract_id,
+                article,
+            };
+            if self.filter.matches(&headline) && self.deduplicator.insert(&headline.article) {
+                return Some(headline);
+            }
+        }
+    }
+}
+
+impl Iterator for NewsFeed<'_> {
+    type Item = Headline;
+
+    /// Blocks until the next headline is received. Returns None once all subscriptions have ended.
+    fn next(&mut self) -> Option<Headline> {
+        self.next_before(None)
+    }
+}
+
+// Strips the metadata TWS prefixes to some headlines, e.g. `{A:800015:L:en:K:-0.97:C:0.97}Apple beats estimates`.
+fn strip_metadata(headline: &str) -> &str {
+    headline
+        .strip_prefix('{')
+        .and_then(|rest| rest.split_once('}'))
+        .map_or(headline, |(_, text)| text)
+        .trim()
+}
+
+// Reduces a headline to lowercase words, so copies differing in case or punctuation match.
+fn normalize(headline: &str) -> String {
+    strip_metadata(headline)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
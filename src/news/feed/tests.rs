@@ -0,0 +1,135 @@
+use std::sync::{Arc, RwLock};
+
+use time::macros::datetime;
+
+use super::*;
+use crate::server_versions;
+use crate::stubs::MessageBusStub;
+
+fn article(time: OffsetDateTime, provider_code: &str, article_id: &str, headline: &str) -> NewsArticle {
+    NewsArticle {
+        time,
+        provider_code: provider_code.to_owned(),
+        article_id: article_id.to_owned(),
+        headline: headline.to_owned(),
+        extra_data: String::new(),
+    }
+}
+
+#[test]
+fn filters_headlines() {
+    let time = datetime!(2023-04-10 13:30 UTC);
+    let headline = Headline {
+        contract_id: Some(265598),
+        article: article(time, "BZ", "BZ$1", "{A:800015:L:en:K:-0.97:C:0.97}Apple Raises Guidance"),
+    };
+
+    assert_eq!(headline.text(), "Apple Raises Guidance", "text");
+    assert!(HeadlineFilter::new().matches(&headline), "no criteria");
+    assert!(
+        HeadlineFilter::new().keyword("earnings").keyword("GUIDANCE").matches(&headline),
+        "keyword"
+    );
+    assert!(!HeadlineFilter::new().keyword("earnings").matches(&headline), "other keyword");
+    assert!(!HeadlineFilter::new().keyword("k:-0.97").matches(&headline), "keyword in metadata");
+    assert!(HeadlineFilter::new().provider("DJ-N").provider("BZ").matches(&headline), "provider");
+    assert!(!HeadlineFilter::new().provider("DJ-N").matches(&headline), "other provider");
+    assert!(HeadlineFilter::new().contract_id(265598).matches(&headline), "contract id");
+    assert!(
+        !HeadlineFilter::new().keyword("guidance").contract_id(8314).matches(&headline),
+        "every criteria"
+    );
+
+    let broad_tape = Headline {
+        contract_id: None,
+        ..headline
+    };
+    assert!(!HeadlineFilter::new().contract_id(265598).matches(&broad_tape), "broad tape");
+}
+
+#[test]
+fn recognizes_repeated_stories() {
+    let time = datetime!(2023-04-10 13:30 UTC);
+    let mut deduplicator = HeadlineDeduplicator::new(Duration::from_secs(60));
+
+    assert!(
+        deduplicator.insert(&article(time, "BZ", "BZ$1", "Apple beats estimates")),
+        "first headline"
+    );
+    assert!(
+        !deduplicator.insert(&article(time, "BZ", "BZ$1", "Apple beats estimates (updated)")),
+        "same article"
+    );
+    assert!(
+        !deduplicator.insert(&article(
+            time + Duration::from_secs(30),
+            "DJ-N",
+            "DJ-N$7",
+            "{K:n/a}APPLE BEATS ESTIMATES!"
+        )),
+        "copy from another provider"
+    );
+    assert!(
+        deduplicator.insert(&article(time, "DJ-N", "DJ-N$8", "Apple misses estimates")),
+        "other story"
+    );
+
+    assert!(
+        deduplicator.insert(&article(time + Duration::from_secs(120), "FLY", "FLY$3", "Apple beats estimates")),
+        "copy outside the window"
+    );
+}
+
+#[test]
+fn consolidates_news_subscriptions() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "84|9000|1681133400000|BZ|BZ$1|Apple earnings beat estimates|A:800015|".to_owned(),
+            "84|9000|1681133460000|DJ-N|DJ-N$1|APPLE EARNINGS BEAT ESTIMATES|A:800015|".to_owned(),
+            "84|9000|1681133520000|BZ|BZ$2|Apple opens new store|A:800015|".to_owned(),
+            "84|9000|1681133580000|DJ-N|DJ-N$2|Apple earnings call scheduled|A:800015|".to_owned(),
+        ],
+    });
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    let mut contract = Contract::stock("AAPL");
+    contract.contract_id = 265598;
+
+    let feed = NewsFeed::new(&client)
+        .filter(HeadlineFilter::new().keyword("earnings"))
+        .contract_news(&contract, &["BZ", "DJ-N"])
+        .unwrap()
+        .broad_tape_news("BRFG")
+        .unwrap();
+
+    // each stubbed subscription receives every response, and sources are polled in turn
+    let headlines: Vec<(Option<i32>, String)> = feed.map(|headline| (headline.contract_id, headline.article.article_id)).collect();
+    assert_eq!(
+        headlines,
+        vec![(Some(265598), "BZ$1".to_owned()), (None, "DJ-N$2".to_owned())],
+        "headlines"
+    );
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests.len(), 4, "requests {requests:?}");
+    assert!(requests[2].encode_simple().starts_with("2|"), "cancel contract news");
+    assert!(requests[3].encode_simple().starts_with("2|"), "cancel broad tape news");
+}
+
+#[test]
+fn waits_for_headlines_until_timeout() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![],
+    });
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let mut feed = NewsFeed::new(&client);
+    assert_eq!(feed.try_next(), None, "feed without subscriptions");
+    assert_eq!(feed.next_timeout(Duration::from_millis(10)), None, "feed without subscriptions");
+
+    let mut feed = feed.broad_tape_news("BRFG").unwrap();
+    assert_eq!(feed.next_timeout(Duration::from_millis(10)), None, "ended subscription");
+    assert_eq!(feed.next(), None, "ended subscription");
+}
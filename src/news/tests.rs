@@ -0,0 +1,131 @@
+use std::sync::{Arc, RwLock};
+
+use time::macros::datetime;
+
+use super::*;
+use crate::stubs::MessageBusStub;
+
+#[test]
+fn decodes_binary_articles() {
+    let article = NewsArticleBody {
+        article_type: ArticleType::Binary,
+        article_text: "JVBERi0xLjQK\nJeLjz9M=".into(),
+    };
+    assert_eq!(
+        article.content().unwrap(),
+        ArticleContent::Binary(b"%PDF-1.4\n%\xe2\xe3\xcf\xd3".to_vec())
+    );
+
+    for (text, expected) in [("", ""), ("Zg==", "f"), ("Zm8=", "fo"), ("Zm9v", "foo"), ("Zm9vYmFy", "foobar")] {
+        assert_eq!(decode_base64(text).unwrap(), expected.as_bytes(), "{text}");
+    }
+}
+
+#[test]
+fn rejects_invalid_base64() {
+    for text in ["Zm9", "Zm9v!A==", "Z==="] {
+        assert!(decode_base64(text).is_err(), "{text}");
+    }
+}
+
+#[test]
+fn returns_text_articles() {
+    let article = NewsArticleBody {
+        article_type: ArticleType::Text,
+        article_text: "<p>Shares rose</p>".into(),
+    };
+    assert_eq!(article.content().unwrap(), ArticleContent::Text("<p>Shares rose</p>".into()));
+}
+
+#[test]
+fn requests_historical_news_pages() {
+    let tws = crate::testing::MockTws::new()
+        .respond_to(
+            "86|9000|8314|BZ+FLY|20230401 00:00:00 UTC|20230415 00:00:00 UTC|300|",
+            &[
+                "86|9000|2023-04-14 12:00:00.0|BZ|BZ$1|Shares rose|",
+                "86|9000|2023-04-12 09:30:00.0|BZ|BZ$2|Shares fell|",
+                "87|9000|1|",
+            ],
+        )
+        .respond_to(
+            "86|9001|8314|BZ+FLY|20230401 00:00:00 UTC|20230412 16:30:00 UTC|300|",
+            &[
+                "86|9001|2023-04-12 09:30:00.0|BZ|BZ$2|Shares fell|",
+                "86|9001|2023-04-03 10:00:00.0|FLY|FLY$3|Upgrade|",
+                "87|9001|0|",
+            ],
+        )
+        .start()
+        .expect("error starting mock TWS");
+    let port = tws.address().rsplit_once(':').unwrap().1.parse().unwrap();
+    let connect = || Client::builder().port(port).connect().expect("connection failed");
+
+    let start_time = datetime!(2023-04-01 0:00 UTC);
+    let end_time = datetime!(2023-04-15 0:00 UTC);
+
+    let client = connect();
+    let page = client.historical_news_page(8314, &["BZ", "FLY"], start_time, end_time, 300).unwrap();
+    assert_eq!(page.headlines.len(), 2, "headlines");
+    assert!(page.has_more, "has more");
+
+    drop(client);
+
+    // requests ids start again from 9000 on the new connection
+    let client = connect();
+    let articles = client.historical_news_all(8314, &["BZ", "FLY"], start_time, end_time).unwrap();
+    let ids: Vec<&str> = articles.iter().map(|article| article.article_id.as_str()).collect();
+    assert_eq!(ids, ["BZ$1", "BZ$2", "FLY$3"], "first page repeated the oldest headline");
+    assert_eq!(articles[0].headline, "Shares rose");
+}
+
+#[test]
+fn decodes_news_bulletin_types() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "14|1|7|2|Trading halted in XYZ|NYSE|".to_owned(),
+            "14|1|8|3|Trading resumed in XYZ|NYSE|".to_owned(),
+            "14|1|9|1|Holiday schedule|ISLAND|".to_owned(),
+        ],
+    });
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let bulletins = client.news_bulletins(true).unwrap();
+    let bulletins: Vec<NewsBulletin> = bulletins.iter().take(3).collect();
+
+    assert_eq!(bulletins[0].bulletin_type(), NewsBulletinType::ExchangeUnavailable);
+    assert_eq!(bulletins[0].message, "Trading halted in XYZ");
+    assert_eq!(bulletins[0].exchange, "NYSE");
+    assert_eq!(bulletins[1].bulletin_type(), NewsBulletinType::ExchangeAvailable);
+    assert_eq!(bulletins[2].bulletin_type(), NewsBulletinType::Regular);
+}
+
+#[test]
+fn cancels_news_ticks_when_dropped() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "81|9000|0.01|NASDAQ|0|".to_owned(),
+            "84|9000|1681133400000|DJ-N|DJ-N$1222|Shares rose|A:800015:CAT:...|".to_owned(),
+        ],
+    });
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    let subscription = client.contract_news(&Contract::stock("AAPL"), &["DJ-N"]).unwrap();
+    let article = subscription.next().unwrap();
+    assert_eq!(article.time, datetime!(2023-04-10 13:30 UTC), "time");
+    assert_eq!(article.provider_code, "DJ-N", "provider code");
+    assert_eq!(article.article_id, "DJ-N$1222", "article id");
+    assert_eq!(article.headline, "Shares rose", "headline");
+    assert_eq!(article.extra_data, "A:800015:CAT:...", "extra data");
+    drop(subscription);
+
+    let subscription = client.broad_tape_news("BRFG").unwrap();
+    drop(subscription);
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests.len(), 4, "requests");
+    assert_eq!(requests[1].encode_simple(), "2|1|9000|", "cancel contract news");
+    assert_eq!(requests[3].encode_simple(), "2|1|9001|", "cancel broad tape news");
+}
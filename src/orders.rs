@@ -1,19 +1,25 @@
+use std::borrow::Cow;
 use std::convert::From;
 use std::fmt::Debug;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::client::{DataStream, ResponseContext, Subscription};
+use crate::client::{DataStream, ResponseContext, Subscription, SubscriptionKind};
 use crate::contracts::{ComboLeg, ComboLegOpenClose, Contract, DeltaNeutralContract, SecurityType};
 use crate::messages::{IncomingMessages, Notice, OutgoingMessages};
 use crate::messages::{RequestMessage, ResponseMessage};
+use crate::money::Money;
 use crate::Client;
 use crate::{encode_option_field, ToField};
 use crate::{server_versions, Error};
 
+pub use journal::{JournalEntry, TradeJournal};
+
 mod decoders;
-mod encoders;
+pub(crate) mod encoders;
+mod journal;
 #[cfg(test)]
 mod tests;
 
@@ -27,7 +33,8 @@ pub use crate::contracts::TagValue;
 
 const COMPETE_AGAINST_BEST_OFFSET_UP_TO_MID: Option<f64> = Some(f64::INFINITY);
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Order describes the order.
 pub struct Order {
     /// The API client's order id.
@@ -586,7 +593,8 @@ impl Order {
 /// For general account types, a SELL order will be able to enter a short position automatically if the order quantity is larger than your current long position.
 /// SSHORT is only supported for institutional account configured with Long/Short account segments or clearing with a separate account.
 /// SLONG is available in specially-configured institutional accounts to indicate that long position not yet delivered is being sold.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Copy, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Action {
     #[default]
     Buy,
@@ -626,18 +634,31 @@ impl Action {
         }
     }
 
+    /// Parses an action, e.g. BUY. Panics if the action is unknown, use [str::parse] to handle unknown actions.
     pub fn from(name: &str) -> Self {
+        name.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl std::str::FromStr for Action {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
         match name {
-            "BUY" => Self::Buy,
-            "SELL" => Self::Sell,
-            "SSHORT" => Self::SellShort,
-            "SLONG" => Self::SellLong,
-            &_ => todo!(),
+            "BUY" => Ok(Self::Buy),
+            "SELL" => Ok(Self::Sell),
+            "SSHORT" => Ok(Self::SellShort),
+            "SLONG" => Ok(Self::SellLong),
+            _ => Err(Error::InvalidFormat {
+                kind: "action",
+                reason: name.to_owned(),
+            }),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Rule80A {
     Individual,
     Agency,
@@ -697,18 +718,21 @@ impl Rule80A {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AuctionStrategy {
     Match,
     Improvement,
     Transparent,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OrderComboLeg {
     price: Option<f64>,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OrderCondition {
     Price = 1,
     Time = 3,
@@ -730,29 +754,43 @@ impl ToField for Option<OrderCondition> {
     }
 }
 
-impl From<i32> for OrderCondition {
-    fn from(val: i32) -> Self {
+impl OrderCondition {
+    // Returns None for condition types that are not supported, so decoders can reject them.
+    pub(crate) fn from_i32(val: i32) -> Option<Self> {
         match val {
-            1 => OrderCondition::Price,
-            3 => OrderCondition::Time,
-            4 => OrderCondition::Volume,
-            5 => OrderCondition::Execution,
-            6 => OrderCondition::Volume,
-            7 => OrderCondition::PercentChange,
-            _ => panic!("OrderCondition({val}) is unsupported"),
+            1 => Some(OrderCondition::Price),
+            3 => Some(OrderCondition::Time),
+            4 => Some(OrderCondition::Volume),
+            5 => Some(OrderCondition::Execution),
+            6 => Some(OrderCondition::Volume),
+            7 => Some(OrderCondition::PercentChange),
+            _ => None,
         }
     }
 }
 
+impl From<i32> for OrderCondition {
+    fn from(val: i32) -> Self {
+        Self::from_i32(val).unwrap_or_else(|| panic!("OrderCondition({val}) is unsupported"))
+    }
+}
+
 /// Stores Soft Dollar Tier information.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+///
+/// Institutional accounts can tag an [Order] with a tier by setting [Order::soft_dollar_tier] to one of the tiers returned by [Client::soft_dollar_tiers].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SoftDollarTier {
+    /// The name of the tier.
     pub name: String,
+    /// The value of the tier.
     pub value: String,
+    /// The display name of the tier.
     pub display_name: String,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OrderData {
     /// The order's unique id
     pub order_id: i32,
@@ -765,7 +803,8 @@ pub struct OrderData {
 }
 
 /// Provides an active order's current state.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OrderState {
     /// The order's current status
     pub status: String,
@@ -805,7 +844,8 @@ pub struct OrderState {
 /// Available for institutional clients to determine if this order is to open or close a position.
 /// When Action = "BUY" and OpenClose = "O" this will open a new position.
 /// When Action = "BUY" and OpenClose = "C" this will close and existing short position.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OrderOpenClose {
     Open,
     Close,
@@ -846,15 +886,14 @@ impl OrderOpenClose {
 
 /// Represents the commission generated by an execution.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CommissionReport {
     /// the execution's id this commission belongs to.
     pub execution_id: String,
-    /// the commissions cost.
-    pub commission: f64,
-    /// the reporting currency.
-    pub currency: String,
-    /// the realized profit and loss
-    pub realized_pnl: Option<f64>,
+    /// the commissions cost, in the reporting currency.
+    pub commission: Money,
+    /// the realized profit and loss, in the reporting currency.
+    pub realized_pnl: Option<Money>,
     /// The income return.
     pub yields: Option<f64>,
     /// date expressed in yyyymmdd format.
@@ -862,6 +901,7 @@ pub struct CommissionReport {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Liquidity {
     #[default]
     None = 0,
@@ -883,6 +923,7 @@ impl From<i32> for Liquidity {
 
 /// Describes an order's execution.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Execution {
     /// The API client's order Id. May not be unique to an account.
     pub order_id: i32,
@@ -930,6 +971,7 @@ pub struct Execution {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExecutionData {
     pub request_id: i32,
     pub contract: Contract,
@@ -937,6 +979,7 @@ pub struct ExecutionData {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::large_enum_variant)]
 pub enum PlaceOrder {
     OrderStatus(OrderStatus),
@@ -947,7 +990,8 @@ pub enum PlaceOrder {
 }
 
 /// Contains all relevant information on the current status of the order execution-wise (i.e. amount filled and pending, filling price, etc.).
-#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OrderStatus {
     /// The order's client id.
     pub order_id: i32,
@@ -989,13 +1033,112 @@ pub(crate) fn place_order<'a>(client: &'a Client, order_id: i32, contract: &Cont
     verify_order(client, order, order_id)?;
     verify_order_contract(client, contract, order_id)?;
 
-    let request = encoders::encode_place_order(client.server_version(), order_id, contract, order)?;
+    let mut order = Cow::Borrowed(order);
+    let account = client.resolve_account(&order.account);
+    if account != order.account {
+        order.to_mut().account = account;
+    }
+
+    let request = encoders::encode_place_order(client.server_version(), order_id, contract, &order)?;
     let subscription = client.send_order(order_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
+}
+
+/// Projected margin impact of a basket of orders, aggregated from what-if submissions.
+///
+/// Each order is evaluated by TWS independently against the current portfolio, so the aggregated changes
+/// are an approximation of the combined impact and do not account for offsets between orders in the basket.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MarginImpact {
+    /// The account's current initial margin.
+    pub initial_margin_before: Option<f64>,
+    /// The account's current maintenance margin.
+    pub maintenance_margin_before: Option<f64>,
+    /// The account's current equity with loan.
+    pub equity_with_loan_before: Option<f64>,
+    /// Sum of the initial margin changes across all orders.
+    pub initial_margin_change: f64,
+    /// Sum of the maintenance margin changes across all orders.
+    pub maintenance_margin_change: f64,
+    /// Sum of the equity with loan changes across all orders.
+    pub equity_with_loan_change: f64,
+    /// Sum of the projected commissions across all orders.
+    pub commission: f64,
+    /// The [OrderState] returned for each order, in submission order.
+    pub order_states: Vec<OrderState>,
+}
+
+impl MarginImpact {
+    /// Projected initial margin after all orders in the basket are filled.
+    pub fn initial_margin_after(&self) -> Option<f64> {
+        self.initial_margin_before.map(|before| before + self.initial_margin_change)
+    }
+
+    /// Projected maintenance margin after all orders in the basket are filled.
+    pub fn maintenance_margin_after(&self) -> Option<f64> {
+        self.maintenance_margin_before.map(|before| before + self.maintenance_margin_change)
+    }
+
+    /// Projected equity with loan after all orders in the basket are filled.
+    pub fn equity_with_loan_after(&self) -> Option<f64> {
+        self.equity_with_loan_before.map(|before| before + self.equity_with_loan_change)
+    }
+
+    fn add(&mut self, order_state: OrderState) {
+        if self.order_states.is_empty() {
+            self.initial_margin_before = order_state.initial_margin_before;
+            self.maintenance_margin_before = order_state.maintenance_margin_before;
+            self.equity_with_loan_before = order_state.equity_with_loan_before;
+        }
+
+        self.initial_margin_change += order_state.initial_margin_change.unwrap_or_default();
+        self.maintenance_margin_change += order_state.maintenance_margin_change.unwrap_or_default();
+        self.equity_with_loan_change += order_state.equity_with_loan_change.unwrap_or_default();
+        self.commission += order_state.commission.unwrap_or_default();
+
+        self.order_states.push(order_state);
+    }
+}
+
+// Submits each order as a what-if order and aggregates the projected margin impact.
+pub(crate) fn what_if_margin(client: &Client, orders: &[(Contract, Order)]) -> Result<MarginImpact, Error> {
+    let mut impact = MarginImpact::default();
+
+    for (contract, order) in orders {
+        let order = Order {
+            what_if: true,
+            ..order.clone()
+        };
+
+        let order_id = client.next_order_id();
+        let subscription = place_order(client, order_id, contract, &order)?;
+
+        let order_state = loop {
+            match subscription.next() {
+                Some(PlaceOrder::OpenOrder(order_data)) => break order_data.order_state,
+                Some(PlaceOrder::Message(notice)) if notice.is_warning() => continue,
+                Some(PlaceOrder::Message(notice)) => {
+                    return Err(Error::Tws {
+                        code: notice.code,
+                        message: notice.message,
+                        request_id: order_id,
+                    })
+                }
+                Some(_) => continue,
+                None => return Err(Error::UnexpectedEndOfStream),
+            }
+        };
+
+        impact.add(order_state);
+    }
+
+    Ok(impact)
 }
 
 impl DataStream<PlaceOrder> for PlaceOrder {
+    const KIND: SubscriptionKind = SubscriptionKind::PlaceOrder;
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<PlaceOrder, Error> {
         match message.message_type() {
             IncomingMessages::OpenOrder => Ok(PlaceOrder::OpenOrder(decoders::decode_open_order(
@@ -1122,6 +1265,10 @@ fn verify_order(client: &Client, order: &Order, _order_id: i32) -> Result<(), Er
         client.check_server_version(server_versions::EXT_OPERATOR, "It does not support ext_operator parameter")?
     }
 
+    if !order.model_code.is_empty() {
+        client.check_server_version(server_versions::MODELS_SUPPORT, "It does not support model code parameter")?
+    }
+
     if order.cash_qty.is_some() {
         client.check_server_version(server_versions::CASH_QTY, "It does not support cash_qty parameter")?
     }
@@ -1189,6 +1336,10 @@ fn verify_order(client: &Client, order: &Order, _order_id: i32) -> Result<(), Er
 
 // Verifies that Contract is properly formed.
 fn verify_order_contract(client: &Client, contract: &Contract, _order_id: i32) -> Result<(), Error> {
+    if contract.is_bag() && contract.combo_legs.is_empty() {
+        return Err(Error::InvalidArgument("combo contract must have at least one leg".into()));
+    }
+
     if contract
         .combo_legs
         .iter()
@@ -1240,12 +1391,14 @@ pub(crate) fn cancel_order<'a>(client: &'a Client, order_id: i32, manual_order_c
 
 /// Enumerates possible results from cancelling an order.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CancelOrder {
     OrderStatus(OrderStatus),
     Notice(Notice),
 }
 
 impl DataStream<CancelOrder> for CancelOrder {
+    const KIND: SubscriptionKind = SubscriptionKind::CancelOrder;
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<CancelOrder, Error> {
         match message.message_type() {
             IncomingMessages::OrderStatus => Ok(CancelOrder::OrderStatus(decoders::decode_order_status(client.server_version, message)?)),
@@ -1267,6 +1420,22 @@ pub(crate) fn global_cancel(client: &Client) -> Result<(), Error> {
     Ok(())
 }
 
+// Requests the soft dollar tiers available to the account.
+pub(crate) fn soft_dollar_tiers(client: &Client) -> Result<Vec<SoftDollarTier>, Error> {
+    client.check_server_version(server_versions::SOFT_DOLLAR_TIER, "It does not support soft dollar tier requests.")?;
+
+    let request_id = client.next_request_id();
+    let message = encoders::encode_soft_dollar_tiers(request_id)?;
+    let subscription = client.send_request(request_id, message)?;
+
+    match subscription.next() {
+        Some(Ok(mut message)) if message.message_type() == IncomingMessages::SoftDollarTier => decoders::decode_soft_dollar_tiers(&mut message),
+        Some(Ok(message)) => Err(Error::from_response(message)),
+        Some(Err(e)) => Err(e),
+        None => Ok(Vec::default()),
+    }
+}
+
 // Gets next valid order id
 pub(crate) fn next_valid_order_id(client: &Client) -> Result<i32, Error> {
     let message = encoders::encode_next_valid_order_id()?;
@@ -1286,7 +1455,7 @@ pub(crate) fn next_valid_order_id(client: &Client) -> Result<i32, Error> {
 }
 
 // Requests completed [Order]s.
-pub(crate) fn completed_orders(client: &Client, api_only: bool) -> Result<Subscription<Orders>, Error> {
+pub(crate) fn completed_orders(client: &Client, api_only: bool) -> Result<Subscription<'_, Orders>, Error> {
     client.check_server_version(server_versions::COMPLETED_ORDERS, "It does not support completed orders requests.")?;
 
     let request = encoders::encode_completed_orders(api_only)?;
@@ -1297,6 +1466,7 @@ pub(crate) fn completed_orders(client: &Client, api_only: bool) -> Result<Subscr
 
 /// Enumerates possible results from querying an [Order].
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::large_enum_variant)]
 pub enum Orders {
     OrderData(OrderData),
@@ -1305,6 +1475,7 @@ pub enum Orders {
 }
 
 impl DataStream<Orders> for Orders {
+    const KIND: SubscriptionKind = SubscriptionKind::Orders;
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Orders, Error> {
         match message.message_type() {
             IncomingMessages::CompletedOrder => Ok(Orders::OrderData(decoders::decode_completed_order(
@@ -1327,7 +1498,7 @@ impl DataStream<Orders> for Orders {
 /// # Arguments
 /// * `client` - [Client] used to communicate with server.
 ///
-pub(crate) fn open_orders(client: &Client) -> Result<Subscription<Orders>, Error> {
+pub(crate) fn open_orders(client: &Client) -> Result<Subscription<'_, Orders>, Error> {
     let request = encoders::encode_open_orders()?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestOpenOrders, request)?;
 
@@ -1336,7 +1507,7 @@ pub(crate) fn open_orders(client: &Client) -> Result<Subscription<Orders>, Error
 
 // Requests all *current* open orders in associated accounts at the current moment.
 // Open orders are returned once; this function does not initiate a subscription.
-pub(crate) fn all_open_orders(client: &Client) -> Result<Subscription<Orders>, Error> {
+pub(crate) fn all_open_orders(client: &Client) -> Result<Subscription<'_, Orders>, Error> {
     let request = encoders::encode_all_open_orders()?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestAllOpenOrders, request)?;
 
@@ -1344,7 +1515,7 @@ pub(crate) fn all_open_orders(client: &Client) -> Result<Subscription<Orders>, E
 }
 
 // Requests status updates about future orders placed from TWS. Can only be used with client ID 0.
-pub(crate) fn auto_open_orders(client: &Client, auto_bind: bool) -> Result<Subscription<Orders>, Error> {
+pub(crate) fn auto_open_orders(client: &Client, auto_bind: bool) -> Result<Subscription<'_, Orders>, Error> {
     let request = encoders::encode_auto_open_orders(auto_bind)?;
     let subscription = client.send_shared_request(OutgoingMessages::RequestAutoOpenOrders, request)?;
 
@@ -1352,6 +1523,7 @@ pub(crate) fn auto_open_orders(client: &Client, auto_bind: bool) -> Result<Subsc
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Filter criteria used to determine which execution reports are returned.
 pub struct ExecutionFilter {
     /// The API client which placed the order.
@@ -1379,7 +1551,7 @@ pub struct ExecutionFilter {
 //
 // # Arguments
 // * `filter` - filter criteria used to determine which execution reports are returned
-pub(crate) fn executions(client: &Client, filter: ExecutionFilter) -> Result<Subscription<Executions>, Error> {
+pub(crate) fn executions(client: &Client, filter: ExecutionFilter) -> Result<Subscription<'_, Executions>, Error> {
     let request_id = client.next_request_id();
 
     let request = encoders::encode_executions(client.server_version(), request_id, &filter)?;
@@ -1390,6 +1562,7 @@ pub(crate) fn executions(client: &Client, filter: ExecutionFilter) -> Result<Sub
 
 /// Enumerates possible results from querying an [Execution].
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::large_enum_variant)]
 pub enum Executions {
     ExecutionData(ExecutionData),
@@ -1398,6 +1571,7 @@ pub enum Executions {
 }
 
 impl DataStream<Executions> for Executions {
+    const KIND: SubscriptionKind = SubscriptionKind::Executions;
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<Executions, Error> {
         match message.message_type() {
             IncomingMessages::ExecutionData => Ok(Executions::ExecutionData(decoders::decode_execution_data(
@@ -1416,12 +1590,14 @@ impl DataStream<Executions> for Executions {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExerciseAction {
     Exercise = 1,
     Lapse = 2,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(clippy::large_enum_variant)]
 pub enum ExerciseOptions {
     OpenOrder(OrderData),
@@ -1430,6 +1606,7 @@ pub enum ExerciseOptions {
 }
 
 impl DataStream<ExerciseOptions> for ExerciseOptions {
+    const KIND: SubscriptionKind = SubscriptionKind::ExerciseOptions;
     fn decode(client: &Client, message: &mut ResponseMessage) -> Result<ExerciseOptions, Error> {
         match message.message_type() {
             IncomingMessages::OpenOrder => Ok(ExerciseOptions::OpenOrder(decoders::decode_open_order(
@@ -1469,5 +1646,5 @@ pub(crate) fn exercise_options<'a>(
     )?;
     let subscription = client.send_request(request_id, request)?;
 
-    Ok(Subscription::new(client, subscription, ResponseContext::default()))
+    Ok(Subscription::new(client, subscription, ResponseContext::for_contract(contract)))
 }
@@ -1,3 +1,4 @@
+use crate::money::Currency;
 use crate::Error;
 
 use super::*;
@@ -60,7 +61,7 @@ impl OrderDecoder {
 
     fn read_action(&mut self) -> Result<(), Error> {
         let action = self.message.next_string()?;
-        self.order.action = Action::from(&action);
+        self.order.action = action.parse()?;
 
         Ok(())
     }
@@ -322,7 +323,8 @@ impl OrderDecoder {
                 ratio,
                 action,
                 exchange,
-                open_close: ComboLegOpenClose::from(open_close),
+                open_close: ComboLegOpenClose::from_i32(open_close)
+                    .ok_or_else(|| self.message.decode_error(format!("unsupported combo leg open/close: {open_close}")))?,
                 short_sale_slot,
                 designated_location,
                 exempt_code,
@@ -475,7 +477,9 @@ impl OrderDecoder {
             let conditions_count = self.message.next_int()?;
             for _ in 0..conditions_count {
                 let order_condition = self.message.next_int()?;
-                self.order.conditions.push(OrderCondition::from(order_condition));
+                let order_condition = OrderCondition::from_i32(order_condition)
+                    .ok_or_else(|| self.message.decode_error(format!("unsupported order condition: {order_condition}")))?;
+                self.order.conditions.push(order_condition);
             }
             if conditions_count > 0 {
                 self.order.conditions_ignore_rth = self.message.next_bool()?;
@@ -808,16 +812,38 @@ pub(crate) fn decode_commission_report(_server_version: i32, message: &mut Respo
     message.skip(); // message type
     message.skip(); // message version
 
+    let execution_id = message.next_string()?;
+    let commission = message.next_double()?;
+    let currency = Currency::new(&message.next_string()?);
+    let realized_pnl = message.next_optional_double()?;
+
     Ok(CommissionReport {
-        execution_id: message.next_string()?,
-        commission: message.next_double()?,
-        currency: message.next_string()?,
-        realized_pnl: message.next_optional_double()?,
+        execution_id,
+        commission: Money::new(commission, currency.clone()),
+        realized_pnl: realized_pnl.map(|amount| Money::new(amount, currency)),
         yields: message.next_optional_double()?,
         yield_redemption_date: message.next_string()?, // TODO: use date type?
     })
 }
 
+pub(crate) fn decode_soft_dollar_tiers(message: &mut ResponseMessage) -> Result<Vec<SoftDollarTier>, Error> {
+    message.skip(); // message type
+    message.skip(); // request id
+
+    let tiers_count = message.next_int()?;
+    let mut tiers = Vec::new();
+
+    for _ in 0..tiers_count {
+        tiers.push(SoftDollarTier {
+            name: message.next_string()?,
+            value: message.next_string()?,
+            display_name: message.next_string()?,
+        });
+    }
+
+    Ok(tiers)
+}
+
 pub(crate) fn decode_completed_order(server_version: i32, message: ResponseMessage) -> Result<OrderData, Error> {
     let mut decoder = OrderDecoder::new(server_version, message);
 
@@ -233,14 +233,7 @@ pub(crate) fn encode_place_order(server_version: i32, order_id: i32, contract: &
     }
 
     if server_version >= server_versions::DELTA_NEUTRAL {
-        if let Some(delta_neutral_contract) = &contract.delta_neutral_contract {
-            message.push_field(&true);
-            message.push_field(&delta_neutral_contract.contract_id);
-            message.push_field(&delta_neutral_contract.delta);
-            message.push_field(&delta_neutral_contract.price);
-        } else {
-            message.push_field(&false);
-        }
+        contract.push_delta_neutral_contract(&mut message);
     }
 
     if server_version >= server_versions::ALGO_ORDERS {
@@ -493,6 +486,15 @@ pub(crate) fn encode_executions(server_version: i32, request_id: i32, filter: &E
     Ok(message)
 }
 
+pub(crate) fn encode_soft_dollar_tiers(request_id: i32) -> Result<RequestMessage, Error> {
+    let mut message = RequestMessage::default();
+
+    message.push_field(&OutgoingMessages::RequestSoftDollarTiers);
+    message.push_field(&request_id);
+
+    Ok(message)
+}
+
 fn f64_max_to_zero(num: Option<f64>) -> Option<f64> {
     if num == Some(f64::MAX) {
         Some(0.0)
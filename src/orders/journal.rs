@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{CommissionReport, ExecutionData, Executions, Order, OrderData, PlaceOrder};
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+const CSV_HEADER: &str = "execution_id,time,account,symbol,security_type,local_symbol,exchange,currency,contract_id,multiplier,side,quantity,price,order_id,perm_id,order_type,limit_price,time_in_force,order_reference,commission,commission_currency,realized_pnl";
+
+/// One fill in a [TradeJournal], joined with its commission report and order.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JournalEntry {
+    /// Unique execution id.
+    pub execution_id: String,
+    /// Execution time as reported by TWS.
+    pub time: String,
+    /// Account the fill was allocated to.
+    pub account: String,
+    /// Symbol of the contract.
+    pub symbol: String,
+    /// Security type of the contract.
+    pub security_type: String,
+    /// Local symbol of the contract.
+    pub local_symbol: String,
+    /// Exchange the fill happened on.
+    pub exchange: String,
+    /// Currency of the contract.
+    pub currency: String,
+    /// Contract id.
+    pub contract_id: i32,
+    /// Contract multiplier.
+    pub multiplier: String,
+    /// `BOT` or `SLD`.
+    pub side: String,
+    /// Filled quantity.
+    pub quantity: f64,
+    /// Fill price.
+    pub price: f64,
+    /// Order id of the order, for the client that placed it.
+    pub order_id: i32,
+    /// Permanent id of the order.
+    pub perm_id: i32,
+    /// Order type. Empty if the order was not recorded.
+    pub order_type: String,
+    /// Limit price of the order.
+    pub limit_price: Option<f64>,
+    /// Time in force of the order.
+    pub time_in_force: String,
+    /// Order reference.
+    pub order_reference: String,
+    /// Commission charged for the fill. `None` until the commission report is recorded.
+    pub commission: Option<f64>,
+    /// Currency of the commission.
+    pub commission_currency: String,
+    /// Realized PnL of the fill.
+    pub realized_pnl: Option<f64>,
+}
+
+/// Joins executions, commission reports and orders into a journal with one entry per fill.
+///
+/// The journal can be written as CSV or JSON for import into tax and reporting tools. With the `serde` feature [JournalEntry] also implements
+/// `serde::Serialize` for other formats.
+///
+/// ```no_run
+/// use ibapi::orders::{ExecutionFilter, TradeJournal};
+/// use ibapi::Client;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut journal = TradeJournal::new();
+///
+/// let executions = client.executions(ExecutionFilter::default()).expect("error requesting executions");
+/// for event in &executions {
+///     journal.apply_executions(&event);
+/// }
+///
+/// let file = std::fs::File::create("journal.csv").expect("error creating journal");
+/// journal.write_csv(file).expect("error writing journal");
+/// ```
+#[derive(Debug, Default)]
+pub struct TradeJournal {
+    executions: Vec<ExecutionData>,
+    commissions: HashMap<String, CommissionReport>,
+    orders: HashMap<i32, Order>,
+}
+
+impl TradeJournal {
+    /// Creates an empty journal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a fill. Executions already recorded are ignored.
+    pub fn add_execution(&mut self, execution_data: &ExecutionData) {
+        let execution_id = &execution_data.execution.execution_id;
+        if !self.executions.iter().any(|recorded| &recorded.execution.execution_id == execution_id) {
+            self.executions.push(execution_data.clone());
+        }
+    }
+
+    /// Records the commission report of a fill.
+    pub fn add_commission(&mut self, report: &CommissionReport) {
+        self.commissions.insert(report.execution_id.clone(), report.clone());
+    }
+
+    /// Records the order fills are joined with, matched by permanent id.
+    pub fn add_order(&mut self, order_data: &OrderData) {
+        self.orders.insert(order_data.order.perm_id, order_data.order.clone());
+    }
+
+    /// Records the events of an order placement.
+    pub fn apply(&mut self, event: &PlaceOrder) {
+        match event {
+            PlaceOrder::OpenOrder(order_data) => self.add_order(order_data),
+            PlaceOrder::ExecutionData(execution_data) => self.add_execution(execution_data),
+            PlaceOrder::CommissionReport(report) => self.add_commission(report),
+            _ => {}
+        }
+    }
+
+    /// Records the events of an executions request.
+    pub fn apply_executions(&mut self, event: &Executions) {
+        match event {
+            Executions::ExecutionData(execution_data) => self.add_execution(execution_data),
+            Executions::CommissionReport(report) => self.add_commission(report),
+            Executions::Notice(_) => {}
+        }
+    }
+
+    /// Journal entries, one per fill in the order fills were recorded.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.executions.iter().map(|execution_data| self.entry(execution_data)).collect()
+    }
+
+    fn entry(&self, execution_data: &ExecutionData) -> JournalEntry {
+        let contract = &execution_data.contract;
+        let execution = &execution_data.execution;
+
+        let mut entry = JournalEntry {
+            execution_id: execution.execution_id.clone(),
+            time: execution.time.clone(),
+            account: execution.account_number.clone(),
+            symbol: contract.symbol.clone(),
+            security_type: contract.security_type.to_string(),
+            local_symbol: contract.local_symbol.clone(),
+            exchange: execution.exchange.clone(),
+            currency: contract.currency.clone(),
+            contract_id: contract.contract_id,
+            multiplier: contract.multiplier.clone(),
+            side: execution.side.clone(),
+            quantity: execution.shares,
+            price: execution.price,
+            order_id: execution.order_id,
+            perm_id: execution.perm_id,
+            order_reference: execution.order_reference.clone(),
+            ..JournalEntry::default()
+        };
+
+        if let Some(order) = self.orders.get(&execution.perm_id) {
+            entry.order_type = order.order_type.clone();
+            entry.limit_price = order.limit_price;
+            entry.time_in_force = order.tif.clone();
+            if entry.order_reference.is_empty() {
+                entry.order_reference = order.order_ref.clone();
+            }
+        }
+
+        if let Some(report) = self.commissions.get(&execution.execution_id) {
+            entry.commission = Some(report.commission.amount);
+            entry.commission_currency = report.commission.currency.to_string();
+            entry.realized_pnl = report.realized_pnl.as_ref().map(|pnl| pnl.amount);
+        }
+
+        entry
+    }
+
+    /// Writes the journal as CSV with a header row.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), Error> {
+        writeln!(writer, "{CSV_HEADER}")?;
+
+        for entry in self.entries() {
+            let fields = [
+                csv_field(&entry.execution_id),
+                csv_field(&entry.time),
+                csv_field(&entry.account),
+                csv_field(&entry.symbol),
+                csv_field(&entry.security_type),
+                csv_field(&entry.local_symbol),
+                csv_field(&entry.exchange),
+                csv_field(&entry.currency),
+                entry.contract_id.to_string(),
+                csv_field(&entry.multiplier),
+                csv_field(&entry.side),
+                entry.quantity.to_string(),
+                entry.price.to_string(),
+                entry.order_id.to_string(),
+                entry.perm_id.to_string(),
+                csv_field(&entry.order_type),
+                optional(entry.limit_price),
+                csv_field(&entry.time_in_force),
+                csv_field(&entry.order_reference),
+                optional(entry.commission),
+                csv_field(&entry.commission_currency),
+                optional(entry.realized_pnl),
+            ];
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the journal as a JSON array of entries.
+    pub fn write_json(&self, mut writer: impl Write) -> Result<(), Error> {
+        let entries: Vec<String> = self.entries().iter().map(json_entry).collect();
+        write!(writer, "[{}]", entries.join(","))?;
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn json_entry(entry: &JournalEntry) -> String {
+    let fields = [
+        ("execution_id", json_string(&entry.execution_id)),
+        ("time", json_string(&entry.time)),
+        ("account", json_string(&entry.account)),
+        ("symbol", json_string(&entry.symbol)),
+        ("security_type", json_string(&entry.security_type)),
+        ("local_symbol", json_string(&entry.local_symbol)),
+        ("exchange", json_string(&entry.exchange)),
+        ("currency", json_string(&entry.currency)),
+        ("contract_id", entry.contract_id.to_string()),
+        ("multiplier", json_string(&entry.multiplier)),
+        ("side", json_string(&entry.side)),
+        ("quantity", json_number(Some(entry.quantity))),
+        ("price", json_number(Some(entry.price))),
+        ("order_id", entry.order_id.to_string()),
+        ("perm_id", entry.perm_id.to_string()),
+        ("order_type", json_string(&entry.order_type)),
+        ("limit_price", json_number(entry.limit_price)),
+        ("time_in_force", json_string(&entry.time_in_force)),
+        ("order_reference", json_string(&entry.order_reference)),
+        ("commission", json_number(entry.commission)),
+        ("commission_currency", json_string(&entry.commission_currency)),
+        ("realized_pnl", json_number(entry.realized_pnl)),
+    ];
+
+    let fields: Vec<String> = fields.iter().map(|(name, value)| format!("\"{name}\":{value}")).collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_number(value: Option<f64>) -> String {
+    match value {
+        Some(value) if value.is_finite() => value.to_string(),
+        _ => "null".to_owned(),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,119 @@
+use super::*;
+use crate::contracts::Contract;
+use crate::money::{Currency, Money};
+use crate::orders::Execution;
+
+fn execution(execution_id: &str, side: &str, shares: f64, price: f64) -> ExecutionData {
+    ExecutionData {
+        contract: Contract {
+            contract_id: 76792991,
+            local_symbol: "TSLA".to_owned(),
+            ..Contract::stock("TSLA")
+        },
+        execution: Execution {
+            order_id: 13,
+            execution_id: execution_id.to_owned(),
+            time: "20230224  12:04:56".to_owned(),
+            account_number: "DU1234567".to_owned(),
+            exchange: "ISLAND".to_owned(),
+            side: side.to_owned(),
+            shares,
+            price,
+            perm_id: 1376327563,
+            ..Execution::default()
+        },
+        ..ExecutionData::default()
+    }
+}
+
+fn sample_journal() -> TradeJournal {
+    let mut journal = TradeJournal::new();
+
+    journal.add_order(&OrderData {
+        order_id: 13,
+        order: Order {
+            perm_id: 1376327563,
+            order_type: "LMT".to_owned(),
+            limit_price: Some(196.60),
+            tif: "DAY".to_owned(),
+            order_ref: "rebalance, march".to_owned(),
+            ..Order::default()
+        },
+        ..OrderData::default()
+    });
+    journal.add_execution(&execution("0001", "BOT", 100.0, 196.52));
+    journal.add_execution(&execution("0001", "BOT", 100.0, 196.52));
+    journal.add_execution(&execution("0002", "BOT", 50.0, 196.55));
+    journal.add_commission(&CommissionReport {
+        execution_id: "0001".to_owned(),
+        commission: Money::new(1.0, Currency::USD),
+        realized_pnl: Some(Money::new(12.5, Currency::USD)),
+        ..CommissionReport::default()
+    });
+
+    journal
+}
+
+#[test]
+fn test_entries() {
+    let entries = sample_journal().entries();
+
+    assert_eq!(entries.len(), 2, "duplicate execution ignored");
+
+    let entry = &entries[0];
+    assert_eq!(entry.execution_id, "0001", "entry.execution_id");
+    assert_eq!(entry.account, "DU1234567", "entry.account");
+    assert_eq!(entry.symbol, "TSLA", "entry.symbol");
+    assert_eq!(entry.security_type, "STK", "entry.security_type");
+    assert_eq!(entry.side, "BOT", "entry.side");
+    assert_eq!(entry.quantity, 100.0, "entry.quantity");
+    assert_eq!(entry.price, 196.52, "entry.price");
+    assert_eq!(entry.order_type, "LMT", "entry.order_type");
+    assert_eq!(entry.limit_price, Some(196.60), "entry.limit_price");
+    assert_eq!(entry.time_in_force, "DAY", "entry.time_in_force");
+    assert_eq!(entry.order_reference, "rebalance, march", "entry.order_reference");
+    assert_eq!(entry.commission, Some(1.0), "entry.commission");
+    assert_eq!(entry.commission_currency, "USD", "entry.commission_currency");
+    assert_eq!(entry.realized_pnl, Some(12.5), "entry.realized_pnl");
+
+    assert_eq!(entries[1].commission, None, "commission not reported yet");
+}
+
+#[test]
+fn test_write_csv() {
+    let mut output = Vec::new();
+    sample_journal().write_csv(&mut output).expect("error writing csv");
+
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines.len(), 3, "header and one line per fill");
+    assert_eq!(lines[0], CSV_HEADER, "header");
+    assert_eq!(
+        lines[1],
+        "0001,20230224  12:04:56,DU1234567,TSLA,STK,TSLA,ISLAND,USD,76792991,,BOT,100,196.52,13,1376327563,LMT,196.6,DAY,\"rebalance, march\",1,USD,12.5"
+    );
+    assert_eq!(
+        lines[2],
+        "0002,20230224  12:04:56,DU1234567,TSLA,STK,TSLA,ISLAND,USD,76792991,,BOT,50,196.55,13,1376327563,LMT,196.6,DAY,\"rebalance, march\",,,"
+    );
+}
+
+#[test]
+fn test_write_json() {
+    let mut journal = TradeJournal::new();
+    journal.add_execution(&execution("0001", "SLD", 10.0, 200.0));
+
+    let mut output = Vec::new();
+    journal.write_json(&mut output).expect("error writing json");
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "[{\"execution_id\":\"0001\",\"time\":\"20230224  12:04:56\",\"account\":\"DU1234567\",\"symbol\":\"TSLA\",\"security_type\":\"STK\",\"local_symbol\":\"TSLA\",\"exchange\":\"ISLAND\",\"currency\":\"USD\",\"contract_id\":76792991,\"multiplier\":\"\",\"side\":\"SLD\",\"quantity\":10,\"price\":200,\"order_id\":13,\"perm_id\":1376327563,\"order_type\":\"\",\"limit_price\":null,\"time_in_force\":\"\",\"order_reference\":\"\",\"commission\":null,\"commission_currency\":\"\",\"realized_pnl\":null}]"
+    );
+}
+
+#[test]
+fn test_json_string() {
+    assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+}
@@ -1,6 +1,7 @@
 use std::sync::{Arc, RwLock};
 
-use crate::contracts::{contract_samples, Contract, SecurityType};
+use crate::contracts::{contract_samples, ComboLeg, ComboLegOpenClose, Contract, SecurityType};
+use crate::money::Currency;
 use crate::stubs::MessageBusStub;
 
 use super::order_builder::*;
@@ -86,8 +87,8 @@ fn place_order() {
         assert_eq!(order.order_ref, "", "order.order_ref");
         assert_eq!(order.client_id, 100, "order.client_id");
         assert_eq!(order.perm_id, 1376327563, "order.perm_id");
-        assert_eq!(order.outside_rth, false, "order.outside_rth");
-        assert_eq!(order.hidden, false, "order.hidden");
+        assert!(!order.outside_rth, "order.outside_rth");
+        assert!(!order.hidden, "order.hidden");
         assert_eq!(order.discretionary_amt, 0.0, "order.discretionary_amt");
         assert_eq!(order.good_after_time, "", "order.good_after_time");
         assert_eq!(order.fa_group, "", "order.fa_group");
@@ -109,9 +110,9 @@ fn place_order() {
         assert_eq!(order.stock_range_lower, None, "order.stock_range_lower");
         assert_eq!(order.stock_range_upper, None, "order.stock_range_upper");
         assert_eq!(order.display_size, None, "order.display_size");
-        assert_eq!(order.block_order, false, "order.block_order");
-        assert_eq!(order.sweep_to_fill, false, "order.sweep_to_fill");
-        assert_eq!(order.all_or_none, false, "order.all_or_none");
+        assert!(!order.block_order, "order.block_order");
+        assert!(!order.sweep_to_fill, "order.sweep_to_fill");
+        assert!(!order.all_or_none, "order.all_or_none");
         assert_eq!(order.min_qty, None, "order.min_qty");
         assert_eq!(order.oca_type, 3, "order.oca_type");
         assert_eq!(order.parent_id, 0, "order.parent_id");
@@ -125,10 +126,10 @@ fn place_order() {
         assert_eq!(order.delta_neutral_clearing_account, "", "order.delta_neutral_clearing_account");
         assert_eq!(order.delta_neutral_clearing_intent, "", "order.delta_neutral_clearing_intent");
         assert_eq!(order.delta_neutral_open_close, "?", "order.delta_neutral_open_close");
-        assert_eq!(order.delta_neutral_short_sale, false, "order.delta_neutral_short_sale");
+        assert!(!order.delta_neutral_short_sale, "order.delta_neutral_short_sale");
         assert_eq!(order.delta_neutral_short_sale_slot, 0, "order.delta_neutral_short_sale_slot");
         assert_eq!(order.delta_neutral_designated_location, "", "order.delta_neutral_designated_location");
-        assert_eq!(order.continuous_update, false, "order.continuous_update");
+        assert!(!order.continuous_update, "order.continuous_update");
         assert_eq!(order.reference_price_type, Some(0), "order.reference_price_type");
         assert_eq!(order.trail_stop_price, None, "order.trail_stop_price");
         assert_eq!(order.trailing_percent, None, "order.trailing_percent");
@@ -142,14 +143,14 @@ fn place_order() {
         assert_eq!(order.scale_subs_level_size, None, "order.scale_subs_level_size");
         assert_eq!(order.scale_price_increment, None, "order.scale_price_increment");
         assert_eq!(order.hedge_type, "", "order.hedge_type");
-        assert_eq!(order.opt_out_smart_routing, false, "order.opt_out_smart_routing");
+        assert!(!order.opt_out_smart_routing, "order.opt_out_smart_routing");
         assert_eq!(order.clearing_account, "", "order.clearing_account");
         assert_eq!(order.clearing_intent, "IB", "order.clearing_intent");
-        assert_eq!(order.not_held, false, "order.not_held");
+        assert!(!order.not_held, "order.not_held");
         assert_eq!(order.algo_strategy, "", "order.algo_strategy");
         assert_eq!(order.algo_params.len(), 0, "order.algo_params.len()");
-        assert_eq!(order.solicited, false, "order.solicited");
-        assert_eq!(order.what_if, false, "order.what_if");
+        assert!(!order.solicited, "order.solicited");
+        assert!(!order.what_if, "order.what_if");
         assert_eq!(order_state.status, "PreSubmitted", "order_state.status");
         assert_eq!(order_state.initial_margin_before, None, "order_state.initial_margin_before");
         assert_eq!(order_state.maintenance_margin_before, None, "order_state.maintenance_margin_before");
@@ -165,8 +166,8 @@ fn place_order() {
         assert_eq!(order_state.maximum_commission, None, "order_state.maximum_commission");
         assert_eq!(order_state.commission_currency, "", "order_state.commission_currency");
         assert_eq!(order_state.warning_text, "", "order_state.warning_text");
-        assert_eq!(order.randomize_size, false, "order.randomize_size");
-        assert_eq!(order.randomize_price, false, "order.randomize_price");
+        assert!(!order.randomize_size, "order.randomize_size");
+        assert!(!order.randomize_price, "order.randomize_price");
         assert_eq!(order.conditions.len(), 0, "order.conditions.len()");
         assert_eq!(order.adjusted_order_type, "None", "order.adjusted_order_type");
         assert_eq!(order.trigger_price, None, "order.trigger_price");
@@ -180,20 +181,20 @@ fn place_order() {
         assert_eq!(order.soft_dollar_tier.value, "", "order.soft_dollar_tier.value");
         assert_eq!(order.soft_dollar_tier.display_name, "", "order.soft_dollar_tier.display_name");
         assert_eq!(order.cash_qty, Some(0.0), "order.cash_qty");
-        assert_eq!(order.dont_use_auto_price_for_hedge, true, "order.dont_use_auto_price_for_hedge");
-        assert_eq!(order.is_oms_container, false, "order.is_oms_container");
-        assert_eq!(order.discretionary_up_to_limit_price, false, "order.discretionary_up_to_limit_price");
-        assert_eq!(order.use_price_mgmt_algo, false, "order.use_price_mgmt_algo");
+        assert!(order.dont_use_auto_price_for_hedge, "order.dont_use_auto_price_for_hedge");
+        assert!(!order.is_oms_container, "order.is_oms_container");
+        assert!(!order.discretionary_up_to_limit_price, "order.discretionary_up_to_limit_price");
+        assert!(!order.use_price_mgmt_algo, "order.use_price_mgmt_algo");
         assert_eq!(order.duration, None, "order.duration");
         assert_eq!(order.post_to_ats, None, "order.post_to_ats");
-        assert_eq!(order.auto_cancel_parent, false, "order.auto_cancel_parent");
+        assert!(!order.auto_cancel_parent, "order.auto_cancel_parent");
         assert_eq!(order.min_trade_qty, None, "order.min_trade_qty");
         assert_eq!(order.min_compete_size, None, "order.min_compete_size");
         assert_eq!(order.compete_against_best_offset, None, "order.compete_against_best_offset");
         assert_eq!(order.mid_offset_at_whole, None, "order.mid_offset_at_whole");
         assert_eq!(order.mid_offset_at_half, None, "order.mid_offset_at_half");
     } else {
-        assert!(false, "message[0] expected an open order notification");
+        panic!("message[0] expected an open order notification");
     }
 
     if let Some(PlaceOrder::OrderStatus(order_status)) = notifications.next() {
@@ -209,7 +210,7 @@ fn place_order() {
         assert_eq!(order_status.why_held, "", "order_status.why_held");
         assert_eq!(order_status.market_cap_price, 0.0, "order_status.market_cap_price");
     } else {
-        assert!(false, "message[1] expected order status notification");
+        panic!("message[1] expected order status notification");
     }
 
     if let Some(PlaceOrder::ExecutionData(execution_data)) = notifications.next() {
@@ -250,7 +251,7 @@ fn place_order() {
         assert_eq!(execution.model_code, "", "execution.model_code");
         assert_eq!(execution.last_liquidity, Liquidity::RemovedLiquidity, "execution.last_liquidity");
     } else {
-        assert!(false, "message[2] expected execution notification");
+        panic!("message[2] expected execution notification");
     }
 
     if let Some(PlaceOrder::OpenOrder(open_order)) = notifications.next() {
@@ -259,7 +260,7 @@ fn place_order() {
         assert_eq!(open_order.order_id, 13, "open_order.order_id");
         assert_eq!(order_state.status, "Filled", "order_state.status");
     } else {
-        assert!(false, "message[3] expected an open order notification");
+        panic!("message[3] expected an open order notification");
     }
 
     if let Some(PlaceOrder::OrderStatus(order_status)) = notifications.next() {
@@ -270,7 +271,7 @@ fn place_order() {
         assert_eq!(order_status.average_fill_price, 196.52, "order_status.average_fill_price");
         assert_eq!(order_status.last_fill_price, 196.52, "order_status.last_fill_price");
     } else {
-        assert!(false, "message[4] expected order status notification");
+        panic!("message[4] expected order status notification");
     }
 
     if let Some(PlaceOrder::OpenOrder(open_order)) = notifications.next() {
@@ -283,21 +284,89 @@ fn place_order() {
         assert_eq!(order_state.maximum_commission, None, "order_state.maximum_commission");
         assert_eq!(order_state.commission_currency, "USD", "order_state.commission_currency");
     } else {
-        assert!(false, "message[5] expected an open order notification");
+        panic!("message[5] expected an open order notification");
     }
 
     if let Some(PlaceOrder::CommissionReport(report)) = notifications.next() {
         assert_eq!(report.execution_id, "00025b46.63f8f39c.01.01", "report.execution_id");
-        assert_eq!(report.commission, 1.0, "report.commission");
-        assert_eq!(report.currency, "USD", "report.currency");
+        assert_eq!(report.commission, Money::new(1.0, Currency::USD), "report.commission");
         assert_eq!(report.realized_pnl, None, "report.realized_pnl");
         assert_eq!(report.yields, None, "report.yielded");
         assert_eq!(report.yield_redemption_date, "", "report.yield_redemption_date");
     } else {
-        assert!(false, "message[6] expected a commission report notification");
+        panic!("message[6] expected a commission report notification");
     }
 }
 
+#[test]
+fn what_if_margin() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["5|13|76792991|TSLA|STK||0|?||SMART|USD|TSLA|NMS|BUY|100|MKT|0.0|0.0|DAY||DU1234567||0||100|1376327563|0|0|0||1376327563.0/DU1234567/100||||||||||0||-1|0||||||2147483647|0|0|0||3|0|0||0|0||0|None||0||||?|0|0||0|0||||||0|0|0|2147483647|2147483647|||0||IB|0|0||0|1|PreSubmitted|25000.0|20000.0|100000.0|1500.0|1200.0|-1.0|26500.0|21200.0|99999.0|1.0|||USD||0|0|0|None|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|0||||0|1|0|0|0|||0||".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let basket = vec![
+        (Contract::stock("TSLA"), order_builder::market_order(Action::Buy, 100.0)),
+        (Contract::stock("TSLA"), order_builder::market_order(Action::Buy, 100.0)),
+    ];
+
+    let impact = client.what_if_margin(&basket).expect("what-if request failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages.len(), 2, "request_messages.len()");
+
+    assert_eq!(impact.order_states.len(), 2, "impact.order_states.len()");
+    assert_eq!(impact.initial_margin_before, Some(25000.0), "impact.initial_margin_before");
+    assert_eq!(impact.maintenance_margin_before, Some(20000.0), "impact.maintenance_margin_before");
+    assert_eq!(impact.equity_with_loan_before, Some(100000.0), "impact.equity_with_loan_before");
+    assert_eq!(impact.initial_margin_change, 3000.0, "impact.initial_margin_change");
+    assert_eq!(impact.maintenance_margin_change, 2400.0, "impact.maintenance_margin_change");
+    assert_eq!(impact.equity_with_loan_change, -2.0, "impact.equity_with_loan_change");
+    assert_eq!(impact.commission, 2.0, "impact.commission");
+    assert_eq!(impact.initial_margin_after(), Some(28000.0), "impact.initial_margin_after()");
+    assert_eq!(impact.maintenance_margin_after(), Some(22400.0), "impact.maintenance_margin_after()");
+    assert_eq!(impact.equity_with_loan_after(), Some(99998.0), "impact.equity_with_loan_after()");
+}
+
+#[test]
+fn what_if_margin_skips_warnings() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "4|2|13|2109|Order Event Warning: Attribute 'Outside Regular Trading Hours' is ignored.||".to_owned(),
+            "5|13|76792991|TSLA|STK||0|?||SMART|USD|TSLA|NMS|BUY|100|MKT|0.0|0.0|DAY||DU1234567||0||100|1376327563|0|0|0||1376327563.0/DU1234567/100||||||||||0||-1|0||||||2147483647|0|0|0||3|0|0||0|0||0|None||0||||?|0|0||0|0||||||0|0|0|2147483647|2147483647|||0||IB|0|0||0|1|PreSubmitted|25000.0|20000.0|100000.0|1500.0|1200.0|-1.0|26500.0|21200.0|99999.0|1.0|||USD||0|0|0|None|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|0||||0|1|0|0|0|||0||".to_owned(),
+        ],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let basket = vec![(Contract::stock("TSLA"), order_builder::market_order(Action::Buy, 100.0))];
+
+    let impact = client.what_if_margin(&basket).expect("what-if request failed");
+
+    assert_eq!(impact.order_states.len(), 1, "impact.order_states.len()");
+    assert_eq!(impact.initial_margin_change, 1500.0, "impact.initial_margin_change");
+}
+
+#[test]
+fn what_if_margin_rejected() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["4|2|13|201|Order rejected - reason:Insufficient margin||".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let basket = vec![(Contract::stock("TSLA"), order_builder::market_order(Action::Buy, 100.0))];
+
+    let error = client.what_if_margin(&basket).expect_err("expected rejected what-if order");
+
+    assert_eq!(error.code(), Some(201), "error.code()");
+    assert!(matches!(error, Error::Tws { .. }), "unexpected error {error:?}");
+}
+
 #[test]
 fn cancel_order() {
     let message_bus = Arc::new(MessageBusStub {
@@ -347,9 +416,9 @@ fn global_cancel() {
         response_messages: vec![],
     });
 
-    let mut client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
 
-    let results = super::global_cancel(&mut client);
+    let results = super::global_cancel(&client);
 
     let request_messages = client.message_bus.request_messages();
 
@@ -364,9 +433,9 @@ fn next_valid_order_id() {
         response_messages: vec!["9|1|43||".to_owned()],
     });
 
-    let mut client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
 
-    let results = super::next_valid_order_id(&mut client);
+    let results = super::next_valid_order_id(&client);
 
     let request_messages = client.message_bus.request_messages();
 
@@ -431,8 +500,8 @@ fn completed_orders() {
         assert_eq!(order.origin, 0, "order.origin");
         assert_eq!(order.order_ref, "", "order.order_ref");
         assert_eq!(order.perm_id, 1824933227, "order.perm_id");
-        assert_eq!(order.outside_rth, false, "order.outside_rth");
-        assert_eq!(order.hidden, false, "order.hidden");
+        assert!(!order.outside_rth, "order.outside_rth");
+        assert!(!order.hidden, "order.hidden");
         assert_eq!(order.discretionary_amt, 0.0, "order.discretionary_amt");
         assert_eq!(order.good_after_time, "", "order.good_after_time");
         assert_eq!(order.fa_group, "", "order.fa_group");
@@ -453,8 +522,8 @@ fn completed_orders() {
         assert_eq!(order.stock_range_lower, None, "order.stock_range_lower");
         assert_eq!(order.stock_range_upper, None, "order.stock_range_upper");
         assert_eq!(order.display_size, None, "order.display_size");
-        assert_eq!(order.sweep_to_fill, false, "order.sweep_to_fill");
-        assert_eq!(order.all_or_none, false, "order.all_or_none");
+        assert!(!order.sweep_to_fill, "order.sweep_to_fill");
+        assert!(!order.all_or_none, "order.all_or_none");
         assert_eq!(order.min_qty, None, "order.min_qty");
         assert_eq!(order.oca_type, 3, "order.oca_type");
         assert_eq!(order.trigger_method, 0, "order.trigger_method");
@@ -463,10 +532,10 @@ fn completed_orders() {
         assert_eq!(order.delta_neutral_order_type, "None", "order.delta_neutral_order_type");
         assert_eq!(order.delta_neutral_aux_price, None, "order.delta_neutral_aux_price");
         assert_eq!(order.delta_neutral_con_id, 0, "order.delta_neutral_con_id");
-        assert_eq!(order.delta_neutral_short_sale, false, "order.delta_neutral_short_sale");
+        assert!(!order.delta_neutral_short_sale, "order.delta_neutral_short_sale");
         assert_eq!(order.delta_neutral_short_sale_slot, 0, "order.delta_neutral_short_sale_slot");
         assert_eq!(order.delta_neutral_designated_location, "", "order.delta_neutral_designated_location");
-        assert_eq!(order.continuous_update, false, "order.continuous_update");
+        assert!(!order.continuous_update, "order.continuous_update");
         assert_eq!(order.reference_price_type, Some(0), "order.reference_price_type");
         assert_eq!(order.trail_stop_price, None, "order.trail_stop_price");
         assert_eq!(order.trailing_percent, None, "order.trailing_percent");
@@ -480,27 +549,27 @@ fn completed_orders() {
         assert_eq!(order.hedge_type, "", "order.hedge_type");
         assert_eq!(order.clearing_account, "", "order.clearing_account");
         assert_eq!(order.clearing_intent, "IB", "order.clearing_intent");
-        assert_eq!(order.not_held, false, "order.not_held");
+        assert!(!order.not_held, "order.not_held");
         assert_eq!(contract.delta_neutral_contract, None, "contract.delta_neutral_contract");
         assert_eq!(order.algo_strategy, "", "order.algo_strategy");
         assert_eq!(order.algo_params.len(), 0, "order.algo_params.len()");
-        assert_eq!(order.solicited, false, "order.solicited");
+        assert!(!order.solicited, "order.solicited");
         assert_eq!(order_state.status, "Filled", "order_state.status");
-        assert_eq!(order.randomize_size, false, "order.randomize_size");
-        assert_eq!(order.randomize_price, false, "order.randomize_price");
+        assert!(!order.randomize_size, "order.randomize_size");
+        assert!(!order.randomize_price, "order.randomize_price");
         assert_eq!(order.conditions.len(), 0, "order.conditions.len()");
         assert_eq!(order.trail_stop_price, None, "order.trail_stop_price");
         assert_eq!(order.limit_price_offset, None, "order.limit_price_offset");
         assert_eq!(order.cash_qty, Some(0.0), "order.cash_qty");
-        assert_eq!(order.dont_use_auto_price_for_hedge, true, "order.dont_use_auto_price_for_hedge");
-        assert_eq!(order.is_oms_container, false, "order.is_oms_container");
+        assert!(order.dont_use_auto_price_for_hedge, "order.dont_use_auto_price_for_hedge");
+        assert!(!order.is_oms_container, "order.is_oms_container");
         assert_eq!(order.auto_cancel_date, "", "order.auto_cancel_date");
         assert_eq!(order.filled_quantity, 100.0, "order.filled_quantity");
         assert_eq!(order.ref_futures_con_id, None, "order.ref_futures_con_id");
-        assert_eq!(order.auto_cancel_parent, false, "order.auto_cancel_parent");
+        assert!(!order.auto_cancel_parent, "order.auto_cancel_parent");
         assert_eq!(order.shareholder, "Not an insider or substantial shareholder", "order.shareholder");
-        assert_eq!(order.imbalance_only, false, "order.imbalance_only");
-        assert_eq!(order.route_marketable_to_bbo, false, "order.route_marketable_to_bbo");
+        assert!(!order.imbalance_only, "order.imbalance_only");
+        assert!(!order.route_marketable_to_bbo, "order.route_marketable_to_bbo");
         assert_eq!(order.parent_perm_id, None, "order.parent_perm_id");
         assert_eq!(
             order_state.completed_time, "20230306 12:28:30 America/Los_Angeles",
@@ -508,7 +577,7 @@ fn completed_orders() {
         );
         assert_eq!(order_state.completed_status, "Filled Size: 100", "order_state.completed_status");
     } else {
-        assert!(false, "expected order data");
+        panic!("expected order data");
     }
 }
 
@@ -642,8 +711,165 @@ fn encode_combo_market_order() {
 
     assert_eq!(
         request_messages[0].encode_simple(),
-        "3|12|0|WTI|BAG||0|||SMART||USD|||||SELL|150|MKT|||||||0||1|0|0|0|0|0|0|0|2|55928698|1|BUY|IPE|0|0||0|55850663|1|SELL|IPE|0|0||0|0|1|NonGuaranteed|1||0||||||||0||-1|0|||0|||0|0||0||||||0|||||0|||||||||||0|||0|0|||0||0|0|0|0|||||||0|||||||||0|0|0|0|||0|"
+        "3|12|0|WTI|BAG||0|||SMART||USD|||||SELL|150|MKT|||||||0||1|0|0|0|0|0|0|0|2|55928698|1|BUY|IPE|0|0||-1|55850663|1|SELL|IPE|0|0||-1|0|1|NonGuaranteed|1||0||||||||0||-1|0|||0|||0|0||0||||||0|||||0|||||||||||0|||0|0|||0||0|0|0|0|||||||0|||||||||0|0|0|0|||0|"
     );
 
     assert!(results.is_ok(), "failed to place order: {}", results.err().unwrap());
 }
+
+#[test]
+fn place_combo_order_with_leg_attributes() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let legs = vec![
+        ComboLeg::buy(55928698, 1, "IPE").with_open_close(ComboLegOpenClose::Open),
+        ComboLeg::sell(55850663, 2, "IPE").with_short_sale_slot(2, "DLOC"),
+    ];
+    let contract = Contract::combo("WTI", "SMART", "USD", legs);
+    let order = order_builder::combo_market_order(Action::Buy, 10.0, false);
+
+    let _ = client.place_order(12, &contract, &order).expect("place order failed");
+
+    let request_messages = client.message_bus.request_messages();
+    let request = request_messages[0].encode_simple();
+
+    assert!(
+        request.starts_with("3|12|0|WTI|BAG||0|||SMART||USD|||||BUY|10|MKT|"),
+        "request: {request}"
+    );
+    assert!(
+        request.contains("|2|55928698|1|BUY|IPE|1|0||-1|55850663|2|SELL|IPE|0|2|DLOC|-1|0|"),
+        "request: {request}"
+    );
+}
+
+#[test]
+fn place_combo_order_without_legs() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::combo("WTI", "SMART", "USD", vec![]);
+    let order = order_builder::combo_market_order(Action::Buy, 10.0, false);
+
+    let result = client.place_order(12, &contract, &order);
+
+    assert!(matches!(result, Err(Error::InvalidArgument(_))), "result: {result:?}");
+    assert!(client.message_bus.request_messages().is_empty());
+}
+
+#[test]
+fn place_volatility_order_with_delta_neutral_contract() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::option("AAPL", "20231215", 190.0, "C").with_delta_neutral_contract(265598, 0.45, 189.5);
+    let order = order_builder::volatility(Action::Buy, 1.0, 0.4, 2);
+
+    let _ = client.place_order(14, &contract, &order).expect("place order failed");
+
+    let request_messages = client.message_bus.request_messages();
+    let request = request_messages[0].encode_simple();
+
+    assert!(request.contains("|1|265598|0.45|189.5|"), "request: {request}");
+}
+
+#[test]
+fn soft_dollar_tiers() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["77|9000|2|Tier1|1|Tier One|Tier2|2|Tier Two|".to_owned()],
+    });
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let tiers = client.soft_dollar_tiers().expect("request soft dollar tiers failed");
+
+    let request_messages = client.message_bus.request_messages();
+    assert_eq!(request_messages[0].encode_simple(), "79|9000|");
+
+    assert_eq!(tiers.len(), 2, "tiers.len()");
+    assert_eq!(
+        tiers[1],
+        SoftDollarTier {
+            name: "Tier2".to_owned(),
+            value: "2".to_owned(),
+            display_name: "Tier Two".to_owned(),
+        },
+        "tiers[1]"
+    );
+}
+
+#[test]
+fn encode_soft_dollar_tier() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::stock("TSLA");
+    let mut order = order_builder::market_order(super::Action::Buy, 100.0);
+    order.soft_dollar_tier = SoftDollarTier {
+        name: "Tier1".to_owned(),
+        value: "1".to_owned(),
+        display_name: "Tier One".to_owned(),
+    };
+
+    let _ = client.place_order(13, &contract, &order).expect("place order failed");
+
+    let request_messages = client.message_bus.request_messages();
+
+    assert_eq!(request_messages[0][99], "Tier1", "message.soft_dollar_tier.name");
+    assert_eq!(request_messages[0][100], "1", "message.soft_dollar_tier.value");
+}
+
+#[test]
+fn place_order_for_model() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let contract = Contract::stock("TSLA");
+    let order = order_builder::market_order(super::Action::Buy, 100.0);
+
+    let model = client.model("DU1234567", "TARGET2024");
+    let _ = model.place_order(13, &contract, &order).expect("place order failed");
+
+    let request_messages = client.message_bus.request_messages();
+
+    assert_eq!(request_messages[0][23], "DU1234567", "message.account");
+    assert_eq!(request_messages[0][43], "TARGET2024", "message.model_code");
+}
+
+#[test]
+fn place_order_model_code_requires_models_support() {
+    let message_bus = Arc::new(MessageBusStub::default());
+
+    let client = Client::stubbed(message_bus, server_versions::MODELS_SUPPORT - 1);
+
+    let contract = Contract::stock("TSLA");
+    let mut order = order_builder::market_order(super::Action::Buy, 100.0);
+    order.model_code = "TARGET2024".to_owned();
+
+    let result = client.place_order(13, &contract, &order);
+    assert!(
+        matches!(result, Err(Error::ServerVersion(server_versions::MODELS_SUPPORT, _, _))),
+        "expected models support server version error"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn order_types_implement_serde() {
+    fn assert_serde<T: Serialize + for<'de> Deserialize<'de>>() {}
+
+    assert_serde::<Order>();
+    assert_serde::<PlaceOrder>();
+    assert_serde::<Orders>();
+    assert_serde::<Executions>();
+    assert_serde::<ExecutionFilter>();
+    assert_serde::<CancelOrder>();
+    assert_serde::<JournalEntry>();
+}
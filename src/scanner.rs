@@ -1,13 +1,17 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    client::{DataStream, ResponseContext, Subscription},
-    messages::{IncomingMessages, OutgoingMessages},
+    client::{DataStream, ResponseContext, Subscription, SubscriptionKind},
+    messages::{IncomingMessages, OutgoingMessages, RequestMessage},
     orders::TagValue,
     server_versions, Client, Error,
 };
 
 mod decoders;
+pub mod parameters;
+#[cfg(test)]
+mod tests;
 
 // Requests an XML list of scanner parameters valid in TWS.
 pub(super) fn scanner_parameters(client: &Client) -> Result<String, Error> {
@@ -21,6 +25,8 @@ pub(super) fn scanner_parameters(client: &Client) -> Result<String, Error> {
     }
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ScannerSubscription {
     /// The number of rows to be returned for the query
     pub number_of_rows: i32,
@@ -95,15 +101,22 @@ impl Default for ScannerSubscription {
 }
 
 impl DataStream<Vec<ScannerData>> for Vec<ScannerData> {
+    const KIND: SubscriptionKind = SubscriptionKind::Scanner;
     fn decode(_client: &Client, message: &mut crate::messages::ResponseMessage) -> Result<Vec<ScannerData>, Error> {
         match message.message_type() {
             IncomingMessages::ScannerData => Ok(decoders::decode_scanner_data(message.clone())?),
             _ => Err(Error::UnexpectedResponse(message.clone())),
         }
     }
+
+    fn cancel_message(_server_version: i32, request_id: Option<i32>, _context: &ResponseContext) -> Result<RequestMessage, Error> {
+        let request_id = request_id.expect("Request ID required to encode cancel scanner subscription");
+        encoders::encode_cancel_scanner_subscription(request_id)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Provides the data resulting from the market scanner request.
 pub struct ScannerData {
     /// The ranking position of the contract in the scanner sort.
@@ -153,6 +166,18 @@ mod encoders {
         Ok(message)
     }
 
+    pub(super) fn encode_cancel_scanner_subscription(request_id: i32) -> Result<RequestMessage, Error> {
+        const VERSION: i32 = 1;
+
+        let mut message = RequestMessage::new();
+
+        message.push_field(&OutgoingMessages::CancelScannerSubscription);
+        message.push_field(&VERSION);
+        message.push_field(&request_id);
+
+        Ok(message)
+    }
+
     pub(super) fn encode_scanner_subscription(
         request_id: i32,
         server_version: i32,
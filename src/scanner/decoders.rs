@@ -20,7 +20,7 @@ pub(super) fn decode_scanner_data(mut message: ResponseMessage) -> Result<Vec<Sc
     message.skip(); // request id
 
     let number_of_elements = message.next_int()?;
-    let mut matches = Vec::with_capacity(number_of_elements as usize);
+    let mut matches = Vec::new();
 
     for _ in 0..number_of_elements {
         let mut scanner_data = ScannerData {
@@ -98,7 +98,7 @@ fn test_decode_scanner_data_complex_orders() {
     let scanner_data = super::decode_scanner_data(message).expect("error decoding pnl single");
     assert_eq!(scanner_data.len(), 50, "scanner_data.len()");
 
-    let expected = vec![
+    let expected = [
         ScanRow {
             rank: 0,
             contract_id: 28812380,
@@ -0,0 +1,221 @@
+//! Scanner parameters.
+//!
+//! TWS describes the instruments, locations, scan codes and filters valid in scanner subscriptions
+//! as an XML document, returned by [Client::scanner_parameters](crate::Client::scanner_parameters).
+//! The document is decoded into the types in this module, so valid [ScannerSubscription](super::ScannerSubscription)s can be built dynamically.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::xml::Element;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// Instruments, locations, scan codes and filters valid in scanner subscriptions.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScannerParameters {
+    /// Instrument types that can be scanned.
+    pub instruments: Vec<ScanInstrument>,
+    /// Locations that can be scanned, nested by region.
+    pub locations: Vec<ScanLocation>,
+    /// Scan codes, e.g. TOP_PERC_GAIN.
+    pub scan_codes: Vec<ScanCode>,
+    /// Filters that can be applied to scans.
+    pub filters: Vec<ScanFilter>,
+}
+
+/// An instrument type that can be scanned.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanInstrument {
+    /// Display name, e.g. US Stocks.
+    pub name: String,
+    /// Instrument type used in [ScannerSubscription::instrument](super::ScannerSubscription::instrument), e.g. STK.
+    pub instrument_type: String,
+    /// Identifiers of the filters that apply to the instrument.
+    pub filters: Vec<String>,
+}
+
+/// A location that can be scanned.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanLocation {
+    /// Display name, e.g. US Stocks.
+    pub display_name: String,
+    /// Location code used in [ScannerSubscription::location_code](super::ScannerSubscription::location_code), e.g. STK.US.MAJOR.
+    pub location_code: String,
+    /// Instrument types available at the location.
+    pub instruments: Vec<String>,
+    /// Locations within the location.
+    pub locations: Vec<ScanLocation>,
+}
+
+/// A scan code.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanCode {
+    /// Display name, e.g. Top % Gainers.
+    pub display_name: String,
+    /// Scan code used in [ScannerSubscription::scan_code](super::ScannerSubscription::scan_code), e.g. TOP_PERC_GAIN.
+    pub scan_code: String,
+    /// Instrument types the scan applies to.
+    pub instruments: Vec<String>,
+}
+
+/// A filter that can be applied to scans, with the fields used to set it.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanFilter {
+    /// Identifier of the filter, as listed by [ScanInstrument::filters].
+    pub id: String,
+    /// Category of the filter, e.g. PriceVolatility.
+    pub category: String,
+    /// True if the filter sets a range, with fields for the lower and upper bounds.
+    pub range: bool,
+    /// Fields of the filter.
+    pub fields: Vec<ScanFilterField>,
+}
+
+/// A field of a [ScanFilter].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanFilterField {
+    /// Code used as the tag of filter options in scanner subscriptions, e.g. priceAbove.
+    pub code: String,
+    /// Display name, e.g. Price.
+    pub display_name: String,
+    /// Values the field accepts, if restricted to a list.
+    pub values: Vec<ScanFilterValue>,
+}
+
+/// A value accepted by a [ScanFilterField].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanFilterValue {
+    /// Value used in filter options.
+    pub code: String,
+    /// Display name.
+    pub display_name: String,
+}
+
+impl ScannerParameters {
+    /// Parses the XML document returned by [Client::scanner_parameters](crate::Client::scanner_parameters).
+    pub fn parse(xml: &str) -> Result<ScannerParameters, Error> {
+        let root = Element::parse(xml)?;
+
+        let instruments = root
+            .child("InstrumentList")
+            .map(|list| list.children_named("Instrument").map(parse_instrument).collect())
+            .unwrap_or_default();
+        let locations = root.child("LocationTree").map(parse_locations).unwrap_or_default();
+        let scan_codes = root
+            .child("ScanTypeList")
+            .map(|list| list.children_named("ScanType").map(parse_scan_code).collect())
+            .unwrap_or_default();
+        let filters = root
+            .child("FilterList")
+            .map(|list| list.children.iter().filter_map(parse_filter).collect())
+            .unwrap_or_default();
+
+        Ok(ScannerParameters {
+            instruments,
+            locations,
+            scan_codes,
+            filters,
+        })
+    }
+
+    /// Returns the location with the given code, searching nested locations.
+    pub fn location(&self, location_code: &str) -> Option<&ScanLocation> {
+        fn find<'a>(locations: &'a [ScanLocation], location_code: &str) -> Option<&'a ScanLocation> {
+            locations.iter().find_map(|location| {
+                (location.location_code == location_code)
+                    .then_some(location)
+                    .or_else(|| find(&location.locations, location_code))
+            })
+        }
+        find(&self.locations, location_code)
+    }
+
+    /// Returns the scan codes that apply to the given instrument type.
+    pub fn scan_codes_for<'a>(&'a self, instrument_type: &'a str) -> impl Iterator<Item = &'a ScanCode> + 'a {
+        self.scan_codes
+            .iter()
+            .filter(move |scan_code| scan_code.instruments.iter().any(|instrument| instrument == instrument_type))
+    }
+}
+
+fn parse_instrument(element: &Element) -> ScanInstrument {
+    ScanInstrument {
+        name: element.child_text("name").to_owned(),
+        instrument_type: element.child_text("type").to_owned(),
+        filters: split_list(element.child_text("filters")),
+    }
+}
+
+fn parse_locations(tree: &Element) -> Vec<ScanLocation> {
+    tree.children_named("Location")
+        .map(|location| ScanLocation {
+            display_name: location.child_text("displayName").to_owned(),
+            location_code: location.child_text("locationCode").to_owned(),
+            instruments: split_list(location.child_text("instruments")),
+            locations: location.child("LocationTree").map(parse_locations).unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn parse_scan_code(element: &Element) -> ScanCode {
+    ScanCode {
+        display_name: element.child_text("displayName").to_owned(),
+        scan_code: element.child_text("scanCode").to_owned(),
+        instruments: split_list(element.child_text("instruments")),
+    }
+}
+
+fn parse_filter(element: &Element) -> Option<ScanFilter> {
+    let range = match element.name.as_str() {
+        "RangeFilter" => true,
+        "SimpleFilter" => false,
+        _ => return None,
+    };
+
+    Some(ScanFilter {
+        id: element.child_text("id").to_owned(),
+        category: element.child_text("category").to_owned(),
+        range,
+        fields: element.children_named("AbstractField").map(parse_field).collect(),
+    })
+}
+
+fn parse_field(element: &Element) -> ScanFilterField {
+    let values = element
+        .child("ComboValues")
+        .map(|values| {
+            values
+                .children_named("ComboValue")
+                .map(|value| ScanFilterValue {
+                    code: value.child_text("code").to_owned(),
+                    display_name: value.child_text("displayName").to_owned(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ScanFilterField {
+        code: element.child_text("code").to_owned(),
+        display_name: element.child_text("displayName").to_owned(),
+        values,
+    }
+}
+
+// Splits a comma separated list, skipping empty entries.
+fn split_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
@@ -0,0 +1,134 @@
+use super::*;
+
+const PARAMETERS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ScanParameterResponse>
+  <InstrumentList varName="instrumentList">
+    <Instrument>
+      <name>US Stocks</name>
+      <type>STK</type>
+      <filters>PRICE,STKTYPE</filters>
+    </Instrument>
+    <Instrument>
+      <name>US Futures</name>
+      <type>FUT.US</type>
+      <filters></filters>
+    </Instrument>
+  </InstrumentList>
+  <LocationTree varName="locationTree">
+    <Location>
+      <displayName>US Stocks</displayName>
+      <locationCode>STK.US</locationCode>
+      <instruments>STK</instruments>
+      <LocationTree varName="locationTree">
+        <Location>
+          <displayName>Listed/NASDAQ</displayName>
+          <locationCode>STK.US.MAJOR</locationCode>
+          <instruments>STK</instruments>
+        </Location>
+      </LocationTree>
+    </Location>
+  </LocationTree>
+  <ScanTypeList varName="scanTypeList">
+    <ScanType>
+      <displayName>Top % Gainers</displayName>
+      <scanCode>TOP_PERC_GAIN</scanCode>
+      <instruments>STK,FUT.US</instruments>
+    </ScanType>
+    <ScanType>
+      <displayName>Hot Contracts by Volume</displayName>
+      <scanCode>HOT_BY_VOLUME</scanCode>
+      <instruments>FUT.US</instruments>
+    </ScanType>
+  </ScanTypeList>
+  <FilterList varName="filterList">
+    <RangeFilter>
+      <id>PRICE</id>
+      <category>PriceVolatility</category>
+      <AbstractField varName="abstractField" type="DoubleField">
+        <code>priceAbove</code>
+        <displayName>Price Above</displayName>
+      </AbstractField>
+      <AbstractField varName="abstractField" type="DoubleField">
+        <code>priceBelow</code>
+        <displayName>Price Below</displayName>
+      </AbstractField>
+    </RangeFilter>
+    <SimpleFilter>
+      <id>STKTYPE</id>
+      <category>Fundamentals</category>
+      <AbstractField varName="abstractField" type="ComboField">
+        <code>stkTypes</code>
+        <displayName>Stock Type</displayName>
+        <ComboValues varName="comboValues">
+          <ComboValue><code>inc:CORP</code><displayName>Corporation</displayName></ComboValue>
+          <ComboValue><code>inc:ETF</code><displayName>ETF</displayName></ComboValue>
+        </ComboValues>
+      </AbstractField>
+    </SimpleFilter>
+  </FilterList>
+</ScanParameterResponse>"#;
+
+#[test]
+fn parses_scanner_parameters() {
+    let parameters = ScannerParameters::parse(PARAMETERS_XML).unwrap();
+
+    assert_eq!(
+        parameters.instruments,
+        vec![
+            ScanInstrument {
+                name: "US Stocks".into(),
+                instrument_type: "STK".into(),
+                filters: vec!["PRICE".into(), "STKTYPE".into()],
+            },
+            ScanInstrument {
+                name: "US Futures".into(),
+                instrument_type: "FUT.US".into(),
+                filters: vec![],
+            },
+        ]
+    );
+
+    assert_eq!(parameters.locations.len(), 1, "top level locations");
+    let major = parameters.location("STK.US.MAJOR").expect("nested location");
+    assert_eq!(major.display_name, "Listed/NASDAQ");
+    assert_eq!(major.instruments, vec!["STK"]);
+    assert!(parameters.location("STK.EU").is_none());
+
+    let codes: Vec<&str> = parameters.scan_codes_for("STK").map(|code| code.scan_code.as_str()).collect();
+    assert_eq!(codes, ["TOP_PERC_GAIN"]);
+    assert_eq!(parameters.scan_codes_for("FUT.US").count(), 2);
+
+    assert_eq!(parameters.filters.len(), 2, "filters");
+    let price = &parameters.filters[0];
+    assert_eq!(
+        (price.id.as_str(), price.category.as_str(), price.range),
+        ("PRICE", "PriceVolatility", true)
+    );
+    let codes: Vec<&str> = price.fields.iter().map(|field| field.code.as_str()).collect();
+    assert_eq!(codes, ["priceAbove", "priceBelow"]);
+
+    let stock_type = &parameters.filters[1];
+    assert!(!stock_type.range);
+    assert_eq!(
+        stock_type.fields[0].values,
+        vec![
+            ScanFilterValue {
+                code: "inc:CORP".into(),
+                display_name: "Corporation".into(),
+            },
+            ScanFilterValue {
+                code: "inc:ETF".into(),
+                display_name: "ETF".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn rejects_malformed_scanner_parameters() {
+    assert!(ScannerParameters::parse("<ScanParameterResponse>").is_err());
+    assert_eq!(
+        ScannerParameters::parse("<ScanParameterResponse/>").unwrap(),
+        ScannerParameters::default()
+    );
+}
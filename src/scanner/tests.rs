@@ -0,0 +1,63 @@
+use std::sync::{Arc, RwLock};
+
+use super::*;
+use crate::stubs::MessageBusStub;
+
+#[test]
+fn cancels_scanner_subscription_when_dropped() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec!["20|3|9000|1|0|76792991|TSLA|STK||0||SMART|USD|TSLA|NMS|NMS||||||".to_owned()],
+    });
+    let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+    let scan = ScannerSubscription {
+        number_of_rows: 10,
+        instrument: Some("STK".into()),
+        location_code: Some("STK.US.MAJOR".into()),
+        scan_code: Some("TOP_PERC_GAIN".into()),
+        above_price: Some(5.0),
+        ..ScannerSubscription::default()
+    };
+    let filter = vec![TagValue {
+        tag: "marketCapAbove1e6".into(),
+        value: "1000".into(),
+    }];
+
+    let subscription = client.scanner_subscription(&scan, &filter).unwrap();
+    let rows = subscription.next().unwrap();
+    assert_eq!(rows.len(), 1, "rows");
+    assert_eq!(rows[0].rank, 0, "rank");
+    assert_eq!(rows[0].contract_details.contract.symbol, "TSLA", "symbol");
+    assert_eq!(rows[0].contract_details.market_name, "NMS", "market name");
+    drop(subscription);
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(
+        requests[0].encode_simple(),
+        "22|9000|10|STK|STK.US.MAJOR|TOP_PERC_GAIN|5|||||||||||||0||||marketCapAbove1e6=1000;||",
+        "scanner subscription"
+    );
+    assert_eq!(requests[1].encode_simple(), "23|1|9000|", "cancel scanner subscription");
+}
+
+#[test]
+fn streams_scanner_snapshots() {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: vec![
+            "20|3|9000|2|0|76792991|TSLA|STK||0||SMART|USD|TSLA|NMS|NMS|||||1|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS||||||".to_owned(),
+            "20|3|9000|1|0|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS||||||".to_owned(),
+        ],
+    });
+    let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+    let subscription = client.scanner_subscription(&ScannerSubscription::default(), &vec![]).unwrap();
+    let snapshots: Vec<Vec<String>> = subscription
+        .iter()
+        .take(2)
+        .map(|rows| rows.iter().map(|row| row.contract_details.contract.symbol.clone()).collect())
+        .collect();
+
+    assert_eq!(snapshots, vec![vec!["TSLA", "NVDA"], vec!["NVDA"]]);
+}
@@ -2,8 +2,9 @@ use std::sync::{Arc, RwLock};
 
 use crossbeam::channel;
 
+use crate::client::Health;
 use crate::messages::{OutgoingMessages, RequestMessage, ResponseMessage};
-use crate::transport::{InternalSubscription, MessageBus, SubscriptionBuilder};
+use crate::transport::{EventChannel, InternalSubscription, MessageBus, SubscriptionBuilder};
 use crate::Error;
 
 pub(crate) struct MessageBusStub {
@@ -24,6 +25,7 @@ impl Default for MessageBusStub {
 }
 
 impl MessageBus for MessageBusStub {
+    #[cfg(test)]
     fn request_messages(&self) -> Vec<RequestMessage> {
         self.request_messages.read().unwrap().clone()
     }
@@ -46,6 +48,11 @@ impl MessageBus for MessageBusStub {
         Ok(())
     }
 
+    fn send_message(&self, _request_id: Option<i32>, packet: &RequestMessage) -> Result<(), Error> {
+        self.request_messages.write().unwrap().push(packet.clone());
+        Ok(())
+    }
+
     fn send_shared_request(&self, message_type: OutgoingMessages, message: &RequestMessage) -> Result<InternalSubscription, Error> {
         Ok(mock_request(self, None, Some(message_type), message))
     }
@@ -55,8 +62,29 @@ impl MessageBus for MessageBusStub {
         Ok(())
     }
 
+    fn subscribe_events(&self, _channel: EventChannel) -> Result<InternalSubscription, Error> {
+        let (sender, receiver) = channel::unbounded();
+        for message in &self.response_messages {
+            let message = ResponseMessage::from(&message.replace('|', "\0"));
+            sender.send(Ok(message)).unwrap();
+        }
+
+        Ok(SubscriptionBuilder::new().shared_receiver(Arc::new(receiver)).build())
+    }
+
+    fn connection_order_id(&self) -> Option<i32> {
+        None
+    }
+
     fn ensure_shutdown(&self) {}
 
+    fn health(&self) -> Health {
+        Health {
+            connected: true,
+            ..Health::default()
+        }
+    }
+
     // fn process_messages(&mut self, _server_version: i32) -> Result<(), Error> {
     //     Ok(())
     // }
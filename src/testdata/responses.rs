@@ -2,6 +2,9 @@
 
 pub const MANAGED_ACCOUNT: &str = "15|1|DU1234567,DU7654321|";
 
+#[cfg(feature = "fa")]
+pub const RECEIVE_FA_ALIASES: &str = "16|1|3|<?xml version=\"1.0\" encoding=\"UTF-8\"?><ListOfAccountAliases><AccountAlias><account>DU1234567</account><alias>Growth</alias></AccountAlias></ListOfAccountAliases>|";
+
 pub const ACCOUNT_UPDATE_MULTI_CASH_BALANCE: &str = "73|1|9000|DU1234567||CashBalance|94629.71|USD||";
 pub const ACCOUNT_UPDATE_MULTI_CURRENCY: &str = "73|1|9000|DU1234567||Currency|USD|USD||";
 pub const ACCOUNT_UPDATE_MULTI_STOCK_MARKET_VALUE: &str = "73|1|9000|DU1234567||StockMarketValue|0.00|BASE||";
@@ -0,0 +1,206 @@
+use std::io::{ErrorKind, Read};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{debug, warn};
+
+use crate::client::replay::{read_frame, write_frame};
+use crate::Error;
+
+pub use fixtures::{assert_fields, fixture};
+
+mod fixtures;
+#[cfg(test)]
+mod tests;
+
+// How often idle connections check whether the server is stopping.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Scripted TWS server for end-to-end tests of connect, request and decode without TWS installed.
+///
+/// The server completes the handshake, then answers each request matching a scripted prefix with the scripted responses.
+/// Messages are written with `|` separating fields, e.g. `49|1|` for a current time request.
+/// Requests are matched against the scripted prefixes in the order they were added. Unmatched requests are recorded but not answered.
+///
+/// ```no_run
+/// use ibapi::testing::MockTws;
+/// use ibapi::Client;
+///
+/// let tws = MockTws::new().respond_to("49|1|", &["49|1|1678323335|"]).start().expect("error starting mock TWS");
+///
+/// let client = Client::connect(&tws.address(), 100).expect("connection failed");
+/// println!("server time: {:?}", client.server_time());
+///
+/// assert_eq!(tws.requests(), vec!["49|1|"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockTws {
+    server_version: i32,
+    next_order_id: i32,
+    managed_accounts: String,
+    rules: Vec<Rule>,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    prefix: String,
+    responses: Vec<String>,
+}
+
+impl Default for MockTws {
+    fn default() -> Self {
+        MockTws {
+            server_version: 173,
+            next_order_id: 90,
+            managed_accounts: "DU1234567".into(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl MockTws {
+    /// Creates a server reporting server version 173, next order id 90 and managed account DU1234567.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Server version reported in the handshake.
+    pub fn server_version(mut self, server_version: i32) -> Self {
+        self.server_version = server_version;
+        self
+    }
+
+    /// Next valid order id sent when the client connects.
+    pub fn next_order_id(mut self, next_order_id: i32) -> Self {
+        self.next_order_id = next_order_id;
+        self
+    }
+
+    /// Comma separated accounts sent when the client connects.
+    pub fn managed_accounts(mut self, accounts: &str) -> Self {
+        self.managed_accounts = accounts.into();
+        self
+    }
+
+    /// Answers requests starting with `prefix` with `responses`, in order.
+    ///
+    /// # Arguments
+    /// * `prefix`    - Leading fields of the request, e.g. `9|8|9000|` for a contract details request with request id 9000.
+    /// * `responses` - Messages sent in response.
+    pub fn respond_to(mut self, prefix: &str, responses: &[&str]) -> Self {
+        self.rules.push(Rule {
+            prefix: prefix.into(),
+            responses: responses.iter().map(|response| response.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Starts the server on a free port of the loopback interface.
+    pub fn start(self) -> Result<MockTwsServer, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?.to_string();
+
+        let stopping = Arc::new(AtomicBool::new(false));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let handle = {
+            let stopping = Arc::clone(&stopping);
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                // connections are served one at a time, so reconnects are answered in order
+                for stream in listener.incoming() {
+                    if stopping.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    match stream {
+                        Ok(stream) => {
+                            if let Err(e) = self.serve(stream, &requests, &stopping) {
+                                debug!("mock TWS connection closed: {e}");
+                            }
+                        }
+                        Err(e) => warn!("mock TWS error accepting connection: {e}"),
+                    }
+                }
+            })
+        };
+
+        Ok(MockTwsServer {
+            address,
+            requests,
+            stopping,
+            handle: Some(handle),
+        })
+    }
+
+    fn serve(&self, mut stream: TcpStream, requests: &Mutex<Vec<String>>, stopping: &AtomicBool) -> Result<(), Error> {
+        let mut prefix = [0_u8; 4];
+        stream.read_exact(&mut prefix)?;
+        read_frame(&mut stream)?; // supported versions
+        write_frame(&mut stream, &format!("{}\x0020230405 22:20:39 PST\x00", self.server_version))?;
+
+        read_frame(&mut stream)?; // start api
+        write_frame(&mut stream, &format!("9\x001\x00{}\x00", self.next_order_id))?;
+        write_frame(&mut stream, &format!("15\x001\x00{}\x00", self.managed_accounts))?;
+
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        loop {
+            let request = match read_frame(&mut stream) {
+                Ok(request) => request.replace('\0', "|"),
+                Err(Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    if stopping.load(Ordering::SeqCst) {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let rule = self.rules.iter().find(|rule| request.starts_with(&rule.prefix));
+            if rule.is_none() {
+                debug!("mock TWS has no response for {request}");
+            }
+            requests.lock()?.push(request);
+
+            for response in rule.iter().flat_map(|rule| &rule.responses) {
+                write_frame(&mut stream, &response.replace('|', "\0"))?;
+            }
+        }
+    }
+}
+
+/// A running [MockTws]. The server stops when dropped.
+#[derive(Debug)]
+pub struct MockTwsServer {
+    address: String,
+    requests: Arc<Mutex<Vec<String>>>,
+    stopping: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockTwsServer {
+    /// Address the server is listening on, e.g. 127.0.0.1:38217.
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    /// Requests received after the handshake, with `|` separating fields.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockTwsServer {
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::SeqCst);
+
+        // wakes the listener waiting for the next connection
+        let _ = TcpStream::connect(&self.address);
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests;
+
+/// Builds a message from a fixture listing one field per line, returning the fields separated by `|` as accepted by [MockTws](super::MockTws::respond_to).
+///
+/// Leading and trailing whitespace is trimmed and blank lines are skipped. Text after ` # ` describes the field and is ignored.
+/// Empty fields are written as `""`.
+///
+/// ```
+/// use ibapi::testing::fixture;
+///
+/// let message = fixture(
+///     "
+///     88          # head timestamp
+///     9000        # request id
+///     1678323335  # timestamp
+///     \"\"        # empty field
+///     ",
+/// );
+/// assert_eq!(message, "88|9000|1678323335||");
+/// ```
+pub fn fixture(text: &str) -> String {
+    let mut message = String::new();
+    for line in text.lines() {
+        let field = match line.find(" # ") {
+            Some(i) => &line[..i],
+            None => line,
+        };
+        let field = field.trim();
+        if field.is_empty() {
+            continue;
+        }
+
+        if field != "\"\"" {
+            message.push_str(field);
+        }
+        message.push('|');
+    }
+    message
+}
+
+/// Asserts the fields of `message`, separated by `|` or NUL, naming the first field that differs.
+///
+/// # Arguments
+/// * `message`  - Message to check, e.g. a request recorded by [MockTwsServer](super::MockTwsServer::requests).
+/// * `expected` - Name and expected value of each field, starting with the message type.
+///
+/// ```
+/// use ibapi::testing::assert_fields;
+///
+/// assert_fields("49|1|", &[("message_type", "49"), ("version", "1")]);
+/// ```
+#[track_caller]
+pub fn assert_fields(message: &str, expected: &[(&str, &str)]) {
+    let separator = if message.contains('\0') { '\0' } else { '|' };
+    let fields: Vec<&str> = message.strip_suffix(separator).unwrap_or(message).split(separator).collect();
+
+    for (i, (name, value)) in expected.iter().enumerate() {
+        match fields.get(i) {
+            Some(field) => assert_eq!(field, value, "field {i} ({name}) of {message:?}"),
+            None => panic!("field {i} ({name}) missing from {message:?}"),
+        }
+    }
+
+    assert_eq!(
+        fields.len(),
+        expected.len(),
+        "unexpected fields {:?} in {message:?}",
+        &fields[expected.len().min(fields.len())..]
+    );
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn builds_messages_from_fixtures() {
+    let message = fixture(
+        "
+        4     # error
+        2     # version
+        9000  # request id
+        162   # code
+        Historical Market Data Service error message:No market data permissions. #8 not a comment
+        \"\"    # advanced order reject json
+        ",
+    );
+
+    assert_eq!(
+        message,
+        "4|2|9000|162|Historical Market Data Service error message:No market data permissions. #8 not a comment||"
+    );
+}
+
+#[test]
+fn asserts_named_fields() {
+    assert_fields(
+        "71\x002\x00100\x00\x00",
+        &[
+            ("message_type", "71"),
+            ("version", "2"),
+            ("client_id", "100"),
+            ("optional_capabilities", ""),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "field 2 (client_id)")]
+fn names_mismatched_field() {
+    assert_fields(
+        "71|2|100||",
+        &[
+            ("message_type", "71"),
+            ("version", "2"),
+            ("client_id", "101"),
+            ("optional_capabilities", ""),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "unexpected fields [\"\"]")]
+fn reports_unexpected_fields() {
+    assert_fields("71|2|100||", &[("message_type", "71"), ("version", "2"), ("client_id", "100")]);
+}
@@ -0,0 +1,41 @@
+use crate::contracts::Contract;
+use crate::Client;
+
+use super::*;
+
+#[test]
+fn serves_scripted_responses() {
+    let tws = MockTws::new()
+        .next_order_id(95)
+        .managed_accounts("DU1234,DU5678")
+        .respond_to("49|1|", &["49|1|1678323335|"])
+        .respond_to(
+            "9|8|9000|",
+            &[
+                "10|9000|TSLA|STK||0||SMART|USD|TSLA|NMS|NMS|76792991|0.01||LMT,MKT|SMART,AMEX|1|0|TESLA INC|NASDAQ||Consumer, Cyclical|Auto Manufacturers|Auto-Cars/Light Trucks|US/Eastern|20221229:0400-20221229:2000|20221229:0930-20221229:1600|||1|ISIN|US88160R1014|1|||26,26||COMMON|1|1|100|",
+                "52|1|9000|",
+            ],
+        )
+        .start()
+        .expect("error starting mock TWS");
+
+    let client = Client::connect(&tws.address(), 100).expect("connection failed");
+    assert_eq!(client.server_version(), 173, "server version");
+
+    let server_time = client.server_time().expect("error requesting server time");
+    assert_eq!(server_time.unix_timestamp(), 1678323335, "server time");
+
+    let details = client
+        .contract_details(&Contract::stock("TSLA"))
+        .expect("error requesting contract details");
+    assert_eq!(details.len(), 1, "contract details");
+    assert_eq!(details[0].contract.contract_id, 76792991, "contract id");
+
+    let requests = tws.requests();
+    assert_eq!(requests[0], "49|1|", "current time request");
+    assert!(
+        requests[1].starts_with("9|8|9000|0|TSLA|STK|"),
+        "contract details request: {}",
+        requests[1]
+    );
+}
@@ -1 +1,36 @@
+use time::macros::{datetime, offset};
+use time_tz::{timezones, TimeZone};
+
+use super::*;
+
 pub fn assert_send_and_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_parse_time_zone() {
+    for (name, expected) in [
+        ("US/Eastern", "US/Eastern"),
+        ("America/Chicago", "America/Chicago"),
+        ("EST", "EST"),
+        ("MET", "MET"),
+    ] {
+        let time_zone = parse_time_zone(name).unwrap_or_else(|e| panic!("error parsing {name}: {e}"));
+        assert_eq!(time_zone.name(), expected, "time zone for {name}");
+    }
+
+    let error = parse_time_zone("Mars Standard Time").expect_err("expected unknown time zone");
+    assert_eq!(error.to_string(), "unknown time zone: Mars Standard Time");
+}
+
+#[test]
+fn test_assume_time_zone() {
+    let new_york = timezones::db::america::NEW_YORK;
+
+    let time = assume_time_zone(datetime!(2024-03-08 09:30), new_york).expect("error assuming time zone");
+    assert_eq!(time, datetime!(2024-03-08 09:30).assume_offset(offset!(-5)), "standard time");
+
+    let time = assume_time_zone(datetime!(2024-03-11 09:30), new_york).expect("error assuming time zone");
+    assert_eq!(time, datetime!(2024-03-11 09:30).assume_offset(offset!(-4)), "daylight saving time");
+
+    // clocks skip from 02:00 to 03:00 on 2024-03-10
+    assert!(assume_time_zone(datetime!(2024-03-10 02:30), new_york).is_err(), "skipped time");
+}
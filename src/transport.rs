@@ -2,31 +2,49 @@
 //! It provides functionality for routing requests from the Client to TWS,
 //! and responses from TWS back to the Client.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{prelude::*, Cursor, ErrorKind};
-use std::net::TcpStream;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
-use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::thread::{self, JoinHandle, ThreadId};
+use std::time::{Duration, Instant};
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
-use crossbeam::channel::{self, Receiver, Sender};
+use crossbeam::channel::{self, Receiver, RecvTimeoutError, Select, SendError, Sender, TrySendError};
 use log::{debug, error, info, warn};
 use time::macros::format_description;
 use time::OffsetDateTime;
-use time_tz::{timezones, OffsetResult, PrimitiveDateTimeExt, Tz};
-
-use crate::messages::{shared_channel_configuration, IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
-use crate::{server_versions, Error};
+use time_tz::{OffsetResult, PrimitiveDateTimeExt, Tz};
+
+use crate::client::journal::RequestJournal;
+use crate::client::metrics::SharedMetrics;
+use crate::client::stream::{SharedTransport, TransportStream};
+use crate::client::wire::{Direction, SharedObserver};
+use crate::client::{ConnectionEvent, DataFarmState, Health, JournalEntry, NoticeFilter};
+use crate::errors::ErrorCategory;
+use crate::messages::{shared_channel_configuration, IncomingMessages, Notice, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::{parse_time_zone, server_versions, Error, ToField};
+pub(crate) use pool::BufferPool;
+use rate_limiter::RateLimiter;
 use recorder::MessageRecorder;
 
+mod pool;
+mod rate_limiter;
 mod recorder;
 
-const MIN_SERVER_VERSION: i32 = 100;
+pub(crate) const MIN_SERVER_VERSION: i32 = 100;
 const MAX_SERVER_VERSION: i32 = server_versions::HISTORICAL_SCHEDULE;
 const MAX_RETRIES: i32 = 20;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
 const TWS_READ_TIMEOUT: Duration = Duration::from_secs(1);
+pub(crate) const MAX_MESSAGES_PER_SECOND: u32 = 50;
+// Messages queued for a subscription before further messages are dropped.
+pub(crate) const SUBSCRIPTION_CAPACITY: usize = 100_000;
+// Recent requests kept for inspection with Client::request_journal.
+pub(crate) const REQUEST_JOURNAL_CAPACITY: usize = 100;
+const CLIENT_ID_IN_USE: i32 = 326;
+// Largest message TWS sends, as defined by the official API clients.
+const MAX_MESSAGE_LENGTH: usize = 0xFFFFFF;
 
 pub(crate) trait MessageBus: Send + Sync {
     // Sends formatted message to TWS and creates a reply channel by request id.
@@ -46,8 +64,40 @@ pub(crate) trait MessageBus: Send + Sync {
 
     fn cancel_order_subscription(&self, request_id: i32, packet: &RequestMessage) -> Result<(), Error>;
 
+    // Sends formatted message to TWS without creating a reply channel, e.g. to update an existing request.
+    fn send_message(&self, request_id: Option<i32>, packet: &RequestMessage) -> Result<(), Error>;
+
+    // Creates a channel that receives error messages not routed to any request, e.g. error codes broadcast by TWS.
+    fn subscribe_events(&self, channel: EventChannel) -> Result<InternalSubscription, Error>;
+
+    // Next valid order id reported by TWS when the connection was last established.
+    fn connection_order_id(&self) -> Option<i32>;
+
     fn ensure_shutdown(&self);
 
+    // Snapshot of the connection health.
+    fn health(&self) -> Health;
+
+    // Most recent requests sent to TWS, oldest first.
+    fn request_journal(&self) -> Vec<JournalEntry> {
+        Vec::new()
+    }
+
+    // Buffers requests of the calling thread until the matching end_batch, so they are sent to TWS with a single write. Batches may be nested.
+    fn begin_batch(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // Writes the requests buffered by the open batch of the calling thread.
+    fn flush_batch(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // Closes a batch, writing the buffered requests when the outermost batch is closed.
+    fn end_batch(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
     // Testing interface. Tracks requests sent messages when Bus is stubbed.
     #[cfg(test)]
     fn request_messages(&self) -> Vec<RequestMessage> {
@@ -57,39 +107,66 @@ pub(crate) trait MessageBus: Send + Sync {
 
 pub(crate) type Response = Result<ResponseMessage, Error>;
 
+// Notices received by an event subscriber, as classified by the notice filter. Errors, e.g. connection resets, are sent to every subscriber.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EventChannel {
+    All,
+    Errors,
+    Info,
+}
+
 // For requests without an identifier, shared channels are created
 // to route request/response pairs based on message type.
 #[derive(Debug)]
 struct SharedChannels {
     // Maps an inbound reply to channel used to send responses.
-    senders: HashMap<IncomingMessages, Vec<Arc<Sender<Response>>>>,
+    senders: HashMap<IncomingMessages, Vec<Arc<ResponseSender>>>,
     // Maps an outbound request to channel used to receive responses.
     receivers: HashMap<OutgoingMessages, Arc<Receiver<Response>>>,
+    // Maps an outbound request to the sender of its channel.
+    request_senders: HashMap<OutgoingMessages, Arc<ResponseSender>>,
+    // Replies owed to requests with a single response type whose caller stopped waiting, by request and by response type.
+    late_replies: HashMap<OutgoingMessages, LateReplies>,
+    late_reply_types: HashMap<IncomingMessages, LateReplies>,
 }
 
+// Number of replies still expected for shared requests that were abandoned, e.g. after a timeout.
+// TWS answers shared requests in order, so the next replies of the type are discarded instead of answering later requests.
+pub(crate) type LateReplies = Arc<Mutex<usize>>;
+
 impl SharedChannels {
-    // Creates new instance and registers request/reply pairs.
-    pub fn new() -> Self {
+    // Creates new instance and registers request/reply pairs. Channels hold up to `capacity` messages, None is unbounded.
+    pub fn new(capacity: Option<usize>) -> Self {
         let mut instance = Self {
             senders: HashMap::new(),
             receivers: HashMap::new(),
+            request_senders: HashMap::new(),
+            late_replies: HashMap::new(),
+            late_reply_types: HashMap::new(),
         };
 
         // Register request/response pairs.
         for mapping in shared_channel_configuration::CHANNEL_MAPPINGS {
-            instance.register(mapping.request, mapping.responses);
+            instance.register(mapping.request, mapping.responses, capacity);
         }
 
         instance
     }
 
     // Maps an outgoing message to incoming message(s)
-    fn register(&mut self, outbound: OutgoingMessages, inbounds: &[IncomingMessages]) {
-        let (sender, receiver) = channel::unbounded::<Response>();
+    fn register(&mut self, outbound: OutgoingMessages, inbounds: &[IncomingMessages], capacity: Option<usize>) {
+        let (sender, receiver) = response_channel(capacity);
 
         self.receivers.insert(outbound, Arc::new(receiver));
 
+        if let [inbound] = inbounds {
+            let late_replies = LateReplies::default();
+            self.late_replies.insert(outbound, Arc::clone(&late_replies));
+            self.late_reply_types.insert(*inbound, late_replies);
+        }
+
         let sender = &Arc::new(sender);
+        self.request_senders.insert(outbound, Arc::clone(sender));
 
         for inbound in inbounds {
             if !self.senders.contains_key(inbound) {
@@ -113,8 +190,37 @@ impl SharedChannels {
         self.senders.contains_key(&message_type)
     }
 
+    // Forgets messages dropped before the request was sent, so the caller is not told about a lag it did not cause.
+    fn reset_lag(&self, message_type: OutgoingMessages) {
+        if let Some(sender) = self.request_senders.get(&message_type) {
+            sender.reset_lag();
+        }
+    }
+
+    // Late replies of the request, if it has a single response type.
+    fn get_late_replies(&self, message_type: OutgoingMessages) -> Option<LateReplies> {
+        self.late_replies.get(&message_type).cloned()
+    }
+
+    // Forgets replies owed to abandoned requests, as TWS does not answer requests sent before a reconnect.
+    fn clear_late_replies(&self) {
+        for late_replies in self.late_replies.values() {
+            *late_replies.lock().unwrap() = 0;
+        }
+    }
+
     // Notify all listeners of a given message type with message.
     fn send_message(&self, message_type: IncomingMessages, message: &ResponseMessage) {
+        // held while sending, so abandoned requests see either the reply or the count
+        let mut late_replies = self.late_reply_types.get(&message_type).map(|late_replies| late_replies.lock().unwrap());
+        if let Some(pending) = late_replies.as_deref_mut() {
+            if *pending > 0 {
+                *pending -= 1;
+                debug!("discarding late {message_type:?} reply");
+                return;
+            }
+        }
+
         if let Some(senders) = self.senders.get(&message_type) {
             for sender in senders {
                 if let Err(e) = sender.send(Ok(message.clone())) {
@@ -147,18 +253,42 @@ pub enum Signal {
 pub struct TcpMessageBus {
     connection: Connection,
     handles: Mutex<Vec<JoinHandle<()>>>,
-    requests: SenderHash<i32, Response>,
-    orders: SenderHash<i32, Response>,
-    executions: SenderHash<String, Response>,
+    requests: SenderHash<i32>,
+    orders: SenderHash<i32>,
+    executions: SenderHash<String>,
     shared_channels: SharedChannels,
+    events: RwLock<Vec<(EventChannel, ResponseSender)>>,
+    // Streaming requests re-sent to TWS after reconnecting.
+    resubscriptions: RwLock<HashMap<i32, RequestMessage>>,
+    shared_resubscriptions: RwLock<HashMap<OutgoingMessages, RequestMessage>>,
     signals_send: Sender<Signal>,
     signals_recv: Receiver<Signal>,
     shutdown_requested: AtomicBool,
+    // Time the last message was received from TWS. Used to detect stale connections.
+    last_received: Mutex<Instant>,
+    keepalive_sent: AtomicBool,
+    // Message type and send time of requests awaiting their first response. Only tracked when metrics are collected.
+    request_times: Mutex<HashMap<i32, (i32, Instant)>>,
+    reconnecting: AtomicBool,
+    reconnects: AtomicU32,
+    // Last status reported for each data farm, by farm name.
+    data_farms: RwLock<BTreeMap<String, DataFarmState>>,
+    journal: RequestJournal,
+}
+
+// Outcome of checking the connection for activity while no messages are received.
+#[derive(Debug, PartialEq)]
+enum Keepalive {
+    Idle,
+    Probe,
+    Stale,
 }
 
 impl TcpMessageBus {
     pub fn new(connection: Connection) -> Result<TcpMessageBus, Error> {
         let (signals_send, signals_recv) = channel::unbounded();
+        let shared_channels = SharedChannels::new(connection.options.subscription_capacity);
+        let journal = RequestJournal::new(connection.options.request_journal_capacity);
 
         Ok(TcpMessageBus {
             connection,
@@ -166,10 +296,20 @@ impl TcpMessageBus {
             requests: SenderHash::new(),
             orders: SenderHash::new(),
             executions: SenderHash::new(),
-            shared_channels: SharedChannels::new(),
+            shared_channels,
+            events: RwLock::new(Vec::new()),
+            resubscriptions: RwLock::new(HashMap::new()),
+            shared_resubscriptions: RwLock::new(HashMap::new()),
             signals_send,
             signals_recv,
             shutdown_requested: AtomicBool::new(false),
+            last_received: Mutex::new(Instant::now()),
+            keepalive_sent: AtomicBool::new(false),
+            request_times: Mutex::new(HashMap::new()),
+            reconnecting: AtomicBool::new(false),
+            reconnects: AtomicU32::new(0),
+            data_farms: RwLock::new(BTreeMap::new()),
+            journal,
         })
     }
 
@@ -183,10 +323,12 @@ impl TcpMessageBus {
         self.requests.notify_all(&Err(Error::Shutdown));
         self.orders.notify_all(&Err(Error::Shutdown));
         self.shared_channels.notify_all(&Err(Error::Shutdown));
+        self.notify_events(&Err(Error::Shutdown));
 
         self.requests.clear();
         self.orders.clear();
         self.executions.clear();
+        self.events.write().unwrap().clear();
 
         self.shutdown_requested.store(true, Ordering::Relaxed);
     }
@@ -197,15 +339,44 @@ impl TcpMessageBus {
         self.requests.notify_all(&Err(Error::ConnectionReset));
         self.orders.notify_all(&Err(Error::ConnectionReset));
         self.shared_channels.notify_all(&Err(Error::ConnectionReset));
+        self.shared_channels.clear_late_replies();
+        self.notify_events(&Err(Error::ConnectionReset));
 
-        self.requests.clear();
+        let resubscriptions = self.resubscriptions.read().unwrap();
+        self.requests.retain(|request_id| resubscriptions.contains_key(request_id));
         self.orders.clear();
         self.executions.clear();
     }
 
+    // Re-sends streaming requests after reconnecting, so their subscriptions resume after the ConnectionReset notification.
+    fn resubscribe(&self) {
+        let resubscriptions = self.resubscriptions.read().unwrap();
+        let shared_resubscriptions = self.shared_resubscriptions.read().unwrap();
+
+        for (request_id, message) in resubscriptions.iter() {
+            info!("resubscribing request_id {request_id}");
+            if let Err(e) = self.connection.write_message(message) {
+                error!("error resubscribing request_id {request_id}: {e}");
+            }
+        }
+
+        for (message_type, message) in shared_resubscriptions.iter() {
+            info!("resubscribing {message_type:?}");
+            if let Err(e) = self.connection.write_message(message) {
+                error!("error resubscribing {message_type:?}: {e}");
+            }
+        }
+    }
+
     fn clean_request(&self, request_id: i32) {
         self.requests.remove(&request_id);
+        self.resubscriptions.write().unwrap().remove(&request_id);
         debug!("released request_id {}, requests.len()={}", request_id, self.requests.len());
+
+        if let Some(metrics) = self.metrics() {
+            self.request_times.lock().unwrap().remove(&request_id);
+            metrics.pending_requests(self.requests.len(), self.orders.len());
+        }
     }
 
     fn clean_order(&self, order_id: i32) {
@@ -214,12 +385,88 @@ impl TcpMessageBus {
     }
 
     fn read_message(&self) -> Response {
-        self.connection.read_message()
+        let message = self.connection.read_message()?;
+        self.record_activity();
+        Ok(message)
+    }
+
+    fn record_activity(&self) {
+        *self.last_received.lock().unwrap() = Instant::now();
+        self.keepalive_sent.store(false, Ordering::SeqCst);
+    }
+
+    // Decides whether the connection should be probed or treated as lost, based on the time since the last message was received.
+    fn check_keepalive(&self) -> Keepalive {
+        let idle = self.last_received.lock().unwrap().elapsed();
+        let options = &self.connection.options;
+
+        if matches!(options.stale_timeout, Some(stale_timeout) if idle >= stale_timeout) {
+            Keepalive::Stale
+        } else if matches!(options.keepalive_interval, Some(interval) if idle >= interval) && !self.keepalive_sent.load(Ordering::SeqCst) {
+            Keepalive::Probe
+        } else {
+            Keepalive::Idle
+        }
+    }
+
+    // Requests the current time from TWS so an idle but healthy connection produces a message.
+    fn send_keepalive(&self) {
+        const VERSION: i32 = 1;
+
+        let mut message = RequestMessage::default();
+        message.push_field(&OutgoingMessages::RequestCurrentTime);
+        message.push_field(&VERSION);
+
+        // the reply is consumed by the dispatcher rather than answering the next server time request
+        let late_replies = self.shared_channels.get_late_replies(OutgoingMessages::RequestCurrentTime);
+        let mut pending = late_replies.as_ref().map(|late_replies| late_replies.lock().unwrap());
+
+        debug!("sending keepalive");
+        match self.connection.write_message(&message) {
+            Ok(()) => {
+                if let Some(pending) = pending.as_deref_mut() {
+                    *pending += 1;
+                }
+            }
+            Err(e) => warn!("error sending keepalive: {e}"),
+        }
+        drop(pending);
+        self.keepalive_sent.store(true, Ordering::SeqCst);
+    }
+
+    // Re-establishes the connection after it was lost. Returns false if the client was shut down instead.
+    fn reconnect(&self) -> bool {
+        self.reconnecting.store(true, Ordering::SeqCst);
+        self.notify_events(&Err(Error::Disconnected));
+        let endpoint = self.connection.endpoint();
+
+        if let Err(e) = self.connection.reconnect() {
+            error!("failed to reconnect to TWS/Gateway: {:?}", e);
+            self.request_shutdown();
+            return false;
+        }
+
+        info!("successfully reconnected to TWS/Gateway");
+        self.reconnecting.store(false, Ordering::SeqCst);
+        self.reconnects.fetch_add(1, Ordering::SeqCst);
+        // TWS reports the status of each farm again after connecting
+        self.data_farms.write().unwrap().clear();
+        self.record_activity();
+        self.reset();
+
+        let new_endpoint = self.connection.endpoint();
+        if new_endpoint != endpoint {
+            info!("failed over from {endpoint} to {new_endpoint}");
+            self.notify_events(&Err(Error::Failover(new_endpoint)));
+        }
+
+        self.resubscribe();
+        true
     }
 
     // Dispatcher thread reads messages from TWS and dispatches them to
     // appropriate channel.
-    fn start_dispatcher_thread(self: &Arc<Self>, server_version: i32) -> JoinHandle<()> {
+    fn start_dispatcher_thread(self: &Arc<Self>) -> JoinHandle<()> {
         let message_bus = Arc::clone(self);
 
         const RECONNECT_CODES: &[ErrorKind] = &[ErrorKind::ConnectionReset, ErrorKind::ConnectionAborted, ErrorKind::UnexpectedEof];
@@ -229,27 +476,44 @@ impl TcpMessageBus {
             loop {
                 match message_bus.read_message() {
                     Ok(message) => {
-                        message_bus.dispatch_message(server_version, message);
+                        message_bus.dispatch_message(message);
                     }
                     Err(Error::Io(e)) if TIMEOUT_CODES.contains(&e.kind()) => {
                         if message_bus.is_shutting_down() {
                             debug!("dispatcher thread exiting");
                             return;
                         }
+
+                        match message_bus.check_keepalive() {
+                            Keepalive::Idle => {}
+                            Keepalive::Probe => message_bus.send_keepalive(),
+                            Keepalive::Stale => {
+                                error!("no messages received from TWS/Gateway (will attempt reconnect)");
+                                if !message_bus.reconnect() {
+                                    return;
+                                }
+                            }
+                        }
                     }
                     Err(Error::Io(e)) if RECONNECT_CODES.contains(&e.kind()) => {
+                        if message_bus.is_shutting_down() {
+                            debug!("dispatcher thread exiting");
+                            return;
+                        }
+
                         error!("error reading next message (will attempt reconnect): {:?}", e);
 
                         // Attempt to reconnect to TWS.
-                        if let Err(e) = message_bus.connection.reconnect() {
-                            error!("failed to reconnect to TWS/Gateway: {:?}", e);
-                            message_bus.request_shutdown();
+                        if !message_bus.reconnect() {
+                            return;
+                        }
+                    }
+                    Err(Error::MessageTooLong(length)) => {
+                        // the rest of the stream can not be framed, so start over on a new connection
+                        error!("message of {length} bytes exceeds the maximum message length (will attempt reconnect)");
+                        if !message_bus.reconnect() {
                             return;
                         }
-
-                        info!("successfully reconnected to TWS/Gateway");
-                        message_bus.reset();
-                        continue;
                     }
                     Err(err) => {
                         error!("error reading next message (shutting down): {:?}", err);
@@ -261,14 +525,26 @@ impl TcpMessageBus {
         })
     }
 
-    fn dispatch_message(&self, server_version: i32, message: ResponseMessage) {
+    fn dispatch_message(&self, message: ResponseMessage) {
+        if message.message_type() != IncomingMessages::Error {
+            if let Some(request_id) = message.request_id().or_else(|| message.order_id()) {
+                self.journal.record_response(request_id);
+            }
+        }
+
         match message.message_type() {
             IncomingMessages::Error => {
                 let request_id = message.peek_int(2).unwrap_or(-1);
 
                 if request_id == UNSPECIFIED_REQUEST_ID {
-                    error_event(server_version, message).unwrap();
+                    // read per message, as the server version changes when failing over to another endpoint
+                    if let Err(e) = error_event(self.connection.server_version(), message.clone()) {
+                        warn!("error decoding error message: {e}");
+                    }
+                    self.record_data_farm(&message);
+                    self.notify_events(&Ok(message));
                 } else {
+                    self.journal.record_error(request_id, &Notice::from(&message));
                     self.process_response(message);
                 }
             }
@@ -287,16 +563,70 @@ impl TcpMessageBus {
     fn process_response(&self, message: ResponseMessage) {
         let request_id = message.request_id().unwrap_or(-1); // pass in request id?
         if self.requests.contains(&request_id) {
-            self.requests.send(&request_id, Ok(message)).unwrap();
+            self.record_latency(request_id);
+            if let Err(e) = self.requests.send(&request_id, Ok(message)) {
+                warn!("error routing message for request_id({request_id}): {e}");
+            }
         } else if self.orders.contains(&request_id) {
-            self.orders.send(&request_id, Ok(message)).unwrap();
+            if let Err(e) = self.orders.send(&request_id, Ok(message)) {
+                warn!("error routing message for order_id({request_id}): {e}");
+            }
         } else if self.shared_channels.contains_sender(message.message_type()) {
             self.shared_channels.send_message(message.message_type(), &message);
+        } else if message.message_type() == IncomingMessages::Error {
+            // errors for requests that were cancelled or timed out
+            self.notify_events(&Ok(message));
         } else {
-            info!("no recipient found for: {:?}", message)
+            info!("no recipient found for: {:?}", message);
         }
     }
 
+    fn metrics(&self) -> Option<&SharedMetrics> {
+        self.connection.options.metrics.as_ref()
+    }
+
+    fn record_request_sent(&self, request_id: i32, message: &RequestMessage) {
+        if let Some(metrics) = self.metrics() {
+            self.request_times
+                .lock()
+                .unwrap()
+                .insert(request_id, (message.message_id(), Instant::now()));
+            metrics.pending_requests(self.requests.len(), self.orders.len());
+        }
+    }
+
+    fn record_latency(&self, request_id: i32) {
+        if let Some(metrics) = self.metrics() {
+            if let Some((message_type, sent_at)) = self.request_times.lock().unwrap().remove(&request_id) {
+                metrics.request_latency(message_type, sent_at.elapsed());
+            }
+        }
+    }
+
+    fn record_data_farm(&self, message: &ResponseMessage) {
+        if let Some(ConnectionEvent::DataFarm { farm, name, status }) = ConnectionEvent::from_notice(&Notice::from(message)) {
+            self.data_farms
+                .write()
+                .unwrap()
+                .insert(name.clone(), DataFarmState { farm, name, status });
+        }
+    }
+
+    // Sends message to event subscribers, releasing channels of dropped subscribers.
+    fn notify_events(&self, message: &Response) {
+        let info = match message {
+            Ok(message) if message.message_type() == IncomingMessages::Error => self.connection.options.notice_filter.is_info(&Notice::from(message)),
+            _ => false,
+        };
+
+        let mut events = self.events.write().unwrap();
+        events.retain(|(channel, sender)| match (channel, message) {
+            (EventChannel::Errors, Ok(_)) if info => true,
+            (EventChannel::Info, Ok(_)) if !info => true,
+            _ => sender.send(message.clone()).is_ok(),
+        });
+    }
+
     fn process_orders(&self, message: ResponseMessage) {
         match message.message_type() {
             IncomingMessages::ExecutionData => {
@@ -394,8 +724,8 @@ impl TcpMessageBus {
         })
     }
 
-    pub(crate) fn process_messages(self: &Arc<Self>, server_version: i32) -> Result<(), Error> {
-        let handle = self.start_dispatcher_thread(server_version);
+    pub(crate) fn process_messages(self: &Arc<Self>) -> Result<(), Error> {
+        let handle = self.start_dispatcher_thread();
         self.add_join_handle(handle);
 
         let handle = self.start_cleanup_thread();
@@ -422,14 +752,40 @@ impl TcpMessageBus {
 
 const UNSPECIFIED_REQUEST_ID: i32 = -1;
 
+// Requests for data streamed until cancelled. These are re-sent to TWS after reconnecting.
+const STREAMING_REQUESTS: &[OutgoingMessages] = &[
+    OutgoingMessages::RequestMarketData,
+    OutgoingMessages::RequestMarketDepth,
+    OutgoingMessages::RequestRealTimeBars,
+    OutgoingMessages::RequestTickByTickData,
+    OutgoingMessages::RequestPnL,
+    OutgoingMessages::RequestPnLSingle,
+    OutgoingMessages::RequestAccountData,
+    OutgoingMessages::RequestAccountUpdatesMulti,
+    OutgoingMessages::RequestPositions,
+    OutgoingMessages::RequestPositionsMulti,
+    OutgoingMessages::RequestAccountSummary,
+    OutgoingMessages::SubscribeToGroupEvents,
+];
+
+fn is_streaming_request(message: &RequestMessage) -> bool {
+    STREAMING_REQUESTS.iter().any(|request| message[0] == request.to_field())
+}
+
 impl MessageBus for TcpMessageBus {
     fn send_request(&self, request_id: i32, packet: &RequestMessage) -> Result<InternalSubscription, Error> {
-        let (sender, receiver) = channel::unbounded();
+        let (sender, receiver) = response_channel(self.connection.options.subscription_capacity);
         let sender_copy = sender.clone();
 
         self.requests.insert(request_id, sender);
 
+        self.journal.record_request(Some(request_id), packet);
         self.connection.write_message(packet)?;
+        self.record_request_sent(request_id, packet);
+
+        if is_streaming_request(packet) {
+            self.resubscriptions.write()?.insert(request_id, packet.clone());
+        }
 
         let subscription = SubscriptionBuilder::new()
             .receiver(receiver)
@@ -442,6 +798,7 @@ impl MessageBus for TcpMessageBus {
     }
 
     fn cancel_subscription(&self, request_id: i32, message: &RequestMessage) -> Result<(), Error> {
+        self.journal.record_request(Some(request_id), message);
         self.connection.write_message(message)?;
 
         if let Err(e) = self.requests.send(&request_id, Err(Error::Cancelled)) {
@@ -449,16 +806,18 @@ impl MessageBus for TcpMessageBus {
         }
 
         self.requests.remove(&request_id);
+        self.resubscriptions.write()?.remove(&request_id);
 
         Ok(())
     }
 
     fn send_order_request(&self, order_id: i32, message: &RequestMessage) -> Result<InternalSubscription, Error> {
-        let (sender, receiver) = channel::unbounded();
+        let (sender, receiver) = response_channel(self.connection.options.subscription_capacity);
         let sender_copy = sender.clone();
 
         self.orders.insert(order_id, sender);
 
+        self.journal.record_request(Some(order_id), message);
         self.connection.write_message(message)?;
 
         let subscription = SubscriptionBuilder::new()
@@ -472,6 +831,7 @@ impl MessageBus for TcpMessageBus {
     }
 
     fn cancel_order_subscription(&self, request_id: i32, message: &RequestMessage) -> Result<(), Error> {
+        self.journal.record_request(Some(request_id), message);
         self.connection.write_message(message)?;
 
         if let Err(e) = self.orders.send(&request_id, Err(Error::Cancelled)) {
@@ -483,32 +843,98 @@ impl MessageBus for TcpMessageBus {
         Ok(())
     }
 
+    fn send_message(&self, request_id: Option<i32>, packet: &RequestMessage) -> Result<(), Error> {
+        self.journal.record_request(request_id, packet);
+        self.connection.write_message(packet)
+    }
+
     fn send_shared_request(&self, message_type: OutgoingMessages, message: &RequestMessage) -> Result<InternalSubscription, Error> {
+        self.shared_channels.reset_lag(message_type);
+        self.journal.record_request(None, message);
         self.connection.write_message(message)?;
 
+        if is_streaming_request(message) {
+            self.shared_resubscriptions.write()?.insert(message_type, message.clone());
+        }
+
         let shared_receiver = self.shared_channels.get_receiver(message_type);
 
-        let subscription = SubscriptionBuilder::new()
-            .shared_receiver(shared_receiver)
-            .message_type(message_type)
-            .build();
+        let mut builder = SubscriptionBuilder::new().shared_receiver(shared_receiver).message_type(message_type);
+        if let Some(late_replies) = self.shared_channels.get_late_replies(message_type) {
+            builder = builder.late_replies(late_replies);
+        }
+
+        let subscription = builder.build();
 
         Ok(subscription)
     }
 
-    fn cancel_shared_subscription(&self, _message_type: OutgoingMessages, message: &RequestMessage) -> Result<(), Error> {
+    fn cancel_shared_subscription(&self, message_type: OutgoingMessages, message: &RequestMessage) -> Result<(), Error> {
+        self.journal.record_request(None, message);
         self.connection.write_message(message)?;
+        self.shared_resubscriptions.write()?.remove(&message_type);
         // TODO send cancel
         Ok(())
     }
 
+    fn subscribe_events(&self, channel: EventChannel) -> Result<InternalSubscription, Error> {
+        let (sender, receiver) = response_channel(self.connection.options.subscription_capacity);
+        self.events.write()?.push((channel, sender));
+
+        Ok(SubscriptionBuilder::new().shared_receiver(Arc::new(receiver)).build())
+    }
+
+    fn connection_order_id(&self) -> Option<i32> {
+        Some(self.connection.connection_metadata().next_order_id)
+    }
+
     fn ensure_shutdown(&self) {
         self.request_shutdown();
+        self.connection.close();
         self.join();
     }
+
+    fn request_journal(&self) -> Vec<JournalEntry> {
+        self.journal.entries()
+    }
+
+    fn health(&self) -> Health {
+        Health {
+            connected: !self.is_shutting_down() && !self.reconnecting.load(Ordering::SeqCst),
+            last_message_age: self.last_received.lock().unwrap().elapsed(),
+            data_farms: self.data_farms.read().unwrap().values().cloned().collect(),
+            outstanding_requests: self.requests.len() + self.orders.len(),
+            reconnects: self.reconnects.load(Ordering::SeqCst),
+        }
+    }
+
+    fn begin_batch(&self) -> Result<(), Error> {
+        self.connection.begin_batch()
+    }
+
+    fn flush_batch(&self) -> Result<(), Error> {
+        self.connection.flush_batch()
+    }
+
+    fn end_batch(&self) -> Result<(), Error> {
+        self.connection.end_batch()
+    }
 }
 
-fn read_header(mut reader: &TcpStream) -> Result<usize, Error> {
+// Reads a length prefixed message, rejecting lengths over MAX_MESSAGE_LENGTH before allocating.
+pub(crate) fn read_packet<R: Read + ?Sized>(reader: &mut R, pool: &BufferPool) -> Result<String, Error> {
+    let message_size = read_header(reader)?;
+    if message_size > MAX_MESSAGE_LENGTH {
+        return Err(Error::MessageTooLong(message_size));
+    }
+    let mut data = pool.take(message_size);
+
+    reader.read_exact(&mut data)?;
+
+    Ok(String::from_utf8(data)?)
+}
+
+fn read_header<R: Read + ?Sized>(reader: &mut R) -> Result<usize, Error> {
     let buffer = &mut [0_u8; 4];
     reader.read_exact(buffer)?;
 
@@ -538,27 +964,103 @@ fn error_event(server_version: i32, mut packet: ResponseMessage) -> Result<(), E
         if server_version >= server_versions::ADVANCED_ORDER_REJECT {
             advanced_order_reject_json = packet.next_string()?;
         }
-        info!(
-            "request_id: {}, error_code: {}, error_message: {}, advanced_order_reject_json: {}",
-            request_id, error_code, error_message, advanced_order_reject_json
-        );
+        if ErrorCategory::from_code(error_code).is_warning() {
+            info!("request_id: {request_id}, error_code: {error_code}, error_message: {error_message}");
+        } else {
+            error!(
+                "request_id: {}, error_code: {}, error_message: {}, advanced_order_reject_json: {}",
+                request_id, error_code, error_message, advanced_order_reject_json
+            );
+        }
         Ok(())
     }
 }
 
+// Creates a channel for responses holding up to `capacity` messages. None is unbounded.
+// The channel itself is unbounded, as bounded channels allocate every slot up front; the sender enforces the capacity.
+fn response_channel(capacity: Option<usize>) -> (ResponseSender, Receiver<Response>) {
+    let (sender, receiver) = channel::unbounded();
+    (ResponseSender::new(sender, capacity), receiver)
+}
+
+// Sends responses to a subscription without blocking the dispatcher. Messages are dropped while the subscription's queue is full,
+// and the subscriber is sent Error::Lagged with the number of dropped messages once the queue has room again.
+#[derive(Clone, Debug)]
+pub(crate) struct ResponseSender {
+    sender: Sender<Response>,
+    // Maximum number of queued messages. None is unbounded.
+    capacity: Option<usize>,
+    // Messages dropped since Error::Lagged was last delivered. Shared by clones of the sender.
+    dropped: Arc<AtomicU64>,
+}
+
+impl ResponseSender {
+    fn new(sender: Sender<Response>, capacity: Option<usize>) -> Self {
+        ResponseSender {
+            sender,
+            capacity,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    // Queues the response unless the queue holds `capacity` messages.
+    fn try_send(&self, response: Response) -> Result<(), TrySendError<Response>> {
+        if self.capacity.is_some_and(|capacity| self.sender.len() >= capacity) {
+            return Err(TrySendError::Full(response));
+        }
+        self.sender
+            .send(response)
+            .map_err(|SendError(response)| TrySendError::Disconnected(response))
+    }
+
+    // Stops reporting the messages dropped so far.
+    fn reset_lag(&self) {
+        self.dropped.store(0, Ordering::SeqCst);
+    }
+
+    // Fails only if the subscription was dropped.
+    pub(crate) fn send(&self, response: Response) -> Result<(), SendError<Response>> {
+        let dropped = self.dropped.load(Ordering::SeqCst);
+        if dropped > 0 {
+            match self.try_send(Err(Error::Lagged(dropped))) {
+                Ok(()) => {
+                    self.dropped.fetch_sub(dropped, Ordering::SeqCst);
+                }
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::SeqCst);
+                    return Ok(());
+                }
+                Err(TrySendError::Disconnected(_)) => return Err(SendError(response)),
+            }
+        }
+
+        match self.try_send(response) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::SeqCst);
+                if dropped == 0 {
+                    warn!("subscription queue is full, dropping messages until the subscriber catches up");
+                }
+                Ok(())
+            }
+            Err(TrySendError::Disconnected(response)) => Err(SendError(response)),
+        }
+    }
+}
+
 #[derive(Debug)]
-struct SenderHash<K, V> {
-    senders: RwLock<HashMap<K, Sender<V>>>,
+struct SenderHash<K> {
+    senders: RwLock<HashMap<K, ResponseSender>>,
 }
 
-impl<K: std::hash::Hash + Eq + std::fmt::Debug, V: std::fmt::Debug + Clone> SenderHash<K, V> {
+impl<K: std::hash::Hash + Eq + std::fmt::Debug> SenderHash<K> {
     pub fn new() -> Self {
         Self {
             senders: RwLock::new(HashMap::new()),
         }
     }
 
-    pub fn send(&self, id: &K, message: V) -> Result<(), Error> {
+    pub fn send(&self, id: &K, message: Response) -> Result<(), Error> {
         let senders = self.senders.read().unwrap();
         debug!("senders: {senders:?}");
         if let Some(sender) = senders.get(id) {
@@ -571,17 +1073,17 @@ impl<K: std::hash::Hash + Eq + std::fmt::Debug, V: std::fmt::Debug + Clone> Send
         Ok(())
     }
 
-    pub fn copy_sender(&self, id: K) -> Option<Sender<V>> {
+    pub fn copy_sender(&self, id: K) -> Option<ResponseSender> {
         let senders = self.senders.read().unwrap();
         senders.get(&id).cloned()
     }
 
-    pub fn insert(&self, id: K, message: Sender<V>) -> Option<Sender<V>> {
+    pub fn insert(&self, id: K, message: ResponseSender) -> Option<ResponseSender> {
         let mut senders = self.senders.write().unwrap();
         senders.insert(id, message)
     }
 
-    pub fn remove(&self, id: &K) -> Option<Sender<V>> {
+    pub fn remove(&self, id: &K) -> Option<ResponseSender> {
         let mut senders = self.senders.write().unwrap();
         senders.remove(id)
     }
@@ -601,7 +1103,12 @@ impl<K: std::hash::Hash + Eq + std::fmt::Debug, V: std::fmt::Debug + Clone> Send
         senders.clear();
     }
 
-    pub fn notify_all(&self, message: &V) {
+    pub fn retain(&self, keep: impl Fn(&K) -> bool) {
+        let mut senders = self.senders.write().unwrap();
+        senders.retain(|id, _| keep(id));
+    }
+
+    pub fn notify_all(&self, message: &Response) {
         let senders = self.senders.read().unwrap();
         for sender in senders.values() {
             if let Err(e) = sender.send(message.clone()) {
@@ -615,8 +1122,11 @@ impl<K: std::hash::Hash + Eq + std::fmt::Debug, V: std::fmt::Debug + Clone> Send
 #[derive(Debug, Default)]
 pub(crate) struct InternalSubscription {
     receiver: Option<Receiver<Response>>,              // requests with request ids receive responses via this channel
-    sender: Option<Sender<Response>>,                  // requests with request ids receive responses via this channel
+    sender: Option<ResponseSender>,                    // requests with request ids receive responses via this channel
     shared_receiver: Option<Arc<Receiver<Response>>>,  // this channel is for responses that share channel based on message type
+    late_replies: Option<LateReplies>,                 // replies owed to abandoned requests on the shared channel
+    cancel_sender: Option<Sender<Response>>,           // wakes waits on the shared channel when the subscription is cancelled
+    cancel_receiver: Option<Receiver<Response>>,       // waited on alongside the shared channel
     signaler: Option<Sender<Signal>>,                  // for client to signal termination
     pub(crate) request_id: Option<i32>,                // initiating request id
     pub(crate) order_id: Option<i32>,                  // initiating order id
@@ -629,7 +1139,7 @@ impl InternalSubscription {
         if let Some(receiver) = &self.receiver {
             Self::receive(receiver)
         } else if let Some(receiver) = &self.shared_receiver {
-            Self::receive(receiver)
+            self.shared_receive(receiver, None).ok()
         } else {
             None
         }
@@ -640,7 +1150,10 @@ impl InternalSubscription {
         if let Some(receiver) = &self.receiver {
             Self::try_receive(receiver)
         } else if let Some(receiver) = &self.shared_receiver {
-            Self::try_receive(receiver)
+            self.cancel_receiver
+                .as_ref()
+                .and_then(Self::try_receive)
+                .or_else(|| Self::try_receive(receiver))
         } else {
             None
         }
@@ -651,19 +1164,85 @@ impl InternalSubscription {
         if let Some(receiver) = &self.receiver {
             Self::timeout_receive(receiver, timeout)
         } else if let Some(receiver) = &self.shared_receiver {
-            Self::timeout_receive(receiver, timeout)
+            self.shared_receive(receiver, Some(timeout)).ok()
         } else {
             None
         }
     }
 
-    pub(crate) fn cancel(&self) {
-        if let Some(sender) = &self.sender {
-            if let Err(e) = sender.send(Err(Error::Cancelled)) {
-                warn!("error sending cancel notification: {e}")
+    // Blocks until next message become available, or returns Error::Timeout if none arrives within `timeout`.
+    pub(crate) fn next_within(&self, timeout: Option<Duration>) -> Option<Response> {
+        let Some(timeout) = timeout else {
+            return self.next();
+        };
+
+        let received = if let Some(receiver) = &self.receiver {
+            receiver.recv_timeout(timeout)
+        } else {
+            self.shared_receive(self.shared_receiver.as_ref()?, Some(timeout))
+        };
+
+        match received {
+            Ok(response) => Some(response),
+            Err(RecvTimeoutError::Timeout) => {
+                self.abandon();
+                Some(Err(Error::Timeout))
             }
+            Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    // Stops waiting for the reply to a shared request, so it is discarded when it arrives rather than answering a later request.
+    fn abandon(&self) {
+        if let (Some(late_replies), Some(receiver)) = (&self.late_replies, &self.shared_receiver) {
+            let mut pending = late_replies.lock().unwrap();
+            // the reply may have arrived since the wait timed out
+            if receiver.try_recv().is_err() {
+                *pending += 1;
+            }
+        }
+    }
+
+    // Waits for a response on the shared channel, or for the subscription to be cancelled.
+    fn shared_receive(&self, receiver: &Receiver<Response>, timeout: Option<Duration>) -> Result<Response, RecvTimeoutError> {
+        let Some(cancel_receiver) = &self.cancel_receiver else {
+            return match timeout {
+                Some(timeout) => receiver.recv_timeout(timeout),
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+        };
+
+        let mut select = Select::new();
+        let shared = select.recv(receiver);
+        select.recv(cancel_receiver);
+
+        let operation = match timeout {
+            Some(timeout) => select.select_timeout(timeout).map_err(|_| RecvTimeoutError::Timeout)?,
+            None => select.select(),
+        };
+
+        let received = if operation.index() == shared {
+            operation.recv(receiver)
+        } else {
+            operation.recv(cancel_receiver)
+        };
+        received.map_err(|_| RecvTimeoutError::Disconnected)
+    }
+
+    // Creates a waker that ends waits for this subscription from other threads.
+    pub(crate) fn waker(&self) -> SubscriptionWaker {
+        SubscriptionWaker {
+            sender: self.sender.clone(),
+            cancel_sender: self.cancel_sender.clone(),
+            signaler: self.signaler.clone(),
+            request_id: self.request_id,
+            order_id: self.order_id,
         }
-        // TODO - shared sender
+    }
+
+    #[cfg(feature = "news")]
+    pub(crate) fn receiver(&self) -> Option<&Receiver<Response>> {
+        self.receiver.as_ref().or(self.shared_receiver.as_deref())
     }
 
     fn receive(receiver: &Receiver<Response>) -> Option<Response> {
@@ -693,10 +1272,63 @@ impl Drop for InternalSubscription {
     }
 }
 
+// Ends waits for a subscription and releases its routing entry without access to the subscription.
+#[derive(Clone, Default)]
+pub(crate) struct SubscriptionWaker {
+    sender: Option<ResponseSender>,
+    cancel_sender: Option<Sender<Response>>,
+    signaler: Option<Sender<Signal>>,
+    request_id: Option<i32>,
+    order_id: Option<i32>,
+}
+
+impl SubscriptionWaker {
+    // Delivers Error::Cancelled to threads waiting for the next response.
+    pub(crate) fn wake(&self) {
+        if let Some(sender) = &self.sender {
+            if let Err(e) = sender.send(Err(Error::Cancelled)) {
+                warn!("error sending cancel notification: {e}")
+            }
+        }
+        if let Some(sender) = &self.cancel_sender {
+            if let Err(e) = sender.send(Err(Error::Cancelled)) {
+                warn!("error sending cancel notification: {e}")
+            }
+        }
+    }
+
+    // Removes the routing entry so responses to the request are no longer delivered.
+    pub(crate) fn release(&self) {
+        let Some(signaler) = &self.signaler else {
+            return;
+        };
+
+        let signal = match (self.request_id, self.order_id) {
+            (Some(request_id), _) => Signal::Request(request_id),
+            (None, Some(order_id)) => Signal::Order(order_id),
+            (None, None) => return,
+        };
+
+        if let Err(e) = signaler.send(signal) {
+            warn!("error sending release signal: {e}");
+        }
+    }
+}
+
+impl std::fmt::Debug for SubscriptionWaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionWaker")
+            .field("request_id", &self.request_id)
+            .field("order_id", &self.order_id)
+            .finish()
+    }
+}
+
 pub(crate) struct SubscriptionBuilder {
     receiver: Option<Receiver<Response>>,
-    sender: Option<Sender<Response>>,
+    sender: Option<ResponseSender>,
     shared_receiver: Option<Arc<Receiver<Response>>>,
+    late_replies: Option<LateReplies>,
     signaler: Option<Sender<Signal>>,
     order_id: Option<i32>,
     request_id: Option<i32>,
@@ -709,6 +1341,7 @@ impl SubscriptionBuilder {
             receiver: None,
             sender: None,
             shared_receiver: None,
+            late_replies: None,
             signaler: None,
             order_id: None,
             request_id: None,
@@ -721,7 +1354,7 @@ impl SubscriptionBuilder {
         self
     }
 
-    pub(crate) fn sender(mut self, sender: Sender<Response>) -> Self {
+    pub(crate) fn sender(mut self, sender: ResponseSender) -> Self {
         self.sender = Some(sender);
         self
     }
@@ -731,6 +1364,11 @@ impl SubscriptionBuilder {
         self
     }
 
+    pub(crate) fn late_replies(mut self, late_replies: LateReplies) -> Self {
+        self.late_replies = Some(late_replies);
+        self
+    }
+
     pub(crate) fn signaler(mut self, signaler: Sender<Signal>) -> Self {
         self.signaler = Some(signaler);
         self
@@ -757,16 +1395,23 @@ impl SubscriptionBuilder {
                 receiver: Some(receiver),
                 sender: self.sender,
                 shared_receiver: None,
+                late_replies: None,
+                cancel_sender: None,
+                cancel_receiver: None,
                 signaler: Some(signaler),
                 request_id: self.request_id,
                 order_id: self.order_id,
                 message_type: self.message_type,
             }
         } else if let Some(receiver) = self.shared_receiver {
+            let (cancel_sender, cancel_receiver) = channel::unbounded();
             InternalSubscription {
                 receiver: None,
                 sender: None,
                 shared_receiver: Some(receiver),
+                late_replies: self.late_replies,
+                cancel_sender: Some(cancel_sender),
+                cancel_receiver: Some(cancel_receiver),
                 signaler: None,
                 request_id: self.request_id,
                 order_id: self.order_id,
@@ -786,39 +1431,187 @@ pub(crate) struct ConnectionMetadata {
     pub(crate) managed_accounts: String,
     pub(crate) connection_time: Option<OffsetDateTime>,
     pub(crate) time_zone: Option<&'static Tz>,
+    // Connection time as sent by TWS, e.g. 20230405 22:20:39 PST.
+    pub(crate) raw_connection_time: String,
+    // Optional capabilities sent when starting the API. None if the server version does not accept them.
+    pub(crate) optional_capabilities: Option<String>,
+}
+
+// Settings used to establish and re-establish the connection to TWS.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectionOptions {
+    pub(crate) address: String,
+    // Endpoints tried in order when the address does not accept connections.
+    pub(crate) failover_addresses: Vec<String>,
+    pub(crate) client_id: i32,
+    // None waits for the operating system to time out the connection attempt.
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) read_timeout: Duration,
+    // Options sent with the handshake, e.g. +PACEAPI to have TWS pace requests instead of rejecting them.
+    pub(crate) connect_options: String,
+    // Optional capabilities sent when starting the API.
+    pub(crate) optional_capabilities: String,
+    // Classifies notices without a request id as informational.
+    pub(crate) notice_filter: NoticeFilter,
+    // Highest client id tried when the client id is already in use. None fails if the client id is in use.
+    pub(crate) max_client_id: Option<i32>,
+    // Maximum number of messages sent per second. None sends messages without delay.
+    pub(crate) max_messages_per_second: Option<u32>,
+    // Maximum number of messages queued for each subscription. None queues messages without limit.
+    pub(crate) subscription_capacity: Option<usize>,
+    // Number of recent requests kept in the request journal. Zero disables the journal.
+    pub(crate) request_journal_capacity: usize,
+    // Idle time after which the current time is requested to check the connection. None disables keepalives.
+    pub(crate) keepalive_interval: Option<Duration>,
+    // Idle time after which the connection is considered lost. None waits for the socket to report an error.
+    pub(crate) stale_timeout: Option<Duration>,
+    pub(crate) max_retries: i32,
+    pub(crate) max_retry_delay: Duration,
+    pub(crate) message_observer: Option<SharedObserver>,
+    pub(crate) metrics: Option<SharedMetrics>,
+    pub(crate) transport: SharedTransport,
+}
+
+impl ConnectionOptions {
+    pub(crate) fn new(address: &str, client_id: i32) -> Self {
+        ConnectionOptions {
+            address: address.into(),
+            failover_addresses: Vec::new(),
+            client_id,
+            connect_timeout: None,
+            read_timeout: TWS_READ_TIMEOUT,
+            connect_options: String::new(),
+            optional_capabilities: String::new(),
+            notice_filter: NoticeFilter::new(),
+            max_client_id: None,
+            max_messages_per_second: Some(MAX_MESSAGES_PER_SECOND),
+            subscription_capacity: Some(SUBSCRIPTION_CAPACITY),
+            request_journal_capacity: REQUEST_JOURNAL_CAPACITY,
+            keepalive_interval: None,
+            stale_timeout: None,
+            max_retries: MAX_RETRIES,
+            max_retry_delay: MAX_RETRY_DELAY,
+            message_observer: None,
+            metrics: None,
+            transport: SharedTransport::default(),
+        }
+    }
+
+    // Connects to the first endpoint accepting the connection, in order of priority. Returns the stream and the endpoint connected to.
+    fn open_stream(&self) -> Result<(Box<dyn TransportStream>, String), Error> {
+        let mut last_error = None;
+        for address in self.endpoints() {
+            match self.open_stream_to(address) {
+                Ok(stream) => return Ok((stream, address.clone())),
+                Err(e) => {
+                    warn!("error connecting to {address}: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(Error::ConnectionFailed))
+    }
+
+    // Addresses to connect to, in order of preference.
+    fn endpoints(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.address).chain(&self.failover_addresses)
+    }
+
+    fn open_stream_to(&self, address: &str) -> Result<Box<dyn TransportStream>, Error> {
+        let stream = self.transport.connect(address, self.connect_timeout)?;
+        stream.set_read_timeout(Some(self.read_timeout))?;
+        Ok(stream)
+    }
+}
+
+// Frames of the requests a thread writes while its batch is open, sent to TWS with a single write when the batch is flushed.
+#[derive(Debug, Default)]
+struct WriteBatch {
+    depth: usize,
+    packets: Vec<u8>,
 }
 
 #[derive(Debug)]
 pub(crate) struct Connection {
-    client_id: i32,
-    connection_url: String,
-    reader: Mutex<TcpStream>,
-    writer: Mutex<TcpStream>,
+    options: ConnectionOptions,
+    reader: Mutex<Box<dyn TransportStream>>,
+    writer: Mutex<Box<dyn TransportStream>>,
+    write_buffer: Mutex<Vec<u8>>,
+    // open batches by thread, so internal writes and other threads are not held back by a batch
+    batches: Mutex<HashMap<ThreadId, WriteBatch>>,
+    read_pool: Arc<BufferPool>,
     connection_metadata: Mutex<ConnectionMetadata>,
-    max_retries: i32,
+    // Endpoint the connection is established with.
+    endpoint: Mutex<String>,
     recorder: MessageRecorder,
+    rate_limiter: Option<RateLimiter>,
+    closed: AtomicBool,
 }
 
 impl Connection {
-    pub fn connect(client_id: i32, connection_url: &str) -> Result<Self, Error> {
-        let reader = TcpStream::connect(connection_url)?;
+    pub fn connect_with_options(options: ConnectionOptions) -> Result<Self, Error> {
+        let (reader, endpoint) = options.open_stream()?;
         let writer = reader.try_clone()?;
 
-        reader.set_read_timeout(Some(TWS_READ_TIMEOUT))?;
+        let connection_metadata = ConnectionMetadata {
+            client_id: options.client_id,
+            ..ConnectionMetadata::default()
+        };
+        let rate_limiter = options.max_messages_per_second.map(RateLimiter::new);
 
         let connection = Self {
-            client_id,
-            connection_url: connection_url.into(),
+            options,
             reader: Mutex::new(reader),
             writer: Mutex::new(writer),
-            connection_metadata: Mutex::new(ConnectionMetadata::default()),
-            max_retries: MAX_RETRIES,
+            write_buffer: Mutex::new(Vec::new()),
+            batches: Mutex::new(HashMap::new()),
+            read_pool: Arc::new(BufferPool::default()),
+            connection_metadata: Mutex::new(connection_metadata),
+            endpoint: Mutex::new(endpoint),
             recorder: MessageRecorder::new(),
+            rate_limiter,
+            closed: AtomicBool::new(false),
         };
 
-        connection.establish_connection()?;
+        loop {
+            match connection.establish_connection() {
+                Err(Error::ClientIdInUse(client_id)) if connection.options.max_client_id.is_some_and(|max| client_id < max) => {
+                    warn!("client id {client_id} is already in use, connecting with client id {}", client_id + 1);
 
-        Ok(connection)
+                    connection.connection_metadata.lock()?.client_id = client_id + 1;
+                    let stream = connection.options.open_stream_to(&connection.endpoint())?;
+                    connection.replace_stream(stream)?;
+                }
+                result => {
+                    result?;
+                    return Ok(connection);
+                }
+            }
+        }
+    }
+
+    // Opens a new stream to `address` and completes the handshake on it.
+    fn reconnect_to(&self, address: &str) -> Result<(), Error> {
+        let stream = self.options.open_stream_to(address)?;
+        self.replace_stream(stream)?;
+        *self.endpoint.lock()? = address.to_owned();
+
+        self.establish_connection()
+    }
+
+    fn replace_stream(&self, stream: Box<dyn TransportStream>) -> Result<(), Error> {
+        let mut reader = self.reader.lock()?;
+        let mut writer = self.writer.lock()?;
+
+        *reader = stream.try_clone()?;
+        *writer = stream;
+
+        Ok(())
+    }
+
+    pub(crate) fn endpoint(&self) -> String {
+        self.endpoint.lock().unwrap().clone()
     }
 
     pub fn connection_metadata(&self) -> ConnectionMetadata {
@@ -827,33 +1620,25 @@ impl Connection {
     }
 
     pub fn reconnect(&self) -> Result<(), Error> {
-        let mut backoff = FibonacciBackoff::new(30);
+        let mut backoff = FibonacciBackoff::new(self.options.max_retry_delay.as_secs().max(1));
 
-        for i in 0..self.max_retries {
+        for i in 0..self.options.max_retries {
             let next_delay = backoff.next_delay();
             info!("next reconnection attempt in {next_delay:#?}");
 
             thread::sleep(next_delay);
 
-            match TcpStream::connect(&self.connection_url) {
-                Ok(stream) => {
-                    {
-                        let mut reader = self.reader.lock()?;
-                        let mut writer = self.writer.lock()?;
-
-                        *reader = stream.try_clone()?;
-                        reader.set_read_timeout(Some(TWS_READ_TIMEOUT))?;
-
-                        *writer = stream;
+            // a failed handshake, e.g. with the client id still in use, moves on to the next endpoint or attempt
+            for address in self.options.endpoints() {
+                match self.reconnect_to(address) {
+                    Ok(()) => {
+                        info!("reconnected !!!");
+                        return Ok(());
+                    }
+                    Err(Error::Shutdown) => return Err(Error::Shutdown),
+                    Err(e) => {
+                        error!("reconnection attempt {i} of {} to {address} failed: {e}", self.options.max_retries);
                     }
-
-                    info!("reconnected !!!");
-                    self.establish_connection()?;
-
-                    return Ok(());
-                }
-                Err(e) => {
-                    error!("reconnection attempt {i} of {} failed: {e}", self.max_retries);
                 }
             }
         }
@@ -861,6 +1646,22 @@ impl Connection {
         Err(Error::ConnectionFailed)
     }
 
+    // Flushes pending writes and closes the socket, which also unblocks the reader. Later writes fail with Error::Shutdown.
+    pub(crate) fn close(&self) {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        if let Ok(mut writer) = self.writer.lock() {
+            if let Err(e) = writer.flush() {
+                warn!("error flushing connection: {e}");
+            }
+            if let Err(e) = writer.shutdown() {
+                debug!("error closing connection: {e}");
+            }
+        }
+    }
+
     fn establish_connection(&self) -> Result<(), Error> {
         self.handshake()?;
         self.start_api()?;
@@ -875,38 +1676,103 @@ impl Connection {
     }
 
     fn write_message(&self, message: &RequestMessage) -> Result<(), Error> {
-        let mut writer = self.writer.lock()?;
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(Error::Shutdown);
+        }
 
-        let data = message.encode();
-        debug!("-> {data:?}");
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire();
+        }
 
-        let data = data.as_bytes();
+        let mut writer = self.writer.lock()?;
 
-        let mut packet = Vec::with_capacity(data.len() + 4);
+        let encoded = message.as_str();
+        debug!("-> {encoded:?}");
 
-        packet.write_u32::<BigEndian>(data.len() as u32)?;
-        packet.write_all(data)?;
+        // frames are assembled in a buffer reused across writes, guarded by the writer lock
+        let mut packet = self.write_buffer.lock()?;
+        packet.clear();
+        packet.write_u32::<BigEndian>(encoded.len() as u32)?;
+        packet.extend_from_slice(encoded.as_bytes());
 
-        writer.write_all(&packet)?;
+        match self.batches.lock()?.get_mut(&thread::current().id()) {
+            Some(batch) => batch.packets.extend_from_slice(&packet),
+            None => writer.write_all(&packet)?,
+        }
 
         self.recorder.record_request(message);
+        if let Some(observer) = &self.options.message_observer {
+            observer.observe(Direction::Outgoing, encoded);
+        }
+        if let Some(metrics) = &self.options.metrics {
+            metrics.request_sent(message.message_id());
+        }
 
         Ok(())
     }
 
-    fn read_message(&self) -> Response {
-        let mut reader = self.reader.lock()?;
+    pub(crate) fn begin_batch(&self) -> Result<(), Error> {
+        self.batches.lock()?.entry(thread::current().id()).or_default().depth += 1;
+        Ok(())
+    }
 
-        let message_size = read_header(&reader)?;
-        let mut data = vec![0_u8; message_size];
+    pub(crate) fn flush_batch(&self) -> Result<(), Error> {
+        let mut writer = self.writer.lock()?;
+        let mut batches = self.batches.lock()?;
+        match batches.get_mut(&thread::current().id()) {
+            Some(batch) => self.write_batch(writer.as_mut(), &mut batch.packets),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn end_batch(&self) -> Result<(), Error> {
+        let mut writer = self.writer.lock()?;
+        let mut batches = self.batches.lock()?;
+        let thread_id = thread::current().id();
 
-        reader.read_exact(&mut data)?;
+        let Some(batch) = batches.get_mut(&thread_id) else {
+            return Ok(());
+        };
+        batch.depth -= 1;
+        if batch.depth > 0 {
+            return Ok(());
+        }
 
-        let raw_string = String::from_utf8(data)?;
+        let mut batch = batches.remove(&thread_id).unwrap_or_default();
+        self.write_batch(writer.as_mut(), &mut batch.packets)
+    }
+
+    fn write_batch(&self, writer: &mut dyn TransportStream, packets: &mut Vec<u8>) -> Result<(), Error> {
+        if packets.is_empty() {
+            return Ok(());
+        }
+
+        if self.closed.load(Ordering::SeqCst) {
+            packets.clear();
+            return Err(Error::Shutdown);
+        }
+
+        let result = writer.write_all(packets).and_then(|_| writer.flush());
+        packets.clear();
+        Ok(result?)
+    }
+
+    fn read_message(&self) -> Response {
+        let mut reader = self.reader.lock()?;
+
+        let raw_string = read_packet(reader.as_mut(), &self.read_pool)?;
         debug!("<- {:?}", raw_string);
 
-        let message = ResponseMessage::from(&raw_string);
+        if let Some(observer) = &self.options.message_observer {
+            observer.observe(Direction::Incoming, &raw_string);
+        }
+
+        let message = ResponseMessage::from_pooled(raw_string, Arc::clone(&self.read_pool));
         self.recorder.record_response(&message);
+        if let Some(metrics) = &self.options.metrics {
+            metrics.message_received(message.message_type() as i32);
+            metrics.buffer_pool(self.read_pool.stats());
+        }
 
         Ok(message)
     }
@@ -914,7 +1780,7 @@ impl Connection {
     // sends server handshake
     fn handshake(&self) -> Result<(), Error> {
         let prefix = "API\0";
-        let version = format!("v{MIN_SERVER_VERSION}..{MAX_SERVER_VERSION}");
+        let version = supported_versions(&self.options.connect_options);
 
         let packet = prefix.to_owned() + &encode_packet(&version);
         self.write(&packet)?;
@@ -925,10 +1791,19 @@ impl Connection {
 
         match ack {
             Ok(mut response) => {
-                connection_metadata.server_version = response.next_int()?;
+                let server_version = response.next_int()?;
+                if server_version < MIN_SERVER_VERSION {
+                    return Err(Error::ServerVersion(
+                        MIN_SERVER_VERSION,
+                        server_version,
+                        "TWS or Gateway is too old for this client.".into(),
+                    ));
+                }
+                connection_metadata.server_version = server_version;
 
                 let time = response.next_string()?;
                 (connection_metadata.connection_time, connection_metadata.time_zone) = parse_connection_time(time.as_str());
+                connection_metadata.raw_connection_time = time;
             }
             Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
                 return Err(Error::Simple(format!("The server may be rejecting connections from this host: {err}")));
@@ -948,13 +1823,17 @@ impl Connection {
 
         prelude.push_field(&OutgoingMessages::StartApi);
         prelude.push_field(&VERSION);
-        prelude.push_field(&self.client_id);
+        prelude.push_field(&self.client_id());
 
-        if self.server_version() > server_versions::OPTIONAL_CAPABILITIES {
-            prelude.push_field(&"");
-        }
+        let optional_capabilities = if self.server_version() > server_versions::OPTIONAL_CAPABILITIES {
+            prelude.push_field(&self.options.optional_capabilities);
+            Some(self.options.optional_capabilities.clone())
+        } else {
+            None
+        };
 
         self.write_message(prelude)?;
+        self.connection_metadata.lock()?.optional_capabilities = optional_capabilities;
 
         Ok(())
     }
@@ -964,6 +1843,11 @@ impl Connection {
         connection_metadata.server_version
     }
 
+    fn client_id(&self) -> i32 {
+        let connection_metadata = self.connection_metadata.lock().unwrap();
+        connection_metadata.client_id
+    }
+
     // Fetches next order id and managed accounts.
     fn receive_account_info(&self) -> Result<(), Error> {
         let mut saw_next_order_id: bool = false;
@@ -993,6 +1877,9 @@ impl Connection {
                     let mut connection_metadata = self.connection_metadata.lock()?;
                     connection_metadata.managed_accounts = message.next_string()?;
                 }
+                IncomingMessages::Error if Notice::from(&message).code == CLIENT_ID_IN_USE => {
+                    return Err(Error::ClientIdInUse(self.client_id()));
+                }
                 IncomingMessages::Error => {
                     error!("message: {message:?}")
                 }
@@ -1038,16 +1925,21 @@ impl FibonacciBackoff {
 }
 
 // Parses following format: 20230405 22:20:39 PST
+// Time zones may contain spaces, e.g. 20230405 22:20:39 China Standard Time
 fn parse_connection_time(connection_time: &str) -> (Option<OffsetDateTime>, Option<&'static Tz>) {
-    let parts: Vec<&str> = connection_time.split(' ').collect();
-
-    let zones = timezones::find_by_name(parts[2]);
-    if zones.is_empty() {
-        error!("time zone not found for {}", parts[2]);
+    let parts: Vec<&str> = connection_time.splitn(3, ' ').collect();
+    if parts.len() < 3 {
+        error!("could not parse connection time from {connection_time}");
         return (None, None);
     }
 
-    let timezone = zones[0];
+    let timezone = match parse_time_zone(parts[2]) {
+        Ok(timezone) => timezone,
+        Err(err) => {
+            error!("{err}");
+            return (None, None);
+        }
+    };
 
     let format = format_description!("[year][month][day] [hour]:[minute]:[second]");
     let date_str = format!("{} {}", parts[0], parts[1]);
@@ -1067,6 +1959,16 @@ fn parse_connection_time(connection_time: &str) -> (Option<OffsetDateTime>, Opti
     }
 }
 
+// Client versions advertised in the handshake, followed by optional connection options, e.g. v100..165 +PACEAPI
+fn supported_versions(connect_options: &str) -> String {
+    let connect_options = connect_options.trim();
+    if connect_options.is_empty() {
+        format!("v{MIN_SERVER_VERSION}..{MAX_SERVER_VERSION}")
+    } else {
+        format!("v{MIN_SERVER_VERSION}..{MAX_SERVER_VERSION} {connect_options}")
+    }
+}
+
 fn encode_packet(message: &str) -> String {
     let data = message.as_bytes();
 
@@ -0,0 +1,66 @@
+//! Pool of the buffers messages from TWS are read into, so high message rates do not allocate a buffer for each message.
+//!
+//! Buffers are returned to the pool when the [ResponseMessage](crate::messages::ResponseMessage) holding them is dropped.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::client::BufferPoolStats;
+
+#[cfg(test)]
+mod tests;
+
+// Idle buffers kept for reuse.
+const MAX_IDLE_BUFFERS: usize = 64;
+// Buffers grown past this capacity by large messages are released rather than kept.
+const MAX_POOLED_CAPACITY: usize = 64 * 1024;
+
+#[derive(Debug, Default)]
+pub(crate) struct BufferPool {
+    idle: Mutex<Vec<Vec<u8>>>,
+    reused: AtomicU64,
+    allocated: AtomicU64,
+}
+
+impl BufferPool {
+    // Returns a buffer of `length` zeroed bytes, reusing an idle buffer if one is available.
+    pub(crate) fn take(&self, length: usize) -> Vec<u8> {
+        let idle = self.idle.lock().ok().and_then(|mut idle| idle.pop());
+
+        let mut buffer = match idle {
+            Some(buffer) => {
+                self.reused.fetch_add(1, Ordering::Relaxed);
+                buffer
+            }
+            None => {
+                self.allocated.fetch_add(1, Ordering::Relaxed);
+                Vec::new()
+            }
+        };
+
+        buffer.resize(length, 0);
+        buffer
+    }
+
+    // Keeps `buffer` for reuse, unless the pool is full or the buffer is too large to keep.
+    pub(crate) fn give(&self, mut buffer: Vec<u8>) {
+        if buffer.capacity() == 0 || buffer.capacity() > MAX_POOLED_CAPACITY {
+            return;
+        }
+        buffer.clear();
+
+        if let Ok(mut idle) = self.idle.lock() {
+            if idle.len() < MAX_IDLE_BUFFERS {
+                idle.push(buffer);
+            }
+        }
+    }
+
+    pub(crate) fn stats(&self) -> BufferPoolStats {
+        BufferPoolStats {
+            reused: self.reused.load(Ordering::Relaxed),
+            allocated: self.allocated.load(Ordering::Relaxed),
+            idle: self.idle.lock().map(|idle| idle.len()).unwrap_or_default(),
+        }
+    }
+}
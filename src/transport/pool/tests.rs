@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn reuses_returned_buffers() {
+    let pool = BufferPool::default();
+
+    let buffer = pool.take(16);
+    assert_eq!(buffer, vec![0; 16]);
+    pool.give(buffer);
+    assert_eq!(pool.stats().idle, 1, "idle after give");
+
+    let mut buffer = pool.take(8);
+    assert_eq!(buffer, vec![0; 8], "reused buffer is zeroed");
+    assert!(buffer.capacity() >= 16, "capacity kept");
+    buffer[0] = 1;
+    pool.give(buffer);
+
+    assert_eq!(pool.take(4), vec![0; 4]);
+    assert_eq!(
+        pool.stats(),
+        BufferPoolStats {
+            reused: 2,
+            allocated: 1,
+            idle: 0
+        }
+    );
+}
+
+#[test]
+fn releases_large_and_surplus_buffers() {
+    let pool = BufferPool::default();
+
+    pool.give(vec![0; MAX_POOLED_CAPACITY + 1]);
+    pool.give(Vec::new());
+    assert_eq!(pool.stats().idle, 0, "large and empty buffers");
+
+    for _ in 0..MAX_IDLE_BUFFERS + 10 {
+        pool.give(vec![0; 16]);
+    }
+    assert_eq!(pool.stats().idle, MAX_IDLE_BUFFERS, "surplus buffers");
+}
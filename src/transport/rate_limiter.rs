@@ -0,0 +1,57 @@
+//! Limits the rate of messages sent to TWS, which disconnects clients sending more than 50 messages per second.
+//!
+//! Keeps the send times of the last `limit` messages: each message reserves the next send slot at which fewer than `limit` messages
+//! were sent in the preceding second. An idle client may send a burst of up to `limit` messages, which is then counted against the
+//! following second, so no more than `limit` messages are sent in any second. Callers are served in the order they reserve slots.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: usize,
+    // Send slots of the last `limit` messages, oldest first.
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    // Creates a limiter allowing `limit` messages per second.
+    pub(crate) fn new(limit: u32) -> Self {
+        let limit = limit.max(1) as usize;
+
+        RateLimiter {
+            limit,
+            sent: Mutex::new(VecDeque::with_capacity(limit)),
+        }
+    }
+
+    // Blocks until a message may be sent.
+    pub(crate) fn acquire(&self) {
+        let delay = self.reserve(Instant::now());
+        if !delay.is_zero() {
+            thread::sleep(delay);
+        }
+    }
+
+    // Reserves a send slot at or after `now`, returning how long to wait for it.
+    pub(crate) fn reserve(&self, now: Instant) -> Duration {
+        let mut sent = self.sent.lock().unwrap();
+
+        let mut slot = sent.back().map_or(now, |last| (*last).max(now));
+        if sent.len() == self.limit {
+            if let Some(oldest) = sent.pop_front() {
+                slot = slot.max(oldest + WINDOW);
+            }
+        }
+        sent.push_back(slot);
+
+        slot.saturating_duration_since(now)
+    }
+}
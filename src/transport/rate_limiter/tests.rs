@@ -0,0 +1,62 @@
+use super::*;
+
+#[test]
+fn allows_bursts_up_to_limit() {
+    let limiter = RateLimiter::new(50);
+    let now = Instant::now();
+
+    for i in 0..50 {
+        assert_eq!(limiter.reserve(now), Duration::ZERO, "message {i}");
+    }
+
+    assert_eq!(limiter.reserve(now), Duration::from_secs(1), "message 50");
+    assert_eq!(limiter.reserve(now), Duration::from_secs(1), "message 51");
+}
+
+#[test]
+fn sends_at_most_limit_in_any_second() {
+    let limiter = RateLimiter::new(50);
+    let now = Instant::now();
+
+    let mut slots = vec![];
+    for i in 0..200 {
+        let requested = now + Duration::from_millis(i * 7);
+        slots.push(requested + limiter.reserve(requested));
+    }
+
+    for (i, window) in slots.windows(51).enumerate() {
+        assert!(
+            window[50] - window[0] >= Duration::from_secs(1),
+            "messages {i}..={} sent within a second",
+            i + 50
+        );
+    }
+}
+
+#[test]
+fn refills_over_time() {
+    let limiter = RateLimiter::new(10);
+    let now = Instant::now();
+
+    for _ in 0..10 {
+        limiter.reserve(now);
+    }
+    assert_eq!(limiter.reserve(now), Duration::from_secs(1));
+
+    let later = now + Duration::from_secs(2);
+    for i in 0..10 {
+        assert_eq!(limiter.reserve(later), Duration::ZERO, "message {i} after refill");
+    }
+}
+
+#[test]
+fn acquire_waits_for_slot() {
+    let limiter = RateLimiter::new(20);
+    let started = Instant::now();
+
+    for _ in 0..40 {
+        limiter.acquire();
+    }
+
+    assert!(started.elapsed() >= Duration::from_secs(1), "{:?}", started.elapsed());
+}
@@ -58,7 +58,7 @@ impl MessageRecorder {
         }
 
         let record_id = RECORDING_SEQ.fetch_add(1, Ordering::SeqCst);
-        fs::write(self.request_file(record_id), message.encode().replace('\0', "|")).unwrap();
+        fs::write(self.request_file(record_id), message.as_str().replace('\0', "|")).unwrap();
     }
 
     pub fn record_response(&self, message: &ResponseMessage) {
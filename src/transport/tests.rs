@@ -1,6 +1,11 @@
+use std::net::TcpStream;
+
 use time::macros::datetime;
 use time_tz::{timezones, OffsetResult, PrimitiveDateTimeExt};
 
+use crate::client::stream::MemoryTransport;
+use crate::client::{BufferPoolStats, DataFarm, DataFarmStatus, NoticeFilter, RequestOutcome};
+use crate::messages::Notice;
 use crate::tests::assert_send_and_sync;
 
 use super::*;
@@ -22,6 +27,21 @@ fn test_parse_connection_time() {
     }
 }
 
+#[test]
+fn test_parse_malformed_connection_time() {
+    assert_eq!(parse_connection_time("20230405"), (None, None));
+    assert_eq!(parse_connection_time("20230405 22:20:39 Nowhere/Unknown"), (None, None));
+}
+
+#[test]
+fn test_supported_versions() {
+    assert_eq!(supported_versions(""), format!("v{MIN_SERVER_VERSION}..{MAX_SERVER_VERSION}"));
+    assert_eq!(
+        supported_versions(" +PACEAPI "),
+        format!("v{MIN_SERVER_VERSION}..{MAX_SERVER_VERSION} +PACEAPI")
+    );
+}
+
 #[test]
 fn test_fibonacci_backoff() {
     let mut backoff = FibonacciBackoff::new(10);
@@ -34,3 +54,1232 @@ fn test_fibonacci_backoff() {
     assert_eq!(backoff.next_delay(), Duration::from_secs(10));
     assert_eq!(backoff.next_delay(), Duration::from_secs(10));
 }
+
+fn read_packet(stream: &mut impl Read) -> String {
+    let size = stream.read_u32::<BigEndian>().unwrap() as usize;
+    let mut data = vec![0_u8; size];
+    stream.read_exact(&mut data).unwrap();
+    String::from_utf8(data).unwrap()
+}
+
+fn write_packet(stream: &mut impl Write, fields: &str) {
+    let data = fields.replace('|', "\0");
+    stream.write_u32::<BigEndian>(data.len() as u32).unwrap();
+    stream.write_all(data.as_bytes()).unwrap();
+}
+
+// Accepts a connection and completes the handshake of a minimal TWS server.
+fn accept_client(listener: &std::net::TcpListener, next_order_id: i32) -> TcpStream {
+    let (mut stream, _) = listener.accept().unwrap();
+    complete_handshake(&mut stream, next_order_id);
+    stream
+}
+
+fn complete_handshake(stream: &mut (impl Read + Write), next_order_id: i32) {
+    let mut prefix = [0_u8; 4];
+    stream.read_exact(&mut prefix).unwrap();
+    read_packet(stream); // supported versions
+    write_packet(stream, "173|20230405 22:20:39 PST|");
+
+    read_packet(stream); // start api
+    write_packet(stream, &format!("9|1|{next_order_id}|"));
+    write_packet(stream, "15|1|DU1234|");
+}
+
+#[test]
+fn test_connect_with_options() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+
+        let mut prefix = [0_u8; 4];
+        stream.read_exact(&mut prefix).unwrap();
+        assert_eq!(&prefix, b"API\0", "prefix");
+
+        let versions = read_packet(&mut stream);
+        write_packet(&mut stream, "173|20230405 22:20:39 PST|");
+
+        let start_api = read_packet(&mut stream);
+        write_packet(&mut stream, "9|1|90|");
+        write_packet(&mut stream, "15|1|DU1234,DU5678|");
+
+        (versions, start_api)
+    });
+
+    let mut options = ConnectionOptions::new(&address, 7);
+    options.connect_options = "+PACEAPI".into();
+    options.optional_capabilities = "E".into();
+    options.connect_timeout = Some(Duration::from_secs(5));
+    options.read_timeout = Duration::from_millis(500);
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+    let metadata = connection.connection_metadata();
+
+    let (versions, start_api) = server.join().unwrap();
+    assert_eq!(
+        versions,
+        format!("v{MIN_SERVER_VERSION}..{MAX_SERVER_VERSION} +PACEAPI"),
+        "supported versions"
+    );
+    assert_eq!(start_api, "71\x002\x007\x00E\x00", "start api");
+    assert_eq!(metadata.client_id, 7, "client_id");
+    assert_eq!(metadata.raw_connection_time, "20230405 22:20:39 PST", "raw_connection_time");
+    assert_eq!(metadata.optional_capabilities.as_deref(), Some("E"), "optional_capabilities");
+    assert_eq!(metadata.managed_accounts, "DU1234,DU5678", "managed_accounts");
+    assert_eq!(
+        connection.reader.lock().unwrap().read_timeout().unwrap(),
+        Some(Duration::from_millis(500))
+    );
+}
+
+#[test]
+fn test_connects_over_custom_transport() {
+    let transport = MemoryTransport::new();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server_transport = transport.clone();
+    let server = thread::spawn(move || {
+        let mut stream = server_transport.accept();
+        complete_handshake(&mut stream, 90);
+
+        let request = read_packet(&mut stream);
+        write_packet(&mut stream, "49|1|1678323335|");
+        done_recv.recv().unwrap();
+        request
+    });
+
+    let mut options = ConnectionOptions::new("tws.internal:4002", 100);
+    options.transport = SharedTransport::new(transport);
+    options.read_timeout = Duration::from_millis(50);
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+    assert_eq!(connection.server_version(), 173, "server version");
+    assert_eq!(connection.endpoint(), "tws.internal:4002", "endpoint");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestCurrentTime);
+    request.push_field(&1);
+    let subscription = message_bus.send_shared_request(OutgoingMessages::RequestCurrentTime, &request).unwrap();
+
+    let response = subscription.next_timeout(Duration::from_secs(5)).expect("no response").unwrap();
+    assert_eq!(response.peek_str(2), "1678323335", "current time");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    assert_eq!(server.join().unwrap(), "49\x001\x00", "request");
+}
+
+#[test]
+fn test_next_within() {
+    let (sender, receiver) = channel::unbounded();
+    let (signaler, _) = channel::unbounded();
+    let subscription = SubscriptionBuilder::new().receiver(receiver).signaler(signaler).request_id(9000).build();
+
+    assert!(matches!(
+        subscription.next_within(Some(Duration::from_millis(10))),
+        Some(Err(Error::Timeout))
+    ));
+
+    sender.send(Err(Error::Cancelled)).unwrap();
+    assert!(matches!(subscription.next_within(None), Some(Err(Error::Cancelled))));
+
+    drop(sender);
+    assert!(subscription.next_within(Some(Duration::from_millis(10))).is_none(), "disconnected");
+}
+
+#[test]
+#[cfg(feature = "historical")]
+fn test_request_timeout_cancels_request() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let (done_send, done_recv) = channel::unbounded();
+    let (packets_send, packets_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+
+        packets_send.send(read_packet(&mut stream)).unwrap(); // request
+        packets_send.send(read_packet(&mut stream)).unwrap(); // cancel
+
+        done_recv.recv().unwrap();
+    });
+
+    let client = crate::Client::connect(&address, 100).expect("connection failed");
+
+    let result = client.with_timeout(Duration::from_millis(100)).head_timestamp(
+        &crate::contracts::Contract::stock("MSFT"),
+        crate::market_data::historical::WhatToShow::Trades,
+        true,
+    );
+    assert!(matches!(result, Err(Error::Timeout)), "{result:?}");
+
+    let request = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    let cancel = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(request.starts_with("87\x009000\x00"), "{request:?}");
+    assert_eq!(cancel, "90\x009000\x00", "cancel head timestamp");
+
+    drop(client);
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_discards_late_reply_to_timed_out_request() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let (timed_out_send, timed_out_recv) = channel::unbounded();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+
+        read_packet(&mut stream); // request
+        timed_out_recv.recv().unwrap();
+        write_packet(&mut stream, "49|1|1678323335|"); // late reply
+
+        read_packet(&mut stream); // request
+        write_packet(&mut stream, "49|1|1678323400|");
+
+        done_recv.recv().unwrap();
+    });
+
+    let client = crate::Client::connect(&address, 100).expect("connection failed");
+
+    let result = client.with_timeout(Duration::from_millis(100)).server_time();
+    assert!(matches!(result, Err(Error::Timeout)), "{result:?}");
+    timed_out_send.send(()).unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    let server_time = client
+        .with_timeout(Duration::from_secs(5))
+        .server_time()
+        .expect("error requesting server time");
+    assert_eq!(server_time.unix_timestamp(), 1678323400, "fresh reply");
+
+    drop(client);
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+#[cfg(feature = "market-data")]
+fn test_cancel_handle_wakes_waiting_subscription() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let (done_send, done_recv) = channel::unbounded();
+    let (packets_send, packets_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+
+        packets_send.send(read_packet(&mut stream)).unwrap(); // request
+        packets_send.send(read_packet(&mut stream)).unwrap(); // cancel
+
+        done_recv.recv().unwrap();
+    });
+
+    let client = crate::Client::connect(&address, 100).expect("connection failed");
+
+    let subscription = client
+        .realtime_bars(
+            &crate::contracts::Contract::stock("MSFT"),
+            crate::market_data::realtime::BarSize::Sec5,
+            crate::market_data::realtime::WhatToShow::Trades,
+            false,
+        )
+        .expect("error requesting realtime bars");
+
+    let cancel_handle = subscription.cancel_handle();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        cancel_handle.cancel();
+    });
+
+    assert!(subscription.next().is_none(), "next bar");
+    assert!(matches!(subscription.error(), Some(Error::Cancelled)), "{:?}", subscription.error());
+    canceller.join().unwrap();
+
+    let request = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    let cancel = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(request.starts_with("50\x00"), "{request:?}");
+    assert_eq!(cancel, "51\x001\x009000\x00", "cancel realtime bars");
+
+    drop(subscription);
+    drop(client);
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_cancel_handle_wakes_waiting_shared_subscription() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let (done_send, done_recv) = channel::unbounded();
+    let (packets_send, packets_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+
+        packets_send.send(read_packet(&mut stream)).unwrap(); // request
+        packets_send.send(read_packet(&mut stream)).unwrap(); // cancel
+
+        done_recv.recv().unwrap();
+    });
+
+    let client = crate::Client::connect(&address, 100).expect("connection failed");
+
+    let subscription = client.positions().expect("error requesting positions");
+
+    let cancel_handle = subscription.cancel_handle();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        cancel_handle.cancel();
+    });
+
+    assert!(subscription.next().is_none(), "next position");
+    assert!(matches!(subscription.error(), Some(Error::Cancelled)), "{:?}", subscription.error());
+    canceller.join().unwrap();
+
+    let request = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    let cancel = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(request, "61\x001\x00", "request positions");
+    assert_eq!(cancel, "64\x001\x00", "cancel positions");
+
+    drop(subscription);
+    drop(client);
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+// Accepts a connection and rejects it as TWS does when the client id is in use. Returns the start api message.
+fn reject_client_id(listener: &std::net::TcpListener) -> String {
+    let (mut stream, _) = listener.accept().unwrap();
+
+    let mut prefix = [0_u8; 4];
+    stream.read_exact(&mut prefix).unwrap();
+    read_packet(&mut stream); // supported versions
+    write_packet(&mut stream, "173|20230405 22:20:39 PST|");
+
+    let start_api = read_packet(&mut stream);
+    write_packet(
+        &mut stream,
+        "4|2|-1|326|Unable to connect as the client id is already in use. Retry with a unique client id.|",
+    );
+
+    start_api
+}
+
+#[test]
+fn test_retries_client_id_in_use() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let rejected = reject_client_id(&listener);
+        let _stream = accept_client(&listener, 90);
+
+        let _ = reject_client_id(&listener);
+
+        done_recv.recv().unwrap();
+        rejected
+    });
+
+    let client = crate::Client::builder()
+        .port(port)
+        .client_id_range(100..=102)
+        .connect()
+        .expect("connection failed");
+    assert_eq!(client.client_id(), 101, "client id");
+
+    let result = crate::Client::builder()
+        .port(port)
+        .client_id(100)
+        .retry_policy(crate::client::RetryPolicy::never())
+        .connect();
+    assert!(matches!(result, Err(Error::ClientIdInUse(100))), "{result:?}");
+
+    drop(client);
+    done_send.send(()).unwrap();
+    assert_eq!(server.join().unwrap(), "71\x002\x00100\x00\x00", "start api with first client id");
+}
+
+#[test]
+fn test_reconnects_stale_connection() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+    let (packets_send, packets_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        // half-open connection: reads requests but never responds
+        let mut stale = accept_client(&listener, 90);
+        packets_send.send(read_packet(&mut stale)).unwrap(); // keepalive
+
+        let _stream = accept_client(&listener, 95);
+        packets_send.send("reconnected".to_string()).unwrap();
+
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new(&address, 100);
+    options.read_timeout = Duration::from_millis(50);
+    options.keepalive_interval = Some(Duration::from_millis(100));
+    options.stale_timeout = Some(Duration::from_millis(400));
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let events = message_bus.subscribe_events(EventChannel::All).unwrap();
+
+    let keepalive = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(keepalive, "49\x001\x00", "keepalive");
+
+    assert!(matches!(events.next_timeout(Duration::from_secs(5)), Some(Err(Error::Disconnected))));
+    assert_eq!(packets_recv.recv_timeout(Duration::from_secs(5)).unwrap(), "reconnected");
+    assert!(matches!(events.next_timeout(Duration::from_secs(5)), Some(Err(Error::ConnectionReset))));
+    assert_eq!(message_bus.health().reconnects, 1, "reconnects");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_dispatcher_consumes_keepalive_reply() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+    let (packets_send, packets_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+        packets_send.send(read_packet(&mut stream)).unwrap(); // keepalive
+
+        packets_send.send(read_packet(&mut stream)).unwrap(); // request
+        write_packet(&mut stream, "49|1|1678323335|"); // keepalive reply
+        write_packet(&mut stream, "49|1|1678323400|");
+
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new(&address, 100);
+    options.read_timeout = Duration::from_millis(50);
+    options.keepalive_interval = Some(Duration::from_millis(100));
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let keepalive = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(keepalive, "49\x001\x00", "keepalive");
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestCurrentTime);
+    request.push_field(&1);
+    let subscription = message_bus.send_shared_request(OutgoingMessages::RequestCurrentTime, &request).unwrap();
+    assert_eq!(packets_recv.recv_timeout(Duration::from_secs(5)).unwrap(), "49\x001\x00", "request");
+
+    let response = subscription.next_timeout(Duration::from_secs(5)).expect("no response").unwrap();
+    assert_eq!(response.peek_str(2), "1678323400", "current time");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_fails_over_to_next_endpoint() {
+    let primary = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let backup = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let primary_address = primary.local_addr().unwrap().to_string();
+    let backup_address = backup.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        // primary goes away after the client connects
+        drop(accept_client(&primary, 90));
+        drop(primary);
+
+        let _stream = accept_client(&backup, 95);
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new(&primary_address, 100);
+    options.failover_addresses = vec![backup_address.clone()];
+    options.read_timeout = Duration::from_millis(50);
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+    assert_eq!(connection.endpoint(), primary_address, "initial endpoint");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    let events = message_bus.subscribe_events(EventChannel::All).unwrap();
+    message_bus.process_messages().unwrap();
+
+    assert!(matches!(events.next_timeout(Duration::from_secs(5)), Some(Err(Error::Disconnected))));
+    assert!(matches!(events.next_timeout(Duration::from_secs(5)), Some(Err(Error::ConnectionReset))));
+    match events.next_timeout(Duration::from_secs(5)) {
+        Some(Err(Error::Failover(address))) => assert_eq!(address, backup_address, "failover endpoint"),
+        event => panic!("expected failover event, got {event:?}"),
+    }
+    assert_eq!(message_bus.connection.endpoint(), backup_address, "active endpoint");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_fails_over_when_reconnect_is_rejected() {
+    let primary = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let backup = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let primary_address = primary.local_addr().unwrap().to_string();
+    let backup_address = backup.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        // primary drops the client, then rejects it while the old session is still registered
+        drop(accept_client(&primary, 90));
+        reject_client_id(&primary);
+
+        let (mut stream, _) = backup.accept().unwrap();
+        let mut prefix = [0_u8; 4];
+        stream.read_exact(&mut prefix).unwrap();
+        read_packet(&mut stream); // supported versions
+        write_packet(&mut stream, "176|20230405 22:20:39 PST|");
+        read_packet(&mut stream); // start api
+        write_packet(&mut stream, "9|1|95|");
+        write_packet(&mut stream, "15|1|DU1234|");
+
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new(&primary_address, 100);
+    options.failover_addresses = vec![backup_address.clone()];
+    options.read_timeout = Duration::from_millis(50);
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+    assert_eq!(connection.server_version(), 173, "initial server version");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    let events = message_bus.subscribe_events(EventChannel::All).unwrap();
+    message_bus.process_messages().unwrap();
+
+    assert!(matches!(events.next_timeout(Duration::from_secs(5)), Some(Err(Error::Disconnected))));
+    assert!(matches!(events.next_timeout(Duration::from_secs(5)), Some(Err(Error::ConnectionReset))));
+    assert_eq!(message_bus.connection.endpoint(), backup_address, "active endpoint");
+    assert_eq!(message_bus.connection.server_version(), 176, "server version after failover");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_rejects_oversized_message() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+        stream.write_u32::<BigEndian>(0x1000000).unwrap();
+        done_recv.recv().unwrap();
+    });
+
+    let connection = Connection::connect_with_options(ConnectionOptions::new(&address, 100)).expect("connection failed");
+
+    let result = connection.read_message();
+    assert!(matches!(result, Err(Error::MessageTooLong(0x1000000))), "{result:?}");
+
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_disconnect_closes_connection() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+        read_packet(&mut stream); // request
+
+        // reads end when the client closes the socket
+        let mut buffer = Vec::new();
+        stream.read_to_end(&mut buffer).unwrap();
+        buffer.len()
+    });
+
+    let client = crate::Client::connect(&address, 100).expect("connection failed");
+
+    let mut request = RequestMessage::default();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    let subscription = client.message_bus.send_request(9000, &request).unwrap();
+
+    client.disconnect();
+
+    assert_eq!(server.join().unwrap(), 0, "bytes received after disconnect");
+    assert!(matches!(subscription.next(), Some(Err(Error::Shutdown))), "subscription end");
+    assert!(
+        matches!(client.message_bus.send_request(9001, &request), Err(Error::Shutdown)),
+        "request after disconnect"
+    );
+}
+
+#[test]
+fn test_message_observer() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let server = thread::spawn(move || accept_client(&listener, 90));
+
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let messages = Arc::clone(&observed);
+
+    let mut options = ConnectionOptions::new(&address, 100);
+    options.message_observer = Some(SharedObserver::new(move |message: &crate::client::WireMessage| {
+        messages.lock().unwrap().push((message.direction, message.raw.replace('\0', "|")));
+    }));
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+    let _stream = server.join().unwrap();
+
+    let observed = observed.lock().unwrap();
+    assert_eq!(
+        *observed,
+        vec![
+            (Direction::Incoming, "173|20230405 22:20:39 PST|".to_string()),
+            (Direction::Outgoing, "71|2|100||".to_string()),
+            (Direction::Incoming, "9|1|90|".to_string()),
+            (Direction::Incoming, "15|1|DU1234|".to_string()),
+        ]
+    );
+    drop(connection);
+}
+
+#[derive(Default)]
+struct RecordingMetrics {
+    sent: Mutex<Vec<i32>>,
+    received: Mutex<Vec<i32>>,
+    latencies: Mutex<Vec<i32>>,
+    pending: Mutex<Vec<usize>>,
+    buffer_pool: Mutex<BufferPoolStats>,
+}
+
+impl crate::client::Metrics for Arc<RecordingMetrics> {
+    fn request_sent(&self, message_type: i32) {
+        self.sent.lock().unwrap().push(message_type);
+    }
+
+    fn message_received(&self, message_type: i32) {
+        self.received.lock().unwrap().push(message_type);
+    }
+
+    fn request_latency(&self, message_type: i32, _latency: Duration) {
+        self.latencies.lock().unwrap().push(message_type);
+    }
+
+    fn pending_requests(&self, requests: usize, _orders: usize) {
+        self.pending.lock().unwrap().push(requests);
+    }
+
+    fn buffer_pool(&self, stats: BufferPoolStats) {
+        *self.buffer_pool.lock().unwrap() = stats;
+    }
+}
+
+#[test]
+fn test_metrics() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || fake_tws(listener, vec!["52|1|9000|"], done_recv));
+
+    let metrics = Arc::new(RecordingMetrics::default());
+
+    let mut options = ConnectionOptions::new(&address, 100);
+    options.metrics = Some(SharedMetrics::new(Arc::clone(&metrics)));
+
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let mut request = RequestMessage::default();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    request.push_field(&8);
+    request.push_field(&9000);
+
+    let subscription = message_bus.send_request(9000, &request).unwrap();
+    let end = subscription.next_timeout(Duration::from_secs(5)).expect("no response").unwrap();
+    assert_eq!(end.message_type(), IncomingMessages::ContractDataEnd);
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+
+    assert_eq!(*metrics.sent.lock().unwrap(), vec![71, 9], "sent");
+    assert_eq!(*metrics.received.lock().unwrap(), vec![-1, 9, 15, 52], "received");
+    assert_eq!(*metrics.latencies.lock().unwrap(), vec![9], "latencies");
+    assert_eq!(metrics.pending.lock().unwrap().first(), Some(&1), "pending requests");
+
+    let buffer_pool = *metrics.buffer_pool.lock().unwrap();
+    assert_eq!(buffer_pool.reused + buffer_pool.allocated, 4, "pooled buffers");
+    assert!(buffer_pool.reused > 0, "buffers reused: {buffer_pool:?}");
+}
+
+// Minimal TWS server that answers the first request.
+fn fake_tws(listener: std::net::TcpListener, responses: Vec<&'static str>, done: Receiver<()>) {
+    let mut stream = accept_client(&listener, 90);
+
+    read_packet(&mut stream); // request
+    for response in responses {
+        write_packet(&mut stream, response);
+    }
+
+    done.recv().unwrap();
+}
+
+#[test]
+fn test_dispatcher_routes_responses_by_request_id() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        fake_tws(
+            listener,
+            vec![
+                "52|1|9001|", // no recipient
+                "4|2|9000|2104|Market data farm connection is OK:usfarm|",
+                "52|1|9000|",
+            ],
+            done_recv,
+        )
+    });
+
+    let connection = Connection::connect_with_options(ConnectionOptions::new(&address, 100)).expect("connection failed");
+    let server_version = connection.server_version();
+    assert_eq!(server_version, 173, "server_version");
+    assert_eq!(connection.connection_metadata().next_order_id, 90, "next_order_id");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let mut request = RequestMessage::default();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    request.push_field(&8);
+    request.push_field(&9000);
+
+    let subscription = message_bus.send_request(9000, &request).unwrap();
+
+    let notice = subscription.next_timeout(Duration::from_secs(5)).expect("no notice").unwrap();
+    assert_eq!(notice.message_type(), IncomingMessages::Error);
+
+    let end = subscription.next_timeout(Duration::from_secs(5)).expect("no response").unwrap();
+    assert_eq!(end.message_type(), IncomingMessages::ContractDataEnd);
+    assert_eq!(end.peek_int(2).unwrap(), 9000, "request_id");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_dispatcher_broadcasts_unrouted_errors() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        fake_tws(
+            listener,
+            vec![
+                "4|2|-1|2104|Market data farm connection is OK:usfarm|",
+                "52|1|9001|",
+                "4|2|9002|300|Can't find EId with tickerId:9002|",
+            ],
+            done_recv,
+        )
+    });
+
+    let connection = Connection::connect_with_options(ConnectionOptions::new(&address, 100)).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let events = message_bus.subscribe_events(EventChannel::All).unwrap();
+
+    let mut request = RequestMessage::default();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    let _subscription = message_bus.send_request(9000, &request).unwrap();
+
+    let notice = events.next_timeout(Duration::from_secs(5)).expect("no notice").unwrap();
+    assert_eq!(notice.message_type(), IncomingMessages::Error);
+    assert_eq!(Notice::from(&notice).code, 2104);
+
+    let unrouted = events.next_timeout(Duration::from_secs(5)).expect("no unrouted error").unwrap();
+    assert_eq!(unrouted.message_type(), IncomingMessages::Error, "data for unknown requests is dropped");
+    assert_eq!(unrouted.peek_int(2).unwrap(), 9002, "request_id");
+
+    message_bus.ensure_shutdown();
+    assert!(matches!(events.next(), Some(Err(Error::Shutdown))), "shutdown notification");
+
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_resubscribes_streaming_requests_after_reconnect() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (done_send, done_recv) = channel::unbounded::<()>();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+        let request = read_packet(&mut stream);
+        write_packet(&mut stream, "52|1|9000|");
+        drop(stream); // connection lost
+
+        let mut stream = accept_client(&listener, 2000);
+        let resubscribed = read_packet(&mut stream);
+        write_packet(&mut stream, "52|1|9000|");
+
+        done_recv.recv().unwrap();
+        (request, resubscribed)
+    });
+
+    let connection = Connection::connect_with_options(ConnectionOptions::new(&address, 100)).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+    assert_eq!(message_bus.connection_order_id(), Some(90), "next order id");
+
+    let mut request = RequestMessage::default();
+    request.push_field(&OutgoingMessages::RequestMarketData);
+    request.push_field(&11);
+    request.push_field(&9000);
+
+    let subscription = message_bus.send_request(9000, &request).unwrap();
+
+    let timeout = Duration::from_secs(10);
+    assert!(matches!(subscription.next_timeout(timeout), Some(Ok(_))), "message before reconnect");
+    assert!(
+        matches!(subscription.next_timeout(timeout), Some(Err(Error::ConnectionReset))),
+        "connection reset notification"
+    );
+    assert!(matches!(subscription.next_timeout(timeout), Some(Ok(_))), "message after reconnect");
+    assert_eq!(message_bus.connection_order_id(), Some(2000), "next order id after reconnect");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+
+    let (request, resubscribed) = server.join().unwrap();
+    assert_eq!(request, resubscribed, "resubscribed request");
+}
+
+#[test]
+fn test_batches_writes() {
+    let transport = MemoryTransport::new();
+
+    let server_transport = transport.clone();
+    let server = thread::spawn(move || {
+        let mut stream = server_transport.accept();
+        complete_handshake(&mut stream, 90);
+        stream.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+        stream
+    });
+
+    let mut options = ConnectionOptions::new("tws.internal:4002", 100);
+    options.transport = SharedTransport::new(transport);
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+    let mut stream = server.join().unwrap();
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestCurrentTime);
+    request.push_field(&1);
+
+    connection.begin_batch().unwrap();
+    connection.write_message(&request).unwrap();
+    connection.begin_batch().unwrap();
+    connection.write_message(&request).unwrap();
+    connection.end_batch().unwrap();
+
+    let mut buffer = [0_u8; 4];
+    assert!(stream.read_exact(&mut buffer).is_err(), "requests written before the batch closed");
+
+    connection.end_batch().unwrap();
+    assert_eq!(read_packet(&mut stream), "49\x001\x00", "first request");
+    assert_eq!(read_packet(&mut stream), "49\x001\x00", "second request");
+
+    connection.begin_batch().unwrap();
+    connection.write_message(&request).unwrap();
+    connection.flush_batch().unwrap();
+    assert_eq!(read_packet(&mut stream), "49\x001\x00", "flushed request");
+    connection.end_batch().unwrap();
+
+    connection.write_message(&request).unwrap();
+    assert_eq!(read_packet(&mut stream), "49\x001\x00", "request after batch");
+
+    connection.begin_batch().unwrap();
+    thread::scope(|scope| {
+        scope.spawn(|| connection.write_message(&request).unwrap());
+    });
+    assert_eq!(read_packet(&mut stream), "49\x001\x00", "request of another thread");
+    connection.end_batch().unwrap();
+}
+
+#[test]
+fn test_batch_sends_requests_on_panic() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let (done_send, done_recv) = channel::unbounded();
+    let (packets_send, packets_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+
+        packets_send.send(read_packet(&mut stream)).unwrap(); // global cancel
+        packets_send.send(read_packet(&mut stream)).unwrap(); // request after batch
+
+        done_recv.recv().unwrap();
+    });
+
+    let client = crate::Client::connect(&address, 100).expect("connection failed");
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        client.batch(|client| -> Result<(), Error> {
+            client.global_cancel()?;
+            panic!("batch failed");
+        })
+    }));
+    assert!(result.is_err(), "batch should panic");
+
+    let request = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(request, "58\x001\x00", "global cancel");
+
+    client.global_cancel().unwrap();
+    let request = packets_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(request, "58\x001\x00", "request after batch");
+
+    drop(client);
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_health() {
+    let transport = MemoryTransport::new();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server_transport = transport.clone();
+    let server = thread::spawn(move || {
+        let mut stream = server_transport.accept();
+        complete_handshake(&mut stream, 90);
+
+        write_packet(&mut stream, "4|2|-1|2104|Market data farm connection is OK:usfarm|");
+        write_packet(&mut stream, "4|2|-1|2105|HMDS data farm connection is broken:ushmds|");
+        write_packet(&mut stream, "4|2|-1|2104|Market data farm connection is OK:usfarm.nj|");
+        write_packet(&mut stream, "4|2|-1|2103|Market data farm connection is broken:usfarm|");
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new("tws.internal:4002", 100);
+    options.transport = SharedTransport::new(transport);
+    options.read_timeout = Duration::from_millis(50);
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    let events = message_bus.subscribe_events(EventChannel::All).unwrap();
+    message_bus.process_messages().unwrap();
+
+    for _ in 0..4 {
+        events.next_timeout(Duration::from_secs(5)).expect("no notice").unwrap();
+    }
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    let _subscription = message_bus.send_request(9000, &request).unwrap();
+
+    let health = message_bus.health();
+    assert!(health.connected, "connected");
+    assert!(health.last_message_age < Duration::from_secs(5), "last message age");
+    assert_eq!(health.outstanding_requests, 1, "outstanding requests");
+    assert_eq!(health.reconnects, 0, "reconnects");
+    assert_eq!(
+        health.data_farms,
+        vec![
+            DataFarmState {
+                farm: DataFarm::MarketData,
+                name: "usfarm".into(),
+                status: DataFarmStatus::Disconnected,
+            },
+            DataFarmState {
+                farm: DataFarm::MarketData,
+                name: "usfarm.nj".into(),
+                status: DataFarmStatus::Connected,
+            },
+            DataFarmState {
+                farm: DataFarm::Historical,
+                name: "ushmds".into(),
+                status: DataFarmStatus::Disconnected,
+            },
+        ],
+        "data farms"
+    );
+
+    message_bus.ensure_shutdown();
+    assert!(!message_bus.health().connected, "connected after shutdown");
+
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_reports_lagged_subscription() {
+    let transport = MemoryTransport::new();
+    let (resume_send, resume_recv) = channel::unbounded();
+
+    let server_transport = transport.clone();
+    let server = thread::spawn(move || {
+        let mut stream = server_transport.accept();
+        complete_handshake(&mut stream, 90);
+
+        read_packet(&mut stream); // contract details request
+        for i in 1..=5 {
+            write_packet(&mut stream, &format!("4|2|9000|2104|notice {i}|"));
+        }
+        write_packet(&mut stream, "4|2|-1|2104|Market data farm connection is OK:usfarm|");
+
+        resume_recv.recv().unwrap();
+        write_packet(&mut stream, "4|2|9000|2104|notice 6|");
+        resume_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new("tws.internal:4002", 100);
+    options.transport = SharedTransport::new(transport);
+    options.read_timeout = Duration::from_millis(50);
+    options.subscription_capacity = Some(2);
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    let events = message_bus.subscribe_events(EventChannel::All).unwrap();
+    message_bus.process_messages().unwrap();
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    let subscription = message_bus.send_request(9000, &request).unwrap();
+
+    // notices are dispatched in order, so the request's notices were queued or dropped once the broadcast notice arrives
+    events.next_timeout(Duration::from_secs(5)).expect("no broadcast notice").unwrap();
+
+    let notice = |response: Response| Notice::from(&response.unwrap()).message;
+    assert_eq!(notice(subscription.try_next().expect("no notice")), "notice 1");
+    assert_eq!(notice(subscription.try_next().expect("no notice")), "notice 2");
+    assert!(subscription.try_next().is_none(), "notices beyond capacity were queued");
+
+    resume_send.send(()).unwrap();
+    let lagged = subscription.next_timeout(Duration::from_secs(5)).expect("no lagged notification");
+    assert!(matches!(lagged, Err(Error::Lagged(3))), "{lagged:?}");
+    assert_eq!(notice(subscription.next_timeout(Duration::from_secs(5)).expect("no notice")), "notice 6");
+
+    message_bus.ensure_shutdown();
+    resume_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_subscription_skips_dropped_messages() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (written_send, written_recv) = channel::unbounded();
+    let (resume_send, resume_recv) = channel::unbounded();
+
+    let server = thread::spawn(move || {
+        let mut stream = accept_client(&listener, 90);
+
+        read_packet(&mut stream); // positions request
+        for _ in 0..4 {
+            write_packet(&mut stream, "61|3|DU1234567|76792991|TSLA|STK||0.0|||NASDAQ|USD|TSLA|NMS|500|196.77|");
+        }
+        written_send.send(()).unwrap();
+
+        resume_recv.recv().unwrap();
+        write_packet(&mut stream, "62|1|");
+        resume_recv.recv().unwrap();
+    });
+
+    let client = crate::Client::builder()
+        .port(port)
+        .subscription_capacity(2)
+        .connect()
+        .expect("connection failed");
+
+    let subscription = client.positions().expect("error requesting positions");
+    written_recv.recv_timeout(Duration::from_secs(5)).unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    assert!(matches!(subscription.next(), Some(crate::accounts::PositionUpdate::Position(_))));
+    assert!(matches!(subscription.next(), Some(crate::accounts::PositionUpdate::Position(_))));
+
+    resume_send.send(()).unwrap();
+    assert!(matches!(subscription.next(), Some(crate::accounts::PositionUpdate::PositionEnd)));
+    assert!(subscription.error().is_none(), "{:?}", subscription.error());
+    assert_eq!(subscription.dropped_messages(), 2, "dropped messages");
+
+    drop(subscription);
+    drop(client);
+    resume_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_response_channel_drops_messages_beyond_capacity() {
+    let (sender, receiver) = response_channel(Some(2));
+
+    for _ in 0..3 {
+        sender.send(Err(Error::Timeout)).unwrap();
+    }
+    assert_eq!(receiver.len(), 2, "queued messages");
+
+    receiver.try_recv().unwrap().unwrap_err();
+    sender.send(Err(Error::Timeout)).unwrap();
+    assert!(matches!(receiver.try_recv().unwrap(), Err(Error::Timeout)), "queued before the gap");
+    assert!(
+        matches!(receiver.try_recv().unwrap(), Err(Error::Lagged(1))),
+        "gap reported once there is room"
+    );
+
+    drop(receiver);
+    assert!(sender.send(Err(Error::Timeout)).is_err(), "subscription dropped");
+}
+
+#[test]
+fn test_reset_lag() {
+    let (sender, receiver) = response_channel(Some(1));
+
+    sender.send(Err(Error::Cancelled)).unwrap();
+    sender.send(Err(Error::Cancelled)).unwrap(); // dropped
+    receiver.try_recv().unwrap().unwrap_err();
+
+    sender.reset_lag();
+    sender.send(Err(Error::Timeout)).unwrap();
+    assert!(matches!(receiver.try_recv().unwrap(), Err(Error::Timeout)), "lag reported after reset");
+}
+
+#[test]
+fn test_routes_info_notices_to_separate_channel() {
+    let transport = MemoryTransport::new();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server_transport = transport.clone();
+    let server = thread::spawn(move || {
+        let mut stream = server_transport.accept();
+        complete_handshake(&mut stream, 90);
+
+        write_packet(&mut stream, "4|2|-1|2104|Market data farm connection is OK:usfarm|");
+        write_packet(&mut stream, "4|2|-1|1100|Connectivity between IB and Trader Workstation has been lost.|");
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new("tws.internal:4002", 100);
+    options.transport = SharedTransport::new(transport);
+    options.read_timeout = Duration::from_millis(50);
+    options.notice_filter = NoticeFilter::data_farm_status();
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    let all = message_bus.subscribe_events(EventChannel::All).unwrap();
+    let errors = message_bus.subscribe_events(EventChannel::Errors).unwrap();
+    let info = message_bus.subscribe_events(EventChannel::Info).unwrap();
+    message_bus.process_messages().unwrap();
+
+    let code = |response: Option<Response>| Notice::from(&response.expect("no notice").unwrap()).code;
+    assert_eq!(code(all.next_timeout(Duration::from_secs(5))), 2104, "all");
+    assert_eq!(code(all.next_timeout(Duration::from_secs(5))), 1100, "all");
+    assert_eq!(code(errors.next_timeout(Duration::from_secs(5))), 1100, "errors");
+    assert_eq!(code(info.next_timeout(Duration::from_secs(5))), 2104, "info");
+    assert!(errors.try_next().is_none(), "info notice on error channel");
+    assert!(info.try_next().is_none(), "error notice on info channel");
+
+    message_bus.ensure_shutdown();
+    assert!(matches!(errors.try_next(), Some(Err(Error::Shutdown))), "shutdown on error channel");
+    assert!(matches!(info.try_next(), Some(Err(Error::Shutdown))), "shutdown on info channel");
+
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn test_journals_requests() {
+    let transport = MemoryTransport::new();
+    let (done_send, done_recv) = channel::unbounded();
+
+    let server_transport = transport.clone();
+    let server = thread::spawn(move || {
+        let mut stream = server_transport.accept();
+        complete_handshake(&mut stream, 90);
+
+        read_packet(&mut stream);
+        write_packet(&mut stream, "4|2|9000|200|No security definition has been found for the request|");
+        read_packet(&mut stream);
+        write_packet(&mut stream, "52|1|9001|");
+        done_recv.recv().unwrap();
+    });
+
+    let mut options = ConnectionOptions::new("tws.internal:4002", 100);
+    options.transport = SharedTransport::new(transport);
+    options.read_timeout = Duration::from_millis(50);
+    let connection = Connection::connect_with_options(options).expect("connection failed");
+
+    let message_bus = Arc::new(TcpMessageBus::new(connection).unwrap());
+    message_bus.process_messages().unwrap();
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    request.push_field(&8);
+    request.push_field(&9000);
+    let rejected = message_bus.send_request(9000, &request).unwrap();
+    rejected.next_timeout(Duration::from_secs(5)).expect("no error").unwrap();
+
+    let mut request = RequestMessage::new();
+    request.push_field(&OutgoingMessages::RequestContractData);
+    request.push_field(&8);
+    request.push_field(&9001);
+    let answered = message_bus.send_request(9001, &request).unwrap();
+    answered.next_timeout(Duration::from_secs(5)).expect("no response").unwrap();
+
+    let journal = message_bus.request_journal();
+    assert_eq!(journal.len(), 2, "{journal:?}");
+    assert_eq!(journal[0].message_type, OutgoingMessages::RequestContractData as i32);
+    assert_eq!(journal[0].request_id, Some(9000));
+    assert_eq!(journal[0].fields, vec!["9", "8", "9000"]);
+    assert_eq!(
+        journal[0].outcome,
+        RequestOutcome::Rejected {
+            code: 200,
+            message: "No security definition has been found for the request".into()
+        }
+    );
+    assert_eq!(journal[1].outcome, RequestOutcome::Responded);
+    assert!(journal[0].sent_at <= journal[1].sent_at, "sent_at");
+
+    message_bus.ensure_shutdown();
+    done_send.send(()).unwrap();
+    server.join().unwrap();
+}
@@ -2,21 +2,27 @@
 
 use std::str;
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use time::Date;
 
 use crate::{
-    client::{DataStream, ResponseContext, Subscription},
+    client::{DataStream, ResponseContext, Subscription, SubscriptionKind},
     messages::IncomingMessages,
     server_versions, Client, Error,
 };
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WshMetadata {
     pub data_json: String,
 }
 
 impl DataStream<WshMetadata> for WshMetadata {
+    const KIND: SubscriptionKind = SubscriptionKind::WshMetadata;
     fn decode(_client: &Client, message: &mut crate::messages::ResponseMessage) -> Result<WshMetadata, Error> {
         match message.message_type() {
             IncomingMessages::WshMetaData => Ok(decoders::decode_wsh_metadata(message.clone())?),
@@ -38,19 +44,22 @@ pub(super) fn wsh_metadata(client: &Client) -> Result<WshMetadata, Error> {
     let subscription = client.send_request(request_id, request)?;
 
     match subscription.next() {
-        Some(Ok(message)) => Ok(decoders::decode_wsh_metadata(message)?),
+        Some(Ok(message)) if message.message_type() == IncomingMessages::WshMetaData => Ok(decoders::decode_wsh_metadata(message)?),
+        Some(Ok(message)) => Err(Error::from_response(message)),
         Some(Err(Error::ConnectionReset)) => wsh_metadata(client),
         Some(Err(e)) => Err(e),
         None => Err(Error::UnexpectedEndOfStream),
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WshEventData {
     pub data_json: String,
 }
 
 impl DataStream<WshEventData> for WshEventData {
+    const KIND: SubscriptionKind = SubscriptionKind::WshEventData;
     fn decode(_client: &Client, message: &mut crate::messages::ResponseMessage) -> Result<WshEventData, Error> {
         match message.message_type() {
             IncomingMessages::WshEventData => Ok(decoders::decode_wsh_event_data(message.clone())?),
@@ -64,7 +73,8 @@ impl DataStream<WshEventData> for WshEventData {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AutoFill {
     /// Automatically fill in competitor values of existing positions.
     pub competitors: bool,
@@ -122,7 +132,8 @@ pub(super) fn wsh_event_data_by_contract(
     let subscription = client.send_request(request_id, request)?;
 
     match subscription.next() {
-        Some(Ok(message)) => Ok(decoders::decode_wsh_event_data(message)?),
+        Some(Ok(message)) if message.message_type() == IncomingMessages::WshEventData => Ok(decoders::decode_wsh_event_data(message)?),
+        Some(Ok(message)) => Err(Error::from_response(message)),
         Some(Err(Error::ConnectionReset)) => wsh_event_data_by_contract(client, contract_id, start_date, end_date, limit, auto_fill),
         Some(Err(e)) => Err(e),
         None => Err(Error::UnexpectedEndOfStream),
@@ -0,0 +1,75 @@
+use std::sync::{Arc, RwLock};
+
+use time::macros::date;
+
+use super::*;
+use crate::stubs::MessageBusStub;
+
+fn stubbed_client(response_messages: &[&str]) -> (Client, Arc<MessageBusStub>) {
+    let message_bus = Arc::new(MessageBusStub {
+        request_messages: RwLock::new(vec![]),
+        response_messages: response_messages.iter().map(|message| message.to_string()).collect(),
+    });
+    (
+        Client::stubbed(message_bus.clone(), server_versions::WSH_EVENT_DATA_FILTERS_DATE),
+        message_bus,
+    )
+}
+
+#[test]
+fn requests_wsh_event_data_by_contract() {
+    let (client, message_bus) = stubbed_client(&[r#"105|9000|{"validated":true,"data":[]}|"#]);
+
+    let auto_fill = AutoFill {
+        watchlist: true,
+        ..AutoFill::default()
+    };
+    let event_data = client
+        .wsh_event_data_by_contract(
+            76792991,
+            Some(date!(2023 - 01 - 01)),
+            Some(date!(2023 - 06 - 30)),
+            Some(10),
+            Some(auto_fill),
+        )
+        .unwrap();
+    assert_eq!(event_data.data_json, r#"{"validated":true,"data":[]}"#);
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests[0].encode_simple(), "102|9000|76792991||1|0|0|20230101|20230630|10|");
+}
+
+#[test]
+fn reports_wsh_event_data_errors() {
+    let (client, _) = stubbed_client(&["4|2|9000|10276|News feed is not allowed.|"]);
+
+    let result = client.wsh_event_data_by_contract(76792991, None, None, None, None);
+    assert!(matches!(result, Err(Error::Tws { code: 10276, .. })), "{result:?}");
+}
+
+#[test]
+fn streams_wsh_event_data_by_filter() {
+    let (client, message_bus) = stubbed_client(&[r#"105|9000|{"event":"earnings"}|"#, r#"105|9000|{"event":"dividend"}|"#]);
+
+    let filter = r#"{"watchlist":["76792991"]}"#;
+    let subscription = client.wsh_event_data_by_filter(filter, None, Some(AutoFill::default())).unwrap();
+    let events: Vec<String> = subscription.iter().take(2).map(|event| event.data_json).collect();
+    assert_eq!(events, [r#"{"event":"earnings"}"#, r#"{"event":"dividend"}"#]);
+    drop(subscription);
+
+    let requests = message_bus.request_messages.read().unwrap();
+    assert_eq!(requests[0].encode_simple(), format!("102|9000||{filter}|0|0|0||||"));
+    assert_eq!(requests[1].encode_simple(), "103|9000|", "cancel wsh event data");
+}
+
+#[test]
+fn rejects_wsh_filters_on_older_servers() {
+    let message_bus = Arc::new(MessageBusStub::default());
+    let client = Client::stubbed(message_bus, server_versions::WSHE_CALENDAR);
+
+    let result = client.wsh_event_data_by_contract(76792991, None, None, None, Some(AutoFill::default()));
+    assert!(matches!(result, Err(Error::ServerVersion(..))), "{result:?}");
+
+    let result = client.wsh_event_data_by_filter("{}", None, None);
+    assert!(matches!(result, Err(Error::ServerVersion(..))), "{result:?}");
+}
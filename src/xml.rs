@@ -0,0 +1,88 @@
+//! Minimal XML element tree used to decode the XML documents returned by TWS.
+
+#[cfg(feature = "fa")]
+use std::borrow::Cow;
+
+use ::xml::reader::{EventReader, XmlEvent};
+
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// An XML element with its attributes, text content and child elements.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub text: String,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// Parses the document and returns its root element.
+    pub fn parse(source: &str) -> Result<Element, Error> {
+        let mut stack: Vec<Element> = Vec::new();
+
+        for event in EventReader::from_str(source) {
+            match event.map_err(|e| invalid(e.to_string()))? {
+                XmlEvent::StartElement { name, attributes, .. } => {
+                    stack.push(Element {
+                        name: name.local_name,
+                        attributes: attributes
+                            .into_iter()
+                            .map(|attribute| (attribute.name.local_name, attribute.value))
+                            .collect(),
+                        ..Element::default()
+                    });
+                }
+                XmlEvent::EndElement { .. } => {
+                    let element = stack.pop().ok_or_else(|| invalid("unbalanced end element".into()))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        element.text.push_str(&text);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(invalid("missing root element".into()))
+    }
+
+    /// Returns the value of the attribute with the given name.
+    #[cfg_attr(not(feature = "fundamentals"), allow(dead_code))]
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the first child element with the given name.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Returns all child elements with the given name.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Returns the trimmed text of the first child element with the given name, or an empty string.
+    pub fn child_text(&self, name: &str) -> &str {
+        self.child(name).map(|child| child.text.trim()).unwrap_or_default()
+    }
+}
+
+/// Escapes text for inclusion in XML element content.
+#[cfg(feature = "fa")]
+pub(crate) fn escape(text: &str) -> Cow<'_, str> {
+    ::xml::escape::escape_str_pcdata(text)
+}
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidFormat { kind: "xml", reason }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn test_parse_element_tree() {
+    let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Root version="2">
+    <Item><name>A &amp; B</name></Item>
+    <Item><name>C</name></Item>
+    <Other><![CDATA[raw <text>]]></Other>
+</Root>"#;
+
+    let root = Element::parse(source).expect("error parsing xml");
+
+    assert_eq!(root.name, "Root", "root.name");
+    assert_eq!(root.attribute("version"), Some("2"), "root.attribute(version)");
+    assert_eq!(root.attribute("missing"), None, "root.attribute(missing)");
+
+    let names: Vec<&str> = root.children_named("Item").map(|item| item.child_text("name")).collect();
+    assert_eq!(names, &["A & B", "C"], "item names");
+
+    assert_eq!(root.child_text("Other"), "raw <text>", "root.child_text(Other)");
+    assert_eq!(root.child_text("Missing"), "", "root.child_text(Missing)");
+}
+
+#[test]
+fn test_parse_invalid_xml() {
+    assert!(Element::parse("<Root><Item></Root>").is_err(), "mismatched elements");
+    assert!(Element::parse("").is_err(), "empty document");
+}
+
+#[test]
+#[cfg(feature = "fa")]
+fn test_escape() {
+    assert_eq!(escape("a < b & c"), "a &lt; b &amp; c");
+}